@@ -23,6 +23,10 @@ pub use database_args::DatabaseArgs;
 mod secret_key;
 pub use secret_key::{get_secret_key, SecretKeyError};
 
+/// SnapshotArgs struct for configuring snapshots
+mod snapshot_args;
+pub use snapshot_args::SnapshotArgs;
+
 /// PayloadBuilderArgs struct for configuring the payload builder
 mod payload_builder_args;
 pub use payload_builder_args::PayloadBuilderArgs;
@@ -31,13 +35,17 @@ pub use payload_builder_args::PayloadBuilderArgs;
 mod stage_args;
 pub use stage_args::StageEnum;
 
+/// SyncMode for configuring which parts of the chain are synced
+mod sync_mode;
+pub use sync_mode::SyncMode;
+
 /// Gas price oracle related arguments
 mod gas_price_oracle_args;
 pub use gas_price_oracle_args::GasPriceOracleArgs;
 
 /// TxPoolArgs for configuring the transaction pool
 mod txpool_args;
-pub use txpool_args::TxPoolArgs;
+pub use txpool_args::{TxPoolArgs, TxPoolEvictionPolicy};
 
 /// DevArgs for configuring the dev testnet
 mod dev_args;
@@ -5,10 +5,11 @@ use clap::Args;
 use reth_config::Config;
 use reth_discv4::{DEFAULT_DISCOVERY_ADDR, DEFAULT_DISCOVERY_PORT};
 use reth_net_nat::NatResolver;
-use reth_network::{HelloMessageWithProtocols, NetworkConfigBuilder};
+use reth_network::{HelloMessageWithProtocols, NetworkConfigBuilder, PacketCapture};
 use reth_primitives::{mainnet_nodes, ChainSpec, NodeRecord};
+use reth_trie::{TrieNodeCache, DEFAULT_TRIE_NODE_CACHE_SIZE};
 use secp256k1::SecretKey;
-use std::{net::Ipv4Addr, path::PathBuf, sync::Arc};
+use std::{fs::File, io, net::Ipv4Addr, path::PathBuf, sync::Arc};
 
 /// Parameters for configuring the network more granularity via CLI
 #[derive(Debug, Args, PartialEq, Eq)]
@@ -74,6 +75,28 @@ pub struct NetworkArgs {
     /// Maximum number of inbound requests. default: 30
     #[arg(long)]
     pub max_inbound_peers: Option<usize>,
+
+    /// Answer `GetNodeData` requests of `eth/66` peers.
+    ///
+    /// Trie nodes are served from a cache of recently computed nodes, contract bytecode is read
+    /// from the database.
+    #[arg(long)]
+    pub serve_node_data: bool,
+
+    /// Maximum number of recently computed trie nodes kept for serving `GetNodeData` requests.
+    #[arg(
+        long,
+        value_name = "NODES",
+        default_value_t = DEFAULT_TRIE_NODE_CACHE_SIZE,
+        requires = "serve_node_data"
+    )]
+    pub node_data_cache_size: u32,
+
+    /// Record the messages of all peer sessions to this file.
+    ///
+    /// An existing file is overwritten.
+    #[arg(long, value_name = "FILE")]
+    pub capture_file: Option<PathBuf>,
 }
 
 impl NetworkArgs {
@@ -123,6 +146,18 @@ impl NetworkArgs {
 
         Some(peers_file)
     }
+
+    /// Returns the cache for recently computed trie nodes if serving `GetNodeData` is enabled.
+    pub fn trie_node_cache(&self) -> Option<TrieNodeCache> {
+        self.serve_node_data.then(|| TrieNodeCache::new(self.node_data_cache_size))
+    }
+
+    /// Creates the capture file for the messages of all peer sessions, if configured.
+    pub fn packet_capture(&self) -> io::Result<Option<PacketCapture>> {
+        // every message is written at once, so the file isn't buffered to not lose messages when
+        // the node is killed
+        self.capture_file.as_ref().map(|path| PacketCapture::new(File::create(path)?)).transpose()
+    }
 }
 
 impl Default for NetworkArgs {
@@ -141,6 +176,9 @@ impl Default for NetworkArgs {
             port: DEFAULT_DISCOVERY_PORT,
             max_outbound_peers: None,
             max_inbound_peers: None,
+            serve_node_data: false,
+            node_data_cache_size: DEFAULT_TRIE_NODE_CACHE_SIZE,
+            capture_file: None,
         }
     }
 }
@@ -32,6 +32,7 @@ impl PruningArgs {
                         .map(|contract| PruneMode::Before(contract.block)),
                     account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    bytecodes: None,
                     receipts_log_filter: ReceiptsLogPruneConfig(
                         chain_spec
                             .deposit_contract
@@ -40,6 +41,7 @@ impl PruningArgs {
                             .into_iter()
                             .collect(),
                     ),
+                    history_retention: Default::default(),
                 },
             })
         } else {
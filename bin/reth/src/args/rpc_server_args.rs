@@ -18,6 +18,7 @@ use clap::{
 };
 use futures::TryFutureExt;
 use reth_network_api::{NetworkInfo, Peers};
+use reth_payload_builder::PayloadStore;
 use reth_provider::{
     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
     EvmEnvProvider, HeaderProvider, StateProviderFactory,
@@ -176,6 +177,13 @@ pub struct RpcServerArgs {
     /// Gas price oracle configuration.
     #[clap(flatten)]
     pub gas_price_oracle: GasPriceOracleArgs,
+
+    /// Whether the node only syncs headers, in which case no state is available to the `eth`
+    /// namespace.
+    ///
+    /// This is not a CLI argument, it is derived from the node's sync mode.
+    #[arg(skip)]
+    pub headers_only: bool,
 }
 
 impl RpcServerArgs {
@@ -189,6 +197,7 @@ impl RpcServerArgs {
         &self,
         components: &Reth,
         engine_api: Engine,
+        payload_store: PayloadStore,
         jwt_secret: JwtSecret,
         conf: &mut Conf,
     ) -> eyre::Result<RethRpcServerHandles>
@@ -208,6 +217,7 @@ impl RpcServerArgs {
             .with_network(components.network())
             .with_events(components.events())
             .with_executor(components.task_executor())
+            .with_payload_store(payload_store)
             .build_with_auth_server(module_config, engine_api);
 
         let rpc_components = RethRpcComponents { registry: &mut registry, modules: &mut modules };
@@ -338,6 +348,7 @@ impl RethRpcConfig for RpcServerArgs {
             .rpc_gas_cap(self.rpc_gas_cap)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
+            .headers_only(self.headers_only)
     }
 
     fn state_cache_config(&self) -> EthStateCacheConfig {
@@ -479,6 +490,7 @@ impl Default for RpcServerArgs {
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
+            headers_only: false,
         }
     }
 }
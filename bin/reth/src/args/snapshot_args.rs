@@ -0,0 +1,65 @@
+//! clap [Args](clap::Args) for snapshot configuration
+
+use clap::Args;
+use reth_nippy_jar::Verification;
+
+/// Parameters for snapshot configuration
+#[derive(Debug, Args, PartialEq, Default, Clone, Copy)]
+#[clap(next_help_heading = "Snapshots")]
+pub struct SnapshotArgs {
+    /// Verify the checksums of snapshot files when they are opened. Corrupted snapshots fail to
+    /// load.
+    #[arg(long = "snapshots.verify", default_value_t = false)]
+    pub verify: bool,
+
+    /// Number of chunks to verify per snapshot when opened, evenly spread over the file. Verifies
+    /// every chunk if not set.
+    #[arg(long = "snapshots.verify-sample", value_name = "CHUNKS", requires = "verify")]
+    pub verify_sample: Option<usize>,
+}
+
+impl SnapshotArgs {
+    /// Returns how snapshots are verified when opened, if at all.
+    pub fn verification(&self) -> Option<Verification> {
+        self.verify.then(|| self.verify_sample.map_or(Verification::Full, Verification::Sampled))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[clap(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_snapshot_args() {
+        let args = CommandParser::<SnapshotArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, SnapshotArgs::default());
+        assert_eq!(args.verification(), None);
+
+        let args = CommandParser::<SnapshotArgs>::parse_from(["reth", "--snapshots.verify"]).args;
+        assert_eq!(args.verification(), Some(Verification::Full));
+
+        let args = CommandParser::<SnapshotArgs>::parse_from([
+            "reth",
+            "--snapshots.verify",
+            "--snapshots.verify-sample",
+            "8",
+        ])
+        .args;
+        assert_eq!(args.verification(), Some(Verification::Sampled(8)));
+
+        assert!(CommandParser::<SnapshotArgs>::try_parse_from([
+            "reth",
+            "--snapshots.verify-sample",
+            "8"
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,44 @@
+//! Shared arguments related to the sync mode of the node
+
+/// Determines which parts of the chain the node syncs.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum SyncMode {
+    /// Download and execute all blocks.
+    #[default]
+    Full,
+    /// Only download and validate the header chain.
+    ///
+    /// Block bodies are never downloaded and no state is available.
+    HeadersOnly,
+}
+
+// === impl SyncMode ===
+
+impl SyncMode {
+    /// Returns true if only headers are synced.
+    pub fn is_headers_only(&self) -> bool {
+        matches!(self, SyncMode::HeadersOnly)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser {
+        #[arg(long, value_enum, default_value_t)]
+        sync_mode: SyncMode,
+    }
+
+    #[test]
+    fn parse_sync_mode() {
+        let args = CommandParser::parse_from(["reth"]);
+        assert_eq!(args.sync_mode, SyncMode::Full);
+
+        let args = CommandParser::parse_from(["reth", "--sync-mode", "headers-only"]);
+        assert!(args.sync_mode.is_headers_only());
+    }
+}
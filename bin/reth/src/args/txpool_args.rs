@@ -1,12 +1,19 @@
 //! Transaction pool arguments
 
-use crate::cli::config::RethTransactionPoolConfig;
+use crate::{args::utils::parse_duration_from_secs, cli::config::RethTransactionPoolConfig};
 use clap::Args;
 use reth_transaction_pool::{
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-    TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+    blobstore::{DiskFileBlobStoreConfig, OpenDiskFileBlobStore},
+    bundle::DEFAULT_MAX_BUNDLES_PER_BLOCK,
+    journal::DEFAULT_JOURNAL_ROTATION_INTERVAL,
+    maintain::MaintainPoolConfig,
+    BlobFeeBumpReplacement, DefaultEvictionPolicy, DefaultReplacementPolicy, EvictionPolicy,
+    LocalTransactionConfig, LowestTipEviction, OldestEviction, PoolConfig, PriceBumpConfig,
+    SenderFairnessEviction, SubPoolLimit, DEFAULT_PRICE_BUMP, REPLACE_BLOB_PRICE_BUMP,
+    TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+    TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::{sync::Arc, time::Duration};
 
 /// Parameters for debugging purposes
 #[derive(Debug, Args, PartialEq)]
@@ -44,9 +51,42 @@ pub struct TxPoolArgs {
     /// Price bump percentage to replace an already existing blob transaction
     #[arg(long = "blobpool.pricebump", default_value_t = REPLACE_BLOB_PRICE_BUMP)]
     pub blob_transaction_price_bump: u128,
+    /// Price bump percentage of the max fee per blob gas to replace an already existing blob
+    /// transaction.
+    ///
+    /// Defaults to the blob transaction price bump.
+    #[arg(long = "blobpool.blobfee_pricebump")]
+    pub blob_fee_price_bump: Option<u128>,
+    /// Max size of all blob sidecars in the blob store on disk in megabytes.
+    ///
+    /// Blob sidecars that would exceed this are not stored. Unlimited by default.
+    #[arg(long = "blobpool.max_disk_size")]
+    pub blob_max_disk_size: Option<usize>,
+    /// Number of blocks for which the blob sidecars of included transactions are kept in the
+    /// blob store, even if the block is already finalized.
+    #[arg(long = "blobpool.retention", default_value_t = 0, value_name = "BLOCKS")]
+    pub blob_retention: u64,
+
+    /// Which transactions are evicted first if a sub-pool is full.
+    #[arg(long = "txpool.eviction", value_enum, default_value_t)]
+    pub eviction_policy: TxPoolEvictionPolicy,
+
+    /// Max number of bundles that can target the same block.
+    #[arg(long = "txpool.max_bundles_per_block", default_value_t = DEFAULT_MAX_BUNDLES_PER_BLOCK)]
+    pub max_bundles_per_block: usize,
+
     /// Flag to disable local transaction exemptions.
     #[arg(long = "txpool.nolocals")]
     pub no_locals: bool,
+
+    /// Persist local transactions to a journal in the data directory, and insert them into the
+    /// pool again on restart.
+    #[arg(long = "txpool.journal")]
+    pub journal: bool,
+    /// Interval in seconds at which the journal is rewritten to only contain the local
+    /// transactions still in the pool. If 0, it's only rewritten on startup and shutdown.
+    #[arg(long = "txpool.rejournal", value_parser = parse_duration_from_secs, default_value = "3600", value_name = "SECONDS")]
+    pub rejournal: Duration,
 }
 
 impl Default for TxPoolArgs {
@@ -61,7 +101,14 @@ impl Default for TxPoolArgs {
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bump: DEFAULT_PRICE_BUMP,
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
+            blob_fee_price_bump: None,
+            blob_max_disk_size: None,
+            blob_retention: 0,
+            eviction_policy: TxPoolEvictionPolicy::default(),
+            max_bundles_per_block: DEFAULT_MAX_BUNDLES_PER_BLOCK,
             no_locals: false,
+            journal: false,
+            rejournal: DEFAULT_JOURNAL_ROTATION_INTERVAL,
         }
     }
 }
@@ -92,6 +139,54 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
             },
+            eviction_policy: self.eviction_policy.policy(),
+            replacement_policy: match self.blob_fee_price_bump {
+                Some(blob_fee_price_bump) => {
+                    Arc::new(BlobFeeBumpReplacement { blob_fee_price_bump })
+                }
+                None => Arc::new(DefaultReplacementPolicy),
+            },
+            max_bundles_per_block: self.max_bundles_per_block,
+        }
+    }
+
+    /// Returns the configuration of the blob store.
+    fn blob_store_config(&self) -> DiskFileBlobStoreConfig {
+        DiskFileBlobStoreConfig::default()
+            .with_max_disk_size(self.blob_max_disk_size.map(|size| size * 1024 * 1024))
+            .with_open(OpenDiskFileBlobStore::ReIndex)
+    }
+
+    /// Returns the configuration of the transaction pool maintenance task.
+    fn maintain_config(&self) -> MaintainPoolConfig {
+        MaintainPoolConfig { blob_sidecar_retention: self.blob_retention, ..Default::default() }
+    }
+}
+
+/// Determines which transactions are evicted first if a sub-pool is full.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum TxPoolEvictionPolicy {
+    /// Evict in the built-in order of each sub-pool.
+    #[default]
+    Default,
+    /// Evict the transactions that pay the lowest tip first.
+    LowestTip,
+    /// Evict the transactions that have been in the pool the longest first.
+    Oldest,
+    /// Evict the transactions of the senders with the most transactions first.
+    SenderFairness,
+}
+
+// === impl TxPoolEvictionPolicy ===
+
+impl TxPoolEvictionPolicy {
+    /// Returns the [EvictionPolicy] of the pool.
+    pub fn policy(&self) -> Arc<dyn EvictionPolicy> {
+        match self {
+            TxPoolEvictionPolicy::Default => Arc::new(DefaultEvictionPolicy),
+            TxPoolEvictionPolicy::LowestTip => Arc::new(LowestTipEviction),
+            TxPoolEvictionPolicy::Oldest => Arc::new(OldestEviction),
+            TxPoolEvictionPolicy::SenderFairness => Arc::new(SenderFairnessEviction),
         }
     }
 }
@@ -114,4 +209,26 @@ mod tests {
         let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn txpool_args_eviction_policy() {
+        let args =
+            CommandParser::<TxPoolArgs>::parse_from(["reth", "--txpool.eviction", "lowest-tip"])
+                .args;
+        assert_eq!(args.eviction_policy, TxPoolEvictionPolicy::LowestTip);
+    }
+
+    #[test]
+    fn txpool_args_blob_store() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--blobpool.max_disk_size",
+            "512",
+            "--blobpool.retention",
+            "4096",
+        ])
+        .args;
+        assert_eq!(args.blob_store_config().max_disk_size, Some(512 * 1024 * 1024));
+        assert_eq!(args.maintain_config().blob_sidecar_retention, 4096);
+    }
 }
@@ -0,0 +1,411 @@
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use reth_db::{database::Database, init_db, open_db_read_only, DatabaseEnv};
+use reth_era::{
+    era1_file_name, era1_partial_file_name, BlockTuple, Era1Writer, MAX_BLOCKS_PER_ERA1,
+};
+use reth_interfaces::{executor::BlockValidationError, provider::ProviderResult};
+use reth_primitives::{
+    revm::env::{fill_cfg_and_block_env, tx_env_with_recovered},
+    trie::Nibbles,
+    BlockBody, BlockNumber, BlockWithSenders, ChainSpec, Hardfork, Receipt, Receipts, StorageEntry,
+    TransactionSignedEcRecovered, B256, U256,
+};
+use reth_provider::{
+    AccountReader, BlockReader, BundleStateWithReceipts, ChainSpecProvider, ChangeSetReader,
+    DatabaseProviderRO, HashingWriter, HeaderProvider, ProviderFactory, ReceiptProvider,
+    StateProvider, StorageReader, TransactionVariant,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::states::bundle_state::BundleRetention,
+    eth_dao_fork::{DAO_HARDFORK_BENEFICIARY, DAO_HARDKFORK_ACCOUNTS},
+    primitives::{BlockEnv, CfgEnv, Env, ResultAndState},
+    DatabaseCommit, State, EVM,
+};
+use reth_trie::{prefix_set::PrefixSetMut, StateRoot};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tempfile::TempDir;
+use tracing::info;
+
+/// Exports blocks from the database to Era1 archives.
+#[derive(Debug, Parser)]
+pub struct ExportCommand {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    /// The first block to export.
+    #[arg(long, value_name = "BLOCK", default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block to export.
+    #[arg(long, value_name = "BLOCK")]
+    to: BlockNumber,
+
+    /// The directory the Era1 archives are written to.
+    ///
+    /// Blocks are split into one archive per epoch of 8192 blocks. Archives that do not contain
+    /// all blocks of their epoch are marked as partial in their file name.
+    #[arg(value_name = "EXPORT_DIR", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl ExportCommand {
+    /// Execute `export` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        if self.from > self.to {
+            eyre::bail!("invalid block range {}..={}", self.from, self.to);
+        }
+
+        // add network name to data dir
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db_path = data_dir.db_path();
+
+        info!(target: "reth::cli", path = ?db_path, "Opening database");
+        let db = open_db_read_only(&db_path, self.db.log_level)?;
+        let provider_factory = ProviderFactory::new(db, self.chain.clone());
+        let provider = provider_factory.provider()?;
+
+        fs::create_dir_all(&self.path)?;
+        let network = self.chain.chain.to_string();
+        let epoch_len = MAX_BLOCKS_PER_ERA1 as u64;
+
+        // the receipts of pre-byzantium blocks commit to intermediate state roots, which are
+        // not stored and have to be recomputed
+        let mut intermediate_roots =
+            if self.chain.fork(Hardfork::Byzantium).active_at_block(self.from) {
+                None
+            } else {
+                Some(IntermediateStateRoots::new(self.chain.clone(), &self.path)?)
+            };
+
+        let mut number = self.from;
+        while number <= self.to {
+            let epoch = number / epoch_len;
+            let last = ((epoch + 1) * epoch_len - 1).min(self.to);
+
+            // the file name depends on the accumulator root, which is only known at the end
+            let tmp_path = self.path.join(format!("{network}-{epoch:05}.era1.tmp"));
+            let mut writer = Era1Writer::new(BufWriter::new(File::create(&tmp_path)?))?;
+            for block_number in number..=last {
+                let tuple = self.block_tuple(
+                    &provider_factory,
+                    &provider,
+                    intermediate_roots.as_mut(),
+                    block_number,
+                )?;
+                writer.append(&tuple)?;
+            }
+            let (_, root) = writer.finish()?;
+
+            // only archives of complete epochs get the canonical file name
+            let complete = number == epoch * epoch_len && last == (epoch + 1) * epoch_len - 1;
+            let file_name = if complete {
+                era1_file_name(&network, epoch, root)
+            } else {
+                era1_partial_file_name(&network, epoch, root)
+            };
+            let path = self.path.join(file_name);
+            fs::rename(&tmp_path, &path)?;
+            info!(target: "reth::cli", from = number, to = last, ?path, "Exported Era1 archive");
+
+            number = last + 1;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the block with its receipts and total difficulty from the database.
+    fn block_tuple<DB: Database>(
+        &self,
+        provider_factory: &ProviderFactory<DB>,
+        provider: &DatabaseProviderRO<DB>,
+        intermediate_roots: Option<&mut IntermediateStateRoots>,
+        number: BlockNumber,
+    ) -> eyre::Result<BlockTuple> {
+        let block = provider
+            .block_with_senders(number.into(), TransactionVariant::WithHash)?
+            .ok_or_else(|| eyre::eyre!("block {number} not found"))?;
+        if block.header.difficulty.is_zero() {
+            eyre::bail!(
+                "block {number} is a post-merge block, Era1 only contains pre-merge blocks"
+            );
+        }
+
+        let receipts = provider
+            .receipts_by_block(number.into())?
+            .ok_or_else(|| {
+                eyre::eyre!("receipts of block {number} not found, they might be pruned")
+            })?
+            .into_iter()
+            .map(Receipt::with_bloom)
+            .collect::<Vec<_>>();
+        let total_difficulty = provider
+            .header_td_by_number(number)?
+            .ok_or_else(|| eyre::eyre!("total difficulty of block {number} not found"))?;
+
+        let state_roots = match intermediate_roots {
+            Some(intermediate_roots)
+                if !self.chain.fork(Hardfork::Byzantium).active_at_block(number) =>
+            {
+                Some(intermediate_roots.state_roots(provider_factory, &block, total_difficulty)?)
+            }
+            _ => None,
+        };
+
+        let (block, _) = block.into_components();
+        let body = BlockBody {
+            transactions: block.body,
+            ommers: block.ommers,
+            withdrawals: block.withdrawals,
+        };
+        let tuple = match state_roots {
+            Some(state_roots) => {
+                let receipts = receipts.into_iter().zip(state_roots).collect::<Vec<_>>();
+                BlockTuple::new_pre_byzantium(block.header, body, &receipts, total_difficulty)
+            }
+            None => BlockTuple::new(block.header, body, &receipts, total_difficulty),
+        };
+        tuple.verify()?;
+
+        Ok(tuple)
+    }
+}
+
+/// Tracks the state trie of the parent of the next exported pre-byzantium block in a scratch
+/// database to compute the intermediate state roots of its transactions.
+///
+/// The trie is built from the genesis block and advanced block by block with the changesets of the
+/// source database, so the state roots of a block only depend on the state changes of that block.
+/// The trie is verified against the state root of every block it is advanced by.
+struct IntermediateStateRoots {
+    /// The scratch database with the hashed state and the trie.
+    factory: ProviderFactory<DatabaseEnv>,
+    /// The first block whose state changes are not applied yet.
+    next_block: BlockNumber,
+    /// The directory of the scratch database, removed on drop.
+    _dir: TempDir,
+}
+
+impl IntermediateStateRoots {
+    /// Creates an empty scratch database in the given directory.
+    fn new(chain: Arc<ChainSpec>, dir: &Path) -> eyre::Result<Self> {
+        let dir = tempfile::tempdir_in(dir)?;
+        let db = init_db(dir.path(), None)?;
+        Ok(Self { factory: ProviderFactory::new(db, chain), next_block: 0, _dir: dir })
+    }
+
+    /// Re-executes a pre-byzantium block on top of the state of its parent and returns the state
+    /// root after every transaction, which the receipts of the block commit to.
+    ///
+    /// The state roots are computed from the trie of the parent state and the in-memory state of
+    /// the block executed so far.
+    fn state_roots<DB: Database>(
+        &mut self,
+        provider_factory: &ProviderFactory<DB>,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+    ) -> eyre::Result<Vec<B256>> {
+        self.advance(provider_factory, block.number)?;
+        if block.body.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let chain_spec = provider_factory.chain_spec();
+        let state_provider = provider_factory.history_by_block_number(block.number - 1)?;
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(&state_provider))
+            .with_bundle_update()
+            .without_state_clear()
+            .build();
+        db.set_state_clear_flag(
+            chain_spec.fork(Hardfork::SpuriousDragon).active_at_block(block.number),
+        );
+
+        let mut cfg = CfgEnv::default();
+        let mut block_env = BlockEnv::default();
+        fill_cfg_and_block_env(
+            &mut cfg,
+            &mut block_env,
+            &chain_spec,
+            &block.header,
+            total_difficulty,
+        );
+
+        // unlike the other post-block state changes, the irregular state change of the DAO fork
+        // is applied before the transactions of the block
+        if chain_spec.fork(Hardfork::Dao).transitions_at_block(block.number) {
+            let drained_balance: u128 =
+                db.drain_balances(DAO_HARDKFORK_ACCOUNTS)?.into_iter().sum();
+            db.increment_balances(HashMap::from([(DAO_HARDFORK_BENEFICIARY, drained_balance)]))?;
+        }
+
+        let scratch = self.factory.provider()?;
+        let mut state_roots = Vec::with_capacity(block.body.len());
+        for (sender, transaction) in block.transactions_with_sender() {
+            let transaction =
+                TransactionSignedEcRecovered::from_signed_transaction(transaction.clone(), *sender);
+            let env = Env {
+                cfg: cfg.clone(),
+                block: block_env.clone(),
+                tx: tx_env_with_recovered(&transaction),
+            };
+            let mut evm = EVM::with_env(env);
+            evm.database(&mut db);
+            let ResultAndState { state, .. } = evm.transact().map_err(|error| {
+                BlockValidationError::EVM { hash: transaction.hash(), error: error.into() }
+            })?;
+            db.commit(state);
+            db.merge_transitions(BundleRetention::PlainState);
+
+            let bundle = BundleStateWithReceipts::new(
+                db.bundle_state.clone(),
+                Receipts::new(),
+                block.number,
+            );
+            let hashed_state = bundle.hash_state_slow();
+            state_roots.push(bundle.state_root_calculator(scratch.tx_ref(), &hashed_state).root()?);
+        }
+
+        Ok(state_roots)
+    }
+
+    /// Applies the state changes of all blocks before the given block to the scratch database.
+    fn advance<DB: Database>(
+        &mut self,
+        provider_factory: &ProviderFactory<DB>,
+        number: BlockNumber,
+    ) -> eyre::Result<()> {
+        if self.next_block < number {
+            info!(
+                target: "reth::cli",
+                from = self.next_block,
+                to = number - 1,
+                "Advancing the parent state trie"
+            );
+        }
+        while self.next_block < number {
+            self.apply_block(provider_factory, self.next_block)?;
+            self.next_block += 1;
+        }
+        Ok(())
+    }
+
+    /// Applies the state changes of the block to the hashed state and the trie of the scratch
+    /// database and checks the resulting state root against the header.
+    fn apply_block<DB: Database>(
+        &self,
+        provider_factory: &ProviderFactory<DB>,
+        number: BlockNumber,
+    ) -> eyre::Result<()> {
+        let provider = provider_factory.provider()?;
+        let header = provider
+            .header_by_number(number)?
+            .ok_or_else(|| eyre::eyre!("header of block {number} not found"))?;
+        let state = provider_factory.history_by_block_number(number)?;
+
+        let scratch = self.factory.provider_rw()?;
+        let mut account_prefix_set = PrefixSetMut::default();
+        let mut storage_prefix_set: HashMap<B256, PrefixSetMut> = HashMap::default();
+        let mut destroyed_accounts = HashSet::default();
+
+        let storages = provider
+            .changed_storages_with_range(number..=number)?
+            .into_iter()
+            .map(|(address, slots)| {
+                let entries = slots
+                    .into_iter()
+                    .map(|key| {
+                        let value = state.storage(address, key)?.unwrap_or_default();
+                        Ok(StorageEntry { key, value })
+                    })
+                    .collect::<ProviderResult<Vec<_>>>()?;
+                Ok((address, entries))
+            })
+            .collect::<ProviderResult<Vec<_>>>()?;
+        for (hashed_address, hashed_slots) in scratch.insert_storage_for_hashing(storages)? {
+            account_prefix_set.insert(Nibbles::unpack(hashed_address));
+            let prefix_set = storage_prefix_set.entry(hashed_address).or_default();
+            for slot in hashed_slots {
+                prefix_set.insert(Nibbles::unpack(slot));
+            }
+        }
+
+        let accounts = provider
+            .account_block_changeset(number)?
+            .into_iter()
+            .map(|change| Ok((change.address, state.basic_account(change.address)?)))
+            .collect::<ProviderResult<Vec<_>>>()?;
+        for (hashed_address, account) in scratch.insert_account_for_hashing(accounts)? {
+            account_prefix_set.insert(Nibbles::unpack(hashed_address));
+            if account.is_none() {
+                destroyed_accounts.insert(hashed_address);
+            }
+        }
+
+        let (state_root, trie_updates) = StateRoot::new(scratch.tx_ref())
+            .with_changed_account_prefixes(account_prefix_set.freeze())
+            .with_changed_storage_prefixes(
+                storage_prefix_set.into_iter().map(|(k, v)| (k, v.freeze())).collect(),
+            )
+            .with_destroyed_accounts(destroyed_accounts)
+            .root_with_updates()?;
+        if state_root != header.state_root {
+            eyre::bail!(
+                "state root mismatch of block {number}: got {state_root}, expected {}",
+                header.state_root
+            );
+        }
+        trie_updates.flush(scratch.tx_ref())?;
+        scratch.commit()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_export_command() {
+        let args = ExportCommand::parse_from(["reth", "--from", "8192", "--to", "16383", "era"]);
+        assert_eq!(args.from, 8192);
+        assert_eq!(args.to, 16383);
+        assert_eq!(args.path, PathBuf::from("era"));
+    }
+}
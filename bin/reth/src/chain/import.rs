@@ -13,23 +13,29 @@ use eyre::Context;
 use futures::{Stream, StreamExt};
 use reth_beacon_consensus::BeaconConsensus;
 use reth_config::Config;
-use reth_db::{database::Database, init_db};
+use reth_db::{database::Database, database_metrics::DatabaseMetadata, init_db};
 use reth_downloaders::{
-    bodies::bodies::BodiesDownloaderBuilder,
-    headers::reverse_headers::ReverseHeadersDownloaderBuilder, test_utils::FileClient,
+    bodies::bodies::BodiesDownloaderBuilder, file_client::FileClient,
+    headers::reverse_headers::ReverseHeadersDownloaderBuilder,
 };
+use reth_era::Era1Archive;
 use reth_interfaces::consensus::Consensus;
 use reth_primitives::{stage::StageId, ChainSpec, B256};
-use reth_provider::{HeaderSyncMode, ProviderFactory, StageCheckpointReader};
+use reth_provider::{
+    BlockHashReader, HeaderProvider, HeaderSyncMode, ProviderFactory, StageCheckpointReader,
+};
 use reth_stages::{
     prelude::*,
     stages::{ExecutionStage, ExecutionStageThresholds, SenderRecoveryStage, TotalDifficultyStage},
 };
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use tokio::sync::watch;
 use tracing::{debug, info};
 
-/// Syncs RLP encoded blocks from a file.
+/// Syncs RLP encoded blocks or Era1 archives from a file.
 #[derive(Debug, Parser)]
 pub struct ImportCommand {
     /// The path to the configuration file to use.
@@ -61,6 +67,14 @@ pub struct ImportCommand {
     #[clap(flatten)]
     db: DatabaseArgs,
 
+    /// Import Era1 archives instead of RLP encoded blocks.
+    ///
+    /// The import path can either be a single Era1 file or a directory of Era1 files, which are
+    /// verified and imported in order. Archives must keep their original file names, which commit
+    /// to the accumulator root of the archive.
+    #[arg(long, verbatim_doc_comment)]
+    era: bool,
+
     /// The path to a block file for import.
     ///
     /// The online stages (headers and bodies) are replaced by a file import, after which the
@@ -95,16 +109,53 @@ impl ImportCommand {
         let consensus = Arc::new(BeaconConsensus::new(self.chain.clone()));
         info!(target: "reth::cli", "Consensus engine initialized");
 
-        // create a new FileClient
-        info!(target: "reth::cli", "Importing chain file");
-        let file_client = Arc::new(FileClient::new(&self.path).await?);
+        if self.era {
+            for path in era1_files(&self.path)? {
+                let Some(file_client) = self.read_era1_file(&path, &provider_factory)? else {
+                    continue
+                };
+                let completed = self
+                    .run_import(config.clone(), &provider_factory, &consensus, file_client, &db)
+                    .await?;
+                if !completed {
+                    break
+                }
+            }
+        } else {
+            // create a new FileClient
+            info!(target: "reth::cli", "Importing chain file");
+            let file_client = FileClient::new(&self.path).await?;
+            info!(target: "reth::cli", "Chain file imported");
+
+            self.run_import(config, &provider_factory, &consensus, file_client, &db).await?;
+        }
+
+        info!(target: "reth::cli", "Finishing up");
+        Ok(())
+    }
+
+    /// Runs the import pipeline until all blocks of the client are imported.
+    ///
+    /// Returns `false` if the import was interrupted.
+    async fn run_import<DB, C>(
+        &self,
+        config: Config,
+        provider_factory: &ProviderFactory<DB>,
+        consensus: &Arc<C>,
+        file_client: FileClient,
+        db: &DB,
+    ) -> eyre::Result<bool>
+    where
+        DB: Database + DatabaseMetadata + Clone + Unpin + 'static,
+        C: Consensus + 'static,
+    {
+        let file_client = Arc::new(file_client);
 
         // override the tip
         let tip = file_client.tip().expect("file client has no tip");
-        info!(target: "reth::cli", "Chain file imported");
 
         let (mut pipeline, events) = self
-            .build_import_pipeline(config, provider_factory.clone(), &consensus, file_client)
+            .build_import_pipeline(config, provider_factory.clone(), consensus, file_client)
             .await?;
 
         // override the tip
@@ -121,11 +172,82 @@ impl ImportCommand {
         info!(target: "reth::cli", "Starting sync pipeline");
         tokio::select! {
             res = pipeline.run() => res?,
-            _ = tokio::signal::ctrl_c() => {},
+            _ = tokio::signal::ctrl_c() => return Ok(false),
         };
 
-        info!(target: "reth::cli", "Finishing up");
-        Ok(())
+        Ok(true)
+    }
+
+    /// Reads and verifies an Era1 archive.
+    ///
+    /// Returns `None` if all blocks of the archive were already imported.
+    fn read_era1_file<DB: Database>(
+        &self,
+        path: &Path,
+        provider_factory: &ProviderFactory<DB>,
+    ) -> eyre::Result<Option<FileClient>> {
+        info!(target: "reth::cli", ?path, "Importing Era1 archive");
+        let archive = Era1Archive::open(path)
+            .wrap_err_with(|| format!("Failed to read Era1 archive {}", path.display()))?;
+        archive.verify().wrap_err_with(|| format!("Invalid Era1 archive {}", path.display()))?;
+        let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        archive
+            .verify_file_name(&self.chain.chain.to_string(), file_name)
+            .wrap_err_with(|| format!("Invalid Era1 archive {}", path.display()))?;
+
+        let (Some(first), Some(last)) = (archive.blocks.first(), archive.blocks.last()) else {
+            return Ok(None)
+        };
+
+        let provider = provider_factory.provider()?;
+        let imported = provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default();
+        if last.number() <= imported.block_number {
+            info!(target: "reth::cli", ?path, "Era1 archive already imported");
+            return Ok(None)
+        }
+
+        // the archive must continue the local chain
+        if first.number() > imported.block_number + 1 {
+            eyre::bail!(
+                "Era1 archive {} starts at block {}, but the local chain ends at block {}",
+                path.display(),
+                first.number(),
+                imported.block_number
+            );
+        }
+
+        // the archive is anchored to the local chain by the hash of the last imported block, either
+        // as a block of the archive or as the parent of its first block
+        let local_hash = provider
+            .block_hash(imported.block_number)?
+            .ok_or_else(|| eyre::eyre!("block {} not found", imported.block_number))?;
+        let archive_hash = match imported.block_number.checked_sub(first.number()) {
+            Some(index) => archive.blocks[index as usize].header.hash_slow(),
+            None => first.header.parent_hash,
+        };
+        if archive_hash != local_hash {
+            eyre::bail!(
+                "Era1 archive {} does not continue the local chain, hash mismatch at block {}",
+                path.display(),
+                imported.block_number
+            );
+        }
+        if let Some(parent_td) = first
+            .number()
+            .checked_sub(1)
+            .map(|parent| provider.header_td_by_number(parent))
+            .transpose()?
+            .flatten()
+        {
+            if parent_td + first.header.difficulty != first.total_difficulty {
+                eyre::bail!(
+                    "Era1 archive {} does not continue the local chain, total difficulty mismatch",
+                    path.display()
+                );
+            }
+        }
+
+        Ok(Some(FileClient::from_blocks(archive.into_blocks())))
     }
 
     async fn build_import_pipeline<DB, C>(
@@ -205,6 +327,25 @@ impl ImportCommand {
     }
 }
 
+/// Returns the Era1 files at the given path, sorted by name.
+///
+/// Era1 file names start with the network and the zero padded epoch, so this sorts them by epoch.
+fn era1_files(path: &Path) -> eyre::Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()])
+    }
+
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.extension().map_or(false, |ext| ext == "era1") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
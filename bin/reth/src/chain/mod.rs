@@ -1,7 +1,9 @@
 //! Command line utilities for initializing a chain.
 
+mod export;
 mod import;
 mod init;
 
+pub use export::ExportCommand;
 pub use import::ImportCommand;
 pub use init::InitCommand;
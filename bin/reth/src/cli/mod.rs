@@ -7,7 +7,7 @@ use crate::{
     dirs::{LogsDir, PlatformPath},
     node, p2p, recover,
     runner::CliRunner,
-    stage, test_vectors,
+    snapshot, stage, test_vectors,
     version::{LONG_VERSION, SHORT_VERSION},
 };
 use clap::{value_parser, ArgAction, Args, Parser, Subcommand, ValueEnum};
@@ -88,7 +88,9 @@ impl<Ext: RethCliExt> Cli<Ext> {
             Commands::Node(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Init(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Import(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::Export(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Db(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::Snapshot(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Stage(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
@@ -138,12 +140,18 @@ pub enum Commands<Ext: RethCliExt = ()> {
     /// Initialize the database from a genesis file.
     #[command(name = "init")]
     Init(chain::InitCommand),
-    /// This syncs RLP encoded blocks from a file.
+    /// This syncs RLP encoded blocks or Era1 archives from a file.
     #[command(name = "import")]
     Import(chain::ImportCommand),
+    /// Export blocks to Era1 archives.
+    #[command(name = "export")]
+    Export(chain::ExportCommand),
     /// Database debugging utilities
     #[command(name = "db")]
     Db(db::Command),
+    /// Snapshot distribution utilities
+    #[command(name = "snapshot")]
+    Snapshot(snapshot::Command),
     /// Manipulate individual stages.
     #[command(name = "stage")]
     Stage(stage::Command),
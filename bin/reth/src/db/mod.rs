@@ -224,7 +224,12 @@ impl Command {
                 command.execute(&db)?;
             }
             Subcommands::Snapshot(command) => {
-                command.execute(&db_path, self.db.log_level, self.chain.clone())?;
+                command.execute(
+                    &db_path,
+                    &data_dir.snapshots_path(),
+                    self.db.log_level,
+                    self.chain.clone(),
+                )?;
             }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
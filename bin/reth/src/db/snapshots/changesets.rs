@@ -0,0 +1,178 @@
+use super::{
+    bench::{bench, BenchKind},
+    Command, Compression,
+};
+use rand::seq::SliceRandom;
+use reth_db::{
+    cursor::DbCursorRO,
+    models::{
+        AccountStorageChangeSet, BlockAccountChangeSet, BlockNumberAddress, BlockStorageChangeSet,
+    },
+    open_db_read_only,
+    snapshot::{AccountChangeSetMask, StorageChangeSetMask},
+    tables,
+    transaction::DbTx,
+};
+use reth_interfaces::db::LogLevel;
+use reth_primitives::{snapshot::Filters, BlockNumber, ChainSpec, SnapshotSegment};
+use reth_provider::{
+    providers::SnapshotProvider, BlockNumReader, ChangeSetReader, ProviderError, ProviderFactory,
+    TransactionsProviderExt,
+};
+use std::{
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+impl Command {
+    pub(crate) fn bench_account_changesets_snapshot(
+        &self,
+        db_path: &Path,
+        log_level: Option<LogLevel>,
+        chain: Arc<ChainSpec>,
+        compression: Compression,
+    ) -> eyre::Result<()> {
+        let segment = SnapshotSegment::AccountChangeSets;
+        let (block_range, path) =
+            self.changesets_snapshot_path(db_path, log_level, chain.clone(), segment, compression)?;
+        let mut row_indexes = block_range.collect::<Vec<_>>();
+        let mut rng = rand::thread_rng();
+
+        let provider = SnapshotProvider::new(PathBuf::default())?;
+        let jar_provider =
+            provider.get_segment_provider_from_block(segment, self.from, Some(&path))?;
+        let mut cursor = jar_provider.cursor()?;
+
+        for bench_kind in [BenchKind::Walk, BenchKind::RandomAll] {
+            bench(
+                bench_kind,
+                (open_db_read_only(db_path, log_level)?, chain.clone()),
+                segment,
+                Filters::WithoutFilters,
+                compression,
+                || {
+                    let mut changesets = Vec::with_capacity(row_indexes.len());
+                    for num in row_indexes.iter() {
+                        changesets.push(
+                            cursor
+                                .get_one::<AccountChangeSetMask<BlockAccountChangeSet>>(
+                                    (*num).into(),
+                                )?
+                                .ok_or(ProviderError::MissingSnapshotBlock(segment, *num))?
+                                .accounts,
+                        );
+                    }
+                    Ok(changesets)
+                },
+                |provider| {
+                    let mut changesets = Vec::with_capacity(row_indexes.len());
+                    for num in row_indexes.iter() {
+                        changesets.push(provider.account_block_changeset(*num)?);
+                    }
+                    Ok(changesets)
+                },
+            )?;
+
+            // For random walk
+            row_indexes.shuffle(&mut rng);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn bench_storage_changesets_snapshot(
+        &self,
+        db_path: &Path,
+        log_level: Option<LogLevel>,
+        chain: Arc<ChainSpec>,
+        compression: Compression,
+    ) -> eyre::Result<()> {
+        let segment = SnapshotSegment::StorageChangeSets;
+        let (block_range, path) =
+            self.changesets_snapshot_path(db_path, log_level, chain.clone(), segment, compression)?;
+        let mut row_indexes = block_range.collect::<Vec<_>>();
+        let mut rng = rand::thread_rng();
+
+        let provider = SnapshotProvider::new(PathBuf::default())?;
+        let jar_provider =
+            provider.get_segment_provider_from_block(segment, self.from, Some(&path))?;
+        let mut cursor = jar_provider.cursor()?;
+
+        for bench_kind in [BenchKind::Walk, BenchKind::RandomAll] {
+            bench(
+                bench_kind,
+                (open_db_read_only(db_path, log_level)?, chain.clone()),
+                segment,
+                Filters::WithoutFilters,
+                compression,
+                || {
+                    let mut changesets = Vec::with_capacity(row_indexes.len());
+                    for num in row_indexes.iter() {
+                        changesets.push(
+                            cursor
+                                .get_one::<StorageChangeSetMask<BlockStorageChangeSet>>(
+                                    (*num).into(),
+                                )?
+                                .ok_or(ProviderError::MissingSnapshotBlock(segment, *num))?,
+                        );
+                    }
+                    Ok(changesets)
+                },
+                |provider| {
+                    let mut cursor = provider.tx_ref().cursor_read::<tables::StorageChangeSet>()?;
+                    let mut changesets = Vec::with_capacity(row_indexes.len());
+                    for num in row_indexes.iter() {
+                        let mut changeset = BlockStorageChangeSet::default();
+                        for entry in cursor.walk_range(BlockNumberAddress::range(*num..=*num))? {
+                            let (block_address, entry) = entry?;
+                            match changeset.accounts.last_mut() {
+                                Some(account) if account.address == block_address.address() => {
+                                    account.storage.push(entry)
+                                }
+                                _ => changeset.accounts.push(AccountStorageChangeSet {
+                                    address: block_address.address(),
+                                    storage: vec![entry],
+                                }),
+                            }
+                        }
+                        changesets.push(changeset);
+                    }
+                    Ok(changesets)
+                },
+            )?;
+
+            // For random walk
+            row_indexes.shuffle(&mut rng);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the block range and the path of the first generated changesets snapshot.
+    fn changesets_snapshot_path(
+        &self,
+        db_path: &Path,
+        log_level: Option<LogLevel>,
+        chain: Arc<ChainSpec>,
+        segment: SnapshotSegment,
+        compression: Compression,
+    ) -> eyre::Result<(RangeInclusive<BlockNumber>, PathBuf)> {
+        let provider =
+            ProviderFactory::new(open_db_read_only(db_path, log_level)?, chain).provider()?;
+        let tip = provider.last_block_number()?;
+        let block_range =
+            self.block_ranges(tip).first().expect("has been generated before").clone();
+        let tx_range = provider.transaction_range_by_block_range(block_range.clone())?;
+
+        let path = segment
+            .filename_with_configuration(
+                Filters::WithoutFilters,
+                compression,
+                &block_range,
+                &tx_range,
+            )
+            .into();
+        Ok((block_range, path))
+    }
+}
@@ -0,0 +1,320 @@
+use super::ZstdLongWindowArgs;
+use clap::{Parser, ValueEnum};
+use human_bytes::human_bytes;
+use rand::Rng;
+use reth_db::{open_db_read_only, DatabaseEnv};
+use reth_interfaces::db::LogLevel;
+use reth_nippy_jar::{filter::InclusionFilter as _, NippyJar, NippyJarCursor};
+use reth_primitives::{
+    snapshot::{
+        Compression, Filters, InclusionFilter, PerfectHashingFunction, SegmentHeader,
+        ZstdLongWindowConfig,
+    },
+    BlockNumber, ChainSpec, SnapshotSegment,
+};
+use reth_provider::{BlockNumReader, DatabaseProviderRO, ProviderFactory, TransactionsProviderExt};
+use reth_snapshot::{segments as snap_segments, segments::Segment};
+use std::{
+    ops::RangeInclusive,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Arguments for the `reth db snapshot bench` command.
+///
+/// Builds one snapshot of every segment for each combination of compression and inclusion filter
+/// into a temporary directory, and compares their compression ratio and lookup latency.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Snapshot segments to compare settings for.
+    #[arg(required = true)]
+    segments: Vec<SnapshotSegment>,
+
+    /// Starting block of the compared snapshots.
+    #[arg(long, short, default_value = "0")]
+    from: BlockNumber,
+
+    /// Number of blocks in the compared snapshots.
+    #[arg(long, short, default_value = "500000")]
+    block_interval: u64,
+
+    /// Compression algorithms to compare. Compares all of them if not set.
+    #[arg(long, short, value_delimiter = ',')]
+    compression: Vec<Compression>,
+
+    /// Inclusion filters to compare, on segments which use them. Compares all of them if not set.
+    #[arg(long, value_delimiter = ',')]
+    filter: Vec<InclusionFilter>,
+
+    /// Perfect hashing function used alongside the inclusion filters.
+    #[arg(long, default_value = "fmph")]
+    phf: PerfectHashingFunction,
+
+    /// Number of random lookups used to measure latency.
+    #[arg(long, default_value = "10000")]
+    lookups: usize,
+
+    #[clap(flatten)]
+    zstd_long_window: ZstdLongWindowArgs,
+}
+
+impl Command {
+    /// Execute `db snapshot bench` command
+    pub fn execute(
+        &self,
+        db_path: &Path,
+        log_level: Option<LogLevel>,
+        chain: Arc<ChainSpec>,
+    ) -> eyre::Result<()> {
+        let factory = ProviderFactory::new(open_db_read_only(db_path, log_level)?, chain);
+        let provider = factory.provider()?;
+
+        let tip = provider.last_block_number()?;
+        if self.from > tip {
+            eyre::bail!("starting block {} is past the database tip {tip}", self.from)
+        }
+        let block_range = self.from..=tip.min(self.from + self.block_interval.max(1) - 1);
+
+        let compressions = if self.compression.is_empty() {
+            Compression::value_variants().to_vec()
+        } else {
+            self.compression.clone()
+        };
+        let filters = if self.filter.is_empty() {
+            InclusionFilter::value_variants().to_vec()
+        } else {
+            self.filter.clone()
+        };
+
+        for segment in &self.segments {
+            let segment_filters = if segment.config().filters.has_filters() {
+                filters
+                    .iter()
+                    .map(|filter| Filters::WithFilters(*filter, self.phf))
+                    .collect::<Vec<_>>()
+            } else {
+                vec![Filters::WithoutFilters]
+            };
+
+            println!();
+            println!("## [{segment:?}] blocks {block_range:?}");
+            println!(
+                "{:<24} {:<14} {:>10} {:>7} {:<32} {:>10} {:>10} {:>12} {:>10} {:>12} {:>8}",
+                "compression",
+                "filter",
+                "size",
+                "ratio",
+                "column ratios",
+                "build",
+                "walk",
+                "random row",
+                "filter",
+                "filter query",
+                "fp rate",
+            );
+
+            for compression in &compressions {
+                for filters in &segment_filters {
+                    let result = self.bench_settings(
+                        &provider,
+                        *segment,
+                        *compression,
+                        *filters,
+                        block_range.clone(),
+                    )?;
+                    result.print(*compression, *filters);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Builds a snapshot of `segment` with the given settings in a temporary directory, and
+    /// measures it.
+    fn bench_settings(
+        &self,
+        provider: &DatabaseProviderRO<DatabaseEnv>,
+        segment: SnapshotSegment,
+        compression: Compression,
+        filters: Filters,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> eyre::Result<BenchResult> {
+        let dir = tempfile::tempdir()?;
+
+        let start = Instant::now();
+        snapshot_segment(
+            provider,
+            segment,
+            compression,
+            filters,
+            self.zstd_long_window.config()?,
+            dir.path(),
+            block_range.clone(),
+        )?;
+        let build = start.elapsed();
+
+        let tx_range = provider.transaction_range_by_block_range(block_range.clone())?;
+        let path = dir.path().join(segment.filename(&block_range, &tx_range));
+        let jar = NippyJar::<SegmentHeader>::load(&path)?.load_filters()?;
+
+        let column_sizes = jar.column_sizes()?;
+        let mut raw_column_sizes = vec![0u64; jar.columns()];
+        let mut cursor = NippyJarCursor::new(&jar)?;
+
+        // Decompresses every row, which also gives the size of the original values.
+        let start = Instant::now();
+        while let Some(row) = cursor.next_row()? {
+            for (size, value) in raw_column_sizes.iter_mut().zip(row) {
+                *size += value.len() as u64;
+            }
+        }
+        let walk = start.elapsed();
+
+        let mut rng = rand::thread_rng();
+        let random_row = if jar.rows() > 0 {
+            let rows = (0..self.lookups).map(|_| rng.gen_range(0..jar.rows())).collect::<Vec<_>>();
+            let start = Instant::now();
+            for row in rows {
+                cursor.row_by_number(row)?;
+            }
+            Some(start.elapsed() / self.lookups.max(1) as u32)
+        } else {
+            None
+        };
+
+        // Random keys are absent from the snapshot, so every hit is a false positive.
+        let filter_query = if filters.has_filters() {
+            let keys = (0..self.lookups).map(|_| rng.gen::<[u8; 32]>()).collect::<Vec<_>>();
+            let mut false_positives = 0;
+            let start = Instant::now();
+            for key in &keys {
+                if jar.contains(key)? {
+                    false_positives += 1;
+                }
+            }
+            Some((
+                start.elapsed() / self.lookups.max(1) as u32,
+                false_positives as f64 / self.lookups.max(1) as f64,
+            ))
+        } else {
+            None
+        };
+
+        Ok(BenchResult {
+            file_size: path.metadata()?.len(),
+            column_sizes,
+            raw_column_sizes,
+            build,
+            walk,
+            random_row,
+            filter_size: jar.filter_size(),
+            filter_query,
+        })
+    }
+}
+
+/// Snapshots `block_range` of `segment` into `directory` with the given settings.
+fn snapshot_segment(
+    provider: &DatabaseProviderRO<DatabaseEnv>,
+    segment: SnapshotSegment,
+    compression: Compression,
+    filters: Filters,
+    zstd_long_window: ZstdLongWindowConfig,
+    directory: &Path,
+    block_range: RangeInclusive<BlockNumber>,
+) -> eyre::Result<()> {
+    match segment {
+        SnapshotSegment::Headers => snap_segments::Headers::new(compression, filters)
+            .with_zstd_long_window(zstd_long_window)
+            .snapshot::<DatabaseEnv>(provider, directory, block_range)?,
+        SnapshotSegment::Transactions => snap_segments::Transactions::new(compression, filters)
+            .with_zstd_long_window(zstd_long_window)
+            .snapshot::<DatabaseEnv>(provider, directory, block_range)?,
+        SnapshotSegment::Receipts => snap_segments::Receipts::new(compression, filters)
+            .with_zstd_long_window(zstd_long_window)
+            .snapshot::<DatabaseEnv>(provider, directory, block_range)?,
+        SnapshotSegment::AccountChangeSets => {
+            snap_segments::AccountChangeSets::new(compression, filters)
+                .with_zstd_long_window(zstd_long_window)
+                .snapshot::<DatabaseEnv>(provider, directory, block_range)?
+        }
+        SnapshotSegment::StorageChangeSets => {
+            snap_segments::StorageChangeSets::new(compression, filters)
+                .with_zstd_long_window(zstd_long_window)
+                .snapshot::<DatabaseEnv>(provider, directory, block_range)?
+        }
+    }
+    Ok(())
+}
+
+/// Measurements of a snapshot built with one combination of settings.
+#[derive(Debug)]
+struct BenchResult {
+    /// Size of the data file.
+    file_size: u64,
+    /// Size of each column in the data file.
+    column_sizes: Vec<u64>,
+    /// Size of each column before compression.
+    raw_column_sizes: Vec<u64>,
+    /// Time taken to build the snapshot.
+    build: Duration,
+    /// Time taken to read every row.
+    walk: Duration,
+    /// Average latency of reading a random row by number.
+    random_row: Option<Duration>,
+    /// Size of the inclusion filter.
+    filter_size: usize,
+    /// Average latency of an inclusion filter query, and its false positive rate.
+    filter_query: Option<(Duration, f64)>,
+}
+
+impl BenchResult {
+    fn print(&self, compression: Compression, filters: Filters) {
+        let ratio = |raw: u64, compressed: u64| {
+            if compressed == 0 {
+                "-".to_string()
+            } else {
+                format!("{:.2}x", raw as f64 / compressed as f64)
+            }
+        };
+        let filter = match filters {
+            Filters::WithFilters(filter, phf) => format!("{}-{}", filter.as_ref(), phf.as_ref()),
+            Filters::WithoutFilters => "none".to_string(),
+        };
+        let column_ratios = self
+            .raw_column_sizes
+            .iter()
+            .zip(&self.column_sizes)
+            .map(|(raw, compressed)| ratio(*raw, *compressed))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let (filter_query, false_positives) = match self.filter_query {
+            Some((latency, rate)) => {
+                (format!("{:.2} µs", micros(latency)), format!("{:.2}%", rate * 100.0))
+            }
+            None => ("-".to_string(), "-".to_string()),
+        };
+
+        println!(
+            "{:<24} {:<14} {:>10} {:>7} {:<32} {:>7.2} s {:>7} ms {:>12} {:>10} {:>12} {:>8}",
+            format!("{compression:?}"),
+            filter,
+            human_bytes(self.file_size as f64),
+            ratio(self.raw_column_sizes.iter().sum(), self.column_sizes.iter().sum()),
+            column_ratios,
+            self.build.as_secs_f64(),
+            self.walk.as_millis(),
+            self.random_row.map_or("-".to_string(), |latency| format!("{:.2} µs", micros(latency))),
+            human_bytes(self.filter_size as f64),
+            filter_query,
+            false_positives,
+        );
+    }
+}
+
+/// Returns `duration` in microseconds, with sub-microsecond precision.
+fn micros(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1_000.0
+}
@@ -1,4 +1,4 @@
-use clap::{builder::RangedU64ValueParser, Parser};
+use clap::{builder::RangedU64ValueParser, Parser, Subcommand};
 use human_bytes::human_bytes;
 use itertools::Itertools;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
@@ -6,7 +6,10 @@ use reth_db::{database::Database, open_db_read_only, DatabaseEnv};
 use reth_interfaces::db::LogLevel;
 use reth_nippy_jar::{NippyJar, NippyJarCursor};
 use reth_primitives::{
-    snapshot::{Compression, Filters, InclusionFilter, PerfectHashingFunction, SegmentHeader},
+    snapshot::{
+        Compression, Filters, InclusionFilter, PerfectHashingFunction, SegmentHeader,
+        ZstdLongWindowConfig,
+    },
     BlockNumber, ChainSpec, SnapshotSegment,
 };
 use reth_provider::{BlockNumReader, ProviderFactory, TransactionsProviderExt};
@@ -19,9 +22,12 @@ use std::{
 };
 
 mod bench;
+mod changesets;
+mod compare;
 mod headers;
 mod receipts;
 mod transactions;
+mod verify;
 
 #[derive(Parser, Debug)]
 /// Arguments for the `reth db snapshot` command.
@@ -62,6 +68,9 @@ pub struct Command {
     #[arg(long, short, value_delimiter = ',', default_value = "uncompressed")]
     compression: Vec<Compression>,
 
+    #[clap(flatten)]
+    zstd_long_window: ZstdLongWindowArgs,
+
     /// Flag to enable inclusion list filters and PHFs.
     #[arg(long, default_value = "false")]
     with_filters: bool,
@@ -69,6 +78,42 @@ pub struct Command {
     /// Specifies the perfect hashing function to use.
     #[arg(long, value_delimiter = ',', default_value_if("with_filters", "true", "fmph"))]
     phf: Vec<PerfectHashingFunction>,
+
+    #[command(subcommand)]
+    command: Option<Subcommands>,
+}
+
+/// Parameters of `zstd-long-window` compression.
+#[derive(Parser, Debug)]
+pub(crate) struct ZstdLongWindowArgs {
+    /// Compression level of `zstd-long-window` compression, between 1 and 22.
+    #[arg(long = "zstd-long.level", default_value_t = ZstdLongWindowConfig::DEFAULT.level())]
+    level: i32,
+
+    /// Window log of the long distance matching of `zstd-long-window` compression, between 10
+    /// and 27.
+    #[arg(
+        long = "zstd-long.window-log",
+        default_value_t = ZstdLongWindowConfig::DEFAULT.window_log()
+    )]
+    window_log: u32,
+}
+
+impl ZstdLongWindowArgs {
+    /// Returns the validated configuration.
+    pub(crate) fn config(&self) -> eyre::Result<ZstdLongWindowConfig> {
+        Ok(ZstdLongWindowConfig::new(self.level, self.window_log)?)
+    }
+}
+
+#[derive(Subcommand, Debug)]
+/// `reth db snapshot` subcommands
+pub enum Subcommands {
+    /// Verifies snapshot files against their checksums and reports the corrupted rows
+    Verify(verify::Command),
+    /// Compares the compression ratio and lookup latency of snapshots built with different
+    /// compression and filter settings
+    Bench(compare::Command),
 }
 
 impl Command {
@@ -76,9 +121,17 @@ impl Command {
     pub fn execute(
         self,
         db_path: &Path,
+        snapshots_path: &Path,
         log_level: Option<LogLevel>,
         chain: Arc<ChainSpec>,
     ) -> eyre::Result<()> {
+        match &self.command {
+            Some(Subcommands::Verify(command)) => return command.execute(snapshots_path),
+            Some(Subcommands::Bench(command)) => return command.execute(db_path, log_level, chain),
+            None => {}
+        }
+
+        let zstd_long_window = self.zstd_long_window.config()?;
         let all_combinations =
             self.segments.iter().cartesian_product(self.compression.iter()).cartesian_product(
                 if self.phf.is_empty() {
@@ -103,16 +156,31 @@ impl Command {
                     match mode {
                         SnapshotSegment::Headers => self.generate_snapshot::<DatabaseEnv>(
                             factory.clone(),
-                            snap_segments::Headers::new(*compression, filters),
+                            snap_segments::Headers::new(*compression, filters)
+                                .with_zstd_long_window(zstd_long_window),
                         )?,
                         SnapshotSegment::Transactions => self.generate_snapshot::<DatabaseEnv>(
                             factory.clone(),
-                            snap_segments::Transactions::new(*compression, filters),
+                            snap_segments::Transactions::new(*compression, filters)
+                                .with_zstd_long_window(zstd_long_window),
                         )?,
                         SnapshotSegment::Receipts => self.generate_snapshot::<DatabaseEnv>(
                             factory.clone(),
-                            snap_segments::Receipts::new(*compression, filters),
+                            snap_segments::Receipts::new(*compression, filters)
+                                .with_zstd_long_window(zstd_long_window),
                         )?,
+                        SnapshotSegment::AccountChangeSets => self
+                            .generate_snapshot::<DatabaseEnv>(
+                                factory.clone(),
+                                snap_segments::AccountChangeSets::new(*compression, filters)
+                                    .with_zstd_long_window(zstd_long_window),
+                            )?,
+                        SnapshotSegment::StorageChangeSets => self
+                            .generate_snapshot::<DatabaseEnv>(
+                                factory.clone(),
+                                snap_segments::StorageChangeSets::new(*compression, filters)
+                                    .with_zstd_long_window(zstd_long_window),
+                            )?,
                     }
                 }
             }
@@ -145,6 +213,18 @@ impl Command {
                         InclusionFilter::Cuckoo,
                         phf,
                     )?,
+                    SnapshotSegment::AccountChangeSets => self.bench_account_changesets_snapshot(
+                        db_path,
+                        log_level,
+                        chain.clone(),
+                        *compression,
+                    )?,
+                    SnapshotSegment::StorageChangeSets => self.bench_storage_changesets_snapshot(
+                        db_path,
+                        log_level,
+                        chain.clone(),
+                        *compression,
+                    )?,
                 }
             }
         }
@@ -0,0 +1,79 @@
+use clap::Parser;
+use reth_db::snapshot::iter_snapshots;
+use reth_nippy_jar::{NippyJar, Verification};
+use reth_primitives::snapshot::SegmentHeader;
+use std::path::{Path, PathBuf};
+
+/// Arguments for the `reth db snapshot verify` command.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Snapshot files, or directories of snapshot files, to verify. Defaults to the snapshots
+    /// directory of the data dir.
+    paths: Vec<PathBuf>,
+
+    /// Number of chunks to verify per snapshot, evenly spread over the file. Verifies every chunk
+    /// if not set.
+    #[arg(long)]
+    sample: Option<usize>,
+}
+
+impl Command {
+    /// Execute `db snapshot verify` command
+    pub fn execute(&self, snapshots_path: &Path) -> eyre::Result<()> {
+        let verification = self.sample.map_or(Verification::Full, Verification::Sampled);
+
+        let paths = if self.paths.is_empty() {
+            vec![snapshots_path.to_path_buf()]
+        } else {
+            self.paths.clone()
+        };
+
+        let mut files = Vec::new();
+        for path in paths {
+            if path.is_dir() {
+                for (segment, ranges) in iter_snapshots(&path)? {
+                    files.extend(ranges.into_iter().map(|(block_range, tx_range)| {
+                        path.join(segment.filename(&block_range, &tx_range))
+                    }));
+                }
+            } else {
+                files.push(path);
+            }
+        }
+        files.sort();
+
+        let mut corrupted = 0;
+        for file in &files {
+            let jar = NippyJar::<SegmentHeader>::load(file)?;
+            let corruptions = jar.verify(verification)?;
+            let name = file.file_name().unwrap_or_default().to_string_lossy();
+
+            if jar.checksums().is_none() {
+                println!("{name}: has no checksums, only checked for missing rows");
+            }
+
+            if corruptions.is_empty() {
+                println!("{name}: OK ({} rows)", jar.rows());
+                continue
+            }
+
+            corrupted += 1;
+            let header = jar.user_header();
+            let unit = if header.segment().is_block_based() { "blocks" } else { "transactions" };
+            for corruption in corruptions {
+                let rows = corruption.rows();
+                println!(
+                    "{name}: {corruption} ({unit} {}..={})",
+                    header.start() + rows.start as u64,
+                    header.start() + rows.end.saturating_sub(1) as u64
+                );
+            }
+        }
+
+        if corrupted > 0 {
+            eyre::bail!("{corrupted} out of {} snapshots are corrupted", files.len())
+        }
+
+        Ok(())
+    }
+}
@@ -41,6 +41,7 @@ pub mod p2p;
 pub mod prometheus_exporter;
 pub mod recover;
 pub mod runner;
+pub mod snapshot;
 pub mod stage;
 pub mod test_vectors;
 pub mod utils;
@@ -6,7 +6,7 @@ use crate::{
         get_secret_key,
         utils::{chain_help, genesis_value_parser, parse_socket_address, SUPPORTED_CHAINS},
         DatabaseArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs,
-        RpcServerArgs, TxPoolArgs,
+        RpcServerArgs, SnapshotArgs, SyncMode, TxPoolArgs,
     },
     cli::{
         components::RethNodeComponentsImpl,
@@ -49,20 +49,25 @@ use reth_interfaces::{
         either::EitherDownloader,
         headers::{client::HeadersClient, downloader::HeaderDownloader},
     },
+    provider::ProviderError,
     RethResult,
 };
-use reth_network::{NetworkBuilder, NetworkConfig, NetworkEvents, NetworkHandle, NetworkManager};
+use reth_network::{
+    NetworkBuilder, NetworkConfig, NetworkEvents, NetworkHandle, NetworkManager, PacketCapture,
+};
 use reth_network_api::{NetworkInfo, PeersInfo};
 use reth_primitives::{
     constants::eip4844::{LoadKzgSettingsError, MAINNET_KZG_TRUSTED_SETUP},
     fs,
     kzg::KzgSettings,
     stage::StageId,
-    BlockHashOrNumber, BlockNumber, ChainSpec, DisplayHardforks, Head, SealedHeader, B256,
+    BlockHashOrNumber, BlockNumber, ChainSpec, DisplayHardforks, Head, PruneModes, SealedHeader,
+    B256,
 };
 use reth_provider::{
-    providers::BlockchainProvider, BlockHashReader, BlockReader, CanonStateSubscriptions,
-    HeaderProvider, HeaderSyncMode, ProviderFactory, StageCheckpointReader,
+    providers::{BlockchainProvider, CachedNodeDataProvider},
+    BlockHashReader, BlockNumReader, BlockReader, CanonStateSubscriptions, HeaderProvider,
+    HeaderSyncMode, NodeDataProvider, ProviderFactory, StageCheckpointReader,
 };
 use reth_prune::{segments::SegmentSet, Pruner};
 use reth_revm::EvmProcessorFactory;
@@ -79,10 +84,13 @@ use reth_stages::{
 };
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::{
-    blobstore::InMemoryBlobStore, TransactionPool, TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore, journal::TransactionJournalConfig, TransactionPool,
+    TransactionValidationTaskExecutor,
 };
+use reth_trie::TrieNodeCache;
 use secp256k1::SecretKey;
 use std::{
+    collections::HashSet,
     net::{SocketAddr, SocketAddrV4},
     path::PathBuf,
     sync::Arc,
@@ -150,6 +158,13 @@ pub struct NodeCommand<Ext: RethCliExt = ()> {
     #[arg(long, value_name = "PATH")]
     pub trusted_setup_file: Option<PathBuf>,
 
+    /// Which parts of the chain the node syncs.
+    ///
+    /// In `headers-only` mode only the header chain is downloaded and followed, state related RPC
+    /// methods are not available.
+    #[arg(long, value_enum, value_name = "MODE", default_value_t, conflicts_with = "dev")]
+    pub sync_mode: SyncMode,
+
     /// All networking related arguments
     #[clap(flatten)]
     pub network: NetworkArgs,
@@ -174,6 +189,10 @@ pub struct NodeCommand<Ext: RethCliExt = ()> {
     #[clap(flatten)]
     pub db: DatabaseArgs,
 
+    /// All snapshot related arguments
+    #[clap(flatten)]
+    pub snapshots: SnapshotArgs,
+
     /// All dev related arguments with --dev prefix
     #[clap(flatten)]
     pub dev: DevArgs,
@@ -203,6 +222,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             metrics,
             trusted_setup_file,
             instance,
+            sync_mode,
             network,
             rpc,
             txpool,
@@ -222,6 +242,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             metrics,
             instance,
             trusted_setup_file,
+            sync_mode,
             network,
             rpc,
             txpool,
@@ -265,8 +286,11 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             self.chain.snapshot_block_interval,
         )?;
 
-        provider_factory = provider_factory
-            .with_snapshots(data_dir.snapshots_path(), snapshotter.highest_snapshot_receiver())?;
+        provider_factory = provider_factory.with_snapshots(
+            data_dir.snapshots_path(),
+            snapshotter.highest_snapshot_receiver(),
+            self.snapshots.verification(),
+        )?;
 
         self.start_metrics_endpoint(prometheus_handle, Arc::clone(&db)).await?;
 
@@ -285,13 +309,28 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
 
         let prune_config =
             self.pruning.prune_config(Arc::clone(&self.chain))?.or(config.prune.clone());
+        if let Some(prune_config) = &prune_config {
+            provider_factory = provider_factory
+                .with_history_retention(prune_config.segments.history_retention.clone());
+        }
+
+        let backfill_target = prepare_prune_backfill(
+            &provider_factory,
+            &prune_config.as_ref().map(|config| config.segments.clone()).unwrap_or_default(),
+        )?;
+
+        // recently computed trie nodes, used to serve `GetNodeData` requests
+        let trie_node_cache = self.network.trie_node_cache();
 
         // configure blockchain tree
-        let tree_externals = TreeExternals::new(
+        let mut tree_externals = TreeExternals::new(
             provider_factory.clone(),
             Arc::clone(&consensus),
             EvmProcessorFactory::new(self.chain.clone()),
         );
+        if let Some(trie_node_cache) = trie_node_cache.clone() {
+            tree_externals = tree_externals.with_node_cache(trie_node_cache);
+        }
         let tree_config = BlockchainTreeConfig::default();
         let tree = BlockchainTree::new(
             tree_externals,
@@ -310,17 +349,36 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         // setup the blockchain provider
         let blockchain_db =
             BlockchainProvider::new(provider_factory.clone(), blockchain_tree.clone())?;
-        let blob_store = InMemoryBlobStore::default();
+        let blob_store =
+            DiskFileBlobStore::open(data_dir.blobstore_path(), self.txpool.blob_store_config())?;
         let validator = TransactionValidationTaskExecutor::eth_builder(Arc::clone(&self.chain))
             .with_head_timestamp(head.timestamp)
             .kzg_settings(self.kzg_settings()?)
             .with_additional_tasks(1)
             .build_with_tasks(blockchain_db.clone(), ctx.task_executor.clone(), blob_store.clone());
 
-        let transaction_pool =
-            reth_transaction_pool::Pool::eth_pool(validator, blob_store, self.txpool.pool_config());
+        let transaction_pool = reth_transaction_pool::Pool::eth_pool(
+            validator,
+            blob_store.clone(),
+            self.txpool.pool_config(),
+        );
         info!(target: "reth::cli", "Transaction pool initialized");
 
+        // remove all blob sidecars left over from a previous run that are neither in the pool nor
+        // included in a block within the retention period
+        let blob_store_tracker = reth_transaction_pool::maintain::load_retained_blob_txs(
+            &blockchain_db,
+            head.number,
+            self.txpool.blob_retention,
+        )?;
+        {
+            let mut keep =
+                transaction_pool.pooled_transaction_hashes().into_iter().collect::<HashSet<_>>();
+            keep.extend(blob_store_tracker.blob_txs().copied());
+            let removed = blob_store.reconcile(&keep);
+            debug!(target: "reth::cli", removed, "Reconciled blob store");
+        }
+
         // spawn txpool maintenance task
         {
             let pool = transaction_pool.clone();
@@ -328,23 +386,44 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             let client = blockchain_db.clone();
             ctx.task_executor.spawn_critical(
                 "txpool maintenance task",
-                reth_transaction_pool::maintain::maintain_transaction_pool_future(
+                reth_transaction_pool::maintain::maintain_transaction_pool_future_with_tracker(
                     client,
                     pool,
                     chain_events,
                     ctx.task_executor.clone(),
-                    Default::default(),
+                    self.txpool.maintain_config(),
+                    blob_store_tracker,
                 ),
             );
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
         }
 
+        if self.txpool.journal {
+            let pool = transaction_pool.clone();
+            let task_executor = ctx.task_executor.clone();
+            let config = TransactionJournalConfig::new(data_dir.txpool_journal_path())
+                .with_rotation_interval(self.txpool.rejournal);
+            ctx.task_executor.spawn_critical_with_graceful_shutdown_signal(
+                "local transactions journal task",
+                |shutdown| {
+                    reth_transaction_pool::journal::journal_local_transactions(
+                        pool,
+                        task_executor,
+                        config,
+                        shutdown,
+                    )
+                },
+            );
+            debug!(target: "reth::cli", "Spawned local transactions journal task");
+        }
+
         info!(target: "reth::cli", "Connecting to P2P network");
         let network_secret_path =
             self.network.p2p_secret_key.clone().unwrap_or_else(|| data_dir.p2p_secret_path());
         debug!(target: "reth::cli", ?network_secret_path, "Loading p2p key file");
         let secret_key = get_secret_key(&network_secret_path)?;
         let default_peers_path = data_dir.known_peers_path();
+        let packet_capture = self.network.packet_capture()?;
         let network_config = self.load_network_config(
             &config,
             provider_factory.clone(),
@@ -352,6 +431,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             head,
             secret_key,
             default_peers_path.clone(),
+            packet_capture,
         );
 
         let network_client = network_config.client.clone();
@@ -375,6 +455,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             transaction_pool.clone(),
             network_client,
             default_peers_path,
+            trie_node_cache.clone(),
         );
 
         info!(target: "reth::cli", peer_id = %network.peer_id(), local_addr = %network.local_addr(), enode = %network.local_node_record(), "Connected to P2P network");
@@ -431,6 +512,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                     sync_metrics_tx,
                     prune_config.clone(),
                     max_block,
+                    trie_node_cache,
                 )
                 .await?;
 
@@ -451,6 +533,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                     sync_metrics_tx,
                     prune_config.clone(),
                     max_block,
+                    trie_node_cache,
                 )
                 .await?;
 
@@ -463,6 +546,10 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             // Set the provided tip as the initial pipeline target.
             debug!(target: "reth::cli", %tip, "Tip manually set");
             Some(tip)
+        } else if let Some(tip) = backfill_target {
+            // Backfill the loosened prune segments before any block is made canonical.
+            debug!(target: "reth::cli", %tip, "Backfilling loosened prune segments");
+            Some(tip)
         } else if self.debug.continuous {
             // Set genesis as the initial pipeline target.
             // This will allow the downloader to start
@@ -492,21 +579,23 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         };
 
         // Configure the consensus engine
-        let (beacon_consensus_engine, beacon_engine_handle) = BeaconConsensusEngine::with_channel(
-            client,
-            pipeline,
-            blockchain_db.clone(),
-            Box::new(ctx.task_executor.clone()),
-            Box::new(network.clone()),
-            max_block,
-            self.debug.continuous,
-            payload_builder.clone(),
-            initial_target,
-            MIN_BLOCKS_FOR_PIPELINE_RUN,
-            consensus_engine_tx,
-            consensus_engine_rx,
-            hooks,
-        )?;
+        let (mut beacon_consensus_engine, beacon_engine_handle) =
+            BeaconConsensusEngine::with_channel(
+                client,
+                pipeline,
+                blockchain_db.clone(),
+                Box::new(ctx.task_executor.clone()),
+                Box::new(network.clone()),
+                max_block,
+                self.debug.continuous,
+                payload_builder.clone(),
+                initial_target,
+                MIN_BLOCKS_FOR_PIPELINE_RUN,
+                consensus_engine_tx,
+                consensus_engine_rx,
+                hooks,
+            )?;
+        beacon_consensus_engine.set_headers_only(self.sync_mode.is_headers_only());
         info!(target: "reth::cli", "Consensus engine initialized");
 
         let events = stream_select!(
@@ -532,7 +621,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             blockchain_db.clone(),
             self.chain.clone(),
             beacon_engine_handle,
-            payload_builder.into(),
+            payload_builder.clone().into(),
             Box::new(ctx.task_executor.clone()),
         );
         info!(target: "reth::cli", "Engine API handler initialized");
@@ -545,8 +634,17 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         self.adjust_instance_ports();
 
         // Start RPC servers
-        let _rpc_server_handles =
-            self.rpc.start_servers(&components, engine_api, jwt_secret, &mut self.ext).await?;
+        self.rpc.headers_only = self.sync_mode.is_headers_only();
+        let _rpc_server_handles = self
+            .rpc
+            .start_servers(
+                &components,
+                engine_api,
+                payload_builder.into(),
+                jwt_secret,
+                &mut self.ext,
+            )
+            .await?;
 
         // Run consensus engine to completion
         let (tx, rx) = oneshot::channel();
@@ -616,6 +714,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         metrics_tx: reth_stages::MetricEventsSender,
         prune_config: Option<PruneConfig>,
         max_block: Option<BlockNumber>,
+        trie_node_cache: Option<TrieNodeCache>,
     ) -> eyre::Result<Pipeline<DB>>
     where
         DB: Database + Unpin + Clone + 'static,
@@ -641,6 +740,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                 self.debug.continuous,
                 metrics_tx,
                 prune_config,
+                trie_node_cache,
             )
             .await?;
 
@@ -725,13 +825,18 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         pool: Pool,
         client: C,
         default_peers_path: PathBuf,
+        trie_node_cache: Option<TrieNodeCache>,
     ) -> NetworkHandle
     where
-        C: BlockReader + HeaderProvider + Clone + Unpin + 'static,
+        C: BlockReader + HeaderProvider + NodeDataProvider + Clone + Unpin + 'static,
         Pool: TransactionPool + Unpin + 'static,
     {
-        let (handle, network, txpool, eth) =
-            builder.transactions(pool).request_handler(client).split_with_handle();
+        let (handle, network, txpool, mut eth) =
+            builder.transactions(pool).request_handler(client.clone()).split_with_handle();
+
+        if let Some(trie_node_cache) = trie_node_cache {
+            eth = eth.with_node_data_provider(CachedNodeDataProvider::new(client, trie_node_cache));
+        }
 
         task_executor.spawn_critical("p2p txpool", txpool);
         task_executor.spawn_critical("p2p eth request handler", eth);
@@ -828,6 +933,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn load_network_config<DB: Database>(
         &self,
         config: &Config,
@@ -836,8 +942,9 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         head: Head,
         secret_key: SecretKey,
         default_peers_path: PathBuf,
+        packet_capture: Option<PacketCapture>,
     ) -> NetworkConfig<ProviderFactory<DB>> {
-        let cfg_builder = self
+        let mut cfg_builder = self
             .network
             .network_config(config, self.chain.clone(), secret_key, default_peers_path)
             .with_task_executor(Box::new(executor))
@@ -853,6 +960,10 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                 self.network.port + self.instance - 1,
             )));
 
+        if let Some(capture) = packet_capture {
+            cfg_builder = cfg_builder.packet_capture(capture);
+        }
+
         // When `sequencer_endpoint` is configured, the node will forward all transactions to a
         // Sequencer node for execution and inclusion on L1, and disable its own txpool
         // gossip to prevent other parties in the network from learning about them.
@@ -876,6 +987,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         continuous: bool,
         metrics_tx: reth_stages::MetricEventsSender,
         prune_config: Option<PruneConfig>,
+        trie_node_cache: Option<TrieNodeCache>,
     ) -> eyre::Result<Pipeline<DB>>
     where
         DB: Database + Clone + 'static,
@@ -910,11 +1022,35 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
 
         let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+        let mut merkle_stage = MerkleStage::new_execution(config.merkle.clean_threshold);
+        if let Some(trie_node_cache) = trie_node_cache {
+            merkle_stage = merkle_stage.with_node_cache(trie_node_cache);
+        }
+
         let header_mode =
             if continuous { HeaderSyncMode::Continuous } else { HeaderSyncMode::Tip(tip_rx) };
+        let builder = builder.with_tip_sender(tip_tx).with_metrics_tx(metrics_tx.clone());
+
+        if self.sync_mode.is_headers_only() {
+            info!(target: "reth::cli", "Only syncing headers");
+            let pipeline = builder
+                .add_stages(
+                    HeaderOnlyStages::new(
+                        provider_factory.clone(),
+                        header_mode,
+                        Arc::clone(&consensus),
+                        header_downloader,
+                    )
+                    .set(
+                        TotalDifficultyStage::new(consensus)
+                            .with_commit_threshold(config.total_difficulty.commit_threshold),
+                    ),
+                )
+                .build(provider_factory);
+            return Ok(pipeline)
+        }
+
         let pipeline = builder
-            .with_tip_sender(tip_tx)
-            .with_metrics_tx(metrics_tx.clone())
             .add_stages(
                 DefaultStages::new(
                     provider_factory.clone(),
@@ -956,18 +1092,24 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                     config.storage_hashing.clean_threshold,
                     config.storage_hashing.commit_threshold,
                 ))
-                .set(MerkleStage::new_execution(config.merkle.clean_threshold))
+                .set(merkle_stage)
                 .set(TransactionLookupStage::new(
                     config.transaction_lookup.commit_threshold,
                     prune_modes.transaction_lookup,
                 ))
+                // Retained history needs to be indexed in full, so the history of other addresses
+                // is pruned by the pruner instead.
                 .set(IndexAccountHistoryStage::new(
                     config.index_account_history.commit_threshold,
-                    prune_modes.account_history,
+                    prune_modes
+                        .account_history
+                        .filter(|_| prune_modes.history_retention.is_empty()),
                 ))
                 .set(IndexStorageHistoryStage::new(
                     config.index_storage_history.commit_threshold,
-                    prune_modes.storage_history,
+                    prune_modes
+                        .storage_history
+                        .filter(|_| prune_modes.history_retention.is_empty()),
                 )),
             )
             .build(provider_factory);
@@ -1004,13 +1146,20 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                 config.segments.sender_recovery.map(reth_prune::segments::SenderRecovery::new),
             )
             // Account history
-            .segment_opt(
-                config.segments.account_history.map(reth_prune::segments::AccountHistory::new),
-            )
+            .segment_opt(config.segments.account_history.map(|mode| {
+                reth_prune::segments::AccountHistory::new(mode)
+                    .with_retention(config.segments.history_retention.clone())
+            }))
             // Storage history
-            .segment_opt(
-                config.segments.storage_history.map(reth_prune::segments::StorageHistory::new),
-            );
+            .segment_opt(config.segments.storage_history.map(|mode| {
+                reth_prune::segments::StorageHistory::new(mode)
+                    .with_retention(config.segments.history_retention.clone())
+            }))
+            // Bytecodes
+            .segment_opt(config.segments.bytecodes.map(|mode| {
+                reth_prune::segments::Bytecodes::new(mode)
+                    .with_retention(config.segments.history_retention.clone())
+            }));
 
         Pruner::new(
             provider_factory,
@@ -1033,6 +1182,29 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
     }
 }
 
+/// Prepares the data of the segments with a loosened prune configuration to be backfilled by the
+/// pipeline, or rejects the configuration if the data can't be restored.
+///
+/// Returns the hash of the tip if any segment needs to be backfilled. The pipeline must run to it
+/// before the blockchain tree makes new blocks canonical, because that moves all stage checkpoints
+/// to the new tip.
+fn prepare_prune_backfill<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    prune_modes: &PruneModes,
+) -> eyre::Result<Option<B256>> {
+    let provider = provider_factory.provider_rw()?;
+    let backfills = reth_prune::prepare_backfill(&provider, prune_modes)?;
+    if backfills.is_empty() {
+        return Ok(None)
+    }
+
+    let tip = provider.last_block_number()?;
+    let tip_hash = provider.block_hash(tip)?.ok_or(ProviderError::HeaderNotFound(tip.into()))?;
+    provider.commit()?;
+
+    Ok(Some(tip_hash))
+}
+
 /// Drives the [NetworkManager] future until a [Shutdown](reth_tasks::shutdown::Shutdown) signal is
 /// received. If configured, this writes known peers to `persistent_peers_file` afterwards.
 async fn run_network_until_shutdown<C>(
@@ -1075,7 +1247,13 @@ async fn run_network_until_shutdown<C>(
 mod tests {
     use super::*;
     use crate::args::utils::SUPPORTED_CHAINS;
+    use reth_db::test_utils::create_test_rw_db;
     use reth_discv4::DEFAULT_DISCOVERY_PORT;
+    use reth_interfaces::test_utils::{generators, generators::random_block_range};
+    use reth_primitives::{
+        stage::StageCheckpoint, PruneCheckpoint, PruneMode, PruneSegment, MAINNET,
+    };
+    use reth_provider::{BlockWriter, PruneCheckpointWriter, StageCheckpointWriter};
     use std::{
         net::{IpAddr, Ipv4Addr},
         path::Path,
@@ -1228,4 +1406,43 @@ mod tests {
         // check network listening port number
         assert_eq!(cmd.network.port, 30305);
     }
+
+    #[test]
+    fn prepare_prune_backfill_targets_tip() {
+        let factory = ProviderFactory::new(create_test_rw_db(), MAINNET.clone());
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=20, B256::ZERO, 1..3);
+
+        let provider = factory.provider_rw().unwrap();
+        for block in &blocks {
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None).unwrap();
+        }
+        provider.save_stage_checkpoint(StageId::SenderRecovery, StageCheckpoint::new(20)).unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::SenderRecovery,
+                PruneCheckpoint {
+                    block_number: Some(15),
+                    tx_number: None,
+                    prune_mode: PruneMode::Distance(5),
+                },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        // Unchanged configuration doesn't need a pipeline run
+        let modes =
+            PruneModes { sender_recovery: Some(PruneMode::Distance(5)), ..PruneModes::none() };
+        assert_eq!(prepare_prune_backfill(&factory, &modes).unwrap(), None);
+
+        // Loosened configuration runs the pipeline to the tip, from the moved back checkpoint
+        assert_eq!(
+            prepare_prune_backfill(&factory, &PruneModes::none()).unwrap(),
+            Some(blocks[20].hash())
+        );
+        assert_eq!(
+            factory.provider().unwrap().get_stage_checkpoint(StageId::SenderRecovery).unwrap(),
+            Some(StageCheckpoint::new(0))
+        );
+    }
 }
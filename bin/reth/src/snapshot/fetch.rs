@@ -0,0 +1,286 @@
+use clap::Parser;
+use reth_nippy_jar::{NippyJar, Verification};
+use reth_primitives::{snapshot::SegmentHeader, SnapshotSegment};
+use reth_provider::providers::SnapshotProvider;
+use reth_snapshot::{ManifestSnapshot, SnapshotManifest, MANIFEST_FILE_NAME};
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// Arguments for the `reth snapshot fetch` command.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Where snapshots are fetched from: an `http(s)://` or `file://` URL, or a local directory.
+    ///
+    /// It must contain a `manifest.json` and the files listed in it.
+    #[arg(value_name = "URL", verbatim_doc_comment)]
+    url: String,
+
+    /// Snapshot segments to fetch. Fetches all segments of the manifest if not set.
+    #[arg(long, value_delimiter = ',')]
+    segments: Vec<SnapshotSegment>,
+}
+
+impl Command {
+    /// Execute `snapshot fetch` command
+    pub async fn execute(self, snapshots_path: &Path) -> eyre::Result<()> {
+        let source = SnapshotSource::new(&self.url);
+
+        info!(target: "reth::cli", url = %self.url, "Fetching snapshot manifest");
+        let manifest: SnapshotManifest =
+            serde_json::from_slice(&source.read(MANIFEST_FILE_NAME).await?)?;
+
+        reth_primitives::fs::create_dir_all(snapshots_path)?;
+        let provider = SnapshotProvider::new(snapshots_path)?;
+
+        let fetched =
+            fetch_snapshots(&source, &manifest, &self.segments, snapshots_path, &provider).await?;
+        info!(target: "reth::cli", fetched, "Fetched snapshots");
+
+        let segments = manifest
+            .segments(&self.segments)
+            .map(|snapshot| snapshot.segment)
+            .collect::<BTreeSet<_>>();
+        for segment in segments {
+            if let Some(block) = provider.get_highest_snapshot_block(segment) {
+                info!(target: "reth::cli", %segment, block, "Highest snapshotted block");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Downloads every snapshot of `segments` in `manifest` which is missing from `directory`, or
+/// doesn't match the manifest, and registers them with the [`SnapshotProvider`].
+///
+/// Returns the number of fetched snapshots.
+async fn fetch_snapshots(
+    source: &SnapshotSource,
+    manifest: &SnapshotManifest,
+    segments: &[SnapshotSegment],
+    directory: &Path,
+    provider: &SnapshotProvider,
+) -> eyre::Result<usize> {
+    let mut fetched = 0;
+
+    for snapshot in manifest.segments(segments) {
+        snapshot.validate()?;
+
+        let missing = snapshot.missing_files(directory)?;
+        if missing.is_empty() {
+            continue
+        }
+
+        info!(target: "reth::cli", snapshot = snapshot.filename(), files = missing.len(), "Fetching snapshot");
+
+        // The data file is what makes a snapshot visible to the provider, so it goes last.
+        let data_file = snapshot.filename();
+        let (data, supporting): (Vec<_>, Vec<_>) =
+            missing.into_iter().partition(|file| file.name == data_file);
+
+        for file in supporting.into_iter().chain(data) {
+            let path = directory.join(&file.name);
+            let part_path = directory.join(format!("{}.part", file.name));
+
+            source.download(&file.name, &part_path, file.size).await?;
+            if !file.verify(&part_path)? {
+                eyre::bail!("downloaded {} does not match the manifest", file.name)
+            }
+            reth_primitives::fs::rename(&part_path, &path)?;
+        }
+
+        if let Err(err) = verify_snapshot(snapshot, directory) {
+            // Don't leave a snapshot behind which the provider would pick up later on.
+            for file in &snapshot.files {
+                let _ = std::fs::remove_file(directory.join(&file.name));
+            }
+            return Err(err)
+        }
+        fetched += 1;
+    }
+
+    provider.update_index()?;
+
+    Ok(fetched)
+}
+
+/// Checks the fetched snapshot data against its own checksums, and its header against the
+/// manifest.
+fn verify_snapshot(snapshot: &ManifestSnapshot, directory: &Path) -> eyre::Result<()> {
+    NippyJar::<SegmentHeader>::load_verified(
+        &directory.join(snapshot.filename()),
+        Verification::Full,
+    )?;
+    snapshot.verify_header(directory)?;
+    Ok(())
+}
+
+/// Location snapshots are fetched from.
+#[derive(Debug)]
+enum SnapshotSource {
+    /// HTTP(S) server, with the base URL of the files.
+    Http { client: reqwest::Client, url: String },
+    /// Local directory.
+    File(PathBuf),
+}
+
+impl SnapshotSource {
+    /// Creates a [`SnapshotSource`] from an `http(s)://` or `file://` URL, or a local path.
+    fn new(url: &str) -> Self {
+        if url.starts_with("http://") || url.starts_with("https://") {
+            Self::Http {
+                client: reqwest::Client::new(),
+                url: url.trim_end_matches('/').to_string(),
+            }
+        } else {
+            Self::File(PathBuf::from(url.strip_prefix("file://").unwrap_or(url)))
+        }
+    }
+
+    /// Reads the file `name` into memory.
+    async fn read(&self, name: &str) -> eyre::Result<Vec<u8>> {
+        match self {
+            Self::Http { client, url } => {
+                let response =
+                    client.get(format!("{url}/{name}")).send().await?.error_for_status()?;
+                Ok(response.bytes().await?.to_vec())
+            }
+            Self::File(path) => Ok(reth_primitives::fs::read(path.join(name))?),
+        }
+    }
+
+    /// Downloads the file `name` to `destination`, failing if it's larger than `max_size` bytes.
+    async fn download(&self, name: &str, destination: &Path, max_size: u64) -> eyre::Result<()> {
+        match self {
+            Self::Http { client, url } => {
+                let mut response =
+                    client.get(format!("{url}/{name}")).send().await?.error_for_status()?;
+                let mut file = File::create(destination)?;
+                let mut size = 0;
+                while let Some(chunk) = response.chunk().await? {
+                    size += chunk.len() as u64;
+                    if size > max_size {
+                        eyre::bail!("{name} is larger than the {max_size} bytes of the manifest")
+                    }
+                    file.write_all(&chunk)?;
+                }
+                file.sync_all()?;
+            }
+            Self::File(path) => {
+                let source = path.join(name);
+                if source.metadata()?.len() > max_size {
+                    eyre::bail!("{name} is larger than the {max_size} bytes of the manifest")
+                }
+                std::fs::copy(source, destination)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_nippy_jar::{ColumnResult, DEFAULT_CHECKSUM_CHUNK_ROWS};
+
+    /// Creates a headers snapshot of blocks and transactions `0..=9` in `directory`.
+    fn create_snapshot(directory: &Path) {
+        let (block_range, tx_range) = (0..=9, 0..=9);
+        let segment = SnapshotSegment::Headers;
+        let mut jar = NippyJar::new(
+            1,
+            &directory.join(segment.filename(&block_range, &tx_range)),
+            SegmentHeader::new(block_range, tx_range, segment),
+        )
+        .with_checksums(DEFAULT_CHECKSUM_CHUNK_ROWS);
+        let rows = (0..10u8).map(|i| Ok(vec![i; 32])).collect::<Vec<ColumnResult<Vec<u8>>>>();
+        jar.freeze(vec![rows], 10).unwrap();
+    }
+
+    #[tokio::test]
+    async fn fetch_from_directory() {
+        let server_dir = tempfile::tempdir().unwrap();
+        let node_dir = tempfile::tempdir().unwrap();
+        let segment = SnapshotSegment::Headers;
+        create_snapshot(server_dir.path());
+
+        let manifest = SnapshotManifest::from_dir(server_dir.path()).unwrap();
+        let source = SnapshotSource::new(&format!("file://{}", server_dir.path().display()));
+        let provider = SnapshotProvider::new(node_dir.path()).unwrap();
+
+        // Nothing to fetch for other segments
+        let fetched = fetch_snapshots(
+            &source,
+            &manifest,
+            &[SnapshotSegment::Transactions],
+            node_dir.path(),
+            &provider,
+        )
+        .await
+        .unwrap();
+        assert_eq!(fetched, 0);
+        assert_eq!(provider.get_highest_snapshot_block(segment), None);
+
+        let fetched =
+            fetch_snapshots(&source, &manifest, &[], node_dir.path(), &provider).await.unwrap();
+        assert_eq!(fetched, 1);
+        assert_eq!(provider.get_highest_snapshot_block(segment), Some(9));
+        assert!(manifest.snapshots[0].missing_files(node_dir.path()).unwrap().is_empty());
+
+        // Already fetched snapshots are skipped
+        let fetched =
+            fetch_snapshots(&source, &manifest, &[], node_dir.path(), &provider).await.unwrap();
+        assert_eq!(fetched, 0);
+    }
+
+    #[tokio::test]
+    async fn fetch_rejects_invalid_manifest() {
+        let server_dir = tempfile::tempdir().unwrap();
+        let node_dir = tempfile::tempdir().unwrap();
+        create_snapshot(server_dir.path());
+
+        let manifest = SnapshotManifest::from_dir(server_dir.path()).unwrap();
+        let source = SnapshotSource::new(&format!("file://{}", server_dir.path().display()));
+        let provider = SnapshotProvider::new(node_dir.path()).unwrap();
+        let fetch = |manifest: SnapshotManifest| {
+            let (source, provider, directory) = (&source, &provider, node_dir.path());
+            async move { fetch_snapshots(source, &manifest, &[], directory, provider).await }
+        };
+
+        // A file outside of the snapshots directory
+        let mut invalid = manifest.clone();
+        invalid.snapshots[0].files[1].name = "../snapshot_headers_0_9_0_9.off".to_string();
+        assert!(fetch(invalid).await.is_err());
+
+        // A file which doesn't belong to the snapshot
+        let mut invalid = manifest.clone();
+        invalid.snapshots[0].files[1].name = "manifest.json".to_string();
+        assert!(fetch(invalid).await.is_err());
+
+        // A file larger than listed in the manifest
+        let mut invalid = manifest.clone();
+        invalid.snapshots[0].files[0].size -= 1;
+        assert!(fetch(invalid).await.is_err());
+
+        // A snapshot whose header doesn't match the ranges of the manifest
+        let mut invalid = manifest.clone();
+        let snapshot = &mut invalid.snapshots[0];
+        snapshot.block_range = 0..=19;
+        for file in &mut snapshot.files {
+            let name = file.name.replace("_0_9_0_9", "_0_19_0_9");
+            std::fs::copy(server_dir.path().join(&file.name), server_dir.path().join(&name))
+                .unwrap();
+            file.name = name;
+        }
+        assert!(fetch(invalid).await.is_err());
+
+        assert!(!node_dir.path().join("snapshot_headers_0_9_0_9").exists());
+        assert!(!node_dir.path().join("snapshot_headers_0_19_0_9").exists());
+        assert_eq!(provider.get_highest_snapshot_block(SnapshotSegment::Headers), None);
+    }
+}
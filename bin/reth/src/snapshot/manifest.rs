@@ -0,0 +1,32 @@
+use clap::Parser;
+use reth_snapshot::{SnapshotManifest, MANIFEST_FILE_NAME};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Arguments for the `reth snapshot manifest` command.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Directory of the snapshots to list. Defaults to the snapshots directory of the data dir.
+    #[arg(long, value_name = "DIR")]
+    path: Option<PathBuf>,
+
+    /// Where to write the manifest. Defaults to `manifest.json` inside the snapshots directory.
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+impl Command {
+    /// Execute `snapshot manifest` command
+    pub fn execute(self, snapshots_path: &Path) -> eyre::Result<()> {
+        let path = self.path.unwrap_or_else(|| snapshots_path.to_path_buf());
+        let output = self.output.unwrap_or_else(|| path.join(MANIFEST_FILE_NAME));
+
+        info!(target: "reth::cli", ?path, "Hashing snapshot files");
+        let manifest = SnapshotManifest::from_dir(&path)?;
+
+        reth_primitives::fs::write(&output, serde_json::to_vec_pretty(&manifest)?)?;
+        info!(target: "reth::cli", snapshots = manifest.snapshots.len(), ?output, "Wrote manifest");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+//! `reth snapshot` command.
+use crate::{
+    args::utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::{Parser, Subcommand};
+use reth_primitives::ChainSpec;
+use std::sync::Arc;
+
+mod fetch;
+mod manifest;
+
+/// `reth snapshot` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t, global = true)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser,
+        global = true,
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[clap(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth snapshot` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Writes the manifest of the snapshots directory, listing every snapshot file with its size
+    /// and checksum
+    Manifest(manifest::Command),
+    /// Downloads the snapshots listed in a manifest which are missing from the snapshots directory
+    Fetch(fetch::Command),
+}
+
+impl Command {
+    /// Execute `snapshot` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        // add network name to data dir
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let snapshots_path = data_dir.snapshots_path();
+
+        match self.command {
+            Subcommands::Manifest(command) => command.execute(&snapshots_path),
+            Subcommands::Fetch(command) => command.execute(&snapshots_path).await,
+        }
+    }
+}
@@ -28,6 +28,7 @@ use reth_provider::{
     ChainSpecProvider, DisplayBlocksChain, ExecutorFactory, HeaderProvider, ProviderError,
 };
 use reth_stages::{MetricEvent, MetricEventsSender};
+use reth_trie::ParallelStateRoot;
 use std::{
     collections::{BTreeMap, HashSet},
     sync::Arc,
@@ -1161,14 +1162,11 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
     fn commit_canonical_to_database(&self, chain: Chain) -> RethResult<()> {
         // Compute state root before opening write transaction.
         let hashed_state = chain.state().hash_state_slow();
-        let (state_root, trie_updates) = chain
-            .state()
-            .state_root_calculator(
-                self.externals.provider_factory.provider()?.tx_ref(),
-                &hashed_state,
-            )
-            .root_with_updates()
-            .map_err(Into::<DatabaseError>::into)?;
+        let (state_root, trie_updates) =
+            ParallelStateRoot::new(self.externals.provider_factory.db_ref(), &hashed_state)
+                .with_node_cache(self.externals.node_cache.clone())
+                .incremental_root_with_updates()
+                .map_err(Into::<DatabaseError>::into)?;
         let tip = chain.tip();
         if state_root != tip.state_root {
             return Err(RethError::Provider(ProviderError::StateRootMismatch(Box::new(
@@ -4,7 +4,7 @@
 //! blocks, as well as a list of the blocks the chain is composed of.
 use super::externals::TreeExternals;
 use crate::BundleStateDataRef;
-use reth_db::database::Database;
+use reth_db::{database::Database, DatabaseError};
 use reth_interfaces::{
     blockchain_tree::{
         error::{BlockchainTreeError, InsertBlockError},
@@ -18,8 +18,9 @@ use reth_primitives::{
 };
 use reth_provider::{
     providers::BundleStateProvider, BundleStateDataProvider, BundleStateWithReceipts, Chain,
-    ExecutorFactory, StateRootProvider,
+    ExecutorFactory,
 };
+use reth_trie::ParallelStateRoot;
 use std::{
     collections::BTreeMap,
     ops::{Deref, DerefMut},
@@ -207,7 +208,7 @@ impl AppendableChain {
         let state_provider =
             externals.provider_factory.history_by_block_number(canonical_fork.number)?;
 
-        let provider = BundleStateProvider::new(state_provider, bundle_state_data_provider);
+        let provider = BundleStateProvider::new(state_provider, &bundle_state_data_provider);
 
         let mut executor = externals.executor_factory.with_state(&provider);
         let block = block.unseal();
@@ -217,8 +218,16 @@ impl AppendableChain {
         // check state root if the block extends the canonical chain __and__ if state root
         // validation was requested.
         if block_kind.extends_canonical_head() && block_validation_kind.is_exhaustive() {
-            // check state root
-            let state_root = provider.state_root(&bundle_state)?;
+            // the canonical fork is the database tip, so the root of the chain state on top of
+            // the database state can be computed in parallel
+            let mut state = bundle_state_data_provider.state().clone();
+            state.extend(bundle_state.clone());
+            let hashed_state = state.hash_state_slow();
+            let state_root =
+                ParallelStateRoot::new(externals.provider_factory.db_ref(), &hashed_state)
+                    .with_node_cache(externals.node_cache.clone())
+                    .incremental_root()
+                    .map_err(Into::<DatabaseError>::into)?;
             if block.state_root != state_root {
                 return Err(ConsensusError::BodyStateRootDiff(
                     GotExpected { got: state_root, expected: block.state_root }.into(),
@@ -4,6 +4,7 @@ use reth_db::{cursor::DbCursorRO, database::Database, tables, transaction::DbTx}
 use reth_interfaces::{consensus::Consensus, RethResult};
 use reth_primitives::{BlockHash, BlockNumber};
 use reth_provider::ProviderFactory;
+use reth_trie::TrieNodeCache;
 use std::{collections::BTreeMap, sync::Arc};
 
 /// A container for external components.
@@ -23,6 +24,8 @@ pub struct TreeExternals<DB, EF> {
     pub(crate) consensus: Arc<dyn Consensus>,
     /// The executor factory to execute blocks with.
     pub(crate) executor_factory: EF,
+    /// The cache that is populated with the trie nodes computed by state root calculations.
+    pub(crate) node_cache: Option<TrieNodeCache>,
 }
 
 impl<DB, EF> TreeExternals<DB, EF> {
@@ -32,7 +35,14 @@ impl<DB, EF> TreeExternals<DB, EF> {
         consensus: Arc<dyn Consensus>,
         executor_factory: EF,
     ) -> Self {
-        Self { provider_factory, consensus, executor_factory }
+        Self { provider_factory, consensus, executor_factory, node_cache: None }
+    }
+
+    /// Sets the cache that is populated with the trie nodes computed when the state root of
+    /// canonical blocks is calculated.
+    pub fn with_node_cache(mut self, node_cache: TrieNodeCache) -> Self {
+        self.node_cache = Some(node_cache);
+        self
     }
 }
 
@@ -152,7 +152,9 @@ enum SnapshotterState<DB> {
 impl From<SnapshotterError> for EngineHookError {
     fn from(err: SnapshotterError) -> Self {
         match err {
-            SnapshotterError::InconsistentData(_) => EngineHookError::Internal(Box::new(err)),
+            SnapshotterError::InconsistentData(_) |
+            SnapshotterError::NippyJar(_) |
+            SnapshotterError::Io(_) => EngineHookError::Internal(Box::new(err)),
             SnapshotterError::Interface(err) => err.into(),
             SnapshotterError::Database(err) => RethError::Database(err).into(),
             SnapshotterError::Provider(err) => RethError::Provider(err).into(),
@@ -210,6 +210,11 @@ where
     /// be used to download and execute the missing blocks.
     pipeline_run_threshold: u64,
     hooks: EngineHooksController,
+    /// Whether the node only syncs the header chain.
+    ///
+    /// In this mode no blocks are executed: forkchoice updates only move the canonical header
+    /// chain and new payloads are never validated.
+    headers_only: bool,
 }
 
 impl<DB, BT, Client> BeaconConsensusEngine<DB, BT, Client>
@@ -308,6 +313,7 @@ where
             metrics: EngineMetrics::default(),
             pipeline_run_threshold,
             hooks: EngineHooksController::new(hooks),
+            headers_only: false,
         };
 
         let maybe_pipeline_target = match target {
@@ -366,6 +372,14 @@ where
         Ok(None)
     }
 
+    /// Configures the engine to only follow the header chain.
+    ///
+    /// This must be used together with a pipeline that only syncs headers, see
+    /// [`HeaderOnlyStages`](reth_stages::sets::HeaderOnlyStages).
+    pub fn set_headers_only(&mut self, headers_only: bool) {
+        self.headers_only = headers_only;
+    }
+
     /// Returns a new [`BeaconConsensusEngineHandle`] that can be cloned and shared.
     ///
     /// The [`BeaconConsensusEngineHandle`] can be used to interact with this
@@ -662,6 +676,10 @@ where
             return Ok(OnForkChoiceUpdated::syncing());
         }
 
+        if self.headers_only {
+            return self.headers_only_forkchoice_updated(state)
+        }
+
         let start = Instant::now();
         let make_canonical_result = self.blockchain.make_canonical(&state.head_block_hash);
         let elapsed = self.record_make_canonical_latency(start, &make_canonical_result);
@@ -767,6 +785,52 @@ where
         Ok(OnForkChoiceUpdated::valid(status))
     }
 
+    /// Handles a forkchoice update if the engine only follows the header chain.
+    ///
+    /// If the pipeline already synced the head header, it becomes the new canonical head,
+    /// otherwise the head is set as the new pipeline target. Because blocks are never executed the
+    /// head can't be considered `VALID`, so this always returns a `SYNCING` response and payload
+    /// attributes are ignored.
+    fn headers_only_forkchoice_updated(
+        &mut self,
+        state: ForkchoiceState,
+    ) -> RethResult<OnForkChoiceUpdated> {
+        match self.canonical_header_by_hash(state.head_block_hash)? {
+            Some(head) => {
+                debug!(target: "consensus::engine", hash=?head.hash, number=head.number, "Updating canonical header chain head");
+                self.update_head(head)?;
+                if let Some(safe) = self.canonical_header_by_hash(state.safe_block_hash)? {
+                    self.blockchain.set_safe(safe);
+                }
+                if let Some(finalized) =
+                    self.canonical_header_by_hash(state.finalized_block_hash)?
+                {
+                    self.blockchain.set_finalized(finalized);
+                }
+            }
+            None => {
+                trace!(target: "consensus::engine", head=?state.head_block_hash, "Head header is missing, syncing headers");
+                self.sync.set_pipeline_sync_target(state.head_block_hash);
+            }
+        }
+
+        Ok(OnForkChoiceUpdated::syncing())
+    }
+
+    /// Returns the header with the given hash if it is part of the canonical header chain in the
+    /// database.
+    fn canonical_header_by_hash(&self, hash: B256) -> RethResult<Option<SealedHeader>> {
+        if hash.is_zero() {
+            return Ok(None)
+        }
+        let Some(header) = self.blockchain.header(&hash)? else { return Ok(None) };
+        if self.blockchain.block_hash(header.number)? != Some(hash) {
+            // the header was synced but is no longer part of the canonical chain
+            return Ok(None)
+        }
+        Ok(Some(header.seal(hash)))
+    }
+
     /// Record latency metrics for one call to make a block canonical
     /// Takes start time of the call and result of the make canonical call
     ///
@@ -1122,6 +1186,12 @@ where
             Ok(block) => block,
             Err(status) => return Ok(status),
         };
+
+        if self.headers_only {
+            // blocks are never executed, the header chain is only advanced by the pipeline
+            return Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))
+        }
+
         let block_hash = block.hash();
         let block_num_hash = block.num_hash();
 
@@ -1583,6 +1653,24 @@ where
                     return None;
                 }
 
+                if self.headers_only {
+                    // the blockchain tree is not used if only headers are synced, the pipeline
+                    // progress is the tip of the canonical header chain
+                    if let Some(progress) = ctrl.block_number() {
+                        match self.blockchain.sealed_header(progress) {
+                            Ok(Some(header)) => self.blockchain.set_canonical_head(header),
+                            Ok(None) => {
+                                return Some(Err(RethError::Provider(
+                                    ProviderError::HeaderNotFound(progress.into()),
+                                )
+                                .into()))
+                            }
+                            Err(error) => return Some(Err(RethError::Provider(error).into())),
+                        }
+                    }
+                    return None
+                }
+
                 // update the canon chain if continuous is enabled
                 if self.sync.run_pipeline_continuously() {
                     let max_block = ctrl.block_number().unwrap_or_default();
@@ -1882,7 +1970,7 @@ mod tests {
     use assert_matches::assert_matches;
     use reth_interfaces::test_utils::generators::{self, Rng};
     use reth_primitives::{stage::StageCheckpoint, ChainSpec, ChainSpecBuilder, B256, MAINNET};
-    use reth_provider::{BlockWriter, ProviderFactory};
+    use reth_provider::{BlockNumReader, BlockWriter, ProviderFactory};
     use reth_rpc_types::engine::{ForkchoiceState, ForkchoiceUpdated, PayloadStatus};
     use reth_rpc_types_compat::engine::payload::try_block_to_payload_v1;
     use reth_stages::{ExecOutput, PipelineError, StageError};
@@ -2021,6 +2109,33 @@ mod tests {
         );
     }
 
+    // Test that the consensus engine runs the pipeline to the target it was started with before
+    // any forkchoice update is received, as the node does to backfill loosened prune segments.
+    #[tokio::test]
+    async fn runs_pipeline_to_initial_target() {
+        let mut rng = generators::rng();
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(MAINNET.genesis.clone())
+                .paris_activated()
+                .build(),
+        );
+
+        let (consensus_engine, _env) = TestConsensusEngineBuilder::new(chain_spec.clone())
+            .with_pipeline_exec_outputs(VecDeque::from([Err(StageError::ChannelClosed)]))
+            .with_pipeline_target(rng.gen())
+            .disable_blockchain_tree_sync()
+            .build();
+
+        let rx = spawn_consensus_engine(consensus_engine);
+
+        assert_matches!(
+            rx.await,
+            Ok(Err(BeaconConsensusEngineError::Pipeline(n))) if matches!(*n.as_ref(), PipelineError::Stage(StageError::ChannelClosed))
+        );
+    }
+
     #[tokio::test]
     async fn terminates_upon_reaching_max_block() {
         let mut rng = generators::rng();
@@ -2157,6 +2272,50 @@ mod tests {
             assert_matches!(engine_rx.try_recv(), Err(TryRecvError::Empty));
         }
 
+        #[tokio::test]
+        async fn headers_only_forkchoice() {
+            let mut rng = generators::rng();
+            let chain_spec = Arc::new(
+                ChainSpecBuilder::default()
+                    .chain(MAINNET.chain)
+                    .genesis(MAINNET.genesis.clone())
+                    .paris_activated()
+                    .build(),
+            );
+
+            let (mut consensus_engine, env) = TestConsensusEngineBuilder::new(chain_spec.clone())
+                .with_pipeline_exec_outputs(VecDeque::from([Ok(ExecOutput {
+                    checkpoint: StageCheckpoint::new(0),
+                    done: true,
+                })]))
+                .build();
+            consensus_engine.set_headers_only(true);
+            let blockchain = consensus_engine.blockchain.clone();
+
+            let genesis = random_block(&mut rng, 0, None, None, Some(0));
+            let block1 = random_block(&mut rng, 1, Some(genesis.hash), None, Some(0));
+            insert_blocks(env.db.as_ref(), chain_spec.clone(), [&genesis, &block1].into_iter());
+
+            let mut engine_rx = spawn_consensus_engine(consensus_engine);
+
+            // the head is known, but it was never executed
+            let forkchoice = ForkchoiceState {
+                head_block_hash: block1.hash,
+                finalized_block_hash: block1.hash,
+                ..Default::default()
+            };
+            let result = env.send_forkchoice_updated(forkchoice).await.unwrap();
+            assert_eq!(result, ForkchoiceUpdated::from_status(PayloadStatusEnum::Syncing));
+            assert_matches!(engine_rx.try_recv(), Err(TryRecvError::Empty));
+
+            // the head header became the canonical head
+            let chain_info = blockchain.chain_info().unwrap();
+            assert_eq!(chain_info.best_hash, block1.hash);
+            assert_eq!(chain_info.best_number, block1.number);
+            assert_eq!(blockchain.best_block_number().unwrap(), block1.number);
+            assert_eq!(blockchain.finalized_block_hash().unwrap(), Some(block1.hash));
+        }
+
         #[tokio::test]
         async fn unknown_head_hash() {
             let mut rng = generators::rng();
@@ -287,6 +287,7 @@ pub struct TestConsensusEngineBuilder {
     executor_config: TestExecutorConfig,
     pipeline_run_threshold: Option<u64>,
     max_block: Option<BlockNumber>,
+    pipeline_target: Option<B256>,
     consensus: TestConsensusConfig,
 }
 
@@ -299,6 +300,7 @@ impl TestConsensusEngineBuilder {
             executor_config: Default::default(),
             pipeline_run_threshold: None,
             max_block: None,
+            pipeline_target: None,
             consensus: Default::default(),
         }
     }
@@ -324,6 +326,12 @@ impl TestConsensusEngineBuilder {
         self
     }
 
+    /// Sets the target the pipeline runs to when the engine is started.
+    pub fn with_pipeline_target(mut self, target: B256) -> Self {
+        self.pipeline_target = Some(target);
+        self
+    }
+
     /// Uses the real pipeline instead of a pipeline with empty exec outputs.
     pub fn with_real_pipeline(mut self) -> Self {
         self.pipeline_config = TestPipelineConfig::Real;
@@ -406,6 +414,13 @@ where
         self
     }
 
+    /// Sets the target the pipeline runs to when the engine is started.
+    #[allow(dead_code)]
+    pub fn with_pipeline_target(mut self, target: B256) -> Self {
+        self.base_config.pipeline_target = Some(target);
+        self
+    }
+
     /// Uses the real pipeline instead of a pipeline with empty exec outputs.
     #[allow(dead_code)]
     pub fn with_real_pipeline(mut self) -> Self {
@@ -538,7 +553,7 @@ where
             None,
             false,
             payload_builder,
-            None,
+            self.base_config.pipeline_target,
             self.base_config.pipeline_run_threshold.unwrap_or(MIN_BLOCKS_FOR_PIPELINE_RUN),
             hooks,
         )
@@ -0,0 +1,85 @@
+use crate::MAX_BLOCKS_PER_ERA1;
+use reth_primitives::{B256, U256};
+use sha2::{Digest, Sha256};
+
+/// The depth of the header accumulator tree, `log2(MAX_BLOCKS_PER_ERA1)`.
+const ACCUMULATOR_DEPTH: usize = MAX_BLOCKS_PER_ERA1.trailing_zeros() as usize;
+
+/// An entry of the header accumulator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderRecord {
+    /// The hash of the block.
+    pub block_hash: B256,
+    /// The total difficulty of the block.
+    pub total_difficulty: U256,
+}
+
+// === impl HeaderRecord ===
+
+impl HeaderRecord {
+    /// Returns the SSZ `hash_tree_root` of the record.
+    fn tree_root(&self) -> B256 {
+        sha256_pair(self.block_hash.as_slice(), &self.total_difficulty.to_le_bytes::<32>())
+    }
+}
+
+/// Computes the root of the header accumulator of the given records.
+///
+/// This is the SSZ `hash_tree_root` of a `List[HeaderRecord, 8192]`, as used by Era1 archives.
+///
+/// # Panics
+///
+/// If there are more than [`MAX_BLOCKS_PER_ERA1`] records.
+pub fn accumulator_root(records: &[HeaderRecord]) -> B256 {
+    assert!(records.len() <= MAX_BLOCKS_PER_ERA1, "too many header records");
+
+    let mut zero_hash = B256::ZERO;
+    let mut layer = records.iter().map(HeaderRecord::tree_root).collect::<Vec<_>>();
+    for _ in 0..ACCUMULATOR_DEPTH {
+        if layer.len() % 2 == 1 {
+            layer.push(zero_hash);
+        }
+        layer = layer
+            .chunks(2)
+            .map(|pair| sha256_pair(pair[0].as_slice(), pair[1].as_slice()))
+            .collect();
+        zero_hash = sha256_pair(zero_hash.as_slice(), zero_hash.as_slice());
+    }
+    let root = layer.first().copied().unwrap_or(zero_hash);
+
+    // mix in the length of the list
+    sha256_pair(root.as_slice(), &U256::from(records.len()).to_le_bytes::<32>())
+}
+
+fn sha256_pair(left: &[u8], right: &[u8]) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_accumulator() {
+        // the root of an empty list is the zero hash of the tree mixed with a zero length
+        let mut zero_hash = B256::ZERO;
+        for _ in 0..ACCUMULATOR_DEPTH {
+            zero_hash = sha256_pair(zero_hash.as_slice(), zero_hash.as_slice());
+        }
+        assert_eq!(accumulator_root(&[]), sha256_pair(zero_hash.as_slice(), &[0u8; 32]));
+    }
+
+    #[test]
+    fn accumulator_commits_to_records() {
+        let record =
+            HeaderRecord { block_hash: B256::with_last_byte(1), total_difficulty: U256::from(1) };
+        let root = accumulator_root(&[record]);
+
+        let other = HeaderRecord { total_difficulty: U256::from(2), ..record };
+        assert_ne!(root, accumulator_root(&[other]));
+        assert_ne!(root, accumulator_root(&[record, record]));
+    }
+}
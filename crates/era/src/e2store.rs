@@ -0,0 +1,213 @@
+//! The [e2store](https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md) container
+//! format.
+//!
+//! An e2store file is a sequence of type-length-value entries. Each entry starts with an 8 byte
+//! header: the little endian `u16` entry type, the little endian `u32` length of the data and two
+//! reserved zero bytes.
+
+use crate::EraError;
+use std::io::{self, Read, Write};
+
+/// The size of an entry header.
+pub const HEADER_SIZE: usize = 8;
+
+/// The maximum size of the data of an entry.
+///
+/// The length in the entry header is read from the file, so entries above this size are rejected
+/// instead of allocating a buffer of up to 4 GiB.
+pub const MAX_ENTRY_SIZE: usize = 64 * 1024 * 1024;
+
+/// The version entry, the first entry of every file.
+pub const VERSION: u16 = 0x3265;
+/// A snappy compressed, rlp encoded block header.
+pub const COMPRESSED_HEADER: u16 = 0x03;
+/// A snappy compressed, rlp encoded block body.
+pub const COMPRESSED_BODY: u16 = 0x04;
+/// Snappy compressed, rlp encoded block receipts.
+pub const COMPRESSED_RECEIPTS: u16 = 0x05;
+/// The little endian total difficulty of a block.
+pub const TOTAL_DIFFICULTY: u16 = 0x06;
+/// The root of the header accumulator.
+pub const ACCUMULATOR: u16 = 0x07;
+/// The index of the block offsets.
+pub const BLOCK_INDEX: u16 = 0x3266;
+
+/// A single e2store entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The type of the entry.
+    pub entry_type: u16,
+    /// The data of the entry.
+    pub data: Vec<u8>,
+}
+
+// === impl Entry ===
+
+impl Entry {
+    /// Creates a new entry.
+    pub fn new(entry_type: u16, data: Vec<u8>) -> Self {
+        Self { entry_type, data }
+    }
+
+    /// Returns an error if the entry is not of the expected type.
+    pub fn ensure_type(&self, expected: u16) -> Result<(), EraError> {
+        if self.entry_type != expected {
+            return Err(EraError::UnexpectedEntry { expected, got: self.entry_type })
+        }
+        Ok(())
+    }
+
+    /// Returns the size of the encoded entry.
+    pub fn encoded_len(&self) -> usize {
+        HEADER_SIZE + self.data.len()
+    }
+}
+
+/// Reads [`Entry`]s from an e2store file.
+#[derive(Debug)]
+pub struct E2StoreReader<R> {
+    reader: R,
+}
+
+impl<R: Read> E2StoreReader<R> {
+    /// Creates a new reader.
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Reads the next entry.
+    ///
+    /// Returns `None` if the end of the file was reached.
+    pub fn read_entry(&mut self) -> Result<Option<Entry>, EraError> {
+        let mut header = [0u8; HEADER_SIZE];
+        let mut read = 0;
+        while read < HEADER_SIZE {
+            match self.reader.read(&mut header[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(EraError::UnexpectedEof),
+                Ok(n) => read += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let entry_type = u16::from_le_bytes([header[0], header[1]]);
+        let len = u32::from_le_bytes([header[2], header[3], header[4], header[5]]);
+        if header[6..] != [0, 0] {
+            return Err(EraError::InvalidEntryHeader)
+        }
+
+        let len = len as usize;
+        if len > MAX_ENTRY_SIZE {
+            return Err(EraError::EntryTooLarge { entry_type, len })
+        }
+
+        let mut data = vec![0u8; len];
+        self.reader.read_exact(&mut data).map_err(|err| {
+            if err.kind() == io::ErrorKind::UnexpectedEof {
+                EraError::UnexpectedEof
+            } else {
+                err.into()
+            }
+        })?;
+
+        Ok(Some(Entry { entry_type, data }))
+    }
+
+    /// Reads the next entry and returns an error if there is none.
+    pub fn expect_entry(&mut self) -> Result<Entry, EraError> {
+        self.read_entry()?.ok_or(EraError::UnexpectedEof)
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+/// Writes [`Entry`]s to an e2store file.
+#[derive(Debug)]
+pub struct E2StoreWriter<W> {
+    writer: W,
+    /// The number of bytes written so far.
+    position: u64,
+}
+
+impl<W: Write> E2StoreWriter<W> {
+    /// Creates a new writer.
+    pub fn new(writer: W) -> Self {
+        Self { writer, position: 0 }
+    }
+
+    /// Returns the number of bytes written so far, which is the offset of the next entry.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Writes the entry and returns its offset.
+    pub fn write_entry(&mut self, entry: &Entry) -> Result<u64, EraError> {
+        let len = u32::try_from(entry.data.len()).map_err(|_| EraError::InvalidEntryLength {
+            entry_type: entry.entry_type,
+            len: entry.data.len(),
+        })?;
+
+        let mut header = [0u8; HEADER_SIZE];
+        header[..2].copy_from_slice(&entry.entry_type.to_le_bytes());
+        header[2..6].copy_from_slice(&len.to_le_bytes());
+        self.writer.write_all(&header)?;
+        self.writer.write_all(&entry.data)?;
+
+        let offset = self.position;
+        self.position += entry.encoded_len() as u64;
+        Ok(offset)
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<(), EraError> {
+        Ok(self.writer.flush()?)
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_roundtrip() {
+        let mut writer = E2StoreWriter::new(Vec::new());
+        assert_eq!(writer.write_entry(&Entry::new(VERSION, Vec::new())).unwrap(), 0);
+        assert_eq!(writer.write_entry(&Entry::new(ACCUMULATOR, vec![1; 32])).unwrap(), 8);
+        assert_eq!(writer.position(), 48);
+
+        let buf = writer.into_inner();
+        assert_eq!(&buf[..8], &[0x65, 0x32, 0, 0, 0, 0, 0, 0]);
+
+        let mut reader = E2StoreReader::new(&buf[..]);
+        assert_eq!(reader.read_entry().unwrap(), Some(Entry::new(VERSION, Vec::new())));
+        assert_eq!(reader.read_entry().unwrap(), Some(Entry::new(ACCUMULATOR, vec![1; 32])));
+        assert_eq!(reader.read_entry().unwrap(), None);
+
+        // truncated entry
+        let mut reader = E2StoreReader::new(&buf[..20]);
+        reader.read_entry().unwrap();
+        assert!(matches!(reader.read_entry(), Err(EraError::UnexpectedEof)));
+    }
+
+    #[test]
+    fn reject_oversized_entry() {
+        let mut header = [0u8; HEADER_SIZE];
+        header[..2].copy_from_slice(&COMPRESSED_BODY.to_le_bytes());
+        header[2..6].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        let mut reader = E2StoreReader::new(&header[..]);
+        assert!(matches!(
+            reader.read_entry(),
+            Err(EraError::EntryTooLarge { entry_type: COMPRESSED_BODY, len }) if len == u32::MAX as usize
+        ));
+    }
+}
@@ -0,0 +1,842 @@
+use crate::{
+    accumulator_root,
+    e2store::{
+        E2StoreReader, E2StoreWriter, Entry, ACCUMULATOR, BLOCK_INDEX, COMPRESSED_BODY,
+        COMPRESSED_HEADER, COMPRESSED_RECEIPTS, MAX_ENTRY_SIZE, TOTAL_DIFFICULTY, VERSION,
+    },
+    EraError, HeaderRecord,
+};
+use alloy_rlp::{Decodable, Header as RlpHeader, RlpEncodable};
+use reth_primitives::{
+    proofs::{calculate_ommers_root, calculate_transaction_root, ordered_trie_root_with_encoder},
+    Block, BlockBody, BlockNumber, Bloom, BufMut, Bytes, Header, Log, ReceiptWithBloom, B256, U256,
+};
+use std::{
+    fs::File,
+    io::{BufReader, Read, Write},
+    path::Path,
+};
+
+/// The maximum number of blocks in an Era1 archive.
+pub const MAX_BLOCKS_PER_ERA1: usize = 8192;
+
+/// Returns the canonical file name of an Era1 archive: `<network>-<epoch>-<short root>.era1`.
+///
+/// The epoch is the number of the first block of the archive divided by
+/// [`MAX_BLOCKS_PER_ERA1`], the short root are the first 4 bytes of the accumulator root.
+pub fn era1_file_name(network: &str, epoch: u64, accumulator_root: B256) -> String {
+    let short_root =
+        accumulator_root[..4].iter().map(|byte| format!("{byte:02x}")).collect::<String>();
+    format!("{network}-{epoch:05}-{short_root}.era1")
+}
+
+/// Returns the file name of an Era1 archive that does not contain all blocks of its epoch:
+/// `<network>-<epoch>-<short root>-partial.era1`.
+///
+/// Partial archives must not use the canonical file name of [`era1_file_name`], which identifies
+/// the archive of the complete epoch.
+pub fn era1_partial_file_name(network: &str, epoch: u64, accumulator_root: B256) -> String {
+    let canonical = era1_file_name(network, epoch, accumulator_root);
+    format!("{}-partial.era1", canonical.trim_end_matches(".era1"))
+}
+
+/// The parsed file name of an Era1 archive, see [`era1_file_name`] and [`era1_partial_file_name`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Era1FileName {
+    /// The name of the network.
+    pub network: String,
+    /// The epoch of the first block of the archive.
+    pub epoch: u64,
+    /// The first 4 bytes of the accumulator root.
+    pub short_root: [u8; 4],
+    /// Whether the archive is marked as partial.
+    pub partial: bool,
+}
+
+// === impl Era1FileName ===
+
+impl Era1FileName {
+    /// Parses a file name of the form `<network>-<epoch>-<short root>[-partial].era1`.
+    pub fn parse(file_name: &str) -> Option<Self> {
+        let name = file_name.strip_suffix(".era1")?;
+        let (name, partial) = match name.strip_suffix("-partial") {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+
+        let mut parts = name.rsplitn(3, '-');
+        let short_root = parts.next()?;
+        let epoch = parts.next()?;
+        let network = parts.next().filter(|network| !network.is_empty())?;
+        if short_root.len() != 8 ||
+            !short_root.bytes().all(|b| b.is_ascii_hexdigit()) ||
+            epoch.is_empty() ||
+            !epoch.bytes().all(|b| b.is_ascii_digit())
+        {
+            return None
+        }
+
+        let mut root = [0u8; 4];
+        for (i, byte) in root.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(short_root.get(2 * i..2 * i + 2)?, 16).ok()?;
+        }
+
+        Some(Self {
+            network: network.to_string(),
+            epoch: epoch.parse().ok()?,
+            short_root: root,
+            partial,
+        })
+    }
+}
+
+/// A single block of an Era1 archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockTuple {
+    /// The header of the block.
+    pub header: Header,
+    /// The body of the block.
+    pub body: BlockBody,
+    /// The rlp encoded list of the receipts of the block.
+    ///
+    /// Receipts are kept encoded because the receipts of pre-byzantium blocks contain an
+    /// intermediate state root instead of a status code.
+    pub receipts: Bytes,
+    /// The total difficulty of the block.
+    pub total_difficulty: U256,
+}
+
+// === impl BlockTuple ===
+
+impl BlockTuple {
+    /// Creates a new block tuple and encodes the given receipts.
+    pub fn new(
+        header: Header,
+        body: BlockBody,
+        receipts: &[ReceiptWithBloom],
+        total_difficulty: U256,
+    ) -> Self {
+        Self { header, body, receipts: alloy_rlp::encode(receipts).into(), total_difficulty }
+    }
+
+    /// Creates a new block tuple of a pre-byzantium block and encodes the given receipts.
+    ///
+    /// Pre-byzantium receipts contain the state root after the transaction instead of its status
+    /// code, so every receipt is paired with its intermediate state root.
+    pub fn new_pre_byzantium(
+        header: Header,
+        body: BlockBody,
+        receipts: &[(ReceiptWithBloom, B256)],
+        total_difficulty: U256,
+    ) -> Self {
+        let receipts = receipts
+            .iter()
+            .map(|(receipt, state_root)| PreByzantiumReceipt {
+                state_root: *state_root,
+                cumulative_gas_used: receipt.receipt.cumulative_gas_used,
+                bloom: receipt.bloom,
+                logs: &receipt.receipt.logs,
+            })
+            .collect::<Vec<_>>();
+        Self { header, body, receipts: alloy_rlp::encode(receipts).into(), total_difficulty }
+    }
+
+    /// Returns the number of the block.
+    pub fn number(&self) -> BlockNumber {
+        self.header.number
+    }
+
+    /// Returns the accumulator record of the block.
+    pub fn header_record(&self) -> HeaderRecord {
+        HeaderRecord {
+            block_hash: self.header.hash_slow(),
+            total_difficulty: self.total_difficulty,
+        }
+    }
+
+    /// Converts the tuple into a block, discarding the receipts and total difficulty.
+    pub fn into_block(self) -> Block {
+        Block {
+            header: self.header,
+            body: self.body.transactions,
+            ommers: self.body.ommers,
+            withdrawals: self.body.withdrawals,
+        }
+    }
+
+    /// Computes the receipts root of the encoded receipts.
+    pub fn receipts_root(&self) -> Result<B256, EraError> {
+        let mut buf = &self.receipts[..];
+        let header = RlpHeader::decode(&mut buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString.into())
+        }
+        let mut payload =
+            buf.get(..header.payload_length).ok_or(alloy_rlp::Error::InputTooShort)?;
+
+        // legacy receipts are encoded as lists and typed receipts as strings, the trie values are
+        // the list including its header and the content of the string respectively
+        let mut receipts = Vec::new();
+        while !payload.is_empty() {
+            let item = payload;
+            let header = RlpHeader::decode(&mut payload)?;
+            let header_len = item.len() - payload.len();
+            let receipt = if header.list {
+                item.get(..header_len + header.payload_length)
+            } else {
+                payload.get(..header.payload_length)
+            }
+            .ok_or(alloy_rlp::Error::InputTooShort)?;
+            receipts.push(receipt);
+            payload = &payload[header.payload_length..];
+        }
+
+        Ok(ordered_trie_root_with_encoder(&receipts, |receipt, buf: &mut dyn BufMut| {
+            buf.put_slice(receipt)
+        }))
+    }
+
+    /// Verifies that the body and receipts match the roots of the header.
+    pub fn verify(&self) -> Result<(), EraError> {
+        if calculate_transaction_root(&self.body.transactions) != self.header.transactions_root ||
+            calculate_ommers_root(&self.body.ommers) != self.header.ommers_hash
+        {
+            return Err(EraError::BodyMismatch(self.number()))
+        }
+        if self.receipts_root()? != self.header.receipts_root {
+            return Err(EraError::ReceiptsRootMismatch(self.number()))
+        }
+        Ok(())
+    }
+
+    fn to_entries(&self) -> Result<[Entry; 4], EraError> {
+        Ok([
+            Entry::new(COMPRESSED_HEADER, compress(&alloy_rlp::encode(&self.header))?),
+            Entry::new(COMPRESSED_BODY, compress(&alloy_rlp::encode(&self.body))?),
+            Entry::new(COMPRESSED_RECEIPTS, compress(&self.receipts)?),
+            Entry::new(TOTAL_DIFFICULTY, self.total_difficulty.to_le_bytes::<32>().to_vec()),
+        ])
+    }
+
+    fn from_entries(
+        header: Entry,
+        body: Entry,
+        receipts: Entry,
+        total_difficulty: Entry,
+    ) -> Result<Self, EraError> {
+        header.ensure_type(COMPRESSED_HEADER)?;
+        body.ensure_type(COMPRESSED_BODY)?;
+        receipts.ensure_type(COMPRESSED_RECEIPTS)?;
+        total_difficulty.ensure_type(TOTAL_DIFFICULTY)?;
+
+        let total_difficulty: [u8; 32] =
+            total_difficulty.data.as_slice().try_into().map_err(|_| {
+                EraError::InvalidEntryLength {
+                    entry_type: TOTAL_DIFFICULTY,
+                    len: total_difficulty.data.len(),
+                }
+            })?;
+
+        Ok(Self {
+            header: Header::decode(&mut decompress(&header)?.as_slice())?,
+            body: BlockBody::decode(&mut decompress(&body)?.as_slice())?,
+            receipts: decompress(&receipts)?.into(),
+            total_difficulty: U256::from_le_bytes(total_difficulty),
+        })
+    }
+}
+
+/// The encoding of a pre-byzantium receipt.
+#[derive(RlpEncodable)]
+struct PreByzantiumReceipt<'a> {
+    state_root: B256,
+    cumulative_gas_used: u64,
+    bloom: Bloom,
+    logs: &'a [Log],
+}
+
+/// Writes blocks to an Era1 archive.
+///
+/// The archive is only complete after [`Era1Writer::finish`] was called.
+#[derive(Debug)]
+pub struct Era1Writer<W> {
+    writer: E2StoreWriter<W>,
+    /// The number of the first block.
+    starting_number: Option<BlockNumber>,
+    /// The offsets of the header entries of all blocks.
+    offsets: Vec<u64>,
+    /// The accumulator records of all blocks.
+    records: Vec<HeaderRecord>,
+}
+
+impl<W: Write> Era1Writer<W> {
+    /// Creates a new writer and writes the version entry.
+    pub fn new(writer: W) -> Result<Self, EraError> {
+        let mut writer = E2StoreWriter::new(writer);
+        writer.write_entry(&Entry::new(VERSION, Vec::new()))?;
+        Ok(Self { writer, starting_number: None, offsets: Vec::new(), records: Vec::new() })
+    }
+
+    /// Returns the number of blocks written so far.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns true if no blocks were written yet.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Appends the next block to the archive.
+    ///
+    /// Blocks must be appended in order.
+    pub fn append(&mut self, block: &BlockTuple) -> Result<(), EraError> {
+        if self.records.len() >= MAX_BLOCKS_PER_ERA1 {
+            return Err(EraError::TooManyBlocks(MAX_BLOCKS_PER_ERA1))
+        }
+        let starting_number = *self.starting_number.get_or_insert(block.number());
+        let expected = starting_number + self.records.len() as u64;
+        if block.number() != expected {
+            return Err(EraError::NonConsecutiveBlock { expected, got: block.number() })
+        }
+
+        let [header, body, receipts, total_difficulty] = block.to_entries()?;
+        self.offsets.push(self.writer.write_entry(&header)?);
+        self.writer.write_entry(&body)?;
+        self.writer.write_entry(&receipts)?;
+        self.writer.write_entry(&total_difficulty)?;
+        self.records.push(block.header_record());
+
+        Ok(())
+    }
+
+    /// Writes the accumulator and block index and returns the underlying writer and the
+    /// accumulator root.
+    pub fn finish(mut self) -> Result<(W, B256), EraError> {
+        let starting_number = self.starting_number.ok_or(EraError::EmptyArchive)?;
+
+        let root = accumulator_root(&self.records);
+        self.writer.write_entry(&Entry::new(ACCUMULATOR, root.to_vec()))?;
+
+        // offsets are relative to the start of the block index entry
+        let index_position = self.writer.position() as i64;
+        let mut index = Vec::with_capacity(16 + self.offsets.len() * 8);
+        index.extend_from_slice(&starting_number.to_le_bytes());
+        for offset in &self.offsets {
+            index.extend_from_slice(&(*offset as i64 - index_position).to_le_bytes());
+        }
+        index.extend_from_slice(&(self.offsets.len() as u64).to_le_bytes());
+        self.writer.write_entry(&Entry::new(BLOCK_INDEX, index))?;
+        self.writer.flush()?;
+
+        Ok((self.writer.into_inner(), root))
+    }
+}
+
+/// Reads blocks from an Era1 archive.
+#[derive(Debug)]
+pub struct Era1Reader<R> {
+    reader: E2StoreReader<R>,
+    /// The offset of the next entry.
+    position: u64,
+    /// The number of the first block.
+    starting_number: Option<BlockNumber>,
+    /// The offsets of the header entries of all blocks read so far.
+    offsets: Vec<u64>,
+    /// The accumulator root, once all blocks were read.
+    accumulator: Option<B256>,
+}
+
+impl<R: Read> Era1Reader<R> {
+    /// Creates a new reader and reads the version entry.
+    pub fn new(reader: R) -> Result<Self, EraError> {
+        let mut reader = E2StoreReader::new(reader);
+        let version = reader.read_entry()?.ok_or(EraError::MissingVersion)?;
+        if version.entry_type != VERSION {
+            return Err(EraError::MissingVersion)
+        }
+        Ok(Self {
+            reader,
+            position: version.encoded_len() as u64,
+            starting_number: None,
+            offsets: Vec::new(),
+            accumulator: None,
+        })
+    }
+
+    /// Returns the accumulator root of the archive.
+    ///
+    /// This is only available after all blocks were read.
+    pub fn accumulator(&self) -> Option<B256> {
+        self.accumulator
+    }
+
+    /// Reads the next block.
+    ///
+    /// Returns `None` once all blocks were read.
+    pub fn read_block(&mut self) -> Result<Option<BlockTuple>, EraError> {
+        if self.accumulator.is_some() {
+            return Ok(None)
+        }
+
+        loop {
+            let offset = self.position;
+            let entry = self.next_entry()?.ok_or(EraError::MissingAccumulator)?;
+            match entry.entry_type {
+                COMPRESSED_HEADER => {
+                    let body = self.expect_entry()?;
+                    let receipts = self.expect_entry()?;
+                    let total_difficulty = self.expect_entry()?;
+                    let block = BlockTuple::from_entries(entry, body, receipts, total_difficulty)?;
+
+                    let starting_number = *self.starting_number.get_or_insert(block.number());
+                    let expected = starting_number + self.offsets.len() as u64;
+                    if block.number() != expected {
+                        return Err(EraError::NonConsecutiveBlock { expected, got: block.number() })
+                    }
+                    self.offsets.push(offset);
+
+                    return Ok(Some(block))
+                }
+                ACCUMULATOR => {
+                    if entry.data.len() != 32 {
+                        return Err(EraError::InvalidEntryLength {
+                            entry_type: ACCUMULATOR,
+                            len: entry.data.len(),
+                        })
+                    }
+                    let index_position = self.position;
+                    let index = self.expect_entry()?;
+                    index.ensure_type(BLOCK_INDEX)?;
+                    self.verify_block_index(&index.data, index_position)?;
+
+                    self.accumulator = Some(B256::from_slice(&entry.data));
+                    return Ok(None)
+                }
+                // skip other entries
+                _ => continue,
+            }
+        }
+    }
+
+    /// Verifies that the block index matches the blocks that were read.
+    fn verify_block_index(&self, index: &[u8], index_position: u64) -> Result<(), EraError> {
+        let starting_number = self.starting_number.ok_or(EraError::EmptyArchive)?;
+        if index.len() != 16 + self.offsets.len() * 8 {
+            return Err(EraError::InvalidBlockIndex)
+        }
+
+        let words = index
+            .chunks_exact(8)
+            .map(|word| u64::from_le_bytes(word.try_into().expect("8 bytes")))
+            .collect::<Vec<_>>();
+        let (count, offsets) = words[1..].split_last().expect("not empty");
+        if words[0] != starting_number || *count != self.offsets.len() as u64 {
+            return Err(EraError::InvalidBlockIndex)
+        }
+        for (relative, offset) in offsets.iter().zip(&self.offsets) {
+            if (*relative as i64) + index_position as i64 != *offset as i64 {
+                return Err(EraError::InvalidBlockIndex)
+            }
+        }
+
+        Ok(())
+    }
+
+    fn next_entry(&mut self) -> Result<Option<Entry>, EraError> {
+        let entry = self.reader.read_entry()?;
+        if let Some(entry) = &entry {
+            self.position += entry.encoded_len() as u64;
+        }
+        Ok(entry)
+    }
+
+    fn expect_entry(&mut self) -> Result<Entry, EraError> {
+        self.next_entry()?.ok_or(EraError::UnexpectedEof)
+    }
+}
+
+impl<R: Read> Iterator for Era1Reader<R> {
+    type Item = Result<BlockTuple, EraError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_block().transpose()
+    }
+}
+
+/// All blocks of an Era1 archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Era1Archive {
+    /// The blocks of the archive, in order.
+    pub blocks: Vec<BlockTuple>,
+    /// The accumulator root of the archive.
+    pub accumulator: B256,
+}
+
+// === impl Era1Archive ===
+
+impl Era1Archive {
+    /// Reads the archive at the given path.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, EraError> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads all blocks of the archive.
+    pub fn read<R: Read>(reader: R) -> Result<Self, EraError> {
+        let mut reader = Era1Reader::new(reader)?;
+        let blocks = reader.by_ref().collect::<Result<Vec<_>, _>>()?;
+        let accumulator = reader.accumulator().ok_or(EraError::MissingAccumulator)?;
+        Ok(Self { blocks, accumulator })
+    }
+
+    /// Returns the number of the first block.
+    pub fn first_number(&self) -> Option<BlockNumber> {
+        self.blocks.first().map(BlockTuple::number)
+    }
+
+    /// Returns the number of the last block.
+    pub fn last_number(&self) -> Option<BlockNumber> {
+        self.blocks.last().map(BlockTuple::number)
+    }
+
+    /// Verifies the archive.
+    ///
+    /// This checks that the blocks form a chain with consistent total difficulties, that all
+    /// bodies and receipts match their headers and that the accumulator root commits to all
+    /// blocks.
+    pub fn verify(&self) -> Result<(), EraError> {
+        if self.blocks.is_empty() {
+            return Err(EraError::EmptyArchive)
+        }
+        if self.blocks.len() > MAX_BLOCKS_PER_ERA1 {
+            return Err(EraError::TooManyBlocks(MAX_BLOCKS_PER_ERA1))
+        }
+
+        let mut records = Vec::with_capacity(self.blocks.len());
+        let mut parent: Option<(&BlockTuple, HeaderRecord)> = None;
+        for block in &self.blocks {
+            let record = block.header_record();
+            if let Some((parent, parent_record)) = parent {
+                let expected = parent.number() + 1;
+                if block.number() != expected ||
+                    block.header.parent_hash != parent_record.block_hash
+                {
+                    return Err(EraError::NonConsecutiveBlock { expected, got: block.number() })
+                }
+                if parent.total_difficulty + block.header.difficulty != block.total_difficulty {
+                    return Err(EraError::TotalDifficultyMismatch {
+                        number: block.number(),
+                        total_difficulty: block.total_difficulty,
+                    })
+                }
+            }
+            block.verify()?;
+
+            records.push(record);
+            parent = Some((block, record));
+        }
+
+        let root = accumulator_root(&records);
+        if root != self.accumulator {
+            return Err(EraError::AccumulatorMismatch { expected: self.accumulator, got: root })
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that the archive matches its file name.
+    ///
+    /// The file name commits to the network, the epoch of the first block and the first 4 bytes of
+    /// the accumulator root. Unlike [`Era1Archive::verify`], which only checks the archive against
+    /// itself, this detects archives whose blocks were replaced and whose accumulator was
+    /// recomputed.
+    pub fn verify_file_name(&self, network: &str, file_name: &str) -> Result<(), EraError> {
+        let name = Era1FileName::parse(file_name)
+            .ok_or_else(|| EraError::InvalidFileName(file_name.to_string()))?;
+        let first = self.first_number().ok_or(EraError::EmptyArchive)?;
+        if name.network != network ||
+            name.epoch != first / MAX_BLOCKS_PER_ERA1 as u64 ||
+            name.short_root != self.accumulator[..4]
+        {
+            return Err(EraError::FileNameMismatch(file_name.to_string()))
+        }
+        Ok(())
+    }
+
+    /// Returns the blocks of the archive.
+    pub fn into_blocks(self) -> impl Iterator<Item = Block> {
+        self.blocks.into_iter().map(BlockTuple::into_block)
+    }
+}
+
+/// Compresses the data with the snappy framing format.
+fn compress(data: &[u8]) -> Result<Vec<u8>, EraError> {
+    let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+    encoder.write_all(data)?;
+    encoder.into_inner().map_err(|err| err.into_error().into())
+}
+
+/// Decompresses the data of an entry with the snappy framing format.
+///
+/// Fails if the decompressed data of the entry exceeds [`MAX_ENTRY_SIZE`].
+fn decompress(entry: &Entry) -> Result<Vec<u8>, EraError> {
+    let mut decompressed = Vec::new();
+    snap::read::FrameDecoder::new(entry.data.as_slice())
+        .take(MAX_ENTRY_SIZE as u64 + 1)
+        .read_to_end(&mut decompressed)?;
+    if decompressed.len() > MAX_ENTRY_SIZE {
+        return Err(EraError::EntryTooLarge {
+            entry_type: entry.entry_type,
+            len: decompressed.len(),
+        })
+    }
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use reth_primitives::{
+        address, b256, bytes, constants::EMPTY_ROOT_HASH, Address, Receipt, TxType, MAINNET,
+    };
+
+    fn chain(len: u64) -> Vec<BlockTuple> {
+        let mut parent_hash = B256::ZERO;
+        let mut total_difficulty = U256::ZERO;
+        (0..len)
+            .map(|number| {
+                let header = Header {
+                    number,
+                    parent_hash,
+                    difficulty: U256::from(10 + number),
+                    ..Default::default()
+                };
+                parent_hash = header.hash_slow();
+                total_difficulty += header.difficulty;
+                BlockTuple::new(header, BlockBody::default(), &[], total_difficulty)
+            })
+            .collect()
+    }
+
+    fn write_archive(blocks: &[BlockTuple]) -> (Vec<u8>, B256) {
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        for block in blocks {
+            writer.append(block).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn era1_roundtrip() {
+        let blocks = chain(3);
+        let (buf, root) = write_archive(&blocks);
+
+        let archive = Era1Archive::read(&buf[..]).unwrap();
+        assert_eq!(archive.blocks, blocks);
+        assert_eq!(archive.accumulator, root);
+        assert_eq!(archive.first_number(), Some(0));
+        assert_eq!(archive.last_number(), Some(2));
+        archive.verify().unwrap();
+    }
+
+    #[test]
+    fn reject_invalid_archives() {
+        let blocks = chain(3);
+
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        writer.append(&blocks[0]).unwrap();
+        assert!(matches!(
+            writer.append(&blocks[2]),
+            Err(EraError::NonConsecutiveBlock { expected: 1, got: 2 })
+        ));
+
+        let (buf, _) = write_archive(&blocks);
+        let mut archive = Era1Archive::read(&buf[..]).unwrap();
+        archive.accumulator = B256::ZERO;
+        assert!(matches!(archive.verify(), Err(EraError::AccumulatorMismatch { .. })));
+
+        let mut archive = Era1Archive::read(&buf[..]).unwrap();
+        archive.blocks[1].total_difficulty += U256::from(1);
+        assert!(matches!(
+            archive.verify(),
+            Err(EraError::TotalDifficultyMismatch { number: 1, .. })
+        ));
+
+        // missing accumulator and block index
+        let truncated = &buf[..buf.len() - 40 - 48];
+        assert!(matches!(Era1Archive::read(truncated), Err(EraError::MissingAccumulator)));
+    }
+
+    #[test]
+    fn receipts_root() {
+        let receipts = [
+            Receipt {
+                tx_type: TxType::Legacy,
+                success: true,
+                cumulative_gas_used: 21000,
+                ..Default::default()
+            }
+            .with_bloom(),
+            Receipt {
+                tx_type: TxType::EIP1559,
+                success: true,
+                cumulative_gas_used: 42000,
+                ..Default::default()
+            }
+            .with_bloom(),
+        ];
+        let receipts_root = ordered_trie_root_with_encoder(&receipts, |receipt, buf| {
+            receipt.encode_inner(buf, false)
+        });
+        let header = Header { receipts_root, ..Default::default() };
+        let block = BlockTuple::new(header, BlockBody::default(), &receipts, U256::ZERO);
+        block.verify().unwrap();
+    }
+
+    #[test]
+    fn pre_byzantium_receipts() {
+        let receipt = Receipt {
+            tx_type: TxType::Legacy,
+            success: true,
+            cumulative_gas_used: 21000,
+            logs: vec![Log { address: Address::with_last_byte(1), ..Default::default() }],
+            ..Default::default()
+        }
+        .with_bloom();
+        let state_root = B256::with_last_byte(2);
+
+        let receipts_root = ordered_trie_root_with_encoder(&[&receipt], |receipt, buf| {
+            PreByzantiumReceipt {
+                state_root,
+                cumulative_gas_used: receipt.receipt.cumulative_gas_used,
+                bloom: receipt.bloom,
+                logs: &receipt.receipt.logs,
+            }
+            .encode(buf)
+        });
+        let header = Header { receipts_root, ..Default::default() };
+        let block = BlockTuple::new_pre_byzantium(
+            header,
+            BlockBody::default(),
+            &[(receipt.clone(), state_root)],
+            U256::ZERO,
+        );
+        block.verify().unwrap();
+
+        // the status code is replaced by the intermediate state root
+        let mut buf = &block.receipts[..];
+        assert!(RlpHeader::decode(&mut buf).unwrap().list);
+        assert!(RlpHeader::decode(&mut buf).unwrap().list);
+        assert_eq!(B256::decode(&mut buf).unwrap(), state_root);
+        assert_eq!(u64::decode(&mut buf).unwrap(), 21000);
+        assert_eq!(Bloom::decode(&mut buf).unwrap(), receipt.bloom);
+        assert_eq!(Vec::<Log>::decode(&mut buf).unwrap(), receipt.receipt.logs);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn file_name() {
+        let root = B256::with_last_byte(1);
+        assert_eq!(era1_file_name("mainnet", 12, root), "mainnet-00012-00000000.era1");
+        assert_eq!(
+            era1_partial_file_name("mainnet", 12, root),
+            "mainnet-00012-00000000-partial.era1"
+        );
+    }
+
+    #[test]
+    fn parse_file_name() {
+        assert_eq!(
+            Era1FileName::parse("mainnet-00012-5ec1ffb8.era1"),
+            Some(Era1FileName {
+                network: "mainnet".to_string(),
+                epoch: 12,
+                short_root: [0x5e, 0xc1, 0xff, 0xb8],
+                partial: false,
+            })
+        );
+        assert_eq!(
+            Era1FileName::parse("optimism-goerli-00001-00000000-partial.era1"),
+            Some(Era1FileName {
+                network: "optimism-goerli".to_string(),
+                epoch: 1,
+                short_root: [0; 4],
+                partial: true,
+            })
+        );
+        assert_eq!(Era1FileName::parse("mainnet-00012-5ec1ffb8.era"), None);
+        assert_eq!(Era1FileName::parse("mainnet-00012-5ec1ff.era1"), None);
+        assert_eq!(Era1FileName::parse("mainnet-0x012-5ec1ffb8.era1"), None);
+        assert_eq!(Era1FileName::parse("00012-5ec1ffb8.era1"), None);
+    }
+
+    /// The first two mainnet blocks.
+    fn mainnet_blocks() -> Vec<BlockTuple> {
+        let genesis = MAINNET.genesis_header();
+        let block1 = Header {
+            parent_hash: MAINNET.genesis_hash(),
+            ommers_hash: genesis.ommers_hash,
+            beneficiary: address!("05a56e2d52c817161883f50c441c3228cfe54d9f"),
+            state_root: b256!("d67e4d450343046425ae4271474353857ab860dbc0a1dde64b41b5cd3a532bf3"),
+            transactions_root: EMPTY_ROOT_HASH,
+            receipts_root: EMPTY_ROOT_HASH,
+            difficulty: U256::from(0x3ff800000u64),
+            number: 1,
+            gas_limit: 5000,
+            timestamp: 1438269988,
+            extra_data: bytes!("476574682f76312e302e302f6c696e75782f676f312e342e32"),
+            mix_hash: b256!("969b900de27b6ac6a67742365dd65f55a0526c41fd18e1b16f1a1215c2e66f59"),
+            nonce: 0x539bd4979fef1ec4,
+            ..Default::default()
+        };
+        let genesis_td = genesis.difficulty;
+        let block1_td = genesis_td + block1.difficulty;
+        vec![
+            BlockTuple::new(genesis, BlockBody::default(), &[], genesis_td),
+            BlockTuple::new(block1, BlockBody::default(), &[], block1_td),
+        ]
+    }
+
+    #[test]
+    fn mainnet_archive() {
+        let blocks = mainnet_blocks();
+        assert_eq!(
+            blocks.iter().map(|block| block.header_record().block_hash).collect::<Vec<_>>(),
+            vec![
+                b256!("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"),
+                b256!("88e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6"),
+            ]
+        );
+        assert_eq!(blocks[1].total_difficulty, U256::from(0x7ff800000u64));
+
+        let (buf, root) = write_archive(&blocks);
+        assert_eq!(root, b256!("31aefe616a8ca81a1978a6a494b9ba9dbeac6a5e25811c3bcf5fc3762c337f18"));
+
+        let archive = Era1Archive::read(&buf[..]).unwrap();
+        archive.verify().unwrap();
+        let file_name = era1_partial_file_name("mainnet", 0, root);
+        assert_eq!(file_name, "mainnet-00000-31aefe61-partial.era1");
+        archive.verify_file_name("mainnet", &file_name).unwrap();
+        assert!(matches!(
+            archive.verify_file_name("sepolia", "sepolia-00001-31aefe61.era1"),
+            Err(EraError::FileNameMismatch(_))
+        ));
+        assert!(matches!(
+            archive.verify_file_name("mainnet", "mainnet.era1"),
+            Err(EraError::InvalidFileName(_))
+        ));
+
+        // a tampered archive with a recomputed accumulator is consistent with itself, but not
+        // with the root of its file name
+        let mut tampered = blocks;
+        tampered[1].header.beneficiary = Address::ZERO;
+        let (buf, _) = write_archive(&tampered);
+        let archive = Era1Archive::read(&buf[..]).unwrap();
+        archive.verify().unwrap();
+        assert!(matches!(
+            archive.verify_file_name("mainnet", &file_name),
+            Err(EraError::FileNameMismatch(_))
+        ));
+    }
+}
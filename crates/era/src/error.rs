@@ -0,0 +1,95 @@
+use reth_primitives::{BlockNumber, B256, U256};
+use std::io;
+
+/// Errors when reading, writing or verifying Era1 archives.
+#[derive(thiserror::Error, Debug)]
+pub enum EraError {
+    /// Error of the underlying reader or writer.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Failed to decode an entry.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// The reserved bytes of an entry header are not zero.
+    #[error("invalid e2store entry header")]
+    InvalidEntryHeader,
+    /// The data of an entry, compressed or decompressed, exceeds
+    /// [`MAX_ENTRY_SIZE`](crate::e2store::MAX_ENTRY_SIZE).
+    #[error("entry {entry_type:#06x} of {len} bytes exceeds the maximum entry size")]
+    EntryTooLarge {
+        /// The type of the entry.
+        entry_type: u16,
+        /// The length of the entry data.
+        len: usize,
+    },
+    /// The archive ended in the middle of an entry.
+    #[error("unexpected end of file")]
+    UnexpectedEof,
+    /// The archive does not start with a version entry.
+    #[error("missing version entry")]
+    MissingVersion,
+    /// An entry of a different type was expected.
+    #[error("unexpected entry type {got:#06x}, expected {expected:#06x}")]
+    UnexpectedEntry {
+        /// The expected entry type.
+        expected: u16,
+        /// The entry type that was read.
+        got: u16,
+    },
+    /// An entry has an invalid length.
+    #[error("invalid length {len} of entry {entry_type:#06x}")]
+    InvalidEntryLength {
+        /// The type of the entry.
+        entry_type: u16,
+        /// The length of the entry data.
+        len: usize,
+    },
+    /// The archive does not end with the accumulator and block index.
+    #[error("missing accumulator")]
+    MissingAccumulator,
+    /// The block index does not match the blocks of the archive.
+    #[error("invalid block index")]
+    InvalidBlockIndex,
+    /// More blocks than allowed were appended to an archive.
+    #[error("an era1 archive holds at most {0} blocks")]
+    TooManyBlocks(usize),
+    /// An archive must contain at least one block.
+    #[error("empty archive")]
+    EmptyArchive,
+    /// The blocks of an archive are not consecutive.
+    #[error("expected block {expected}, got block {got}")]
+    NonConsecutiveBlock {
+        /// The expected block number.
+        expected: BlockNumber,
+        /// The block number that was read or appended.
+        got: BlockNumber,
+    },
+    /// The computed header accumulator root does not match the one of the archive.
+    #[error("accumulator root mismatch: expected {expected}, got {got}")]
+    AccumulatorMismatch {
+        /// The accumulator root of the archive.
+        expected: B256,
+        /// The accumulator root computed from the blocks.
+        got: B256,
+    },
+    /// The file name of an archive is not a valid Era1 file name.
+    #[error("invalid era1 file name {0}")]
+    InvalidFileName(String),
+    /// The archive does not match the network, epoch or accumulator root of its file name.
+    #[error("archive does not match its file name {0}")]
+    FileNameMismatch(String),
+    /// The total difficulty of a block does not match its parent's total difficulty.
+    #[error("invalid total difficulty {total_difficulty} of block {number}")]
+    TotalDifficultyMismatch {
+        /// The block number.
+        number: BlockNumber,
+        /// The total difficulty of the block.
+        total_difficulty: U256,
+    },
+    /// The block body does not match the transactions root or ommers hash of the header.
+    #[error("body of block {0} does not match its header")]
+    BodyMismatch(BlockNumber),
+    /// The block receipts do not match the receipts root of the header.
+    #[error("receipts of block {0} do not match the receipts root")]
+    ReceiptsRootMismatch(BlockNumber),
+}
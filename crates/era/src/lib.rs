@@ -0,0 +1,31 @@
+//! Reading and writing of [Era1](https://github.com/ethereum/go-ethereum/pull/26621) history
+//! archives.
+//!
+//! An Era1 archive is an [e2store](e2store) file that contains up to [`MAX_BLOCKS_PER_ERA1`]
+//! consecutive pre-merge blocks. Every block is stored as a tuple of its snappy compressed
+//! header, body and receipts, followed by its total difficulty. The archive is terminated by the
+//! root of the header accumulator, which commits to all block hashes and total difficulties of
+//! the archive, and an index of the file offsets of all blocks.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![warn(missing_debug_implementations, missing_docs, unreachable_pub, rustdoc::all)]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod accumulator;
+pub use accumulator::{accumulator_root, HeaderRecord};
+
+pub mod e2store;
+
+mod era1;
+pub use era1::{
+    era1_file_name, era1_partial_file_name, BlockTuple, Era1Archive, Era1FileName, Era1Reader,
+    Era1Writer, MAX_BLOCKS_PER_ERA1,
+};
+
+mod error;
+pub use error::EraError;
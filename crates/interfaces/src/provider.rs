@@ -110,6 +110,10 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[error("state at block #{0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// History of the address is not available for the given block number because it is pruned,
+    /// and the address is not in the history retention list.
+    #[error("history of {0} at block #{1} is pruned")]
+    AddressHistoryPruned(Address, BlockNumber),
     /// Provider does not support this particular request.
     #[error("this provider does not support this request")]
     UnsupportedProvider,
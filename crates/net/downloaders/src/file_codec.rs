@@ -1,5 +1,5 @@
 //! Codec for reading raw block bodies from a file.
-use super::FileClientError;
+use crate::file_client::FileClientError;
 use alloy_rlp::{Decodable, Encodable};
 use reth_primitives::{
     bytes::{Buf, BytesMut},
@@ -23,5 +23,11 @@ pub mod headers;
 /// Common downloader metrics.
 pub mod metrics;
 
+/// A client that serves headers and bodies of blocks read from a file.
+pub mod file_client;
+
+/// Codec for reading blocks from a file.
+mod file_codec;
+
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
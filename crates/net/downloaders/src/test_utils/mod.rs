@@ -12,12 +12,10 @@ use tokio::{
 use tokio_util::codec::FramedWrite;
 
 mod bodies_client;
-mod file_client;
-mod file_codec;
 
+pub use crate::file_client::{FileClient, FileClientError};
+pub(crate) use crate::file_codec::BlockFileCodec;
 pub use bodies_client::TestBodiesClient;
-pub use file_client::{FileClient, FileClientError};
-pub(crate) use file_codec::BlockFileCodec;
 use reth_interfaces::test_utils::generators;
 
 /// Metrics scope used for testing.
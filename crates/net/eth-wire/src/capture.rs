@@ -0,0 +1,533 @@
+//! Capture and replay of RLPx traffic.
+//!
+//! A [`PacketCapture`] records the decrypted and decompressed messages of [`P2PStream`]s to a
+//! capture file. Every message is stored together with the session it belongs to, its direction,
+//! a timestamp, the capability it belongs to and the capability relative message id.
+//!
+//! Captures can be read back with a [`CaptureReader`]. [`replay_eth_session`] feeds the `eth`
+//! messages of a captured session into an [`EthStream`] so decoding issues can be reproduced
+//! offline.
+//!
+//! ## File format
+//!
+//! All integers are big-endian. A capture starts with the magic bytes `RETHRLPX` followed by the
+//! format version (`u16`). Each message is stored as:
+//!
+//! | field      | encoding                                 |
+//! |------------|------------------------------------------|
+//! | session id | `u64`                                    |
+//! | direction  | `u8`, 0 = inbound, 1 = outbound          |
+//! | timestamp  | `u64`, microseconds since the unix epoch |
+//! | capability | `u8` name length, name, `u8` version     |
+//! | message id | `u8`                                     |
+//! | payload    | `u32` length, payload                    |
+//!
+//! [`P2PStream`]: crate::P2PStream
+
+use crate::{
+    capability::{Capability, SharedCapabilities},
+    errors::{CaptureError, EthStreamError},
+    message::ProtocolMessage,
+    EthMessageID, EthStream, EthVersion, MAX_RESERVED_MESSAGE_ID,
+};
+use futures::stream;
+use reth_primitives::bytes::{BufMut, Bytes, BytesMut};
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Read, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::debug;
+
+/// Magic bytes at the start of every capture.
+const CAPTURE_MAGIC: [u8; 8] = *b"RETHRLPX";
+
+/// The current version of the capture format.
+const CAPTURE_FORMAT_VERSION: u16 = 1;
+
+/// The capability all messages in the reserved message id space are recorded with.
+const P2P_CAPABILITY: Capability = Capability::new_static("p2p", 5);
+
+/// The direction of a captured message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaptureDirection {
+    /// The message was received from the remote peer.
+    Inbound,
+    /// The message was sent to the remote peer.
+    Outbound,
+}
+
+// === impl CaptureDirection ===
+
+impl CaptureDirection {
+    /// Returns true if the message was received from the remote peer.
+    pub fn is_inbound(&self) -> bool {
+        matches!(self, CaptureDirection::Inbound)
+    }
+
+    /// Returns true if the message was sent to the remote peer.
+    pub fn is_outbound(&self) -> bool {
+        matches!(self, CaptureDirection::Outbound)
+    }
+}
+
+/// A single captured message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedMessage {
+    /// Identifier of the session within the capture.
+    pub session: u64,
+    /// Whether the message was received or sent.
+    pub direction: CaptureDirection,
+    /// When the message was captured, relative to the unix epoch.
+    pub timestamp: Duration,
+    /// The capability the message belongs to.
+    ///
+    /// Messages of the reserved message id space are recorded as `p2p/5`.
+    pub capability: Capability,
+    /// The message id, relative to the capability's message id offset.
+    pub message_id: u8,
+    /// The decompressed, RLP encoded message.
+    pub payload: Bytes,
+}
+
+// === impl CapturedMessage ===
+
+impl CapturedMessage {
+    /// Returns true if the message belongs to the `eth` capability.
+    pub fn is_eth(&self) -> bool {
+        self.capability.name == "eth"
+    }
+
+    /// Returns the frame as it is yielded by the [`P2PStream`](crate::P2PStream) if the
+    /// capability is the first shared capability: the message id followed by the payload.
+    pub fn to_frame(&self) -> BytesMut {
+        let mut frame = BytesMut::with_capacity(1 + self.payload.len());
+        frame.put_u8(self.message_id);
+        frame.put_slice(&self.payload);
+        frame
+    }
+
+    /// Decodes the message as an `eth` message.
+    ///
+    /// Returns `None` if the message does not belong to the `eth` capability.
+    pub fn decode_eth(&self) -> Option<Result<ProtocolMessage, EthStreamError>> {
+        if !self.is_eth() {
+            return None
+        }
+        let version = match EthVersion::try_from(self.capability.version as u8) {
+            Ok(version) => version,
+            Err(err) => return Some(Err(err.into())),
+        };
+        Some(ProtocolMessage::decode_message(version, &mut self.to_frame().as_ref()))
+    }
+}
+
+/// Writes captured messages in the capture format.
+#[derive(Debug)]
+pub struct CaptureWriter<W> {
+    writer: W,
+}
+
+// === impl CaptureWriter ===
+
+impl<W: Write> CaptureWriter<W> {
+    /// Creates a new writer and writes the capture header.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(&CAPTURE_MAGIC)?;
+        writer.write_all(&CAPTURE_FORMAT_VERSION.to_be_bytes())?;
+        Ok(Self { writer })
+    }
+
+    /// Appends a message to the capture.
+    pub fn write_message(&mut self, msg: &CapturedMessage) -> io::Result<()> {
+        let name = msg.capability.name.as_bytes();
+        let name_len = u8::try_from(name.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "capability name too long"))?;
+        let payload_len = u32::try_from(msg.payload.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "payload too large"))?;
+
+        let mut buf = BytesMut::with_capacity(24 + name.len() + msg.payload.len());
+        buf.put_u64(msg.session);
+        buf.put_u8(msg.direction.is_outbound() as u8);
+        buf.put_u64(msg.timestamp.as_micros() as u64);
+        buf.put_u8(name_len);
+        buf.put_slice(name);
+        buf.put_u8(msg.capability.version as u8);
+        buf.put_u8(msg.message_id);
+        buf.put_u32(payload_len);
+        buf.put_slice(&msg.payload);
+
+        self.writer.write_all(&buf)
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Consumes the type and returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads captured messages from a capture.
+///
+/// Yields all messages of the capture in the order they were recorded.
+#[derive(Debug)]
+pub struct CaptureReader<R> {
+    reader: R,
+}
+
+// === impl CaptureReader ===
+
+impl CaptureReader<io::BufReader<File>> {
+    /// Opens the capture file at the given path.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, CaptureError> {
+        Self::new(io::BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    /// Creates a new reader and validates the capture header.
+    pub fn new(mut reader: R) -> Result<Self, CaptureError> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic).map_err(|_| CaptureError::InvalidMagic)?;
+        if magic != CAPTURE_MAGIC {
+            return Err(CaptureError::InvalidMagic)
+        }
+
+        let version = u16::from_be_bytes(read_array(&mut reader)?);
+        if version != CAPTURE_FORMAT_VERSION {
+            return Err(CaptureError::UnsupportedVersion(version))
+        }
+
+        Ok(Self { reader })
+    }
+
+    /// Reads the next message, returns `None` if the end of the capture was reached.
+    pub fn read_message(&mut self) -> Result<Option<CapturedMessage>, CaptureError> {
+        let mut session = [0u8; 8];
+        let mut read = 0;
+        while read < session.len() {
+            match self.reader.read(&mut session[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                Ok(n) => read += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        let session = u64::from_be_bytes(session);
+
+        let direction = match read_array::<_, 1>(&mut self.reader)?[0] {
+            0 => CaptureDirection::Inbound,
+            1 => CaptureDirection::Outbound,
+            direction => return Err(CaptureError::InvalidDirection(direction)),
+        };
+        let timestamp = Duration::from_micros(u64::from_be_bytes(read_array(&mut self.reader)?));
+
+        let name_len = read_array::<_, 1>(&mut self.reader)?[0];
+        let mut name = vec![0u8; name_len as usize];
+        self.reader.read_exact(&mut name)?;
+        let name = String::from_utf8(name).map_err(|_| CaptureError::InvalidCapabilityName)?;
+        let version = read_array::<_, 1>(&mut self.reader)?[0];
+
+        let message_id = read_array::<_, 1>(&mut self.reader)?[0];
+        let payload_len = u32::from_be_bytes(read_array(&mut self.reader)?);
+        let mut payload = vec![0u8; payload_len as usize];
+        self.reader.read_exact(&mut payload)?;
+
+        Ok(Some(CapturedMessage {
+            session,
+            direction,
+            timestamp,
+            capability: Capability::new(name, version as usize),
+            message_id,
+            payload: payload.into(),
+        }))
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = Result<CapturedMessage, CaptureError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_message().transpose()
+    }
+}
+
+/// Reads exactly `N` bytes.
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> io::Result<[u8; N]> {
+    let mut buf = [0u8; N];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// A capture that is shared by all sessions.
+///
+/// Each session records its messages via its own [`SessionCapture`], obtained with
+/// [`PacketCapture::session`].
+#[derive(Clone)]
+pub struct PacketCapture {
+    writer: Arc<Mutex<CaptureWriter<Box<dyn Write + Send>>>>,
+    next_session: Arc<AtomicU64>,
+}
+
+// === impl PacketCapture ===
+
+impl PacketCapture {
+    /// Creates a new capture file at the given path.
+    ///
+    /// An existing file is truncated.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+
+    /// Creates a new capture that writes to the given writer.
+    pub fn new<W: Write + Send + 'static>(writer: W) -> io::Result<Self> {
+        let writer: Box<dyn Write + Send> = Box::new(writer);
+        Ok(Self {
+            writer: Arc::new(Mutex::new(CaptureWriter::new(writer)?)),
+            next_session: Default::default(),
+        })
+    }
+
+    /// Returns a new [`SessionCapture`] with a unique session id.
+    pub fn session(&self) -> SessionCapture {
+        let id = self.next_session.fetch_add(1, Ordering::Relaxed);
+        SessionCapture { id, writer: self.writer.clone() }
+    }
+
+    /// Flushes all buffered messages.
+    pub fn flush(&self) -> io::Result<()> {
+        self.writer.lock().unwrap_or_else(|err| err.into_inner()).flush()
+    }
+}
+
+impl fmt::Debug for PacketCapture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PacketCapture")
+            .field("next_session", &self.next_session.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Records the messages of a single session to a [`PacketCapture`].
+///
+/// See [`P2PStream::set_capture`](crate::P2PStream::set_capture).
+#[derive(Clone)]
+pub struct SessionCapture {
+    id: u64,
+    writer: Arc<Mutex<CaptureWriter<Box<dyn Write + Send>>>>,
+}
+
+// === impl SessionCapture ===
+
+impl SessionCapture {
+    /// Returns the session id of the messages recorded with this type.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Records a message.
+    ///
+    /// `message_id` is the multiplexed message id, including the reserved message id space. It's
+    /// resolved to the capability the message belongs to and the capability relative message id
+    /// using the given shared capabilities.
+    ///
+    /// Failing to write the message is not fatal for the session, the error is only logged.
+    pub fn record(
+        &self,
+        direction: CaptureDirection,
+        shared_capabilities: &SharedCapabilities,
+        message_id: u8,
+        payload: &[u8],
+    ) {
+        let (capability, message_id) = if message_id <= MAX_RESERVED_MESSAGE_ID {
+            (P2P_CAPABILITY, message_id)
+        } else if let Some(cap) = shared_capabilities.find_by_offset(message_id) {
+            (cap.capability().into_owned(), message_id - cap.message_id_offset())
+        } else {
+            (P2P_CAPABILITY, message_id)
+        };
+
+        let msg = CapturedMessage {
+            session: self.id,
+            direction,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default(),
+            capability,
+            message_id,
+            payload: Bytes::copy_from_slice(payload),
+        };
+
+        let mut writer = self.writer.lock().unwrap_or_else(|err| err.into_inner());
+        if let Err(err) = writer.write_message(&msg) {
+            debug!(target: "net::capture", session=self.id, ?err, "failed to record message");
+        }
+    }
+}
+
+impl fmt::Debug for SessionCapture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionCapture").field("id", &self.id).finish_non_exhaustive()
+    }
+}
+
+/// The stream of captured frames an [`EthStream`] returned by [`replay_eth_session`] decodes.
+pub type ReplayFrames = stream::Iter<std::vec::IntoIter<Result<BytesMut, EthStreamError>>>;
+
+/// Returns an [`EthStream`] that yields the captured `eth` messages of the given session and
+/// direction.
+///
+/// The negotiated `eth` version is taken from the capture. The `Status` message is skipped,
+/// because an [`EthStream`] only accepts it during the handshake, use
+/// [`CapturedMessage::decode_eth`] to decode it.
+pub fn replay_eth_session<R: Read>(
+    reader: CaptureReader<R>,
+    session: u64,
+    direction: CaptureDirection,
+) -> Result<EthStream<ReplayFrames>, CaptureError> {
+    let mut version = None;
+    let mut frames = Vec::new();
+    for msg in reader {
+        let msg = msg?;
+        if msg.session != session || msg.direction != direction || !msg.is_eth() {
+            continue
+        }
+        if version.is_none() {
+            version = Some(EthVersion::try_from(msg.capability.version as u8)?);
+        }
+        if msg.message_id == EthMessageID::Status as u8 {
+            continue
+        }
+        frames.push(Ok(msg.to_frame()));
+    }
+
+    let version = version.ok_or(CaptureError::NoEthMessages(session))?;
+    Ok(EthStream::new(version, stream::iter(frames)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        message::RequestPair,
+        test_utils::{connect_passthrough, eth_handshake, eth_hello},
+        EthMessage, GetBlockBodies, UnauthedEthStream, UnauthedP2PStream,
+    };
+    use futures::{SinkExt, StreamExt};
+    use reth_primitives::B256;
+    use tokio::net::TcpListener;
+    use tokio_util::codec::Decoder;
+
+    /// A writer that can be inspected after it was moved into a [`PacketCapture`].
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn capture_roundtrip() {
+        let msg = CapturedMessage {
+            session: 3,
+            direction: CaptureDirection::Outbound,
+            timestamp: Duration::from_micros(1_700_000_000_000_000),
+            capability: Capability::eth(EthVersion::Eth68),
+            message_id: EthMessageID::GetBlockBodies as u8,
+            payload: Bytes::from_static(&[0xc0]),
+        };
+
+        let mut writer = CaptureWriter::new(Vec::new()).unwrap();
+        writer.write_message(&msg).unwrap();
+        writer.write_message(&msg).unwrap();
+        let buf = writer.into_inner();
+
+        let messages =
+            CaptureReader::new(&buf[..]).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(messages, vec![msg.clone(), msg]);
+
+        // truncated record
+        let mut reader = CaptureReader::new(&buf[..buf.len() - 1]).unwrap();
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_err());
+
+        assert!(matches!(CaptureReader::new(&b"RETHRLP"[..]), Err(CaptureError::InvalidMagic)));
+    }
+
+    #[tokio::test]
+    async fn capture_and_replay_session() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let (status, fork_filter) = eth_handshake();
+        let request = EthMessage::GetBlockBodies(RequestPair {
+            request_id: 1,
+            message: GetBlockBodies(vec![B256::random(), B256::random()]),
+        });
+
+        let (status_clone, fork_filter_clone, request_clone) =
+            (status, fork_filter.clone(), request.clone());
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = crate::PassthroughCodec::default().framed(incoming);
+            let (server_hello, _) = eth_hello();
+            let (p2p_stream, _) =
+                UnauthedP2PStream::new(stream).handshake(server_hello).await.unwrap();
+            let (mut eth_stream, _) = UnauthedEthStream::new(p2p_stream)
+                .handshake(status_clone, fork_filter_clone)
+                .await
+                .unwrap();
+            eth_stream.send(request_clone).await.unwrap();
+        });
+
+        let buf = SharedBuf::default();
+        let capture = PacketCapture::new(buf.clone()).unwrap();
+        let session = capture.session();
+        let session_id = session.id();
+
+        let (client_hello, _) = eth_hello();
+        let mut p2p_stream = connect_passthrough(local_addr, client_hello).await;
+        p2p_stream.set_capture(session);
+        let (mut eth_stream, _) =
+            UnauthedEthStream::new(p2p_stream).handshake(status, fork_filter).await.unwrap();
+        let received = eth_stream.next().await.unwrap().unwrap();
+        assert_eq!(received, request);
+        handle.await.unwrap();
+        drop(eth_stream);
+
+        let buf = buf.0.lock().unwrap().clone();
+        let messages =
+            CaptureReader::new(&buf[..]).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        // outbound and inbound status, inbound request
+        assert_eq!(messages.len(), 3);
+        assert!(messages.iter().all(|msg| msg.is_eth() && msg.session == session_id));
+        assert!(messages[0].direction.is_outbound());
+        assert!(matches!(
+            messages[0].decode_eth().unwrap().unwrap().message,
+            EthMessage::Status(_)
+        ));
+
+        let mut replay = replay_eth_session(
+            CaptureReader::new(&buf[..]).unwrap(),
+            session_id,
+            CaptureDirection::Inbound,
+        )
+        .unwrap();
+        assert_eq!(replay.next().await.unwrap().unwrap(), received);
+        assert!(replay.next().await.is_none());
+    }
+}
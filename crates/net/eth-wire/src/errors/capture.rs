@@ -0,0 +1,28 @@
+use crate::version::ParseVersionError;
+use std::io;
+
+/// Errors when reading or replaying an RLPx traffic capture.
+#[derive(thiserror::Error, Debug)]
+pub enum CaptureError {
+    /// Error of the underlying reader or writer.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The input does not start with the capture file magic.
+    #[error("not an rlpx capture file")]
+    InvalidMagic,
+    /// The capture was written with an unsupported version of the file format.
+    #[error("unsupported capture format version {0}")]
+    UnsupportedVersion(u16),
+    /// A record contains an unknown message direction.
+    #[error("invalid message direction {0}")]
+    InvalidDirection(u8),
+    /// A record contains a capability name that is not valid UTF-8.
+    #[error("invalid capability name")]
+    InvalidCapabilityName,
+    /// The captured `eth` version is not supported.
+    #[error(transparent)]
+    ParseVersionError(#[from] ParseVersionError),
+    /// The capture contains no `eth` messages for the requested session.
+    #[error("no eth messages captured for session {0}")]
+    NoEthMessages(u64),
+}
@@ -1,9 +1,11 @@
 //! Error types for stream variants
 
+mod capture;
 mod eth;
 mod muxdemux;
 mod p2p;
 
+pub use capture::*;
 pub use eth::*;
 pub use muxdemux::*;
 pub use p2p::*;
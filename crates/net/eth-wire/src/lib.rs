@@ -16,6 +16,7 @@
 
 pub mod builder;
 pub mod capability;
+pub mod capture;
 mod disconnect;
 pub mod errors;
 mod ethstream;
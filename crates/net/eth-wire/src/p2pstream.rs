@@ -25,6 +25,7 @@ use reth_primitives::{
 
 use crate::{
     capability::SharedCapabilities,
+    capture::{CaptureDirection, SessionCapture},
     disconnect::CanDisconnect,
     errors::{P2PHandshakeError, P2PStreamError},
     pinger::{Pinger, PingerEvent},
@@ -260,6 +261,9 @@ pub struct P2PStream<S> {
     /// Whether this stream is currently in the process of disconnecting by sending a disconnect
     /// message.
     disconnecting: bool,
+
+    /// Records all messages of this stream, if enabled.
+    capture: Option<SessionCapture>,
 }
 
 impl<S> P2PStream<S> {
@@ -276,6 +280,7 @@ impl<S> P2PStream<S> {
             outgoing_messages: VecDeque::new(),
             outgoing_message_buffer_capacity: MAX_P2P_CAPACITY,
             disconnecting: false,
+            capture: None,
         }
     }
 
@@ -301,6 +306,19 @@ impl<S> P2PStream<S> {
         &self.shared_capabilities
     }
 
+    /// Records all decompressed messages sent and received on this stream, including `p2p`
+    /// messages, to the given capture.
+    pub fn set_capture(&mut self, capture: SessionCapture) {
+        self.capture = Some(capture);
+    }
+
+    /// Records the message with the given multiplexed message id, if capturing is enabled.
+    fn capture(&self, direction: CaptureDirection, message_id: u8, payload: &[u8]) {
+        if let Some(capture) = &self.capture {
+            capture.record(direction, &self.shared_capabilities, message_id, payload);
+        }
+    }
+
     /// Returns `true` if the stream has outgoing capacity.
     fn has_outgoing_capacity(&self) -> bool {
         self.outgoing_messages.len() < self.outgoing_message_buffer_capacity
@@ -311,6 +329,7 @@ impl<S> P2PStream<S> {
         let pong = P2PMessage::Pong;
         let mut pong_bytes = BytesMut::with_capacity(pong.length());
         pong.encode(&mut pong_bytes);
+        self.capture(CaptureDirection::Outbound, P2PMessageID::Pong as u8, &[EMPTY_LIST_CODE]);
         self.outgoing_messages.push_back(pong_bytes.freeze());
     }
 
@@ -319,6 +338,7 @@ impl<S> P2PStream<S> {
         let ping = P2PMessage::Ping;
         let mut ping_bytes = BytesMut::with_capacity(ping.length());
         ping.encode(&mut ping_bytes);
+        self.capture(CaptureDirection::Outbound, P2PMessageID::Ping as u8, &[EMPTY_LIST_CODE]);
         self.outgoing_messages.push_back(ping_bytes.freeze());
     }
 }
@@ -345,6 +365,7 @@ impl<S> DisconnectP2P for P2PStream<S> {
         let disconnect = P2PMessage::Disconnect(reason);
         let mut buf = BytesMut::with_capacity(disconnect.length());
         disconnect.encode(&mut buf);
+        self.capture(CaptureDirection::Outbound, buf[0], &buf[1..]);
 
         let mut compressed = BytesMut::zeroed(1 + snap::raw::max_compress_len(buf.len() - 1));
         let compressed_size =
@@ -445,6 +466,7 @@ where
             })?;
 
             let id = bytes[0];
+            this.capture(CaptureDirection::Inbound, id, &decompress_buf[1..]);
             match id {
                 _ if id == P2PMessageID::Ping as u8 => {
                     trace!("Received Ping, Sending Pong");
@@ -561,6 +583,8 @@ where
             return Err(P2PStreamError::SendBufferFull)
         }
 
+        self.capture(CaptureDirection::Outbound, item[0] + MAX_RESERVED_MESSAGE_ID + 1, &item[1..]);
+
         let this = self.project();
 
         let mut compressed = BytesMut::zeroed(1 + snap::raw::max_compress_len(item.len() - 1));
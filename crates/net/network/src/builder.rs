@@ -1,8 +1,9 @@
 //! Builder support for configuring the entire setup.
 
 use crate::{
-    eth_requests::EthRequestHandler, transactions::TransactionsManager, NetworkHandle,
-    NetworkManager,
+    eth_requests::EthRequestHandler,
+    transactions::{DefaultPropagationPolicy, TransactionPropagationPolicy, TransactionsManager},
+    NetworkHandle, NetworkManager,
 };
 use reth_transaction_pool::TransactionPool;
 use tokio::sync::mpsc;
@@ -51,15 +52,36 @@ impl<C, Tx, Eth> NetworkBuilder<C, Tx, Eth> {
     }
 
     /// Creates a new [`TransactionsManager`] and wires it to the network.
+    ///
+    /// Transactions are propagated according to the [`DefaultPropagationPolicy`].
     pub fn transactions<Pool: TransactionPool>(
         self,
         pool: Pool,
     ) -> NetworkBuilder<C, TransactionsManager<Pool>, Eth> {
+        self.transactions_with_policy(pool, DefaultPropagationPolicy::default())
+    }
+
+    /// Creates a new [`TransactionsManager`] that propagates transactions according to the given
+    /// [`TransactionPropagationPolicy`] and wires it to the network.
+    pub fn transactions_with_policy<Pool, P>(
+        self,
+        pool: Pool,
+        propagation_policy: P,
+    ) -> NetworkBuilder<C, TransactionsManager<Pool>, Eth>
+    where
+        Pool: TransactionPool,
+        P: TransactionPropagationPolicy,
+    {
         let NetworkBuilder { mut network, request_handler, .. } = self;
         let (tx, rx) = mpsc::unbounded_channel();
         network.set_transactions(tx);
         let handle = network.handle().clone();
-        let transactions = TransactionsManager::new(handle, pool, rx);
+        let transactions = TransactionsManager::with_propagation_policy(
+            handle,
+            pool,
+            rx,
+            Box::new(propagation_policy),
+        );
         NetworkBuilder { network, request_handler, transactions }
     }
 
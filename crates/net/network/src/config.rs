@@ -10,7 +10,7 @@ use crate::{
 use reth_discv4::{Discv4Config, Discv4ConfigBuilder, DEFAULT_DISCOVERY_ADDRESS};
 use reth_dns_discovery::DnsDiscoveryConfig;
 use reth_ecies::util::pk2id;
-use reth_eth_wire::{HelloMessage, HelloMessageWithProtocols, Status};
+use reth_eth_wire::{capture::PacketCapture, HelloMessage, HelloMessageWithProtocols, Status};
 use reth_primitives::{
     mainnet_nodes, sepolia_nodes, ChainSpec, ForkFilter, Head, NodeRecord, PeerId, MAINNET,
 };
@@ -74,6 +74,8 @@ pub struct NetworkConfig<C> {
     pub extra_protocols: RlpxSubProtocols,
     /// Whether to disable transaction gossip
     pub tx_gossip_disabled: bool,
+    /// Where the messages of all sessions are recorded, if set.
+    pub packet_capture: Option<PacketCapture>,
     /// Optimism Network Config
     #[cfg(feature = "optimism")]
     pub optimism_network_config: OptimismNetworkConfig,
@@ -172,6 +174,9 @@ pub struct NetworkConfigBuilder {
     /// The block importer type
     #[serde(skip)]
     block_import: Option<Box<dyn BlockImport>>,
+    /// Where the messages of all sessions are recorded
+    #[serde(skip)]
+    packet_capture: Option<PacketCapture>,
     /// Optimism Network Config Builder
     #[cfg(feature = "optimism")]
     optimism_network_config: OptimismNetworkConfigBuilder,
@@ -208,6 +213,7 @@ impl NetworkConfigBuilder {
             head: None,
             tx_gossip_disabled: false,
             block_import: None,
+            packet_capture: None,
             #[cfg(feature = "optimism")]
             optimism_network_config: OptimismNetworkConfigBuilder::default(),
         }
@@ -406,6 +412,14 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Records the messages of all sessions to the given capture.
+    ///
+    /// See also [PacketCapture].
+    pub fn packet_capture(mut self, capture: PacketCapture) -> Self {
+        self.packet_capture = Some(capture);
+        self
+    }
+
     /// Sets the sequencer HTTP endpoint.
     #[cfg(feature = "optimism")]
     pub fn sequencer_endpoint(mut self, endpoint: Option<String>) -> Self {
@@ -438,6 +452,7 @@ impl NetworkConfigBuilder {
             head,
             tx_gossip_disabled,
             block_import,
+            packet_capture,
             #[cfg(feature = "optimism")]
                 optimism_network_config: OptimismNetworkConfigBuilder { sequencer_endpoint },
         } = self;
@@ -492,6 +507,7 @@ impl NetworkConfigBuilder {
             extra_protocols,
             fork_filter,
             tx_gossip_disabled,
+            packet_capture,
             #[cfg(feature = "optimism")]
             optimism_network_config: OptimismNetworkConfig { sequencer_endpoint },
         }
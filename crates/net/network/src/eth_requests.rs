@@ -7,10 +7,11 @@ use reth_eth_wire::{
     Receipts,
 };
 use reth_interfaces::p2p::error::RequestResult;
-use reth_primitives::{BlockBody, BlockHashOrNumber, Header, HeadersDirection, PeerId};
-use reth_provider::{BlockReader, HeaderProvider, ReceiptProvider};
+use reth_primitives::{BlockBody, BlockHashOrNumber, Bytes, Header, HeadersDirection, PeerId};
+use reth_provider::{BlockReader, HeaderProvider, NodeDataProvider, ReceiptProvider};
 use std::{
     borrow::Borrow,
+    fmt,
     future::Future,
     hash::Hash,
     pin::Pin,
@@ -18,6 +19,7 @@ use std::{
 };
 use tokio::sync::{mpsc::Receiver, oneshot};
 use tokio_stream::wrappers::ReceiverStream;
+use tracing::debug;
 
 // Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/eth/handler.go#L34-L56>
 
@@ -37,6 +39,11 @@ const MAX_HEADERS_SERVE: usize = 1024;
 /// SOFT_RESPONSE_LIMIT.
 const MAX_BODIES_SERVE: usize = 1024;
 
+/// Maximum number of state trie nodes or contract codes to serve.
+///
+/// Used to limit lookups.
+const MAX_NODE_DATA_SERVE: usize = 1024;
+
 /// Estimated size in bytes of an RLP encoded receipt.
 const APPROX_RECEIPT_SIZE: usize = 24 * 1024;
 
@@ -53,7 +60,6 @@ const APPROX_HEADER_SIZE: usize = 500;
 /// Manages eth related requests on top of the p2p network.
 ///
 /// This can be spawned to another task and is supposed to be run as background service.
-#[derive(Debug)]
 #[must_use = "Manager does nothing unless polled."]
 pub struct EthRequestHandler<C> {
     /// The client type that can interact with the chain.
@@ -64,6 +70,10 @@ pub struct EthRequestHandler<C> {
     peers: PeersHandle,
     /// Incoming request from the [NetworkManager](crate::NetworkManager).
     incoming_requests: ReceiverStream<IncomingEthRequest>,
+    /// Serves `GetNodeData` requests, if configured.
+    ///
+    /// If not set, `GetNodeData` requests are answered with an empty response.
+    node_data: Option<Box<dyn NodeDataProvider>>,
     /// Metrics for the eth request handler.
     metrics: EthRequestHandlerMetrics,
 }
@@ -73,7 +83,77 @@ impl<C> EthRequestHandler<C> {
     /// Create a new instance
     pub fn new(client: C, peers: PeersHandle, incoming: Receiver<IncomingEthRequest>) -> Self {
         let metrics = Default::default();
-        Self { client, peers, incoming_requests: ReceiverStream::new(incoming), metrics }
+        Self {
+            client,
+            peers,
+            incoming_requests: ReceiverStream::new(incoming),
+            node_data: None,
+            metrics,
+        }
+    }
+
+    /// Configures the provider that is used to answer `GetNodeData` requests.
+    ///
+    /// Note: `GetNodeData` was removed in `eth/67`, so this only has an effect for peers with an
+    /// `eth/66` session.
+    pub fn with_node_data_provider<P>(mut self, provider: P) -> Self
+    where
+        P: NodeDataProvider + 'static,
+    {
+        self.node_data = Some(Box::new(provider));
+        self
+    }
+
+    /// Returns the requested state trie nodes and contract codes that are available.
+    ///
+    /// Unknown hashes are skipped, but at most [MAX_NODE_DATA_SERVE] hashes are looked up.
+    fn get_node_data_response(&self, request: GetNodeData) -> Vec<Bytes> {
+        let mut nodes = Vec::new();
+        let Some(provider) = self.node_data.as_ref() else { return nodes };
+
+        let mut total_bytes = 0;
+
+        for hash in request.0.into_iter().take(MAX_NODE_DATA_SERVE) {
+            let node = match provider.node_data_by_hash(hash) {
+                Ok(Some(node)) => node,
+                Ok(None) => continue,
+                Err(err) => {
+                    debug!(target: "net", %hash, %err, "failed to look up node data");
+                    break
+                }
+            };
+
+            total_bytes += node.len();
+            nodes.push(node);
+
+            if total_bytes > SOFT_RESPONSE_LIMIT {
+                break
+            }
+        }
+
+        nodes
+    }
+
+    fn on_node_data_request(
+        &mut self,
+        _peer_id: PeerId,
+        request: GetNodeData,
+        response: oneshot::Sender<RequestResult<NodeData>>,
+    ) {
+        self.metrics.received_node_data_requests.increment(1);
+        let nodes = self.get_node_data_response(request);
+        let _ = response.send(Ok(NodeData(nodes)));
+    }
+}
+
+impl<C: fmt::Debug> fmt::Debug for EthRequestHandler<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EthRequestHandler")
+            .field("client", &self.client)
+            .field("peers", &self.peers)
+            .field("incoming_requests", &self.incoming_requests)
+            .field("serves_node_data", &self.node_data.is_some())
+            .finish_non_exhaustive()
     }
 }
 
@@ -257,7 +337,9 @@ where
                     IncomingEthRequest::GetBlockBodies { peer_id, request, response } => {
                         this.on_bodies_request(peer_id, request, response)
                     }
-                    IncomingEthRequest::GetNodeData { .. } => {}
+                    IncomingEthRequest::GetNodeData { peer_id, request, response } => {
+                        this.on_node_data_request(peer_id, request, response)
+                    }
                     IncomingEthRequest::GetReceipts { peer_id, request, response } => {
                         this.on_receipts_request(peer_id, request, response)
                     }
@@ -149,4 +149,4 @@ pub use session::{
     SessionLimits, SessionManager, SessionsConfig,
 };
 
-pub use reth_eth_wire::{DisconnectReason, HelloMessageWithProtocols};
+pub use reth_eth_wire::{capture::PacketCapture, DisconnectReason, HelloMessageWithProtocols};
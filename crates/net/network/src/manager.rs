@@ -176,6 +176,7 @@ where
             dns_discovery_config,
             extra_protocols,
             tx_gossip_disabled,
+            packet_capture,
             #[cfg(feature = "optimism")]
                 optimism_network_config: crate::config::OptimismNetworkConfig { sequencer_endpoint },
         } = config;
@@ -213,6 +214,7 @@ where
             fork_filter,
             extra_protocols,
             bandwidth_meter.clone(),
+            packet_capture,
         );
 
         let state = NetworkState::new(
@@ -9,6 +9,7 @@ use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
     capability::{Capabilities, CapabilityMessage},
+    capture::{PacketCapture, SessionCapture},
     errors::EthStreamError,
     DisconnectReason, EthVersion, HelloMessageWithProtocols, Status, UnauthedEthStream,
     UnauthedP2PStream,
@@ -107,6 +108,8 @@ pub struct SessionManager {
     extra_protocols: RlpxSubProtocols,
     /// Used to measure inbound & outbound bandwidth across all managed streams
     bandwidth_meter: BandwidthMeter,
+    /// Where the messages of all sessions are recorded, if set.
+    packet_capture: Option<PacketCapture>,
     /// Metrics for the session manager.
     metrics: SessionManagerMetrics,
 }
@@ -125,6 +128,7 @@ impl SessionManager {
         fork_filter: ForkFilter,
         extra_protocols: RlpxSubProtocols,
         bandwidth_meter: BandwidthMeter,
+        packet_capture: Option<PacketCapture>,
     ) -> Self {
         let (pending_sessions_tx, pending_sessions_rx) = mpsc::channel(config.session_event_buffer);
         let (active_session_tx, active_session_rx) = mpsc::channel(config.session_event_buffer);
@@ -149,6 +153,7 @@ impl SessionManager {
             active_session_rx: ReceiverStream::new(active_session_rx),
             bandwidth_meter,
             extra_protocols,
+            packet_capture,
             metrics: Default::default(),
         }
     }
@@ -235,6 +240,7 @@ impl SessionManager {
         let status = self.status;
         let fork_filter = self.fork_filter.clone();
         let extra_handlers = self.extra_protocols.on_incoming(remote_addr);
+        let capture = self.packet_capture.as_ref().map(PacketCapture::session);
         self.spawn(start_pending_incoming_session(
             disconnect_rx,
             session_id,
@@ -246,6 +252,7 @@ impl SessionManager {
             status,
             fork_filter,
             extra_handlers,
+            capture,
         ));
 
         let handle = PendingSessionHandle {
@@ -270,6 +277,7 @@ impl SessionManager {
             let status = self.status;
             let band_with_meter = self.bandwidth_meter.clone();
             let extra_handlers = self.extra_protocols.on_outgoing(remote_addr, remote_peer_id);
+            let capture = self.packet_capture.as_ref().map(PacketCapture::session);
             self.spawn(start_pending_outbound_session(
                 disconnect_rx,
                 pending_events,
@@ -282,6 +290,7 @@ impl SessionManager {
                 fork_filter,
                 band_with_meter,
                 extra_handlers,
+                capture,
             ));
 
             let handle = PendingSessionHandle {
@@ -768,6 +777,7 @@ pub(crate) async fn start_pending_incoming_session(
     status: Status,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
+    capture: Option<SessionCapture>,
 ) {
     authenticate(
         disconnect_rx,
@@ -781,6 +791,7 @@ pub(crate) async fn start_pending_incoming_session(
         status,
         fork_filter,
         extra_handlers,
+        capture,
     )
     .await
 }
@@ -800,6 +811,7 @@ async fn start_pending_outbound_session(
     fork_filter: ForkFilter,
     bandwidth_meter: BandwidthMeter,
     extra_handlers: RlpxSubProtocolHandlers,
+    capture: Option<SessionCapture>,
 ) {
     let stream = match TcpStream::connect(remote_addr).await {
         Ok(stream) => {
@@ -832,6 +844,7 @@ async fn start_pending_outbound_session(
         status,
         fork_filter,
         extra_handlers,
+        capture,
     )
     .await
 }
@@ -850,6 +863,7 @@ async fn authenticate(
     status: Status,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
+    capture: Option<SessionCapture>,
 ) {
     let local_addr = stream.inner().local_addr().ok();
     let stream = match get_eciess_stream(stream, secret_key, direction).await {
@@ -879,6 +893,7 @@ async fn authenticate(
         status,
         fork_filter,
         extra_handlers,
+        capture,
     )
     .boxed();
 
@@ -920,6 +935,8 @@ async fn get_eciess_stream<Io: AsyncRead + AsyncWrite + Unpin + HasRemoteAddr>(
 ///
 /// If additional [RlpxSubProtocolHandlers] are provided, the hello message will be updated to also
 /// negotiate the additional protocols.
+///
+/// If a [SessionCapture] is provided, all messages after the `Hello` handshake are recorded.
 #[allow(clippy::too_many_arguments)]
 async fn authenticate_stream(
     stream: UnauthedP2PStream<ECIESStream<MeteredStream<TcpStream>>>,
@@ -931,12 +948,13 @@ async fn authenticate_stream(
     mut status: Status,
     fork_filter: ForkFilter,
     mut extra_handlers: RlpxSubProtocolHandlers,
+    capture: Option<SessionCapture>,
 ) -> PendingSessionEvent {
     // Add extra protocols to the hello message
     extra_handlers.retain(|handler| hello.try_add_protocol(handler.protocol()).is_ok());
 
     // conduct the p2p handshake and return the authenticated stream
-    let (p2p_stream, their_hello) = match stream.handshake(hello).await {
+    let (mut p2p_stream, their_hello) = match stream.handshake(hello).await {
         Ok(stream_res) => stream_res,
         Err(err) => {
             return PendingSessionEvent::Disconnected {
@@ -948,6 +966,10 @@ async fn authenticate_stream(
         }
     };
 
+    if let Some(capture) = capture {
+        p2p_stream.set_capture(capture);
+    }
+
     // Ensure we negotiated mandatory eth protocol
     let eth_version = match p2p_stream.shared_capabilities().eth_version() {
         Ok(version) => version,
@@ -8,6 +8,10 @@ use crate::{
     NetworkEvents, NetworkHandle,
 };
 use futures::{stream::FuturesUnordered, Future, FutureExt, StreamExt};
+pub use policy::{
+    DefaultPropagationPolicy, PeerPropagationContext, PropagationCandidate, PropagationMode,
+    TransactionPropagationPolicy,
+};
 use reth_eth_wire::{
     EthVersion, GetPooledTransactions, NewPooledTransactionHashes, NewPooledTransactionHashes66,
     NewPooledTransactionHashes68, PooledTransactions, Transactions,
@@ -37,6 +41,8 @@ use tokio::sync::{mpsc, mpsc::error::TrySendError, oneshot, oneshot::error::Recv
 use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
 use tracing::{debug, trace};
 
+pub mod policy;
+
 /// Cache limit of transactions to keep track of for a single peer.
 const PEER_TRANSACTION_CACHE_LIMIT: usize = 1024 * 10;
 
@@ -101,6 +107,9 @@ impl TransactionsHandle {
     }
 
     /// Manually propagate the transaction that belongs to the hash.
+    ///
+    /// How the transaction is propagated to each peer is decided by the configured
+    /// [`TransactionPropagationPolicy`].
     pub fn propagate(&self, hash: TxHash) {
         self.send(TransactionsCommand::PropagateHash(hash))
     }
@@ -114,7 +123,8 @@ impl TransactionsHandle {
 
     /// Manually propagate the transaction hashes to a specific peer.
     ///
-    /// Note: this only propagates the transactions that are known to the pool.
+    /// Note: this only propagates the transactions that are known to the pool and that the
+    /// configured [`TransactionPropagationPolicy`] doesn't skip for the peer.
     pub fn propagate_hashes_to(&self, hash: impl IntoIterator<Item = TxHash>, peer: PeerId) {
         self.send(TransactionsCommand::PropagateHashesTo(hash.into_iter().collect(), peer))
     }
@@ -127,6 +137,9 @@ impl TransactionsHandle {
     }
 
     /// Manually propagate full transactions to a specific peer.
+    ///
+    /// Note: transactions the configured [`TransactionPropagationPolicy`] skips for the peer are
+    /// not sent.
     pub fn propagate_transactions_to(&self, transactions: Vec<TxHash>, peer: PeerId) {
         self.send(TransactionsCommand::PropagateTransactionsTo(transactions, peer))
     }
@@ -197,6 +210,8 @@ pub struct TransactionsManager<Pool> {
     pending_transactions: ReceiverStream<TxHash>,
     /// Incoming events from the [`NetworkManager`](crate::NetworkManager).
     transaction_events: UnboundedMeteredReceiver<NetworkTransactionEvent>,
+    /// Decides how new transactions are propagated to the connected peers.
+    propagation_policy: Box<dyn TransactionPropagationPolicy>,
     /// TransactionsManager metrics
     metrics: TransactionsManagerMetrics,
 }
@@ -209,6 +224,24 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
         network: NetworkHandle,
         pool: Pool,
         from_network: mpsc::UnboundedReceiver<NetworkTransactionEvent>,
+    ) -> Self {
+        Self::with_propagation_policy(
+            network,
+            pool,
+            from_network,
+            Box::<DefaultPropagationPolicy>::default(),
+        )
+    }
+
+    /// Sets up a new instance that propagates transactions according to the given
+    /// [`TransactionPropagationPolicy`].
+    ///
+    /// Note: This expects an existing [`NetworkManager`](crate::NetworkManager) instance.
+    pub fn with_propagation_policy(
+        network: NetworkHandle,
+        pool: Pool,
+        from_network: mpsc::UnboundedReceiver<NetworkTransactionEvent>,
+        propagation_policy: Box<dyn TransactionPropagationPolicy>,
     ) -> Self {
         let network_events = network.event_listener();
         let (command_tx, command_rx) = mpsc::unbounded_channel();
@@ -232,6 +265,7 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
                 from_network,
                 NETWORK_POOL_TRANSACTIONS_SCOPE,
             ),
+            propagation_policy,
             metrics: Default::default(),
         }
     }
@@ -324,6 +358,9 @@ where
 
     /// Propagate the transactions to all connected peers either as full objects or hashes
     ///
+    /// How a transaction is propagated to a peer is decided by the configured
+    /// [`TransactionPropagationPolicy`].
+    ///
     /// The message for new pooled hashes depends on the negotiated version of the stream.
     /// See [NewPooledTransactionHashes]
     ///
@@ -337,23 +374,30 @@ where
             return propagated
         }
 
-        // send full transactions to a fraction fo the connected peers (square root of the total
-        // number of connected peers)
-        let max_num_full = (self.peers.len() as f64).sqrt() as usize + 1;
+        let num_peers = self.peers.len();
 
         // Note: Assuming ~random~ order due to random state of the peers map hasher
         for (peer_idx, (peer_id, peer)) in self.peers.iter_mut().enumerate() {
+            let ctx = PeerPropagationContext {
+                peer_id: *peer_id,
+                version: peer.version,
+                peer_idx,
+                num_peers,
+            };
+
             // filter all transactions unknown to the peer
             let mut hashes = PooledTransactionsHashesBuilder::new(peer.version);
             let mut full_transactions = FullTransactionsBuilder::default();
 
-            // Iterate through the transactions to propagate and fill the hashes and full
-            // transaction lists, before deciding whether or not to send full transactions to the
-            // peer.
+            // Iterate through the transactions to propagate and let the policy decide whether the
+            // transaction is sent in full, announced as hash, or not propagated to the peer at all.
             for tx in to_propagate.iter() {
-                if peer.transactions.insert(tx.hash()) {
-                    hashes.push(tx);
+                if peer.transactions.contains(&tx.hash()) {
+                    continue
+                }
 
+                match self.propagation_policy.propagation_mode(&ctx, &tx.candidate) {
+                    PropagationMode::Skip => continue,
                     // Do not send full 4844 transaction hashes to peers.
                     //
                     //  Nodes MUST NOT automatically broadcast blob transactions to their peers.
@@ -362,45 +406,45 @@ where
                     //  via `GetPooledTransactions`.
                     //
                     // From: <https://eips.ethereum.org/EIPS/eip-4844#networking>
-                    if !tx.transaction.is_eip4844() {
-                        full_transactions.push(tx);
+                    PropagationMode::Full if !tx.candidate.is_eip4844() => {
+                        // announce the hash if the transaction exceeds the packet size
+                        if !full_transactions.push(tx) {
+                            hashes.push(tx);
+                        }
                     }
+                    PropagationMode::Full | PropagationMode::Hash => hashes.push(tx),
                 }
+                peer.transactions.insert(tx.hash());
             }
+
             let mut new_pooled_hashes = hashes.build();
 
             if !new_pooled_hashes.is_empty() {
-                // determine whether to send full tx objects or hashes. If there are no full
-                // transactions, try to send hashes.
-                if peer_idx > max_num_full || full_transactions.is_empty() {
-                    // enforce tx soft limit per message for the (unlikely) event the number of
-                    // hashes exceeds it
-                    new_pooled_hashes.truncate(NEW_POOLED_TRANSACTION_HASHES_SOFT_LIMIT);
-
-                    for hash in new_pooled_hashes.iter_hashes().copied() {
-                        propagated.0.entry(hash).or_default().push(PropagateKind::Hash(*peer_id));
-                    }
+                // enforce tx soft limit per message for the (unlikely) event the number of
+                // hashes exceeds it
+                new_pooled_hashes.truncate(NEW_POOLED_TRANSACTION_HASHES_SOFT_LIMIT);
 
-                    trace!(target: "net::tx", ?peer_id, num_txs=?new_pooled_hashes.len(), "Propagating tx hashes to peer");
+                for hash in new_pooled_hashes.iter_hashes().copied() {
+                    propagated.0.entry(hash).or_default().push(PropagateKind::Hash(*peer_id));
+                }
 
-                    // send hashes of transactions
-                    self.network.send_transactions_hashes(*peer_id, new_pooled_hashes);
-                } else {
-                    let new_full_transactions = full_transactions.build();
-
-                    for tx in new_full_transactions.iter() {
-                        propagated
-                            .0
-                            .entry(tx.hash())
-                            .or_default()
-                            .push(PropagateKind::Full(*peer_id));
-                    }
+                trace!(target: "net::tx", ?peer_id, num_txs=?new_pooled_hashes.len(), "Propagating tx hashes to peer");
+
+                // send hashes of transactions
+                self.network.send_transactions_hashes(*peer_id, new_pooled_hashes);
+            }
 
-                    trace!(target: "net::tx", ?peer_id, num_txs=?new_full_transactions.len(), "Propagating full transactions to peer");
+            if !full_transactions.is_empty() {
+                let new_full_transactions = full_transactions.build();
 
-                    // send full transactions
-                    self.network.send_transactions(*peer_id, new_full_transactions);
+                for tx in new_full_transactions.iter() {
+                    propagated.0.entry(tx.hash()).or_default().push(PropagateKind::Full(*peer_id));
                 }
+
+                trace!(target: "net::tx", ?peer_id, num_txs=?new_full_transactions.len(), "Propagating full transactions to peer");
+
+                // send full transactions
+                self.network.send_transactions(*peer_id, new_full_transactions);
             }
         }
 
@@ -410,8 +454,22 @@ where
         propagated
     }
 
+    /// Returns the [PeerPropagationContext] for a propagation that only targets the given peer.
+    fn targeted_propagation_context(&self, peer_id: PeerId) -> Option<PeerPropagationContext> {
+        let peer = self.peers.get(&peer_id)?;
+        Some(PeerPropagationContext {
+            peer_id,
+            version: peer.version,
+            peer_idx: 0,
+            num_peers: self.peers.len(),
+        })
+    }
+
     /// Propagate the full transactions to a specific peer
     ///
+    /// Transactions the configured [`TransactionPropagationPolicy`] skips for this peer are not
+    /// sent.
+    ///
     /// Returns the propagated transactions
     fn propagate_full_transactions_to_peer(
         &mut self,
@@ -420,6 +478,7 @@ where
     ) -> Option<PropagatedTransactions> {
         trace!(target: "net::tx", ?peer_id, "Propagating transactions to peer");
 
+        let ctx = self.targeted_propagation_context(peer_id)?;
         let peer = self.peers.get_mut(&peer_id)?;
         let mut propagated = PropagatedTransactions::default();
 
@@ -435,8 +494,13 @@ where
 
         // Iterate through the transactions to propagate and fill the hashes and full transaction
         for tx in to_propagate {
-            if peer.transactions.insert(tx.hash()) {
-                full_transactions.push(&tx);
+            if peer.transactions.contains(&tx.hash()) ||
+                self.propagation_policy.propagation_mode(&ctx, &tx.candidate).is_skip()
+            {
+                continue
+            }
+            if full_transactions.push(&tx) {
+                peer.transactions.insert(tx.hash());
             }
         }
 
@@ -460,6 +524,9 @@ where
 
     /// Propagate the transaction hashes to the given peer
     ///
+    /// Transactions the configured [`TransactionPropagationPolicy`] skips for this peer are not
+    /// announced.
+    ///
     /// Note: This will only send the hashes for transactions that exist in the pool.
     fn propagate_hashes_to(&mut self, hashes: Vec<TxHash>, peer_id: PeerId) {
         trace!(target: "net::tx", "Start propagating transactions as hashes");
@@ -467,6 +534,10 @@ where
         // This fetches a transactions from the pool, including the blob transactions, which are
         // only ever sent as hashes.
         let propagated = {
+            let Some(ctx) = self.targeted_propagation_context(peer_id) else {
+                // no such peer
+                return
+            };
             let Some(peer) = self.peers.get_mut(&peer_id) else {
                 // no such peer
                 return
@@ -481,9 +552,13 @@ where
             let mut hashes = PooledTransactionsHashesBuilder::new(peer.version);
 
             for tx in to_propagate {
-                if peer.transactions.insert(tx.hash()) {
-                    hashes.push(&tx);
+                if peer.transactions.contains(&tx.hash()) ||
+                    self.propagation_policy.propagation_mode(&ctx, &tx.candidate).is_skip()
+                {
+                    continue
                 }
+                peer.transactions.insert(tx.hash());
+                hashes.push(&tx);
             }
 
             let new_pooled_hashes = hashes.build();
@@ -613,8 +688,8 @@ where
                 let peers = self.peers.keys().copied().collect::<HashSet<_>>();
                 tx.send(peers).ok();
             }
-            TransactionsCommand::PropagateTransactionsTo(_txs, _peer) => {
-                if let Some(propagated) = self.propagate_full_transactions_to_peer(_txs, _peer) {
+            TransactionsCommand::PropagateTransactionsTo(txs, peer) => {
+                if let Some(propagated) = self.propagate_full_transactions_to_peer(txs, peer) {
                     self.pool.on_propagated(propagated);
                 }
             }
@@ -643,6 +718,7 @@ where
             NetworkEvent::SessionClosed { peer_id, .. } => {
                 // remove the peer
                 self.peers.remove(&peer_id);
+                self.propagation_policy.on_session_closed(peer_id);
             }
             NetworkEvent::SessionEstablished {
                 peer_id, client_version, messages, version, ..
@@ -667,6 +743,7 @@ where
                     if self.network.tx_gossip_disabled() {
                         return
                     }
+                    let num_peers = self.peers.len();
                     let peer = self.peers.get_mut(&peer_id).expect("is present; qed");
 
                     let mut msg_builder = PooledTransactionsHashesBuilder::new(version);
@@ -678,12 +755,27 @@ where
                         return
                     }
 
+                    // the pool's content is only ever announced as hashes, so only transactions
+                    // the policy does not want to propagate to this peer are filtered out
+                    let ctx = PeerPropagationContext {
+                        peer_id,
+                        version,
+                        peer_idx: num_peers - 1,
+                        num_peers,
+                    };
                     for pooled_tx in pooled_txs.into_iter() {
+                        let candidate = PropagationCandidate::from_pooled(&pooled_tx);
+                        if self.propagation_policy.propagation_mode(&ctx, &candidate).is_skip() {
+                            continue
+                        }
                         peer.transactions.insert(*pooled_tx.hash());
                         msg_builder.push_pooled(pooled_tx);
                     }
 
                     let msg = msg_builder.build();
+                    if msg.is_empty() {
+                        return
+                    }
                     self.network.send_transactions_hashes(peer_id, msg);
                 }
             }
@@ -885,7 +977,7 @@ where
 
 /// A transaction that's about to be propagated to multiple peers.
 struct PropagateTransaction {
-    size: usize,
+    candidate: PropagationCandidate,
     transaction: Arc<TransactionSigned>,
 }
 
@@ -896,11 +988,15 @@ impl PropagateTransaction {
         self.transaction.hash()
     }
 
+    fn size(&self) -> usize {
+        self.candidate.size
+    }
+
     /// Create a new instance from a pooled transaction
     fn new<T: PoolTransaction>(tx: Arc<ValidPoolTransaction<T>>) -> Self {
-        let size = tx.encoded_length();
+        let candidate = PropagationCandidate::from_pooled(&tx);
         let transaction = Arc::new(tx.transaction.to_recovered_transaction().into_signed());
-        Self { size, transaction }
+        Self { candidate, transaction }
     }
 }
 
@@ -916,14 +1012,17 @@ struct FullTransactionsBuilder {
 
 impl FullTransactionsBuilder {
     /// Append a transaction to the list if it doesn't exceed the maximum size.
-    fn push(&mut self, transaction: &PropagateTransaction) {
-        let new_size = self.total_size + transaction.size;
+    ///
+    /// Returns false if the transaction was not added.
+    fn push(&mut self, transaction: &PropagateTransaction) -> bool {
+        let new_size = self.total_size + transaction.size();
         if new_size > MAX_FULL_TRANSACTIONS_PACKET_SIZE {
-            return
+            return false
         }
 
         self.total_size = new_size;
         self.transactions.push(Arc::clone(&transaction.transaction));
+        true
     }
 
     /// Returns whether or not any transactions are in the [FullTransactionsBuilder].
@@ -964,7 +1063,7 @@ impl PooledTransactionsHashesBuilder {
             PooledTransactionsHashesBuilder::Eth66(msg) => msg.0.push(tx.hash()),
             PooledTransactionsHashesBuilder::Eth68(msg) => {
                 msg.hashes.push(tx.hash());
-                msg.sizes.push(tx.size);
+                msg.sizes.push(tx.size());
                 msg.types.push(tx.transaction.tx_type().into());
             }
         }
@@ -1235,12 +1334,20 @@ enum FetchEvent {
 #[derive(Debug)]
 enum TransactionsCommand {
     /// Propagate a transaction hash to the network.
+    ///
+    /// This is propagated like a new pending transaction, so the propagation policy decides how
+    /// the transaction is sent to each peer.
     PropagateHash(B256),
     /// Propagate transaction hashes to a specific peer.
+    ///
+    /// Transactions the propagation policy skips for the peer are not announced.
     PropagateHashesTo(Vec<B256>, PeerId),
     /// Request the list of active peer IDs from the [`TransactionsManager`].
     GetActivePeers(oneshot::Sender<HashSet<PeerId>>),
     /// Propagate a collection of full transactions to a specific peer.
+    ///
+    /// Transactions the propagation policy skips for the peer are not sent, all others are sent
+    /// in full regardless of the policy's propagation mode.
     PropagateTransactionsTo(Vec<TxHash>, PeerId),
     /// Request transaction hashes known by specific peers from the [`TransactionsManager`].
     GetTransactionHashes {
@@ -0,0 +1,350 @@
+//! Policies that decide how new pending transactions are propagated to connected peers.
+//!
+//! The [`TransactionsManager`](crate::transactions::TransactionsManager) consults its
+//! [`TransactionPropagationPolicy`] for every (peer, transaction) pair when it broadcasts new
+//! pending transactions, and when it announces the pool's content to a newly connected peer.
+
+use reth_eth_wire::EthVersion;
+use reth_primitives::{PeerId, TxHash, EIP4844_TX_TYPE_ID};
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, ValidPoolTransaction};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Determines how transactions are propagated to peers.
+///
+/// Policies can be stacked by wrapping another policy, see for example
+/// [`SkipLocalTransactions`] or [`RateLimitedAnnouncements`].
+pub trait TransactionPropagationPolicy: fmt::Debug + Send + Sync + 'static {
+    /// Returns how the given transaction should be propagated to the given peer.
+    ///
+    /// This is only invoked for transactions the peer is not already known to have seen.
+    ///
+    /// Note: EIP-4844 transactions are never broadcast in full, if this returns
+    /// [`PropagationMode::Full`] for a blob transaction it is announced as hash instead.
+    fn propagation_mode(
+        &mut self,
+        peer: &PeerPropagationContext,
+        tx: &PropagationCandidate,
+    ) -> PropagationMode;
+
+    /// Invoked when the session with the peer was closed.
+    ///
+    /// Policies that keep track of per peer state should clean it up here.
+    fn on_session_closed(&mut self, _peer_id: PeerId) {}
+}
+
+/// How a transaction is propagated to a single peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropagationMode {
+    /// Send the full transaction object via a `Transactions` message.
+    Full,
+    /// Announce the transaction hash via a `NewPooledTransactionHashes` message.
+    Hash,
+    /// Do not propagate the transaction to this peer.
+    Skip,
+}
+
+// === impl PropagationMode ===
+
+impl PropagationMode {
+    /// Returns true if the transaction should not be propagated.
+    pub fn is_skip(&self) -> bool {
+        matches!(self, PropagationMode::Skip)
+    }
+}
+
+/// Information about the peer a transaction is about to be propagated to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerPropagationContext {
+    /// Identifier of the peer.
+    pub peer_id: PeerId,
+    /// The negotiated version of the peer's session.
+    pub version: EthVersion,
+    /// Position of the peer in the current propagation round.
+    ///
+    /// The order of the peers is random.
+    pub peer_idx: usize,
+    /// Total number of connected peers.
+    pub num_peers: usize,
+}
+
+/// Information about a transaction that is about to be propagated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropagationCandidate {
+    /// Hash of the transaction.
+    pub hash: TxHash,
+    /// The EIP-2718 type of the transaction.
+    pub tx_type: u8,
+    /// The encoded length of the transaction.
+    pub size: usize,
+    /// Where the transaction originated from.
+    pub origin: TransactionOrigin,
+}
+
+// === impl PropagationCandidate ===
+
+impl PropagationCandidate {
+    /// Creates the candidate for the given pooled transaction.
+    pub fn from_pooled<T: PoolTransaction>(tx: &ValidPoolTransaction<T>) -> Self {
+        Self {
+            hash: *tx.hash(),
+            tx_type: tx.tx_type(),
+            size: tx.encoded_length(),
+            origin: tx.origin,
+        }
+    }
+
+    /// Returns true if this is an EIP-4844 blob transaction.
+    pub fn is_eip4844(&self) -> bool {
+        self.tx_type == EIP4844_TX_TYPE_ID
+    }
+
+    /// Returns true if the transaction was submitted locally.
+    pub fn is_local(&self) -> bool {
+        self.origin.is_local()
+    }
+}
+
+/// The default propagation policy.
+///
+/// Sends full transactions to a fraction of the connected peers (square root of the total number
+/// of connected peers) and announces the hashes to all other peers.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct DefaultPropagationPolicy;
+
+impl TransactionPropagationPolicy for DefaultPropagationPolicy {
+    fn propagation_mode(
+        &mut self,
+        peer: &PeerPropagationContext,
+        tx: &PropagationCandidate,
+    ) -> PropagationMode {
+        // Nodes MUST NOT automatically broadcast blob transactions to their peers.
+        //
+        // From: <https://eips.ethereum.org/EIPS/eip-4844#networking>
+        if tx.is_eip4844() {
+            return PropagationMode::Hash
+        }
+
+        let max_num_full = (peer.num_peers as f64).sqrt() as usize + 1;
+        if peer.peer_idx > max_num_full {
+            PropagationMode::Hash
+        } else {
+            PropagationMode::Full
+        }
+    }
+}
+
+/// Announces all transactions as hashes only, peers have to request the full transactions.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct HashesOnly;
+
+impl TransactionPropagationPolicy for HashesOnly {
+    fn propagation_mode(
+        &mut self,
+        _peer: &PeerPropagationContext,
+        _tx: &PropagationCandidate,
+    ) -> PropagationMode {
+        PropagationMode::Hash
+    }
+}
+
+/// A policy that never propagates transactions that were submitted locally.
+///
+/// All other transactions are propagated according to the wrapped policy.
+#[derive(Debug, Clone, Default)]
+pub struct SkipLocalTransactions<P = DefaultPropagationPolicy> {
+    inner: P,
+}
+
+impl<P> SkipLocalTransactions<P> {
+    /// Wraps the given policy.
+    pub fn new(inner: P) -> Self {
+        Self { inner }
+    }
+}
+
+impl<P: TransactionPropagationPolicy> TransactionPropagationPolicy for SkipLocalTransactions<P> {
+    fn propagation_mode(
+        &mut self,
+        peer: &PeerPropagationContext,
+        tx: &PropagationCandidate,
+    ) -> PropagationMode {
+        if tx.is_local() {
+            return PropagationMode::Skip
+        }
+        self.inner.propagation_mode(peer, tx)
+    }
+
+    fn on_session_closed(&mut self, peer_id: PeerId) {
+        self.inner.on_session_closed(peer_id)
+    }
+}
+
+/// A policy that only propagates transactions to a fixed set of trusted peers.
+///
+/// Transactions are propagated to the trusted peers according to the wrapped policy.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedPeersOnly<P = DefaultPropagationPolicy> {
+    trusted_peers: HashSet<PeerId>,
+    inner: P,
+}
+
+impl<P> TrustedPeersOnly<P> {
+    /// Creates a new policy that only propagates to the given peers.
+    pub fn new(trusted_peers: impl IntoIterator<Item = PeerId>, inner: P) -> Self {
+        Self { trusted_peers: trusted_peers.into_iter().collect(), inner }
+    }
+
+    /// Adds a peer to the set of trusted peers.
+    pub fn add_trusted_peer(&mut self, peer_id: PeerId) {
+        self.trusted_peers.insert(peer_id);
+    }
+
+    /// Removes a peer from the set of trusted peers.
+    pub fn remove_trusted_peer(&mut self, peer_id: &PeerId) {
+        self.trusted_peers.remove(peer_id);
+    }
+}
+
+impl<P: TransactionPropagationPolicy> TransactionPropagationPolicy for TrustedPeersOnly<P> {
+    fn propagation_mode(
+        &mut self,
+        peer: &PeerPropagationContext,
+        tx: &PropagationCandidate,
+    ) -> PropagationMode {
+        if !self.trusted_peers.contains(&peer.peer_id) {
+            return PropagationMode::Skip
+        }
+        self.inner.propagation_mode(peer, tx)
+    }
+
+    fn on_session_closed(&mut self, peer_id: PeerId) {
+        self.inner.on_session_closed(peer_id)
+    }
+}
+
+/// A policy that limits the number of transactions propagated to a single peer within an
+/// interval.
+///
+/// Transactions that exceed the limit are not propagated to the peer. All other transactions are
+/// propagated according to the wrapped policy.
+#[derive(Debug, Clone)]
+pub struct RateLimitedAnnouncements<P = DefaultPropagationPolicy> {
+    /// Maximum number of transactions propagated to a peer per interval.
+    max_per_interval: usize,
+    /// The length of an interval.
+    interval: Duration,
+    /// Start of the current interval and the number of propagated transactions, per peer.
+    peers: HashMap<PeerId, (Instant, usize)>,
+    inner: P,
+}
+
+impl<P> RateLimitedAnnouncements<P> {
+    /// Creates a new policy that propagates at most `max_per_interval` transactions to a peer
+    /// within the given `interval`.
+    pub fn new(max_per_interval: usize, interval: Duration, inner: P) -> Self {
+        Self { max_per_interval, interval, peers: Default::default(), inner }
+    }
+}
+
+impl<P: TransactionPropagationPolicy> TransactionPropagationPolicy for RateLimitedAnnouncements<P> {
+    fn propagation_mode(
+        &mut self,
+        peer: &PeerPropagationContext,
+        tx: &PropagationCandidate,
+    ) -> PropagationMode {
+        let mode = self.inner.propagation_mode(peer, tx);
+        if mode.is_skip() {
+            return mode
+        }
+
+        let now = Instant::now();
+        let (interval_start, num_propagated) = self.peers.entry(peer.peer_id).or_insert((now, 0));
+        if now.duration_since(*interval_start) >= self.interval {
+            *interval_start = now;
+            *num_propagated = 0;
+        }
+
+        if *num_propagated >= self.max_per_interval {
+            return PropagationMode::Skip
+        }
+        *num_propagated += 1;
+
+        mode
+    }
+
+    fn on_session_closed(&mut self, peer_id: PeerId) {
+        self.peers.remove(&peer_id);
+        self.inner.on_session_closed(peer_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::B256;
+
+    fn peer(peer_idx: usize, num_peers: usize) -> PeerPropagationContext {
+        PeerPropagationContext {
+            peer_id: PeerId::random(),
+            version: EthVersion::Eth68,
+            peer_idx,
+            num_peers,
+        }
+    }
+
+    fn candidate(tx_type: u8, origin: TransactionOrigin) -> PropagationCandidate {
+        PropagationCandidate { hash: B256::random(), tx_type, size: 100, origin }
+    }
+
+    #[test]
+    fn default_policy_sends_full_to_sqrt_peers() {
+        let mut policy = DefaultPropagationPolicy;
+        let tx = candidate(2, TransactionOrigin::External);
+
+        // sqrt(16) + 1 = 5
+        assert_eq!(policy.propagation_mode(&peer(5, 16), &tx), PropagationMode::Full);
+        assert_eq!(policy.propagation_mode(&peer(6, 16), &tx), PropagationMode::Hash);
+
+        let blob = candidate(EIP4844_TX_TYPE_ID, TransactionOrigin::External);
+        assert_eq!(policy.propagation_mode(&peer(0, 16), &blob), PropagationMode::Hash);
+    }
+
+    #[test]
+    fn skip_local_and_untrusted() {
+        let mut policy = SkipLocalTransactions::new(DefaultPropagationPolicy);
+        let ctx = peer(0, 1);
+        assert!(policy.propagation_mode(&ctx, &candidate(2, TransactionOrigin::Local)).is_skip());
+        assert_eq!(
+            policy.propagation_mode(&ctx, &candidate(2, TransactionOrigin::External)),
+            PropagationMode::Full
+        );
+
+        let mut policy = TrustedPeersOnly::new([ctx.peer_id], HashesOnly);
+        let tx = candidate(2, TransactionOrigin::Local);
+        assert_eq!(policy.propagation_mode(&ctx, &tx), PropagationMode::Hash);
+        assert!(policy.propagation_mode(&peer(0, 1), &tx).is_skip());
+    }
+
+    #[test]
+    fn rate_limit_per_peer() {
+        let mut policy =
+            RateLimitedAnnouncements::new(2, Duration::from_secs(60), DefaultPropagationPolicy);
+        let ctx = peer(0, 1);
+        let other = peer(0, 1);
+        let tx = candidate(2, TransactionOrigin::External);
+
+        assert!(!policy.propagation_mode(&ctx, &tx).is_skip());
+        assert!(!policy.propagation_mode(&ctx, &tx).is_skip());
+        assert!(policy.propagation_mode(&ctx, &tx).is_skip());
+        assert!(!policy.propagation_mode(&other, &tx).is_skip());
+
+        policy.on_session_closed(ctx.peer_id);
+        assert!(!policy.propagation_mode(&ctx, &tx).is_skip());
+    }
+}
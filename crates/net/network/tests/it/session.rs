@@ -1,13 +1,17 @@
 //! Session tests
 
 use futures::StreamExt;
-use reth_eth_wire::EthVersion;
+use reth_eth_wire::{
+    capture::{CaptureReader, PacketCapture},
+    EthMessage, EthVersion,
+};
 use reth_network::{
-    test_utils::{PeerConfig, Testnet},
-    NetworkEvent, NetworkEvents,
+    test_utils::{NetworkEventStream, PeerConfig, Testnet},
+    NetworkConfigBuilder, NetworkEvent, NetworkEvents, NetworkManager,
 };
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::test_utils::NoopProvider;
+use secp256k1::SecretKey;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_session_established_with_highest_version() {
@@ -81,3 +85,50 @@ async fn test_session_established_with_different_capability() {
 
     handle.terminate().await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_capture_session() {
+    reth_tracing::init_test_tracing();
+
+    let net = Testnet::create(1).await;
+    let other = net.handles().next().unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("capture");
+    let capture = PacketCapture::create(&path).unwrap();
+    let secret_key = SecretKey::new(&mut rand::thread_rng());
+    let config = NetworkConfigBuilder::new(secret_key)
+        .listener_port(0)
+        .disable_discovery()
+        .packet_capture(capture.clone())
+        .build(NoopProvider::default());
+    let network = NetworkManager::new(config).await.unwrap();
+    let handle = network.handle().clone();
+    let mut events = NetworkEventStream::new(handle.event_listener());
+
+    tokio::task::spawn(network);
+    let net_handle = net.spawn();
+
+    handle.add_peer(*other.peer_id(), other.local_addr());
+    assert_eq!(events.next_session_established().await, Some(*other.peer_id()));
+
+    capture.flush().unwrap();
+    let messages = CaptureReader::open(&path).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+
+    // the status messages of the eth handshake are recorded in both directions
+    let statuses = messages
+        .iter()
+        .filter(|msg| {
+            matches!(
+                msg.decode_eth().and_then(Result::ok).map(|msg| msg.message),
+                Some(EthMessage::Status(_))
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(statuses.len(), 2);
+    assert!(statuses.iter().any(|msg| msg.direction.is_outbound()));
+    assert!(statuses.iter().any(|msg| msg.direction.is_inbound()));
+    assert!(messages.iter().all(|msg| msg.session == statuses[0].session));
+
+    net_handle.terminate().await;
+}
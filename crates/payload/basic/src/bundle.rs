@@ -0,0 +1,377 @@
+//! Atomic execution of transaction bundles.
+
+use reth_primitives::{revm::env::tx_env_with_recovered, Address, TxHash, B256, U256};
+use reth_transaction_pool::PoolBundle;
+use revm::{
+    primitives::{
+        Account, AccountInfo, BlockEnv, Bytecode, CfgEnv, EVMError, Env, ExecutionResult,
+        InvalidTransaction, ResultAndState, State as EvmState,
+    },
+    Database, DatabaseCommit,
+};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fmt,
+    sync::Arc,
+};
+
+/// A [Database] that holds the state changes of the already executed transactions of a bundle on
+/// top of the block's state, so that they can be discarded if the bundle is rejected.
+#[derive(Debug)]
+struct BundleOverlay<'a, DB> {
+    db: &'a mut DB,
+    /// The accumulated state changes of the executed transactions.
+    accounts: HashMap<Address, Account>,
+}
+
+impl<'a, DB> BundleOverlay<'a, DB> {
+    fn new(db: &'a mut DB) -> Self {
+        Self { db, accounts: HashMap::new() }
+    }
+}
+
+impl<DB: Database> Database for BundleOverlay<'_, DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        match self.accounts.get(&address) {
+            Some(account) if account.is_selfdestructed() => Ok(None),
+            Some(account) => Ok(Some(account.info.clone())),
+            None => self.db.basic(address),
+        }
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self
+            .accounts
+            .values()
+            .find(|account| account.info.code_hash == code_hash)
+            .and_then(|account| account.info.code.clone());
+        match code {
+            Some(code) => Ok(code),
+            None => self.db.code_by_hash(code_hash),
+        }
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if let Some(account) = self.accounts.get(&address) {
+            if let Some(slot) = account.storage.get(&index) {
+                return Ok(slot.present_value)
+            }
+            // the storage of new accounts is empty
+            if account.is_created() || account.is_selfdestructed() {
+                return Ok(U256::ZERO)
+            }
+        }
+        self.db.storage(address, index)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash(number)
+    }
+}
+
+impl<DB> DatabaseCommit for BundleOverlay<'_, DB> {
+    fn commit(&mut self, changes: EvmState) {
+        for (address, account) in changes {
+            if !account.is_touched() {
+                continue
+            }
+            match self.accounts.entry(address) {
+                Entry::Vacant(entry) => {
+                    entry.insert(account);
+                }
+                Entry::Occupied(mut entry) => {
+                    let existing = entry.get_mut();
+                    if account.is_created() || account.is_selfdestructed() {
+                        *existing = account;
+                    } else {
+                        existing.info = account.info;
+                        existing.storage.extend(account.storage);
+                        existing.status |= account.status;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A bundle that was executed successfully on top of the block's state.
+#[derive(Debug)]
+pub(crate) struct SimulatedBundle {
+    /// The state changes of each transaction, in order.
+    pub(crate) states: Vec<EvmState>,
+    /// The result of each transaction, in order.
+    pub(crate) results: Vec<ExecutionResult>,
+    /// The increase of the coinbase balance.
+    pub(crate) coinbase_profit: U256,
+}
+
+/// Reasons why a bundle can't be included.
+#[derive(Debug)]
+pub(crate) enum BundleError<E> {
+    /// The gas limit of the bundle exceeds the gas that is left in the block.
+    ExceedsGasLimit(u64, u64),
+    /// A transaction of the bundle is invalid.
+    InvalidTransaction(TxHash, InvalidTransaction),
+    /// A transaction of the bundle reverted but is not allowed to revert.
+    Reverted(TxHash),
+    /// The bundle doesn't increase the coinbase balance.
+    Unprofitable,
+    /// Executing the bundle failed, this is fatal for the payload.
+    Evm(EVMError<E>),
+}
+
+impl<E: fmt::Debug> fmt::Display for BundleError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::ExceedsGasLimit(gas_limit, available) => {
+                write!(f, "bundle gas limit {gas_limit} exceeds available gas {available}")
+            }
+            BundleError::InvalidTransaction(hash, err) => {
+                write!(f, "transaction {hash} is invalid: {err:?}")
+            }
+            BundleError::Reverted(hash) => write!(f, "transaction {hash} reverted"),
+            BundleError::Unprofitable => f.write_str("bundle doesn't pay the coinbase"),
+            BundleError::Evm(err) => write!(f, "failed to execute bundle: {err:?}"),
+        }
+    }
+}
+
+/// Executes all transactions of the bundle on top of the given state without modifying it.
+///
+/// The bundle is rejected if the gas limits of its transactions exceed the `available_gas` of the
+/// block, if any transaction is invalid, if a transaction reverts that is not allowed to revert or
+/// if it doesn't pay the coinbase.
+pub(crate) fn simulate_bundle<DB: Database>(
+    db: &mut DB,
+    bundle: &PoolBundle,
+    cfg: &CfgEnv,
+    block_env: &BlockEnv,
+    available_gas: u64,
+) -> Result<SimulatedBundle, BundleError<DB::Error>> {
+    let gas_limit = bundle.gas_limit();
+    if gas_limit > available_gas {
+        return Err(BundleError::ExceedsGasLimit(gas_limit, available_gas))
+    }
+
+    let coinbase = block_env.coinbase;
+    let coinbase_balance_before = db
+        .basic(coinbase)
+        .map_err(|err| BundleError::Evm(EVMError::Database(err)))?
+        .map(|account| account.balance)
+        .unwrap_or_default();
+
+    let env = Env { cfg: cfg.clone(), block: block_env.clone(), ..Default::default() };
+    let mut evm = revm::EVM::with_env(env);
+    evm.database(BundleOverlay::new(db));
+
+    let mut states = Vec::with_capacity(bundle.transactions.len());
+    let mut results = Vec::with_capacity(bundle.transactions.len());
+    for tx in &bundle.transactions {
+        evm.env.tx = tx_env_with_recovered(tx);
+        let ResultAndState { result, state } = match evm.transact() {
+            Ok(res) => res,
+            Err(EVMError::Transaction(err)) => {
+                return Err(BundleError::InvalidTransaction(tx.hash, err))
+            }
+            Err(err) => return Err(BundleError::Evm(err)),
+        };
+        if !result.is_success() && !bundle.may_revert(&tx.hash) {
+            return Err(BundleError::Reverted(tx.hash))
+        }
+
+        evm.db.as_mut().expect("is set").commit(state.clone());
+        states.push(state);
+        results.push(result);
+    }
+
+    let coinbase_balance_after = evm
+        .db
+        .as_mut()
+        .expect("is set")
+        .basic(coinbase)
+        .map_err(|err| BundleError::Evm(EVMError::Database(err)))?
+        .map(|account| account.balance)
+        .unwrap_or_default();
+    let coinbase_profit = coinbase_balance_after.saturating_sub(coinbase_balance_before);
+    if coinbase_profit.is_zero() {
+        return Err(BundleError::Unprofitable)
+    }
+
+    Ok(SimulatedBundle { states, results, coinbase_profit })
+}
+
+/// Orders the bundles by the coinbase profit they yield when simulated on top of the given state,
+/// most profitable first.
+///
+/// Bundles that can't be included on top of the state are moved to the end. Bundles with the same
+/// profit keep their relative order.
+pub(crate) fn order_bundles_by_profit<DB: Database>(
+    db: &mut DB,
+    bundles: Vec<Arc<PoolBundle>>,
+    cfg: &CfgEnv,
+    block_env: &BlockEnv,
+    available_gas: u64,
+) -> Result<Vec<Arc<PoolBundle>>, EVMError<DB::Error>> {
+    let mut candidates = Vec::with_capacity(bundles.len());
+    for bundle in bundles {
+        let coinbase_profit = match simulate_bundle(db, &bundle, cfg, block_env, available_gas) {
+            Ok(simulated) => Some(simulated.coinbase_profit),
+            Err(BundleError::Evm(err)) => return Err(err),
+            Err(_) => None,
+        };
+        candidates.push((coinbase_profit, bundle));
+    }
+
+    candidates.sort_by(|(a, _), (b, _)| b.cmp(a));
+    Ok(candidates.into_iter().map(|(_, bundle)| bundle).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        sign_message, Bytes, Transaction, TransactionKind, TransactionSigned,
+        TransactionSignedEcRecovered, TxLegacy,
+    };
+    use revm::db::{CacheDB, EmptyDB};
+
+    fn coinbase() -> Address {
+        Address::with_last_byte(0xc0)
+    }
+
+    fn envs() -> (CfgEnv, BlockEnv) {
+        let block_env = BlockEnv {
+            coinbase: coinbase(),
+            gas_limit: U256::from(30_000_000),
+            ..Default::default()
+        };
+        (CfgEnv::default(), block_env)
+    }
+
+    /// Returns a transaction that pays a priority fee of one wei per gas.
+    fn call(secret: B256, nonce: u64, to: Address, gas_limit: u64) -> TransactionSignedEcRecovered {
+        let tx = Transaction::Legacy(TxLegacy {
+            nonce,
+            gas_price: 1,
+            gas_limit,
+            to: TransactionKind::Call(to),
+            value: U256::from(1),
+            ..Default::default()
+        });
+        let signature = sign_message(secret, tx.signature_hash()).unwrap();
+        TransactionSigned::from_transaction_and_signature(tx, signature).into_ecrecovered().unwrap()
+    }
+
+    /// Returns a database with the funded sender and a contract that always reverts.
+    fn database(sender: Address) -> (CacheDB<EmptyDB>, Address) {
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            sender,
+            AccountInfo { balance: U256::from(10).pow(U256::from(18)), ..Default::default() },
+        );
+        // PUSH1 0 PUSH1 0 REVERT
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xfd]));
+        let reverting = Address::with_last_byte(0xee);
+        db.insert_account_info(
+            reverting,
+            AccountInfo { code_hash: code.hash_slow(), code: Some(code), ..Default::default() },
+        );
+        (db, reverting)
+    }
+
+    #[test]
+    fn reverting_transaction_rejects_bundle() {
+        let secret = B256::random();
+        let first = call(secret, 0, Address::random(), 21_000);
+        let (mut db, reverting) = database(first.signer());
+        let second = call(secret, 1, reverting, 100_000);
+        let (cfg, block_env) = envs();
+
+        let bundle = PoolBundle::new(vec![first.clone(), second.clone()], 1);
+        let err = simulate_bundle(&mut db, &bundle, &cfg, &block_env, 30_000_000).unwrap_err();
+        assert!(matches!(err, BundleError::Reverted(hash) if hash == second.hash));
+
+        // the state changes of the first transaction are discarded
+        let sender = db.basic(first.signer()).unwrap().unwrap();
+        assert_eq!(sender.nonce, 0);
+        assert_eq!(sender.balance, U256::from(10).pow(U256::from(18)));
+        assert!(db.basic(coinbase()).unwrap().is_none());
+
+        // the bundle is included if the transaction is allowed to revert
+        let bundle = bundle.with_reverting_tx_hashes(vec![second.hash]);
+        let simulated = simulate_bundle(&mut db, &bundle, &cfg, &block_env, 30_000_000).unwrap();
+        assert_eq!(simulated.results.len(), 2);
+        assert!(simulated.results[0].is_success());
+        assert!(!simulated.results[1].is_success());
+    }
+
+    #[test]
+    fn later_transactions_see_bundle_state() {
+        let secret = B256::random();
+        let to = Address::random();
+        let txs = (0..3).map(|nonce| call(secret, nonce, to, 21_000)).collect::<Vec<_>>();
+        let (mut db, _) = database(txs[0].signer());
+        let (cfg, block_env) = envs();
+
+        // the second transaction of the bundle executes on top of the first one
+        let bundle = PoolBundle::new(txs[..2].to_vec(), 1);
+        let simulated = simulate_bundle(&mut db, &bundle, &cfg, &block_env, 30_000_000).unwrap();
+        assert_eq!(simulated.coinbase_profit, U256::from(42_000));
+
+        // a later transaction can't be executed until the bundle is committed
+        let later = PoolBundle::new(vec![txs[2].clone()], 1);
+        let err = simulate_bundle(&mut db, &later, &cfg, &block_env, 30_000_000).unwrap_err();
+        assert!(matches!(err, BundleError::InvalidTransaction(hash, _) if hash == txs[2].hash));
+
+        for state in simulated.states {
+            db.commit(state);
+        }
+        assert_eq!(db.basic(txs[0].signer()).unwrap().unwrap().nonce, 2);
+        simulate_bundle(&mut db, &later, &cfg, &block_env, 30_000_000).unwrap();
+    }
+
+    #[test]
+    fn order_by_profit() {
+        let to = Address::random();
+        let single = call(B256::random(), 0, to, 21_000);
+        let (mut db, reverting) = database(single.signer());
+        let double_secret = B256::random();
+        let double = (0..2).map(|nonce| call(double_secret, nonce, to, 21_000)).collect::<Vec<_>>();
+        let rejected = call(B256::random(), 0, reverting, 100_000);
+        for sender in [double[0].signer(), rejected.signer()] {
+            db.insert_account_info(
+                sender,
+                AccountInfo { balance: U256::from(10).pow(U256::from(18)), ..Default::default() },
+            );
+        }
+        let (cfg, block_env) = envs();
+
+        let bundles = vec![
+            Arc::new(PoolBundle::new(vec![rejected], 1)),
+            Arc::new(PoolBundle::new(vec![single], 1)),
+            Arc::new(PoolBundle::new(double, 1)),
+        ];
+        let ordered =
+            order_bundles_by_profit(&mut db, bundles.clone(), &cfg, &block_env, 30_000_000)
+                .unwrap();
+        assert_eq!(ordered, vec![bundles[2].clone(), bundles[1].clone(), bundles[0].clone()]);
+    }
+
+    #[test]
+    fn bundle_respects_gas_limit() {
+        let secret = B256::random();
+        let to = Address::random();
+        let txs = (0..2).map(|nonce| call(secret, nonce, to, 21_000)).collect::<Vec<_>>();
+        let (mut db, _) = database(txs[0].signer());
+        let (cfg, block_env) = envs();
+        let bundle = PoolBundle::new(txs, 1);
+
+        let err = simulate_bundle(&mut db, &bundle, &cfg, &block_env, 41_999).unwrap_err();
+        assert!(matches!(err, BundleError::ExceedsGasLimit(42_000, 41_999)));
+        assert_eq!(db.basic(bundle.transactions[0].signer()).unwrap().unwrap().nonce, 0);
+
+        simulate_bundle(&mut db, &bundle, &cfg, &block_env, 42_000).unwrap();
+    }
+}
@@ -9,7 +9,15 @@
 #![deny(unused_must_use, rust_2018_idioms)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
-use crate::metrics::PayloadBuilderMetrics;
+use crate::{
+    bundle::{order_bundles_by_profit, simulate_bundle, BundleError, SimulatedBundle},
+    metrics::PayloadBuilderMetrics,
+    strategy::{
+        BestPoolTransactions, InclusionContext, InclusionDecision, PayloadSourceContext,
+        PayloadStrategy,
+    },
+    trace::{PayloadBuildTracer, PayloadBuildTraces},
+};
 use alloy_rlp::Encodable;
 use futures_core::ready;
 use futures_util::FutureExt;
@@ -27,8 +35,8 @@ use reth_primitives::{
     eip4844::calculate_excess_blob_gas,
     proofs,
     revm::{compat::into_reth_log, env::tx_env_with_recovered},
-    Block, BlockNumberOrTag, Bytes, ChainSpec, Header, IntoRecoveredTransaction, Receipt, Receipts,
-    SealedBlock, Withdrawal, B256, EMPTY_OMMER_ROOT_HASH, U256,
+    Block, BlockNumberOrTag, Bytes, ChainSpec, Header, Receipt, Receipts, SealedBlock, Withdrawal,
+    B256, EMPTY_OMMER_ROOT_HASH, U256,
 };
 use reth_provider::{
     BlockReaderIdExt, BlockSource, BundleStateWithReceipts, ProviderError, StateProviderFactory,
@@ -37,6 +45,7 @@ use reth_revm::{
     database::StateProviderDatabase,
     state_change::{apply_beacon_root_contract_call, post_block_withdrawals_balance_increments},
 };
+use reth_rpc_types::{PayloadBuildOutcome, PayloadBuildTrace, PayloadSkipReason};
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::TransactionPool;
 use revm::{
@@ -45,11 +54,12 @@ use revm::{
     Database, DatabaseCommit, State,
 };
 use std::{
+    collections::HashMap,
     future::Future,
     pin::Pin,
     sync::{atomic::AtomicBool, Arc},
     task::{Context, Poll},
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::{
     sync::{oneshot, Semaphore},
@@ -57,7 +67,10 @@ use tokio::{
 };
 use tracing::{debug, trace, warn};
 
+mod bundle;
 mod metrics;
+pub mod strategy;
+pub mod trace;
 
 #[cfg(feature = "optimism")]
 mod optimism;
@@ -65,9 +78,20 @@ mod optimism;
 pub use optimism::OptimismPayloadBuilder;
 
 /// Ethereum payload builder
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder {
+    /// Decides which transactions are included in the payload.
+    strategy: PayloadStrategy,
+}
+
+impl EthereumPayloadBuilder {
+    /// Sets the [PayloadStrategy] that decides which transactions are included in the payload.
+    pub fn with_strategy(mut self, strategy: PayloadStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+}
 
 /// The [`PayloadJobGenerator`] that creates [`BasicPayloadJob`]s.
 #[derive(Debug)]
@@ -88,6 +112,8 @@ pub struct BasicPayloadJobGenerator<Client, Pool, Tasks, Builder = EthereumPaylo
     ///
     /// See [PayloadBuilder]
     builder: Builder,
+    /// The build traces of the most recent payload jobs.
+    traces: PayloadBuildTraces,
 }
 
 // === impl BasicPayloadJobGenerator ===
@@ -107,7 +133,7 @@ impl<Client, Pool, Tasks> BasicPayloadJobGenerator<Client, Pool, Tasks> {
             executor,
             config,
             chain_spec,
-            EthereumPayloadBuilder,
+            EthereumPayloadBuilder::default(),
         )
     }
 }
@@ -130,9 +156,21 @@ impl<Client, Pool, Tasks, Builder> BasicPayloadJobGenerator<Client, Pool, Tasks,
             config,
             chain_spec,
             builder,
+            traces: PayloadBuildTraces::default(),
         }
     }
 
+    /// Sets the store for the build traces of the payload jobs.
+    pub fn with_traces(mut self, traces: PayloadBuildTraces) -> Self {
+        self.traces = traces;
+        self
+    }
+
+    /// Returns the store for the build traces of the payload jobs.
+    pub fn traces(&self) -> &PayloadBuildTraces {
+        &self.traces
+    }
+
     /// Returns the maximum duration a job should be allowed to run.
     ///
     /// This adheres to the following specification:
@@ -215,8 +253,13 @@ where
             payload_task_guard: self.payload_task_guard.clone(),
             metrics: Default::default(),
             builder: self.builder.clone(),
+            traces: self.traces.clone(),
         })
     }
+
+    fn build_trace(&self, id: PayloadId) -> Option<PayloadBuildTrace> {
+        self.traces.get(id)
+    }
 }
 
 /// Restricts how many generator tasks can be executed at once.
@@ -352,6 +395,23 @@ pub struct BasicPayloadJob<Client, Pool, Tasks, Builder> {
     ///
     /// See [PayloadBuilder]
     builder: Builder,
+    /// Where the build trace of every attempt is recorded.
+    traces: PayloadBuildTraces,
+}
+
+impl<Client, Pool, Tasks, Builder> BasicPayloadJob<Client, Pool, Tasks, Builder> {
+    /// Records the trace and metrics of a finished build attempt.
+    fn record_build_iteration(
+        &self,
+        tracer: &PayloadBuildTracer,
+        started: Instant,
+        outcome: PayloadBuildOutcome,
+        fees: Option<U256>,
+    ) {
+        let iteration = tracer.finish(started, outcome, fees);
+        self.metrics.record_build_iteration(&iteration);
+        self.traces.record(self.config.payload_id(), iteration);
+    }
 }
 
 impl<Client, Pool, Tasks, Builder> Future for BasicPayloadJob<Client, Pool, Tasks, Builder>
@@ -388,6 +448,9 @@ where
                 this.metrics.inc_initiated_payload_builds();
                 let cached_reads = this.cached_reads.take().unwrap_or_default();
                 let builder = this.builder.clone();
+                let tracer = PayloadBuildTracer::default();
+                let build_tracer = tracer.clone();
+                let started = Instant::now();
                 this.executor.spawn_blocking(Box::pin(async move {
                     // acquire the permit for executing the task
                     let _permit = guard.0.acquire().await;
@@ -398,12 +461,13 @@ where
                         config: payload_config,
                         cancel,
                         best_payload,
+                        tracer: build_tracer,
                     };
                     let result = builder.try_build(args);
                     let _ = tx.send(result);
                 }));
 
-                this.pending_block = Some(PendingPayload { _cancel, payload: rx });
+                this.pending_block = Some(PendingPayload { _cancel, payload: rx, tracer, started });
             }
         }
 
@@ -416,12 +480,26 @@ where
                         BuildOutcome::Better { payload, cached_reads } => {
                             this.cached_reads = Some(cached_reads);
                             debug!(target: "payload_builder", value = %payload.fees(), "built better payload");
+                            this.metrics.inc_better_payload_builds();
+                            this.record_build_iteration(
+                                &fut.tracer,
+                                fut.started,
+                                PayloadBuildOutcome::Better,
+                                Some(payload.fees()),
+                            );
                             let payload = Arc::new(payload);
                             this.best_payload = Some(payload);
                         }
                         BuildOutcome::Aborted { fees, cached_reads } => {
                             this.cached_reads = Some(cached_reads);
                             trace!(target: "payload_builder", worse_fees = %fees, "skipped payload build of worse block");
+                            this.metrics.inc_aborted_payload_builds();
+                            this.record_build_iteration(
+                                &fut.tracer,
+                                fut.started,
+                                PayloadBuildOutcome::Aborted,
+                                Some(fees),
+                            );
                         }
                         BuildOutcome::Cancelled => {
                             unreachable!("the cancel signal never fired")
@@ -432,6 +510,12 @@ where
                     // job failed, but we simply try again next interval
                     debug!(target: "payload_builder", ?error, "payload build attempt failed");
                     this.metrics.inc_failed_payload_builds();
+                    this.record_build_iteration(
+                        &fut.tracer,
+                        fut.started,
+                        PayloadBuildOutcome::Failed,
+                        None,
+                    );
                 }
                 Poll::Pending => {
                     this.pending_block = Some(fut);
@@ -505,6 +589,7 @@ where
                         config: self.config.clone(),
                         cancel: Cancelled::default(),
                         best_payload: None,
+                        tracer: Default::default(),
                     };
                     if let Ok(BuildOutcome::Better { payload, cached_reads }) =
                         self.builder.try_build(args)
@@ -517,6 +602,9 @@ where
                                 best_payload: Some(payload),
                                 maybe_better,
                                 empty_payload,
+                                payload_id: self.config.payload_id(),
+                                traces: self.traces.clone(),
+                                metrics: self.metrics.clone(),
                             },
                             KeepPayloadJobAlive::Yes,
                         )
@@ -527,7 +615,14 @@ where
             empty_payload = Some(rx);
         }
 
-        let fut = ResolveBestPayload { best_payload, maybe_better, empty_payload };
+        let fut = ResolveBestPayload {
+            best_payload,
+            maybe_better,
+            empty_payload,
+            payload_id: self.config.payload_id(),
+            traces: self.traces.clone(),
+            metrics: self.metrics.clone(),
+        };
 
         (fut, KeepPayloadJobAlive::No)
     }
@@ -550,6 +645,33 @@ pub struct ResolveBestPayload {
     maybe_better: Option<PendingPayload>,
     /// The empty payload building job in progress.
     empty_payload: Option<oneshot::Receiver<Result<BuiltPayload, PayloadBuilderError>>>,
+    /// The id of the resolved payload job.
+    payload_id: PayloadId,
+    /// Where the build trace of the job that's currently running is recorded.
+    traces: PayloadBuildTraces,
+    /// metrics of the resolved payload job
+    metrics: PayloadBuilderMetrics,
+}
+
+impl ResolveBestPayload {
+    /// Records the trace and metrics of the build job that raced the resolution.
+    fn record_build_iteration(
+        &self,
+        fut: &PendingPayload,
+        outcome: PayloadBuildOutcome,
+        fees: Option<U256>,
+    ) {
+        let iteration = fut.tracer.finish(fut.started, outcome, fees);
+        self.metrics.record_build_iteration(&iteration);
+        self.traces.record(self.payload_id, iteration);
+    }
+
+    /// Cancels the build job that's still running, because the payload is resolved without it.
+    fn cancel_maybe_better(&mut self) {
+        if let Some(fut) = self.maybe_better.take() {
+            self.record_build_iteration(&fut, PayloadBuildOutcome::Cancelled, None);
+        }
+    }
 }
 
 impl Future for ResolveBestPayload {
@@ -559,18 +681,37 @@ impl Future for ResolveBestPayload {
         let this = self.get_mut();
 
         // check if there is a better payload before returning the best payload
-        if let Some(fut) = Pin::new(&mut this.maybe_better).as_pin_mut() {
-            if let Poll::Ready(res) = fut.poll(cx) {
-                this.maybe_better = None;
-                if let Ok(BuildOutcome::Better { payload, .. }) = res {
+        if let Some(mut fut) = this.maybe_better.take() {
+            match fut.poll_unpin(cx) {
+                Poll::Ready(Ok(BuildOutcome::Better { payload, .. })) => {
                     debug!(target: "payload_builder", "resolving better payload");
+                    this.metrics.inc_better_payload_builds();
+                    this.record_build_iteration(
+                        &fut,
+                        PayloadBuildOutcome::Better,
+                        Some(payload.fees()),
+                    );
                     return Poll::Ready(Ok(Arc::new(payload)))
                 }
+                Poll::Ready(Ok(BuildOutcome::Aborted { fees, .. })) => {
+                    this.metrics.inc_aborted_payload_builds();
+                    this.record_build_iteration(&fut, PayloadBuildOutcome::Aborted, Some(fees));
+                }
+                Poll::Ready(Ok(BuildOutcome::Cancelled)) => {
+                    this.record_build_iteration(&fut, PayloadBuildOutcome::Cancelled, None);
+                }
+                Poll::Ready(Err(error)) => {
+                    debug!(target: "payload_builder", ?error, "payload build attempt failed");
+                    this.metrics.inc_failed_payload_builds();
+                    this.record_build_iteration(&fut, PayloadBuildOutcome::Failed, None);
+                }
+                Poll::Pending => this.maybe_better = Some(fut),
             }
         }
 
         if let Some(best) = this.best_payload.take() {
             debug!(target: "payload_builder", "resolving best payload");
+            this.cancel_maybe_better();
             return Poll::Ready(Ok(best))
         }
 
@@ -582,9 +723,13 @@ impl Future for ResolveBestPayload {
                 } else {
                     debug!(target: "payload_builder", "resolving empty payload");
                 }
+                this.cancel_maybe_better();
                 Poll::Ready(res.map(Arc::new))
             }
-            Poll::Ready(Err(err)) => Poll::Ready(Err(err.into())),
+            Poll::Ready(Err(err)) => {
+                this.cancel_maybe_better();
+                Poll::Ready(Err(err.into()))
+            }
             Poll::Pending => {
                 this.empty_payload = Some(empty_payload);
                 Poll::Pending
@@ -600,6 +745,10 @@ struct PendingPayload {
     _cancel: Cancelled,
     /// The channel to send the result to.
     payload: oneshot::Receiver<Result<BuildOutcome, PayloadBuilderError>>,
+    /// Records the transactions considered by the job.
+    tracer: PayloadBuildTracer,
+    /// When the job was spawned.
+    started: Instant,
 }
 
 impl Future for PendingPayload {
@@ -729,6 +878,7 @@ pub struct BuildArguments<Pool, Client> {
     config: PayloadConfig,
     cancel: Cancelled,
     best_payload: Option<Arc<BuiltPayload>>,
+    tracer: PayloadBuildTracer,
 }
 
 impl<Pool, Client> BuildArguments<Pool, Client> {
@@ -741,7 +891,21 @@ impl<Pool, Client> BuildArguments<Pool, Client> {
         cancel: Cancelled,
         best_payload: Option<Arc<BuiltPayload>>,
     ) -> Self {
-        Self { client, pool, cached_reads, config, cancel, best_payload }
+        Self {
+            client,
+            pool,
+            cached_reads,
+            config,
+            cancel,
+            best_payload,
+            tracer: Default::default(),
+        }
+    }
+
+    /// Sets the [PayloadBuildTracer] that records the transactions considered by the build.
+    pub fn with_tracer(mut self, tracer: PayloadBuildTracer) -> Self {
+        self.tracer = tracer;
+        self
     }
 }
 
@@ -772,7 +936,7 @@ pub trait PayloadBuilder<Pool, Client>: Send + Sync + Clone {
     ) -> Result<BuildOutcome, PayloadBuilderError>;
 }
 
-// Default implementation of [PayloadBuilder] for unit type
+// Default implementation of [PayloadBuilder] for ethereum
 impl<Pool, Client> PayloadBuilder<Pool, Client> for EthereumPayloadBuilder
 where
     Client: StateProviderFactory,
@@ -782,7 +946,7 @@ where
         &self,
         args: BuildArguments<Pool, Client>,
     ) -> Result<BuildOutcome, PayloadBuilderError> {
-        default_payload_builder(args)
+        payload_builder_with_strategy(args, &self.strategy)
     }
 }
 
@@ -799,7 +963,25 @@ where
     Client: StateProviderFactory,
     Pool: TransactionPool,
 {
-    let BuildArguments { client, pool, mut cached_reads, config, cancel, best_payload } = args;
+    payload_builder_with_strategy(args, &PayloadStrategy::default())
+}
+
+/// Constructs an Ethereum transaction payload with the transactions selected by the given
+/// [PayloadStrategy].
+///
+/// Transactions are included in the order of the strategy until the block is full, the strategy
+/// stops or there are no more transactions.
+pub fn payload_builder_with_strategy<Pool, Client>(
+    args: BuildArguments<Pool, Client>,
+    strategy: &PayloadStrategy,
+) -> Result<BuildOutcome, PayloadBuilderError>
+where
+    Client: StateProviderFactory,
+    Pool: TransactionPool,
+{
+    let started = Instant::now();
+    let BuildArguments { client, pool, mut cached_reads, config, cancel, best_payload, tracer } =
+        args;
 
     let state_provider = client.state_by_block_hash(config.parent_block.hash)?;
     let state = StateProviderDatabase::new(&state_provider);
@@ -822,12 +1004,19 @@ where
     let base_fee = initialized_block_env.basefee.to::<u64>();
 
     let mut executed_txs = Vec::new();
-    let mut best_txs = pool.best_transactions_with_base_fee(base_fee);
+    let block_number = initialized_block_env.number.to::<u64>();
+    let source_ctx =
+        PayloadSourceContext { block_number, timestamp: attributes.timestamp, base_fee };
+    let mut best_txs = strategy.transactions(
+        Box::new(BestPoolTransactions::new(pool.best_transactions_with_base_fee(base_fee))),
+        &source_ctx,
+        &state_provider,
+    );
+    // the number of included transactions per sender
+    let mut sender_txs = HashMap::new();
 
     let mut total_fees = U256::ZERO;
 
-    let block_number = initialized_block_env.number.to::<u64>();
-
     // apply eip-4788 pre block contract call
     pre_block_beacon_root_contract_call(
         &mut db,
@@ -839,13 +1028,109 @@ where
     )?;
 
     let mut receipts = Vec::new();
-    while let Some(pool_tx) = best_txs.next() {
+
+    // include the bundles that target this block atomically at the top of the block, the most
+    // profitable ones first
+    let mut bundles = pool
+        .bundle_pool()
+        .map(|bundle_pool| bundle_pool.bundles_for_block(block_number, attributes.timestamp))
+        .unwrap_or_default();
+    bundles.extend(strategy.bundles(&source_ctx));
+    let bundles = order_bundles_by_profit(
+        &mut db,
+        bundles,
+        &initialized_cfg,
+        &initialized_block_env,
+        block_gas_limit - cumulative_gas_used,
+    )
+    .map_err(PayloadBuilderError::EvmExecutionError)?;
+    for bundle in bundles {
+        // check if the job was cancelled, if so we can exit early
+        if cancel.is_cancelled() {
+            return Ok(BuildOutcome::Cancelled)
+        }
+
+        // the blob sidecars of bundles are not available
+        if bundle.transactions.iter().any(|tx| tx.is_eip4844()) {
+            trace!(target: "payload_builder", bundle=?bundle.hash, "skipping bundle with blob transactions");
+            for tx in &bundle.transactions {
+                tracer.skipped_with_error(
+                    tx.hash,
+                    PayloadSkipReason::BundleRejected,
+                    "bundle contains blob transactions".to_string(),
+                );
+            }
+            continue
+        }
+
+        let SimulatedBundle { states, results, coinbase_profit } = match simulate_bundle(
+            &mut db,
+            &bundle,
+            &initialized_cfg,
+            &initialized_block_env,
+            block_gas_limit - cumulative_gas_used,
+        ) {
+            Ok(simulated) => simulated,
+            Err(BundleError::Evm(err)) => {
+                // this is an error that we should treat as fatal for this attempt
+                return Err(PayloadBuilderError::EvmExecutionError(err))
+            }
+            Err(BundleError::ExceedsGasLimit(..)) => {
+                // we don't have capacity for all transactions of the bundle
+                trace!(target: "payload_builder", bundle=?bundle.hash, "skipping bundle that exceeds the block gas limit");
+                for tx in &bundle.transactions {
+                    tracer.skipped(tx.hash, PayloadSkipReason::InsufficientGas);
+                }
+                continue
+            }
+            Err(err) => {
+                trace!(target: "payload_builder", bundle=?bundle.hash, %err, "skipping bundle");
+                for tx in &bundle.transactions {
+                    tracer.skipped_with_error(
+                        tx.hash,
+                        PayloadSkipReason::BundleRejected,
+                        err.to_string(),
+                    );
+                }
+                continue
+            }
+        };
+
+        for ((tx, state), result) in bundle.transactions.iter().zip(states).zip(results) {
+            // commit changes
+            db.commit(state);
+
+            let gas_used = result.gas_used();
+            cumulative_gas_used += gas_used;
+            let miner_fee = tx.effective_tip_per_gas(Some(base_fee)).unwrap_or_default();
+            tracer.included(tx.hash, gas_used, U256::from(miner_fee) * U256::from(gas_used));
+            receipts.push(Some(Receipt {
+                tx_type: tx.tx_type(),
+                success: result.is_success(),
+                cumulative_gas_used,
+                logs: result.logs().into_iter().map(into_reth_log).collect(),
+                #[cfg(feature = "optimism")]
+                deposit_nonce: None,
+                #[cfg(feature = "optimism")]
+                deposit_receipt_version: None,
+            }));
+
+            *sender_txs.entry(tx.signer()).or_default() += 1;
+            executed_txs.push(tx.clone().into_signed());
+        }
+
+        // the bundle pays the coinbase directly and via the priority fees of its transactions
+        total_fees += coinbase_profit;
+    }
+
+    while let Some(tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
-        if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
+        if cumulative_gas_used + tx.gas_limit() > block_gas_limit {
             // we can't fit this transaction into the block, so we need to mark it as invalid
             // which also removes all dependent transaction from the iterator before we can
             // continue
-            best_txs.mark_invalid(&pool_tx);
+            tracer.skipped(tx.hash, PayloadSkipReason::InsufficientGas);
+            best_txs.mark_invalid(&tx);
             continue
         }
 
@@ -854,8 +1139,27 @@ where
             return Ok(BuildOutcome::Cancelled)
         }
 
-        // convert tx to a signed transaction
-        let tx = pool_tx.to_recovered_transaction();
+        // check if the strategy includes the transaction
+        let ctx = InclusionContext {
+            cumulative_gas_used,
+            block_gas_limit,
+            sender_transactions: sender_txs.get(&tx.signer()).copied().unwrap_or_default(),
+            elapsed: started.elapsed(),
+        };
+        match strategy.check(&tx, &ctx) {
+            InclusionDecision::Include => {}
+            InclusionDecision::Skip => {
+                trace!(target: "payload_builder", tx=?tx.hash, "skipping transaction excluded by the payload strategy");
+                tracer.skipped(tx.hash, PayloadSkipReason::Excluded);
+                best_txs.mark_invalid(&tx);
+                continue
+            }
+            InclusionDecision::Stop => {
+                trace!(target: "payload_builder", tx=?tx.hash, "payload strategy stopped including transactions");
+                tracer.skipped(tx.hash, PayloadSkipReason::Excluded);
+                break
+            }
+        }
 
         // There's only limited amount of blob space available per block, so we need to check if the
         // EIP-4844 can still fit in the block
@@ -866,7 +1170,8 @@ where
                 // which removes its dependent transactions from the iterator. This is similar to
                 // the gas limit condition for regular transactions above.
                 trace!(target: "payload_builder", tx=?tx.hash, ?sum_blob_gas_used, ?tx_blob_gas, "skipping blob transaction because it would exceed the max data gas per block");
-                best_txs.mark_invalid(&pool_tx);
+                tracer.skipped(tx.hash, PayloadSkipReason::InsufficientBlobGas);
+                best_txs.mark_invalid(&tx);
                 continue
             }
         }
@@ -889,11 +1194,21 @@ where
                         if matches!(err, InvalidTransaction::NonceTooLow { .. }) {
                             // if the nonce is too low, we can skip this transaction
                             trace!(target: "payload_builder", ?err, ?tx, "skipping nonce too low transaction");
+                            tracer.skipped(tx.hash, PayloadSkipReason::NonceTooLow);
                         } else {
                             // if the transaction is invalid, we can skip it and all of its
                             // descendants
                             trace!(target: "payload_builder", ?err, ?tx, "skipping invalid transaction and its descendants");
-                            best_txs.mark_invalid(&pool_tx);
+                            if matches!(err, InvalidTransaction::NonceTooHigh { .. }) {
+                                tracer.skipped(tx.hash, PayloadSkipReason::NonceGap);
+                            } else {
+                                tracer.skipped_with_error(
+                                    tx.hash,
+                                    PayloadSkipReason::Invalid,
+                                    format!("{err:?}"),
+                                );
+                            }
+                            best_txs.mark_invalid(&tx);
                         }
 
                         continue
@@ -941,7 +1256,11 @@ where
         let miner_fee = tx
             .effective_tip_per_gas(Some(base_fee))
             .expect("fee is always valid; execution succeeded");
-        total_fees += U256::from(miner_fee) * U256::from(gas_used);
+        let fees = U256::from(miner_fee) * U256::from(gas_used);
+        total_fees += fees;
+        tracer.included(tx.hash, gas_used, fees);
+
+        *sender_txs.entry(tx.signer()).or_default() += 1;
 
         // append transaction to the list of executed transactions
         executed_txs.push(tx.into_signed());
@@ -1,9 +1,13 @@
 //! Metrics for the payload builder impl
 
-use reth_metrics::{metrics::Counter, Metrics};
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+use reth_rpc_types::{PayloadBuildIteration, PayloadTransactionStatus};
 
 /// Transaction pool metrics
-#[derive(Metrics)]
+#[derive(Clone, Metrics)]
 #[metrics(scope = "payloads")]
 pub(crate) struct PayloadBuilderMetrics {
     /// Number of active jobs
@@ -12,6 +16,22 @@ pub(crate) struct PayloadBuilderMetrics {
     pub(crate) initiated_payload_builds: Counter,
     /// Total number of failed payload build attempts
     pub(crate) failed_payload_builds: Counter,
+    /// Total number of payload build attempts that produced a better payload
+    pub(crate) better_payload_builds: Counter,
+    /// Total number of payload build attempts that were discarded because they weren't better
+    pub(crate) aborted_payload_builds: Counter,
+    /// Duration of a payload build attempt in seconds
+    pub(crate) payload_build_duration: Histogram,
+    /// Gas used by the payload of a build attempt
+    pub(crate) payload_gas_used: Histogram,
+    /// Number of transactions included in the payload of a build attempt
+    pub(crate) payload_transactions: Histogram,
+    /// Total number of transactions considered for inclusion
+    pub(crate) considered_transactions: Counter,
+    /// Total number of transactions included in a payload
+    pub(crate) included_transactions: Counter,
+    /// Total number of transactions that were skipped
+    pub(crate) skipped_transactions: Counter,
 }
 
 impl PayloadBuilderMetrics {
@@ -26,4 +46,29 @@ impl PayloadBuilderMetrics {
     pub(crate) fn inc_failed_payload_builds(&self) {
         self.failed_payload_builds.increment(1);
     }
+
+    pub(crate) fn inc_better_payload_builds(&self) {
+        self.better_payload_builds.increment(1);
+    }
+
+    pub(crate) fn inc_aborted_payload_builds(&self) {
+        self.aborted_payload_builds.increment(1);
+    }
+
+    /// Records the summary of a finished payload build attempt.
+    pub(crate) fn record_build_iteration(&self, iteration: &PayloadBuildIteration) {
+        self.payload_build_duration.record(iteration.duration_ms as f64 / 1000.0);
+        self.payload_gas_used.record(iteration.gas_used as f64);
+
+        let included = iteration
+            .transactions
+            .iter()
+            .filter(|tx| matches!(tx.status, PayloadTransactionStatus::Included { .. }))
+            .count();
+        let skipped = iteration.transactions.len() - included;
+        self.payload_transactions.record(included as f64);
+        self.considered_transactions.increment(iteration.transactions.len() as u64);
+        self.included_transactions.increment(included as u64);
+        self.skipped_transactions.increment(skipped as u64);
+    }
 }
@@ -20,7 +20,8 @@ where
     Client: StateProviderFactory,
     Pool: TransactionPool,
 {
-    let BuildArguments { client, pool, mut cached_reads, config, cancel, best_payload } = args;
+    let BuildArguments { client, pool, mut cached_reads, config, cancel, best_payload, tracer } =
+        args;
 
     let state_provider = client.state_by_block_hash(config.parent_block.hash)?;
     let state = StateProviderDatabase::new(&state_provider);
@@ -157,6 +158,7 @@ where
                 // we can't fit this transaction into the block, so we need to mark it as invalid
                 // which also removes all dependent transaction from the iterator before we can
                 // continue
+                tracer.skipped(*pool_tx.hash(), PayloadSkipReason::InsufficientGas);
                 best_txs.mark_invalid(&pool_tx);
                 continue
             }
@@ -187,10 +189,20 @@ where
                             if matches!(err, InvalidTransaction::NonceTooLow { .. }) {
                                 // if the nonce is too low, we can skip this transaction
                                 trace!(target: "payload_builder", ?err, ?tx, "skipping nonce too low transaction");
+                                tracer.skipped(tx.hash, PayloadSkipReason::NonceTooLow);
                             } else {
                                 // if the transaction is invalid, we can skip it and all of its
                                 // descendants
                                 trace!(target: "payload_builder", ?err, ?tx, "skipping invalid transaction and its descendants");
+                                if matches!(err, InvalidTransaction::NonceTooHigh { .. }) {
+                                    tracer.skipped(tx.hash, PayloadSkipReason::NonceGap);
+                                } else {
+                                    tracer.skipped_with_error(
+                                        tx.hash,
+                                        PayloadSkipReason::Invalid,
+                                        format!("{err:?}"),
+                                    );
+                                }
                                 best_txs.mark_invalid(&pool_tx);
                             }
 
@@ -228,7 +240,9 @@ where
             let miner_fee = tx
                 .effective_tip_per_gas(Some(base_fee))
                 .expect("fee is always valid; execution succeeded");
-            total_fees += U256::from(miner_fee) * U256::from(gas_used);
+            let fees = U256::from(miner_fee) * U256::from(gas_used);
+            total_fees += fees;
+            tracer.included(tx.hash, gas_used, fees);
 
             // append transaction to the list of executed transactions
             executed_txs.push(tx.into_signed());
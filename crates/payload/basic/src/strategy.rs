@@ -0,0 +1,697 @@
+//! Strategies that decide which transactions are included in a payload and in which order.
+//!
+//! A [PayloadStrategy] is composed of:
+//!  - [BundleSource]s of bundles that are included atomically at the top of the block, in addition
+//!    to the bundles of the pool
+//!  - a [TransactionSource] that yields the candidate transactions, for example the best
+//!    transactions of the pool, and can add bundles, see [BundleFirstSource]
+//!  - an optional [PayloadOrdering] that reorders the candidates
+//!  - a set of [InclusionFilter]s that decide whether a candidate is included
+//!
+//! The default strategy greedily includes the best transactions of the pool until the block is
+//! full.
+
+use reth_primitives::{
+    Address, IntoRecoveredTransaction, TransactionSignedEcRecovered, TxHash, U256,
+};
+use reth_provider::StateProvider;
+use reth_transaction_pool::{BestTransactions, PoolBundle, PoolTransaction, ValidPoolTransaction};
+use std::{collections::HashMap, fmt, sync::Arc, time::Duration};
+
+/// The transactions that are considered for inclusion in a payload, in the order they should be
+/// included.
+pub trait PayloadTransactions: Send {
+    /// Returns the next transaction to include.
+    fn next(&mut self) -> Option<TransactionSignedEcRecovered>;
+
+    /// Marks the transaction as invalid.
+    ///
+    /// Implementers must ensure that transactions that depend on this transaction are no longer
+    /// returned.
+    fn mark_invalid(&mut self, transaction: &TransactionSignedEcRecovered);
+
+    /// Skips all blob transactions, because there's no blob space left in the block.
+    fn skip_blobs(&mut self);
+}
+
+/// Context for creating the [PayloadTransactions] of a payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadSourceContext {
+    /// The number of the block that is built.
+    pub block_number: u64,
+    /// The timestamp of the block that is built.
+    pub timestamp: u64,
+    /// The base fee of the block that is built.
+    pub base_fee: u64,
+}
+
+/// Creates the candidate transactions of a payload from the best transactions of the pool.
+pub trait TransactionSource: fmt::Debug + Send + Sync {
+    /// Returns the candidate transactions for the payload.
+    fn transactions<'a>(
+        &self,
+        pool_transactions: Box<dyn PayloadTransactions + 'a>,
+        ctx: &PayloadSourceContext,
+    ) -> Box<dyn PayloadTransactions + 'a>;
+
+    /// Returns the bundles of this source that target the payload.
+    ///
+    /// Bundles are included atomically before the candidate transactions. By default there are
+    /// none.
+    fn bundles(&self, _ctx: &PayloadSourceContext) -> Vec<Arc<PoolBundle>> {
+        Vec::new()
+    }
+}
+
+/// A [TransactionSource] that only yields the best transactions of the pool.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct PoolTransactionSource;
+
+impl TransactionSource for PoolTransactionSource {
+    fn transactions<'a>(
+        &self,
+        pool_transactions: Box<dyn PayloadTransactions + 'a>,
+        _ctx: &PayloadSourceContext,
+    ) -> Box<dyn PayloadTransactions + 'a> {
+        pool_transactions
+    }
+}
+
+/// An external source of transaction bundles.
+///
+/// The transactions of a bundle are included atomically: either all of them in order or none.
+pub trait BundleSource: fmt::Debug + Send + Sync {
+    /// Returns the bundles that target the block, in the order they should be included.
+    fn bundles(&self, ctx: &PayloadSourceContext) -> Vec<Arc<PoolBundle>>;
+}
+
+/// A [TransactionSource] that includes the bundles of a [BundleSource] before the best
+/// transactions of the pool.
+///
+/// The bundles are included atomically by the payload builder: if a transaction of a bundle is
+/// invalid or reverts without being allowed to, none of the transactions of the bundle are
+/// included.
+#[derive(Debug, Clone)]
+pub struct BundleFirstSource {
+    bundles: Arc<dyn BundleSource>,
+}
+
+impl BundleFirstSource {
+    /// Creates a new source that includes the bundles of the given source first.
+    pub fn new(bundles: Arc<dyn BundleSource>) -> Self {
+        Self { bundles }
+    }
+}
+
+impl TransactionSource for BundleFirstSource {
+    fn transactions<'a>(
+        &self,
+        pool_transactions: Box<dyn PayloadTransactions + 'a>,
+        _ctx: &PayloadSourceContext,
+    ) -> Box<dyn PayloadTransactions + 'a> {
+        pool_transactions
+    }
+
+    fn bundles(&self, ctx: &PayloadSourceContext) -> Vec<Arc<PoolBundle>> {
+        self.bundles.bundles(ctx)
+    }
+}
+
+/// Context for determining the priority of a candidate transaction.
+#[derive(Clone, Copy)]
+pub struct OrderingContext<'a> {
+    /// The number of the block that is built.
+    pub block_number: u64,
+    /// The base fee of the block that is built.
+    pub base_fee: u64,
+    /// The state of the parent block, for example to simulate the transaction.
+    pub state: &'a dyn StateProvider,
+}
+
+impl fmt::Debug for OrderingContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrderingContext")
+            .field("block_number", &self.block_number)
+            .field("base_fee", &self.base_fee)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Determines the order of the candidate transactions of a payload.
+///
+/// This can be used to order transactions by their simulated profit.
+pub trait PayloadOrdering: fmt::Debug + Send + Sync {
+    /// Returns the priority of the transaction, higher is included first.
+    fn priority(
+        &self,
+        transaction: &TransactionSignedEcRecovered,
+        ctx: &OrderingContext<'_>,
+    ) -> U256;
+}
+
+/// Orders transactions by their effective tip per gas.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct EffectiveTipOrdering;
+
+impl PayloadOrdering for EffectiveTipOrdering {
+    fn priority(
+        &self,
+        transaction: &TransactionSignedEcRecovered,
+        ctx: &OrderingContext<'_>,
+    ) -> U256 {
+        U256::from(transaction.effective_tip_per_gas(Some(ctx.base_fee)).unwrap_or_default())
+    }
+}
+
+/// The state of the payload when a transaction is considered for inclusion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InclusionContext {
+    /// The gas used by all included transactions.
+    pub cumulative_gas_used: u64,
+    /// The gas limit of the block.
+    pub block_gas_limit: u64,
+    /// The number of included transactions of the sender of the transaction.
+    pub sender_transactions: usize,
+    /// The time since the payload building attempt started.
+    pub elapsed: Duration,
+}
+
+/// The decision of an [InclusionFilter].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InclusionDecision {
+    /// Try to include the transaction.
+    Include,
+    /// Skip the transaction and all transactions that depend on it.
+    Skip,
+    /// Stop including transactions.
+    Stop,
+}
+
+/// Decides whether a candidate transaction is included in a payload.
+pub trait InclusionFilter: fmt::Debug + Send + Sync {
+    /// Returns whether the transaction should be included.
+    fn check(
+        &self,
+        transaction: &TransactionSignedEcRecovered,
+        ctx: &InclusionContext,
+    ) -> InclusionDecision;
+}
+
+/// Limits the number of transactions per sender in a payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxTransactionsPerSender(pub usize);
+
+impl InclusionFilter for MaxTransactionsPerSender {
+    fn check(
+        &self,
+        _transaction: &TransactionSignedEcRecovered,
+        ctx: &InclusionContext,
+    ) -> InclusionDecision {
+        if ctx.sender_transactions >= self.0 {
+            return InclusionDecision::Skip
+        }
+        InclusionDecision::Include
+    }
+}
+
+/// Stops including transactions once the given time has passed since the building attempt
+/// started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FillDeadline(pub Duration);
+
+impl InclusionFilter for FillDeadline {
+    fn check(
+        &self,
+        _transaction: &TransactionSignedEcRecovered,
+        ctx: &InclusionContext,
+    ) -> InclusionDecision {
+        if ctx.elapsed >= self.0 {
+            return InclusionDecision::Stop
+        }
+        InclusionDecision::Include
+    }
+}
+
+/// Decides which transactions are included in a payload and in which order.
+#[derive(Debug, Clone)]
+pub struct PayloadStrategy {
+    /// Additional sources of bundles.
+    bundles: Vec<Arc<dyn BundleSource>>,
+    /// The source of the candidate transactions.
+    source: Arc<dyn TransactionSource>,
+    /// The ordering of the candidate transactions and the number of candidates it reorders at
+    /// once.
+    ordering: Option<(Arc<dyn PayloadOrdering>, usize)>,
+    /// The filters that must all include a transaction.
+    filters: Vec<Arc<dyn InclusionFilter>>,
+}
+
+impl PayloadStrategy {
+    /// Adds a source of bundles that are included after the bundles of the pool.
+    pub fn with_bundles(mut self, bundles: Arc<dyn BundleSource>) -> Self {
+        self.bundles.push(bundles);
+        self
+    }
+
+    /// Sets the source of the candidate transactions.
+    pub fn with_source(mut self, source: Arc<dyn TransactionSource>) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Reorders the candidate transactions with the given ordering.
+    ///
+    /// The ordering is applied to the next `window` candidates at once, while the transactions of
+    /// a sender are still returned in nonce order.
+    pub fn with_ordering(mut self, ordering: Arc<dyn PayloadOrdering>, window: usize) -> Self {
+        self.ordering = Some((ordering, window.max(1)));
+        self
+    }
+
+    /// Adds a filter that must include a transaction.
+    pub fn with_filter(mut self, filter: Arc<dyn InclusionFilter>) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Returns the bundles of the transaction source and all additional sources that target the
+    /// payload.
+    pub fn bundles(&self, ctx: &PayloadSourceContext) -> Vec<Arc<PoolBundle>> {
+        let mut bundles = self.source.bundles(ctx);
+        bundles.extend(self.bundles.iter().flat_map(|source| source.bundles(ctx)));
+        bundles
+    }
+
+    /// Returns the candidate transactions of the payload.
+    ///
+    /// The given state of the parent block is used by the ordering of the strategy.
+    pub fn transactions<'a>(
+        &self,
+        pool_transactions: Box<dyn PayloadTransactions + 'a>,
+        ctx: &PayloadSourceContext,
+        state: &'a dyn StateProvider,
+    ) -> Box<dyn PayloadTransactions + 'a> {
+        let transactions = self.source.transactions(pool_transactions, ctx);
+        match &self.ordering {
+            Some((ordering, window)) => {
+                let ctx = OrderingContext {
+                    block_number: ctx.block_number,
+                    base_fee: ctx.base_fee,
+                    state,
+                };
+                Box::new(OrderedTransactions::new(transactions, ordering.clone(), *window, ctx))
+            }
+            None => transactions,
+        }
+    }
+
+    /// Returns the decision of all filters for the transaction.
+    ///
+    /// [InclusionDecision::Stop] takes precedence over [InclusionDecision::Skip].
+    pub fn check(
+        &self,
+        transaction: &TransactionSignedEcRecovered,
+        ctx: &InclusionContext,
+    ) -> InclusionDecision {
+        let mut decision = InclusionDecision::Include;
+        for filter in &self.filters {
+            match filter.check(transaction, ctx) {
+                InclusionDecision::Include => {}
+                InclusionDecision::Skip => decision = InclusionDecision::Skip,
+                InclusionDecision::Stop => return InclusionDecision::Stop,
+            }
+        }
+        decision
+    }
+}
+
+impl Default for PayloadStrategy {
+    fn default() -> Self {
+        Self {
+            bundles: Vec::new(),
+            source: Arc::new(PoolTransactionSource),
+            ordering: None,
+            filters: Vec::new(),
+        }
+    }
+}
+
+/// The best transactions of the pool as [PayloadTransactions].
+pub struct BestPoolTransactions<T: PoolTransaction> {
+    best: Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<T>>>>,
+    /// All transactions that were returned, required to mark them as invalid.
+    returned: HashMap<TxHash, Arc<ValidPoolTransaction<T>>>,
+}
+
+impl<T: PoolTransaction> BestPoolTransactions<T> {
+    /// Creates a new instance from the best transactions of the pool.
+    pub fn new(best: Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<T>>>>) -> Self {
+        Self { best, returned: Default::default() }
+    }
+}
+
+impl<T: PoolTransaction> PayloadTransactions for BestPoolTransactions<T> {
+    fn next(&mut self) -> Option<TransactionSignedEcRecovered> {
+        let pool_tx = self.best.next()?;
+        let tx = pool_tx.to_recovered_transaction();
+        self.returned.insert(tx.hash, pool_tx);
+        Some(tx)
+    }
+
+    fn mark_invalid(&mut self, transaction: &TransactionSignedEcRecovered) {
+        if let Some(pool_tx) = self.returned.remove(&transaction.hash) {
+            self.best.mark_invalid(&pool_tx);
+        }
+    }
+
+    fn skip_blobs(&mut self) {
+        self.best.skip_blobs();
+    }
+}
+
+impl<T: PoolTransaction> fmt::Debug for BestPoolTransactions<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BestPoolTransactions").field("returned", &self.returned.len()).finish()
+    }
+}
+
+/// Yields all transactions of the first [PayloadTransactions] before the second.
+pub struct ChainedTransactions<'a> {
+    first: Box<dyn PayloadTransactions + 'a>,
+    second: Box<dyn PayloadTransactions + 'a>,
+}
+
+impl<'a> ChainedTransactions<'a> {
+    /// Creates a new instance that yields the transactions of `first` before `second`.
+    pub fn new(
+        first: Box<dyn PayloadTransactions + 'a>,
+        second: Box<dyn PayloadTransactions + 'a>,
+    ) -> Self {
+        Self { first, second }
+    }
+}
+
+impl PayloadTransactions for ChainedTransactions<'_> {
+    fn next(&mut self) -> Option<TransactionSignedEcRecovered> {
+        self.first.next().or_else(|| self.second.next())
+    }
+
+    fn mark_invalid(&mut self, transaction: &TransactionSignedEcRecovered) {
+        self.first.mark_invalid(transaction);
+        self.second.mark_invalid(transaction);
+    }
+
+    fn skip_blobs(&mut self) {
+        self.first.skip_blobs();
+        self.second.skip_blobs();
+    }
+}
+
+impl fmt::Debug for ChainedTransactions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChainedTransactions").finish_non_exhaustive()
+    }
+}
+
+/// Reorders the next `window` transactions of the inner [PayloadTransactions] by a
+/// [PayloadOrdering].
+///
+/// The transactions of a sender are still returned in nonce order.
+pub struct OrderedTransactions<'a> {
+    inner: Box<dyn PayloadTransactions + 'a>,
+    ordering: Arc<dyn PayloadOrdering>,
+    window: usize,
+    ctx: OrderingContext<'a>,
+    /// The buffered transactions with their priority.
+    buffer: Vec<(U256, TransactionSignedEcRecovered)>,
+    skip_blobs: bool,
+}
+
+impl<'a> OrderedTransactions<'a> {
+    /// Creates a new instance that reorders the next `window` transactions of `inner`.
+    pub fn new(
+        inner: Box<dyn PayloadTransactions + 'a>,
+        ordering: Arc<dyn PayloadOrdering>,
+        window: usize,
+        ctx: OrderingContext<'a>,
+    ) -> Self {
+        Self { inner, ordering, window, ctx, buffer: Vec::new(), skip_blobs: false }
+    }
+
+    /// Removes all buffered transactions of the sender starting at the given nonce.
+    fn remove_from_nonce(&mut self, sender: Address, nonce: u64) {
+        self.buffer.retain(|(_, tx)| tx.signer() != sender || tx.nonce() < nonce);
+    }
+}
+
+impl PayloadTransactions for OrderedTransactions<'_> {
+    fn next(&mut self) -> Option<TransactionSignedEcRecovered> {
+        while self.buffer.len() < self.window {
+            let Some(tx) = self.inner.next() else { break };
+            if self.skip_blobs && tx.is_eip4844() {
+                continue
+            }
+            let priority = self.ordering.priority(&tx, &self.ctx);
+            self.buffer.push((priority, tx));
+        }
+
+        // only the lowest buffered nonce of each sender can be returned
+        let mut lowest_nonces = HashMap::<Address, u64>::new();
+        for (_, tx) in &self.buffer {
+            let nonce = lowest_nonces.entry(tx.signer()).or_insert(tx.nonce());
+            *nonce = (*nonce).min(tx.nonce());
+        }
+        let (idx, _) = self
+            .buffer
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, tx))| lowest_nonces[&tx.signer()] == tx.nonce())
+            .max_by_key(|(_, (priority, _))| *priority)?;
+        Some(self.buffer.remove(idx).1)
+    }
+
+    fn mark_invalid(&mut self, transaction: &TransactionSignedEcRecovered) {
+        self.remove_from_nonce(transaction.signer(), transaction.nonce());
+        self.inner.mark_invalid(transaction);
+    }
+
+    fn skip_blobs(&mut self) {
+        self.skip_blobs = true;
+        let blob_txs = self
+            .buffer
+            .iter()
+            .filter(|(_, tx)| tx.is_eip4844())
+            .map(|(_, tx)| (tx.signer(), tx.nonce()))
+            .collect::<Vec<_>>();
+        for (sender, nonce) in blob_txs {
+            self.remove_from_nonce(sender, nonce);
+        }
+        self.inner.skip_blobs();
+    }
+}
+
+impl fmt::Debug for OrderedTransactions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrderedTransactions")
+            .field("ordering", &self.ordering)
+            .field("window", &self.window)
+            .field("buffered", &self.buffer.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use reth_primitives::{Signature, Transaction, TransactionSigned, TxEip1559};
+    use reth_provider::test_utils::NoopProvider;
+    use std::collections::VecDeque;
+
+    /// Returns a transaction of the sender that pays the given tip.
+    fn tx(sender: u8, nonce: u64, tip: u128) -> TransactionSignedEcRecovered {
+        let transaction = Transaction::Eip1559(TxEip1559 {
+            nonce,
+            gas_limit: 21_000,
+            max_fee_per_gas: tip,
+            max_priority_fee_per_gas: tip,
+            ..Default::default()
+        });
+        TransactionSigned::from_transaction_and_signature(transaction, Signature::default())
+            .with_signer(Address::with_last_byte(sender))
+    }
+
+    /// Yields the given transactions in order and records the transactions marked as invalid.
+    #[derive(Debug, Default)]
+    struct MockTransactions {
+        transactions: VecDeque<TransactionSignedEcRecovered>,
+        invalid: Arc<Mutex<Vec<TxHash>>>,
+    }
+
+    impl MockTransactions {
+        fn new(transactions: Vec<TransactionSignedEcRecovered>) -> Self {
+            Self { transactions: transactions.into(), invalid: Default::default() }
+        }
+    }
+
+    impl PayloadTransactions for MockTransactions {
+        fn next(&mut self) -> Option<TransactionSignedEcRecovered> {
+            self.transactions.pop_front()
+        }
+
+        fn mark_invalid(&mut self, transaction: &TransactionSignedEcRecovered) {
+            self.invalid.lock().push(transaction.hash);
+            self.transactions.retain(|tx| tx.signer() != transaction.signer());
+        }
+
+        fn skip_blobs(&mut self) {}
+    }
+
+    fn ordered<'a>(
+        transactions: MockTransactions,
+        window: usize,
+        state: &'a NoopProvider,
+    ) -> OrderedTransactions<'a> {
+        let ctx = OrderingContext { block_number: 1, base_fee: 0, state };
+        OrderedTransactions::new(
+            Box::new(transactions),
+            Arc::new(EffectiveTipOrdering),
+            window,
+            ctx,
+        )
+    }
+
+    fn collect(mut transactions: impl PayloadTransactions) -> Vec<TxHash> {
+        std::iter::from_fn(|| transactions.next()).map(|tx| tx.hash).collect()
+    }
+
+    #[test]
+    fn orders_within_window() {
+        let state = NoopProvider::default();
+        let txs = vec![tx(1, 0, 1), tx(2, 0, 5), tx(3, 0, 10)];
+
+        // the whole window is reordered
+        let ordered_txs = ordered(MockTransactions::new(txs.clone()), 3, &state);
+        assert_eq!(collect(ordered_txs), vec![txs[2].hash, txs[1].hash, txs[0].hash]);
+
+        // only the buffered transactions are reordered
+        let ordered_txs = ordered(MockTransactions::new(txs.clone()), 2, &state);
+        assert_eq!(collect(ordered_txs), vec![txs[1].hash, txs[2].hash, txs[0].hash]);
+
+        // a window of one keeps the order of the source
+        let ordered_txs = ordered(MockTransactions::new(txs.clone()), 1, &state);
+        assert_eq!(collect(ordered_txs), vec![txs[0].hash, txs[1].hash, txs[2].hash]);
+    }
+
+    #[test]
+    fn keeps_nonce_order_of_sender() {
+        let state = NoopProvider::default();
+        let txs = vec![tx(1, 0, 1), tx(1, 1, 10), tx(2, 0, 5)];
+
+        let ordered_txs = ordered(MockTransactions::new(txs.clone()), 3, &state);
+        assert_eq!(collect(ordered_txs), vec![txs[2].hash, txs[0].hash, txs[1].hash]);
+    }
+
+    #[test]
+    fn mark_invalid_removes_dependent_transactions() {
+        let state = NoopProvider::default();
+        let txs = vec![tx(1, 0, 10), tx(1, 1, 10), tx(2, 0, 5), tx(1, 2, 10)];
+        let transactions = MockTransactions::new(txs.clone());
+        let invalid = transactions.invalid.clone();
+
+        let mut ordered_txs = ordered(transactions, 3, &state);
+        let first = ordered_txs.next().unwrap();
+        assert_eq!(first.hash, txs[0].hash);
+        ordered_txs.mark_invalid(&first);
+
+        // the buffered and the not yet buffered transactions of the sender are skipped
+        assert_eq!(collect(ordered_txs), vec![txs[2].hash]);
+        assert_eq!(*invalid.lock(), vec![txs[0].hash]);
+    }
+
+    #[test]
+    fn chained_transactions_propagate_mark_invalid() {
+        let first_txs = vec![tx(1, 0, 1), tx(1, 1, 1)];
+        let second_txs = vec![tx(2, 0, 2), tx(1, 2, 1)];
+        let first = MockTransactions::new(first_txs.clone());
+        let second = MockTransactions::new(second_txs.clone());
+        let (first_invalid, second_invalid) = (first.invalid.clone(), second.invalid.clone());
+
+        let mut chained = ChainedTransactions::new(Box::new(first), Box::new(second));
+        let tx = chained.next().unwrap();
+        chained.mark_invalid(&tx);
+
+        // the dependent transactions of both sources are skipped
+        assert_eq!(collect(chained), vec![second_txs[0].hash]);
+        assert_eq!(*first_invalid.lock(), vec![first_txs[0].hash]);
+        assert_eq!(*second_invalid.lock(), vec![first_txs[0].hash]);
+    }
+
+    #[test]
+    fn filter_decisions() {
+        let strategy = PayloadStrategy::default()
+            .with_filter(Arc::new(MaxTransactionsPerSender(1)))
+            .with_filter(Arc::new(FillDeadline(Duration::from_secs(1))));
+        let transaction = tx(1, 0, 1);
+        let ctx = InclusionContext {
+            cumulative_gas_used: 0,
+            block_gas_limit: 30_000_000,
+            sender_transactions: 0,
+            elapsed: Duration::ZERO,
+        };
+
+        assert_eq!(strategy.check(&transaction, &ctx), InclusionDecision::Include);
+        assert_eq!(
+            strategy.check(&transaction, &InclusionContext { sender_transactions: 1, ..ctx }),
+            InclusionDecision::Skip
+        );
+        assert_eq!(
+            strategy
+                .check(&transaction, &InclusionContext { elapsed: Duration::from_secs(1), ..ctx }),
+            InclusionDecision::Stop
+        );
+        // stop takes precedence over skip
+        assert_eq!(
+            strategy.check(
+                &transaction,
+                &InclusionContext {
+                    sender_transactions: 1,
+                    elapsed: Duration::from_secs(2),
+                    ..ctx
+                }
+            ),
+            InclusionDecision::Stop
+        );
+    }
+
+    #[test]
+    fn bundle_first_source_includes_bundles() {
+        #[derive(Debug)]
+        struct StaticBundles(Arc<PoolBundle>);
+
+        impl BundleSource for StaticBundles {
+            fn bundles(&self, _ctx: &PayloadSourceContext) -> Vec<Arc<PoolBundle>> {
+                vec![self.0.clone()]
+            }
+        }
+
+        let bundle = Arc::new(PoolBundle::new(vec![tx(1, 0, 1), tx(1, 1, 1)], 1));
+        let strategy = PayloadStrategy::default()
+            .with_source(Arc::new(BundleFirstSource::new(Arc::new(StaticBundles(bundle.clone())))));
+        let ctx = PayloadSourceContext { block_number: 1, timestamp: 0, base_fee: 0 };
+
+        // the bundle is included atomically instead of being yielded transaction by transaction
+        assert_eq!(strategy.bundles(&ctx), vec![bundle]);
+        let state = NoopProvider::default();
+        let pool_tx = tx(2, 0, 2);
+        let mut transactions = strategy.transactions(
+            Box::new(MockTransactions::new(vec![pool_tx.clone()])),
+            &ctx,
+            &state,
+        );
+        assert_eq!(transactions.next().map(|tx| tx.hash), Some(pool_tx.hash));
+        assert!(transactions.next().is_none());
+    }
+}
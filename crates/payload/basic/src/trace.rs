@@ -0,0 +1,216 @@
+//! Records what happened while building payloads.
+
+use parking_lot::Mutex;
+use reth_payload_builder::PayloadId;
+use reth_primitives::{TxHash, U256};
+use reth_rpc_types::{
+    PayloadBuildIteration, PayloadBuildOutcome, PayloadBuildTrace, PayloadSkipReason,
+    PayloadTransactionStatus, PayloadTransactionTrace,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::Instant,
+};
+
+/// The default number of payload jobs for which the build trace is kept.
+pub const DEFAULT_MAX_TRACED_PAYLOADS: usize = 64;
+
+/// Records the transactions that were considered during a single payload build attempt.
+///
+/// This is cheap to clone, all clones record into the same trace.
+#[derive(Debug, Clone, Default)]
+pub struct PayloadBuildTracer {
+    transactions: Arc<Mutex<Vec<PayloadTransactionTrace>>>,
+}
+
+// === impl PayloadBuildTracer ===
+
+impl PayloadBuildTracer {
+    /// Records that the transaction was included in the payload.
+    pub fn included(&self, hash: TxHash, gas_used: u64, fees: U256) {
+        self.record(hash, PayloadTransactionStatus::Included { gas_used, fees })
+    }
+
+    /// Records that the transaction was skipped for the given reason.
+    pub fn skipped(&self, hash: TxHash, reason: PayloadSkipReason) {
+        self.record(hash, PayloadTransactionStatus::Skipped { reason, error: None })
+    }
+
+    /// Records that the transaction was skipped for the given reason, with additional details.
+    pub fn skipped_with_error(&self, hash: TxHash, reason: PayloadSkipReason, error: String) {
+        self.record(hash, PayloadTransactionStatus::Skipped { reason, error: Some(error) })
+    }
+
+    fn record(&self, hash: TxHash, status: PayloadTransactionStatus) {
+        self.transactions.lock().push(PayloadTransactionTrace { hash, status })
+    }
+
+    /// Takes all recorded transactions.
+    pub(crate) fn take(&self) -> Vec<PayloadTransactionTrace> {
+        std::mem::take(&mut *self.transactions.lock())
+    }
+
+    /// Takes all recorded transactions and summarizes them as a finished build attempt.
+    ///
+    /// If the fees of the built payload are unknown, these are the fees of all included
+    /// transactions.
+    pub(crate) fn finish(
+        &self,
+        started: Instant,
+        outcome: PayloadBuildOutcome,
+        fees: Option<U256>,
+    ) -> PayloadBuildIteration {
+        let transactions = self.take();
+        let mut gas_used = 0;
+        let mut included_fees = U256::ZERO;
+        for tx in &transactions {
+            if let PayloadTransactionStatus::Included { gas_used: tx_gas_used, fees: tx_fees } =
+                tx.status
+            {
+                gas_used += tx_gas_used;
+                included_fees += tx_fees;
+            }
+        }
+        PayloadBuildIteration {
+            outcome,
+            duration_ms: started.elapsed().as_millis() as u64,
+            gas_used,
+            fees: fees.unwrap_or(included_fees),
+            transactions,
+        }
+    }
+}
+
+/// Keeps the build traces of the most recent payload jobs.
+///
+/// This is cheap to clone, all clones share the same traces.
+#[derive(Debug, Clone)]
+pub struct PayloadBuildTraces {
+    inner: Arc<Mutex<PayloadBuildTracesInner>>,
+}
+
+// === impl PayloadBuildTraces ===
+
+impl PayloadBuildTraces {
+    /// Creates a new instance that keeps the traces of at most `max_payloads` payload jobs.
+    pub fn new(max_payloads: usize) -> Self {
+        let inner = PayloadBuildTracesInner {
+            max_payloads,
+            order: VecDeque::new(),
+            traces: HashMap::new(),
+        };
+        Self { inner: Arc::new(Mutex::new(inner)) }
+    }
+
+    /// Returns the build trace of the given payload job, if any.
+    pub fn get(&self, id: PayloadId) -> Option<PayloadBuildTrace> {
+        self.inner.lock().traces.get(&id).cloned()
+    }
+
+    /// Appends a build attempt to the trace of the given payload job.
+    ///
+    /// If this is a new payload job, the trace of the oldest payload job is evicted once the limit
+    /// is reached.
+    pub(crate) fn record(&self, id: PayloadId, iteration: PayloadBuildIteration) {
+        let mut inner = self.inner.lock();
+        if !inner.traces.contains_key(&id) {
+            while inner.order.len() >= inner.max_payloads {
+                let Some(oldest) = inner.order.pop_front() else { break };
+                inner.traces.remove(&oldest);
+            }
+            if inner.max_payloads == 0 {
+                return
+            }
+            inner.order.push_back(id);
+        }
+        inner.traces.entry(id).or_default().iterations.push(iteration);
+    }
+}
+
+impl Default for PayloadBuildTraces {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_TRACED_PAYLOADS)
+    }
+}
+
+#[derive(Debug)]
+struct PayloadBuildTracesInner {
+    /// The maximum number of payload jobs to keep traces for.
+    max_payloads: usize,
+    /// The ids of the traced payload jobs, oldest first.
+    order: VecDeque<PayloadId>,
+    /// The traces of the payload jobs.
+    traces: HashMap<PayloadId, PayloadBuildTrace>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iteration(outcome: PayloadBuildOutcome) -> PayloadBuildIteration {
+        PayloadBuildTracer::default().finish(Instant::now(), outcome, None)
+    }
+
+    #[test]
+    fn evicts_oldest_payload() {
+        let traces = PayloadBuildTraces::new(2);
+        let (first, second, third) =
+            (PayloadId::new([1; 8]), PayloadId::new([2; 8]), PayloadId::new([3; 8]));
+
+        traces.record(first, iteration(PayloadBuildOutcome::Better));
+        traces.record(second, iteration(PayloadBuildOutcome::Better));
+        // more iterations of a known payload don't evict anything
+        traces.record(first, iteration(PayloadBuildOutcome::Aborted));
+        assert_eq!(traces.get(first).unwrap().iterations.len(), 2);
+        assert!(traces.get(second).is_some());
+
+        traces.record(third, iteration(PayloadBuildOutcome::Failed));
+        assert!(traces.get(first).is_none());
+        assert_eq!(traces.get(second).unwrap().iterations.len(), 1);
+        assert_eq!(traces.get(third).unwrap().iterations[0].outcome, PayloadBuildOutcome::Failed);
+    }
+
+    #[test]
+    fn disabled_traces() {
+        let traces = PayloadBuildTraces::new(0);
+        let id = PayloadId::new([1; 8]);
+        traces.record(id, iteration(PayloadBuildOutcome::Better));
+        assert!(traces.get(id).is_none());
+    }
+
+    #[test]
+    fn shared_tracer() {
+        let tracer = PayloadBuildTracer::default();
+        let build_tracer = tracer.clone();
+        let (included, skipped, invalid) =
+            (TxHash::with_last_byte(1), TxHash::with_last_byte(2), TxHash::with_last_byte(3));
+
+        build_tracer.included(included, 21_000, U256::from(42_000));
+        build_tracer.skipped(skipped, PayloadSkipReason::NonceGap);
+        build_tracer.skipped_with_error(invalid, PayloadSkipReason::Invalid, "invalid".into());
+        build_tracer.included(included, 21_000, U256::from(42_000));
+
+        let iteration = tracer.finish(Instant::now(), PayloadBuildOutcome::Aborted, None);
+        assert_eq!(iteration.gas_used, 42_000);
+        assert_eq!(iteration.fees, U256::from(84_000));
+        assert_eq!(
+            iteration.transactions.iter().map(|tx| tx.hash).collect::<Vec<_>>(),
+            vec![included, skipped, invalid, included]
+        );
+        assert_eq!(
+            iteration.transactions[2].status,
+            PayloadTransactionStatus::Skipped {
+                reason: PayloadSkipReason::Invalid,
+                error: Some("invalid".to_string())
+            }
+        );
+
+        // the fees of the built payload take precedence and the recorded transactions are taken
+        let iteration =
+            tracer.finish(Instant::now(), PayloadBuildOutcome::Better, Some(U256::from(1)));
+        assert_eq!(iteration.fees, U256::from(1));
+        assert!(iteration.transactions.is_empty());
+        assert!(build_tracer.take().is_empty());
+    }
+}
@@ -43,6 +43,7 @@ impl Future for NoopPayloadBuilderService {
                 PayloadServiceCommand::BestPayload(_, tx) => tx.send(None).ok(),
                 PayloadServiceCommand::PayloadAttributes(_, tx) => tx.send(None).ok(),
                 PayloadServiceCommand::Resolve(_, tx) => tx.send(None).ok(),
+                PayloadServiceCommand::BuildTrace(_, tx) => tx.send(None).ok(),
             };
         }
     }
@@ -8,7 +8,7 @@ use crate::{
     BuiltPayload, KeepPayloadJobAlive, PayloadBuilderAttributes, PayloadJob,
 };
 use futures_util::{future::FutureExt, StreamExt};
-use reth_rpc_types::engine::PayloadId;
+use reth_rpc_types::{engine::PayloadId, PayloadBuildTrace};
 use std::{
     fmt,
     future::Future,
@@ -59,6 +59,11 @@ impl PayloadStore {
     ) -> Option<Result<PayloadBuilderAttributes, PayloadBuilderError>> {
         self.inner.payload_attributes(id).await
     }
+
+    /// Returns the build trace of the payload job with the given identifier, if any.
+    pub async fn build_trace(&self, id: PayloadId) -> Option<PayloadBuildTrace> {
+        self.inner.build_trace(id).await
+    }
 }
 
 impl From<PayloadBuilderHandle> for PayloadStore {
@@ -124,6 +129,15 @@ impl PayloadBuilderHandle {
         rx.await.ok()?
     }
 
+    /// Returns the build trace of the payload job with the given identifier, if any.
+    ///
+    /// See [PayloadJobGenerator::build_trace].
+    pub async fn build_trace(&self, id: PayloadId) -> Option<PayloadBuildTrace> {
+        let (tx, rx) = oneshot::channel();
+        self.to_service.send(PayloadServiceCommand::BuildTrace(id, tx)).ok()?;
+        rx.await.ok()?
+    }
+
     /// Sends a message to the service to start building a new payload for the given payload.
     ///
     /// This is the same as [PayloadBuilderHandle::new_payload] but does not wait for the result and
@@ -348,6 +362,9 @@ where
                     PayloadServiceCommand::Resolve(id, tx) => {
                         let _ = tx.send(this.resolve(id));
                     }
+                    PayloadServiceCommand::BuildTrace(id, tx) => {
+                        let _ = tx.send(this.generator.build_trace(id));
+                    }
                 }
             }
 
@@ -377,6 +394,8 @@ pub enum PayloadServiceCommand {
     ),
     /// Resolve the payload and return the payload
     Resolve(PayloadId, oneshot::Sender<Option<PayloadFuture>>),
+    /// Get the build trace of the payload job
+    BuildTrace(PayloadId, oneshot::Sender<Option<PayloadBuildTrace>>),
 }
 
 impl fmt::Debug for PayloadServiceCommand {
@@ -392,6 +411,9 @@ impl fmt::Debug for PayloadServiceCommand {
                 f.debug_tuple("PayloadAttributes").field(&f0).field(&f1).finish()
             }
             PayloadServiceCommand::Resolve(f0, _f1) => f.debug_tuple("Resolve").field(&f0).finish(),
+            PayloadServiceCommand::BuildTrace(f0, _f1) => {
+                f.debug_tuple("BuildTrace").field(&f0).finish()
+            }
         }
     }
 }
@@ -1,6 +1,7 @@
 //! Trait abstractions used by the payload crate.
 
 use crate::{error::PayloadBuilderError, BuiltPayload, PayloadBuilderAttributes};
+use reth_rpc_types::{engine::PayloadId, PayloadBuildTrace};
 use std::{future::Future, sync::Arc};
 
 /// A type that can build a payload.
@@ -80,4 +81,12 @@ pub trait PayloadJobGenerator: Send + Sync {
         &self,
         attr: PayloadBuilderAttributes,
     ) -> Result<Self::Job, PayloadBuilderError>;
+
+    /// Returns the build trace of the payload job with the given identifier, if the generator
+    /// records build traces.
+    ///
+    /// By default, no traces are recorded.
+    fn build_trace(&self, _id: PayloadId) -> Option<PayloadBuildTrace> {
+        None
+    }
 }
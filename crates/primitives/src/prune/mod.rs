@@ -8,7 +8,7 @@ pub use checkpoint::PruneCheckpoint;
 pub use mode::PruneMode;
 pub use segment::{PruneSegment, PruneSegmentError};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 pub use target::{PruneModes, MINIMUM_PRUNING_DISTANCE};
 
 /// Configuration for pruning receipts not associated with logs emitted by the specified contracts.
@@ -87,6 +87,27 @@ impl ReceiptsLogPruneConfig {
     }
 }
 
+/// Configuration for retaining the full account and storage history of the specified addresses,
+/// while pruning the history of all other addresses according to the `account_history` and
+/// `storage_history` prune modes.
+///
+/// The history of an address is only complete if it was retained since the first sync, adding an
+/// address doesn't restore its already pruned history.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct HistoryRetentionConfig(pub BTreeSet<Address>);
+
+impl HistoryRetentionConfig {
+    /// Checks if the configuration is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the history of the address is retained.
+    pub fn contains(&self, address: &Address) -> bool {
+        self.0.contains(address)
+    }
+}
+
 /// Progress of pruning.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum PruneProgress {
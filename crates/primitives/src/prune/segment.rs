@@ -23,18 +23,29 @@ pub enum PruneSegment {
     Headers,
     /// Prune segment responsible for the `Transactions` table.
     Transactions,
+    /// Prune segment responsible for the `AccountChangeSet` table, after it was snapshotted.
+    AccountChangeSets,
+    /// Prune segment responsible for the `StorageChangeSet` table, after it was snapshotted.
+    StorageChangeSets,
+    /// Prune segment responsible for the `Bytecodes` table.
+    Bytecodes,
 }
 
 impl PruneSegment {
     /// Returns minimum number of blocks to left in the database for this segment.
     pub fn min_blocks(&self) -> u64 {
         match self {
-            Self::SenderRecovery | Self::TransactionLookup | Self::Headers | Self::Transactions => {
-                0
-            }
-            Self::Receipts | Self::ContractLogs | Self::AccountHistory | Self::StorageHistory => {
-                MINIMUM_PRUNING_DISTANCE
-            }
+            Self::SenderRecovery |
+            Self::TransactionLookup |
+            Self::Headers |
+            Self::Transactions |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets => 0,
+            Self::Receipts |
+            Self::ContractLogs |
+            Self::AccountHistory |
+            Self::StorageHistory |
+            Self::Bytecodes => MINIMUM_PRUNING_DISTANCE,
         }
     }
 }
@@ -1,5 +1,6 @@
 use crate::{
-    serde_helper::deserialize_opt_prune_mode_with_min_blocks, PruneMode, ReceiptsLogPruneConfig,
+    serde_helper::deserialize_opt_prune_mode_with_min_blocks, HistoryRetentionConfig, PruneMode,
+    ReceiptsLogPruneConfig,
 };
 use serde::{Deserialize, Serialize};
 
@@ -39,12 +40,26 @@ pub struct PruneModes {
         deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
     )]
     pub storage_history: Option<PruneMode>,
+    /// Bytecodes pruning configuration. Deletes contract code which is neither used by an account
+    /// of the latest state, nor by the account history retained after the target block.
+    /// Never prunes further than `account_history`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
+    )]
+    pub bytecodes: Option<PruneMode>,
     /// Receipts pruning configuration by retaining only those receipts that contain logs emitted
     /// by the specified addresses, discarding others. This setting is overridden by `receipts`.
     ///
     /// The [BlockNumber](`crate::BlockNumber`) represents the starting block from which point
     /// onwards the receipts are preserved.
     pub receipts_log_filter: ReceiptsLogPruneConfig,
+    /// Addresses whose account and storage history is retained in full, regardless of the
+    /// `account_history` and `storage_history` settings.
+    ///
+    /// If not empty, the changesets and history indices are always written, and the history of
+    /// all other addresses is pruned afterwards.
+    pub history_retention: HistoryRetentionConfig,
 }
 
 impl PruneModes {
@@ -61,7 +76,9 @@ impl PruneModes {
             receipts: Some(PruneMode::Full),
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
+            bytecodes: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
+            history_retention: Default::default(),
         }
     }
 }
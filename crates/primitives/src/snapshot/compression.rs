@@ -1,6 +1,7 @@
+use serde::{Deserialize, Serialize};
 use strum::AsRefStr;
 
-#[derive(Debug, Copy, Clone, Default, AsRefStr)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, AsRefStr, Serialize, Deserialize)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 #[allow(missing_docs)]
 /// Snapshot compression
@@ -11,7 +12,93 @@ pub enum Compression {
     Zstd,
     #[strum(serialize = "zstd-dict")]
     ZstdWithDictionary,
+    #[strum(serialize = "zstd-long")]
+    ZstdLongWindow,
     #[strum(serialize = "uncompressed")]
     #[default]
     Uncompressed,
 }
+
+/// Compression level and long distance matching window of [`Compression::ZstdLongWindow`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ZstdLongWindowConfig {
+    /// Compression level, between 1 and [`ZstdLongWindowConfig::MAX_LEVEL`].
+    level: i32,
+    /// Window log of long distance matching, between [`ZstdLongWindowConfig::MIN_WINDOW_LOG`]
+    /// and [`ZstdLongWindowConfig::MAX_WINDOW_LOG`].
+    window_log: u32,
+}
+
+impl ZstdLongWindowConfig {
+    /// Highest compression level of zstd.
+    pub const MAX_LEVEL: i32 = 22;
+    /// Smallest window log of zstd.
+    pub const MIN_WINDOW_LOG: u32 = 10;
+    /// Largest window log that zstd decoders accept without raising their memory limit.
+    pub const MAX_WINDOW_LOG: u32 = 27;
+    /// Level 19 with the largest window.
+    pub const DEFAULT: Self = Self { level: 19, window_log: Self::MAX_WINDOW_LOG };
+
+    /// Creates a new configuration, fails if the level or the window log exceed the limits of
+    /// zstd.
+    pub fn new(level: i32, window_log: u32) -> Result<Self, ZstdConfigError> {
+        if !(1..=Self::MAX_LEVEL).contains(&level) {
+            return Err(ZstdConfigError::InvalidLevel(level))
+        }
+        if !(Self::MIN_WINDOW_LOG..=Self::MAX_WINDOW_LOG).contains(&window_log) {
+            return Err(ZstdConfigError::InvalidWindowLog(window_log))
+        }
+        Ok(Self { level, window_log })
+    }
+
+    /// Returns the compression level.
+    pub const fn level(&self) -> i32 {
+        self.level
+    }
+
+    /// Returns the window log of long distance matching.
+    pub const fn window_log(&self) -> u32 {
+        self.window_log
+    }
+}
+
+impl Default for ZstdLongWindowConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Error of an invalid [`ZstdLongWindowConfig`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ZstdConfigError {
+    /// The compression level is out of bounds.
+    #[error(
+        "zstd compression level {0} is not between 1 and {max}",
+        max = ZstdLongWindowConfig::MAX_LEVEL
+    )]
+    InvalidLevel(i32),
+    /// The window log is out of bounds.
+    #[error(
+        "zstd window log {0} is not between {min} and {max}",
+        min = ZstdLongWindowConfig::MIN_WINDOW_LOG,
+        max = ZstdLongWindowConfig::MAX_WINDOW_LOG
+    )]
+    InvalidWindowLog(u32),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zstd_long_window_config() {
+        let config = ZstdLongWindowConfig::new(22, 10).unwrap();
+        assert_eq!((config.level(), config.window_log()), (22, 10));
+        assert_eq!(ZstdLongWindowConfig::new(19, 27), Ok(ZstdLongWindowConfig::default()));
+
+        assert_eq!(ZstdLongWindowConfig::new(0, 27), Err(ZstdConfigError::InvalidLevel(0)));
+        assert_eq!(ZstdLongWindowConfig::new(23, 27), Err(ZstdConfigError::InvalidLevel(23)));
+        assert_eq!(ZstdLongWindowConfig::new(19, 9), Err(ZstdConfigError::InvalidWindowLog(9)));
+        assert_eq!(ZstdLongWindowConfig::new(19, 31), Err(ZstdConfigError::InvalidWindowLog(31)));
+    }
+}
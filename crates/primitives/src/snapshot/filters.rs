@@ -23,6 +23,9 @@ pub enum InclusionFilter {
     #[strum(serialize = "cuckoo")]
     /// Cuckoo filter
     Cuckoo,
+    #[strum(serialize = "bloom")]
+    /// Bloom filter
+    Bloom,
 }
 
 #[derive(Debug, Copy, Clone, AsRefStr)]
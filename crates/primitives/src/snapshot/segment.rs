@@ -1,5 +1,5 @@
 use crate::{
-    snapshot::{Compression, Filters, InclusionFilter},
+    snapshot::{Compression, Filters, InclusionFilter, ZstdLongWindowConfig},
     BlockNumber, TxNumber,
 };
 use derive_more::Display;
@@ -35,6 +35,12 @@ pub enum SnapshotSegment {
     #[strum(serialize = "receipts")]
     /// Snapshot segment responsible for the `Receipts` table.
     Receipts,
+    #[strum(serialize = "accountchangesets")]
+    /// Snapshot segment responsible for the `AccountChangeSet` table.
+    AccountChangeSets,
+    #[strum(serialize = "storagechangesets")]
+    /// Snapshot segment responsible for the `StorageChangeSet` table.
+    StorageChangeSets,
 }
 
 impl SnapshotSegment {
@@ -46,15 +52,31 @@ impl SnapshotSegment {
                 super::PerfectHashingFunction::Fmph,
             ),
             compression: Compression::Lz4,
+            zstd_long_window: ZstdLongWindowConfig::DEFAULT,
         };
 
         match self {
             SnapshotSegment::Headers => default_config,
             SnapshotSegment::Transactions => default_config,
             SnapshotSegment::Receipts => default_config,
+            // Changesets are only queried by block number, so filters are not needed.
+            SnapshotSegment::AccountChangeSets | SnapshotSegment::StorageChangeSets => {
+                SegmentConfig { filters: Filters::WithoutFilters, ..default_config }
+            }
         }
     }
 
+    /// Returns `true` if the rows of the segment are indexed by block number, and `false` if they
+    /// are indexed by transaction number.
+    pub const fn is_block_based(&self) -> bool {
+        matches!(
+            self,
+            SnapshotSegment::Headers |
+                SnapshotSegment::AccountChangeSets |
+                SnapshotSegment::StorageChangeSets
+        )
+    }
+
     /// Returns the default file name for the provided segment and range.
     pub fn filename(
         &self,
@@ -154,6 +176,11 @@ impl SegmentHeader {
         Self { block_range, tx_range, segment }
     }
 
+    /// Returns the snapshot segment.
+    pub fn segment(&self) -> SnapshotSegment {
+        self.segment
+    }
+
     /// Returns the transaction range.
     pub fn tx_range(&self) -> &RangeInclusive<TxNumber> {
         &self.tx_range
@@ -181,9 +208,10 @@ impl SegmentHeader {
 
     /// Returns the row offset which depends on whether the segment is block or transaction based.
     pub fn start(&self) -> u64 {
-        match self.segment {
-            SnapshotSegment::Headers => self.block_start(),
-            SnapshotSegment::Transactions | SnapshotSegment::Receipts => self.tx_start(),
+        if self.segment.is_block_based() {
+            self.block_start()
+        } else {
+            self.tx_start()
         }
     }
 }
@@ -195,6 +223,8 @@ pub struct SegmentConfig {
     pub filters: Filters,
     /// Compression used on the segment
     pub compression: Compression,
+    /// Level and window of [`Compression::ZstdLongWindow`], ignored by the other compressions.
+    pub zstd_long_window: ZstdLongWindowConfig,
 }
 
 #[cfg(test)]
@@ -219,6 +249,20 @@ mod tests {
                 "snapshot_transactions_1123233_11223233_1123233_2123233",
                 None,
             ),
+            (
+                SnapshotSegment::AccountChangeSets,
+                0..=499_999,
+                0..=1_000,
+                "snapshot_accountchangesets_0_499999_0_1000",
+                None,
+            ),
+            (
+                SnapshotSegment::StorageChangeSets,
+                500_000..=999_999,
+                1_001..=2_000,
+                "snapshot_storagechangesets_500000_999999_1001_2000",
+                None,
+            ),
             (
                 SnapshotSegment::Headers,
                 2..=30,
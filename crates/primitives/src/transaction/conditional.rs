@@ -0,0 +1,103 @@
+use crate::{serde_helper::u64_hex_opt, Address, BlockNumber, B256};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Conditions under which a transaction may be included in a block, as submitted with
+/// `eth_sendRawTransactionConditional`.
+///
+/// All conditions are checked against the latest block and its state.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionConditional {
+    /// Expected storage of accounts.
+    #[serde(default)]
+    pub known_accounts: HashMap<Address, KnownAccountState>,
+    /// Minimum block number.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "u64_hex_opt")]
+    pub block_number_min: Option<BlockNumber>,
+    /// Maximum block number.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "u64_hex_opt")]
+    pub block_number_max: Option<BlockNumber>,
+    /// Minimum block timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "u64_hex_opt")]
+    pub timestamp_min: Option<u64>,
+    /// Maximum block timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "u64_hex_opt")]
+    pub timestamp_max: Option<u64>,
+}
+
+impl TransactionConditional {
+    /// Returns `true` if the block number and timestamp are within the configured ranges.
+    pub fn matches_block_attributes(&self, number: BlockNumber, timestamp: u64) -> bool {
+        self.block_number_min.map_or(true, |min| number >= min) &&
+            self.timestamp_min.map_or(true, |min| timestamp >= min) &&
+            !self.has_exceeded_block_attributes(number, timestamp)
+    }
+
+    /// Returns `true` if the block number or timestamp exceeds its maximum.
+    ///
+    /// Unlike the minimums, the maximums can't be satisfied by any later block.
+    pub fn has_exceeded_block_attributes(&self, number: BlockNumber, timestamp: u64) -> bool {
+        self.block_number_max.is_some_and(|max| number > max) ||
+            self.timestamp_max.is_some_and(|max| timestamp > max)
+    }
+}
+
+/// Expected storage of an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KnownAccountState {
+    /// Expected storage root of the account.
+    StorageRoot(B256),
+    /// Expected values of storage slots of the account.
+    Slots(HashMap<B256, B256>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, b256};
+
+    #[test]
+    fn serde_transaction_conditional() {
+        let s = r#"{
+            "knownAccounts": {
+                "0x000000000000000000000000000000000000dead": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "0x000000000000000000000000000000000000beef": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000002": "0x0000000000000000000000000000000000000000000000000000000000000003"
+                }
+            },
+            "blockNumberMin": "0xa",
+            "timestampMax": "0x64"
+        }"#;
+        let conditional: TransactionConditional = serde_json::from_str(s).unwrap();
+
+        assert_eq!(
+            conditional.known_accounts[&address!("000000000000000000000000000000000000dead")],
+            KnownAccountState::StorageRoot(b256!(
+                "0000000000000000000000000000000000000000000000000000000000000001"
+            ))
+        );
+        assert_eq!(
+            conditional.known_accounts[&address!("000000000000000000000000000000000000beef")],
+            KnownAccountState::Slots(HashMap::from([(
+                b256!("0000000000000000000000000000000000000000000000000000000000000002"),
+                b256!("0000000000000000000000000000000000000000000000000000000000000003")
+            )]))
+        );
+        assert_eq!(conditional.block_number_min, Some(10));
+        assert_eq!(conditional.block_number_max, None);
+        assert_eq!(conditional.timestamp_max, Some(100));
+
+        assert!(!conditional.matches_block_attributes(9, 0));
+        assert!(conditional.matches_block_attributes(10, 100));
+        assert!(!conditional.has_exceeded_block_attributes(10, 100));
+        assert!(conditional.has_exceeded_block_attributes(10, 101));
+
+        let serialized = serde_json::to_value(&conditional).unwrap();
+        assert_eq!(
+            serde_json::from_value::<TransactionConditional>(serialized).unwrap(),
+            conditional
+        );
+    }
+}
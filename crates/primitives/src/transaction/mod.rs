@@ -14,6 +14,7 @@ use serde::{Deserialize, Serialize};
 use std::mem;
 
 pub use access_list::{AccessList, AccessListItem};
+pub use conditional::{KnownAccountState, TransactionConditional};
 pub use eip1559::TxEip1559;
 pub use eip2930::TxEip2930;
 pub use eip4844::TxEip4844;
@@ -33,6 +34,7 @@ pub use tx_value::TxValue;
 pub use variant::TransactionSignedVariant;
 
 mod access_list;
+mod conditional;
 mod eip1559;
 mod eip2930;
 mod eip4844;
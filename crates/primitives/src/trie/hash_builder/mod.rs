@@ -56,6 +56,7 @@ pub struct HashBuilder {
 
     updated_branch_nodes: Option<HashMap<Nibbles, BranchNodeCompact>>,
     proof_retainer: Option<ProofRetainer>,
+    retained_nodes: Option<HashMap<B256, Bytes>>,
 
     rlp_buf: Vec<u8>,
 }
@@ -72,6 +73,7 @@ impl From<HashBuilderState> for HashBuilder {
             stored_in_database: state.stored_in_database,
             updated_branch_nodes: None,
             proof_retainer: None,
+            retained_nodes: None,
             rlp_buf: Vec::with_capacity(32),
         }
     }
@@ -115,6 +117,32 @@ impl HashBuilder {
         }
     }
 
+    /// Enables the Hash Builder to retain the RLP encoding of all trie nodes that are referenced by
+    /// hash.
+    ///
+    /// Call [HashBuilder::take_retained_nodes] to get the retained nodes.
+    pub fn with_retained_nodes(mut self, retain_nodes: bool) -> Self {
+        self.set_retain_nodes(retain_nodes);
+        self
+    }
+
+    /// Enables the Hash Builder to retain the RLP encoding of all trie nodes that are referenced by
+    /// hash.
+    ///
+    /// Call [HashBuilder::take_retained_nodes] to get the retained nodes.
+    pub fn set_retain_nodes(&mut self, retain_nodes: bool) {
+        if retain_nodes && self.retained_nodes.is_none() {
+            self.retained_nodes = Some(HashMap::default());
+        }
+    }
+
+    /// Take and return the retained trie nodes keyed by their hash.
+    ///
+    /// Returns an empty map if [Self::with_retained_nodes] was not called.
+    pub fn take_retained_nodes(&mut self) -> HashMap<B256, Bytes> {
+        self.retained_nodes.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
     /// Splits the [HashBuilder] into a [HashBuilder] and hash builder updates.
     pub fn split(mut self) -> (Self, HashMap<Nibbles, BranchNodeCompact>) {
         let updates = self.updated_branch_nodes.take();
@@ -170,6 +198,16 @@ impl HashBuilder {
             self.key.clear();
             self.value = HashBuilderValue::Bytes(vec![]);
         }
+
+        // The root node is always referenced by hash, even if its encoding is shorter than 32
+        // bytes.
+        if let Some(nodes) = self.retained_nodes.as_mut() {
+            if let Some(root_node) = self.stack.last().filter(|node| node.len() < B256::len_bytes())
+            {
+                nodes.insert(keccak256(root_node), Bytes::from(root_node.clone()));
+            }
+        }
+
         self.current_root()
     }
 
@@ -278,6 +316,7 @@ impl HashBuilder {
                         self.rlp_buf.clear();
                         self.stack.push(leaf_node.rlp(&mut self.rlp_buf));
                         self.retain_proof_from_buf(&current);
+                        self.retain_node_from_buf();
                     }
                     HashBuilderValue::Hash(hash) => {
                         trace!(target: "trie::hash_builder", ?hash, "pushing branch node hash");
@@ -308,6 +347,7 @@ impl HashBuilder {
                 self.rlp_buf.clear();
                 self.stack.push(extension_node.rlp(&mut self.rlp_buf));
                 self.retain_proof_from_buf(&current.slice(..len_from));
+                self.retain_node_from_buf();
                 self.resize_masks(len_from);
             }
 
@@ -359,6 +399,7 @@ impl HashBuilder {
         self.rlp_buf.clear();
         let rlp = branch_node.rlp(state_mask, &mut self.rlp_buf);
         self.retain_proof_from_buf(&current.slice(..len));
+        self.retain_node_from_buf();
 
         // Clears the stack from the branch node elements
         let first_child_idx = self.stack.len() - state_mask.count_ones() as usize;
@@ -421,6 +462,15 @@ impl HashBuilder {
         }
     }
 
+    /// Retains the node in the RLP buffer if it is referenced by hash.
+    fn retain_node_from_buf(&mut self) {
+        if let Some(nodes) = self.retained_nodes.as_mut() {
+            if self.rlp_buf.len() >= B256::len_bytes() {
+                nodes.insert(keccak256(&self.rlp_buf), Bytes::copy_from_slice(&self.rlp_buf));
+            }
+        }
+    }
+
     fn update_masks(&mut self, current: &Nibbles, len_from: usize) {
         if len_from > 0 {
             let flag = TrieMask::from_nibble(current[len_from - 1]);
@@ -605,6 +655,22 @@ mod tests {
         assert_eq!(hb.root(), root_hash);
     }
 
+    #[test]
+    fn retained_nodes_are_keyed_by_hash() {
+        let mut hb = HashBuilder::default().with_retained_nodes(true);
+        for i in 0u8..16 {
+            hb.add_leaf(Nibbles::unpack(B256::with_last_byte(i)), &[i; 32]);
+        }
+        let root = hb.root();
+
+        let nodes = hb.take_retained_nodes();
+        assert!(nodes.contains_key(&root));
+        for (hash, node) in nodes {
+            assert_eq!(keccak256(&node), hash);
+        }
+        assert!(hb.take_retained_nodes().is_empty());
+    }
+
     #[test]
     fn manual_branch_node_ok() {
         let raw_input = vec![
@@ -10,7 +10,7 @@ pub use hash_builder::HashBuilder;
 
 /// Merkle trie proofs.
 mod proofs;
-pub use proofs::{AccountProof, StorageProof};
+pub use proofs::{AccountProof, MultiProof, StorageMultiProof, StorageProof};
 
 mod account;
 mod mask;
@@ -1,5 +1,80 @@
 use super::Nibbles;
-use crate::{keccak256, Account, Address, Bytes, B256, U256};
+use crate::{constants::EMPTY_ROOT_HASH, keccak256, Account, Address, Bytes, B256, U256};
+use std::collections::{BTreeMap, HashMap};
+
+/// The state multiproof of target accounts and multiproofs of their storage tries.
+///
+/// Trie nodes are keyed by their path, so nodes shared by multiple targets are only included once.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct MultiProof {
+    /// The rlp-serialized account trie nodes on the paths of all target accounts.
+    pub account_subtree: BTreeMap<Nibbles, Bytes>,
+    /// The target accounts that exist, keyed by hashed address.
+    pub accounts: HashMap<B256, Account>,
+    /// The storage multiproofs of the existing target accounts, keyed by hashed address.
+    pub storages: HashMap<B256, StorageMultiProof>,
+}
+
+impl MultiProof {
+    /// Returns the nodes of the account trie on the path of the given hashed address.
+    pub fn account_proof_nodes(&self, hashed_address: B256) -> Vec<Bytes> {
+        proof_nodes(&self.account_subtree, &Nibbles::unpack(hashed_address))
+    }
+
+    /// Extracts the proof of a single target account and its target storage slots.
+    pub fn account_proof(&self, address: Address, slots: &[B256]) -> AccountProof {
+        let hashed_address = keccak256(address);
+        let mut proof = AccountProof::new(address);
+        if let Some(account) = self.accounts.get(&hashed_address) {
+            let empty_storage = StorageMultiProof::default();
+            let storage = self.storages.get(&hashed_address).unwrap_or(&empty_storage);
+            let storage_proofs =
+                slots.iter().map(|slot| storage.storage_proof(*slot)).collect::<Vec<_>>();
+            proof.set_account(*account, storage.root, storage_proofs);
+        }
+        proof.set_proof(self.account_proof_nodes(hashed_address));
+        proof
+    }
+}
+
+/// The multiproof of target slots of a storage trie.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StorageMultiProof {
+    /// The storage trie root.
+    pub root: B256,
+    /// The rlp-serialized storage trie nodes on the paths of all target slots.
+    pub subtree: BTreeMap<Nibbles, Bytes>,
+    /// The non-zero values of the target slots, keyed by hashed slot.
+    pub values: HashMap<B256, U256>,
+}
+
+impl Default for StorageMultiProof {
+    fn default() -> Self {
+        Self { root: EMPTY_ROOT_HASH, subtree: BTreeMap::default(), values: HashMap::default() }
+    }
+}
+
+impl StorageMultiProof {
+    /// Extracts the proof of a single target slot.
+    pub fn storage_proof(&self, slot: B256) -> StorageProof {
+        let hashed_slot = keccak256(slot);
+        let mut proof = StorageProof::new_with_hashed(slot, hashed_slot);
+        proof.set_value(self.values.get(&hashed_slot).copied().unwrap_or_default());
+        proof.set_proof(proof_nodes(&self.subtree, &proof.nibbles));
+        proof
+    }
+}
+
+/// Returns the nodes of the subtree on the path to the target.
+///
+/// The nodes are ordered from the root to the target, since the subtree is ordered by path.
+fn proof_nodes(subtree: &BTreeMap<Nibbles, Bytes>, target: &Nibbles) -> Vec<Bytes> {
+    subtree
+        .iter()
+        .filter(|(path, _)| target.starts_with(path))
+        .map(|(_, node)| node.clone())
+        .collect()
+}
 
 /// The merkle proof with the relevant account info.
 #[derive(PartialEq, Eq, Default, Debug)]
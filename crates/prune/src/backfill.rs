@@ -0,0 +1,389 @@
+//! Support for loosening the prune configuration of an already pruned node.
+
+use crate::PrunerError;
+use reth_db::{database::Database, tables, transaction::DbTxMut};
+use reth_interfaces::provider::ProviderError;
+use reth_primitives::{
+    stage::{StageCheckpoint, StageId},
+    BlockNumber, PruneCheckpoint, PruneMode, PruneModes, PruneSegment,
+};
+use reth_provider::{
+    BlockNumReader, BlockReader, DatabaseProviderRW, PruneCheckpointReader, PruneCheckpointWriter,
+    StageCheckpointWriter, TransactionsProvider,
+};
+use tracing::{debug, info};
+
+/// Range of a segment which was pruned, but is required by the loosened prune configuration, and
+/// is backfilled by the pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruneBackfill {
+    /// Loosened prune segment.
+    pub segment: PruneSegment,
+    /// Stage which backfills the data of the segment.
+    pub stage: StageId,
+    /// First block of the range required by the prune configuration.
+    pub from_block: BlockNumber,
+    /// Last pruned block.
+    pub to_block: BlockNumber,
+}
+
+/// Compares the prune checkpoints with the prune configuration, and prepares the segments whose
+/// configuration was loosened to be backfilled by the pipeline.
+///
+/// Segments which are derived from the transactions (senders and transaction lookup) are deleted
+/// from the first required block on, and the checkpoints of their stages are moved back to it. The
+/// pipeline then detects the inconsistent stage checkpoints and re-runs these stages.
+///
+/// All other segments can only be restored by re-executing the chain, so loosening them returns
+/// [PrunerError::IrreversibleConfigChange]. Nothing is written if any of the changes is rejected.
+pub fn prepare_backfill<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    modes: &PruneModes,
+) -> Result<Vec<PruneBackfill>, PrunerError> {
+    let tip = provider.last_block_number()?;
+
+    let mut backfills = Vec::new();
+    for (segment, mode) in [
+        (PruneSegment::SenderRecovery, modes.sender_recovery),
+        (PruneSegment::TransactionLookup, modes.transaction_lookup),
+        (PruneSegment::Receipts, modes.receipts),
+        (PruneSegment::AccountHistory, modes.account_history),
+        (PruneSegment::StorageHistory, modes.storage_history),
+        (PruneSegment::Bytecodes, modes.bytecodes),
+    ] {
+        if let Some(backfill) = loosened_range(provider, tip, segment, mode)? {
+            backfills.push(backfill);
+        }
+    }
+
+    // The receipts filtered by logs are only required in full if neither the filter, nor the
+    // receipts configuration prune them anymore. The changes of the filter itself are not
+    // tracked by the checkpoint.
+    if modes.receipts_log_filter.is_empty() {
+        if let Some(backfill) =
+            loosened_range(provider, tip, PruneSegment::ContractLogs, modes.receipts)?
+        {
+            backfills.push(backfill);
+        }
+    }
+
+    let transactions_pruned_block = provider
+        .get_prune_checkpoint(PruneSegment::Transactions)?
+        .and_then(|checkpoint| checkpoint.block_number);
+
+    for backfill in &backfills {
+        if !matches!(backfill.stage, StageId::SenderRecovery | StageId::TransactionLookup) {
+            return Err(PrunerError::IrreversibleConfigChange {
+                segment: backfill.segment,
+                pruned_block: backfill.to_block,
+                required_block: backfill.from_block,
+            })
+        }
+
+        if let Some(pruned_block) =
+            transactions_pruned_block.filter(|pruned_block| *pruned_block >= backfill.from_block)
+        {
+            return Err(PrunerError::BackfillTransactionsPruned {
+                segment: backfill.segment,
+                pruned_block,
+                required_block: backfill.from_block,
+            })
+        }
+    }
+
+    for backfill in &backfills {
+        // The stage rebuilds the segment from the first required block up to the tip, so only the
+        // entries of that range are deleted. The data below it doesn't exist anymore, so the
+        // pruner can continue right before it.
+        let last_pruned = match backfill.from_block.checked_sub(1) {
+            Some(block_number) => {
+                let tx_number = provider
+                    .block_body_indices(block_number)?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?
+                    .last_tx_num();
+                Some((block_number, tx_number))
+            }
+            None => None,
+        };
+        let first_tx = last_pruned.map_or(0, |(_, tx_number)| tx_number + 1);
+
+        match backfill.segment {
+            PruneSegment::SenderRecovery => match last_pruned {
+                Some((_, tx_number)) => {
+                    provider.unwind_table_by_num::<tables::TxSenders>(tx_number)?;
+                }
+                None => provider.tx_ref().clear::<tables::TxSenders>()?,
+            },
+            PruneSegment::TransactionLookup => {
+                let end_tx = provider
+                    .block_body_indices(tip)?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(tip))?
+                    .next_tx_num();
+                for (hash, _) in provider.transaction_hashes_by_range(first_tx..end_tx)? {
+                    provider.tx_ref().delete::<tables::TxHashNumber>(hash, None)?;
+                }
+            }
+            _ => unreachable!("only transaction-derived segments are backfilled"),
+        }
+
+        match last_pruned {
+            Some((block_number, tx_number)) => {
+                let prune_mode = match backfill.segment {
+                    PruneSegment::SenderRecovery => modes.sender_recovery,
+                    _ => modes.transaction_lookup,
+                }
+                .unwrap_or(PruneMode::Before(backfill.from_block));
+                provider.save_prune_checkpoint(
+                    backfill.segment,
+                    PruneCheckpoint {
+                        block_number: Some(block_number),
+                        tx_number: Some(tx_number),
+                        prune_mode,
+                    },
+                )?;
+            }
+            None => {
+                provider.tx_ref().delete::<tables::PruneCheckpoints>(backfill.segment, None)?;
+            }
+        }
+
+        provider.save_stage_checkpoint(
+            backfill.stage,
+            StageCheckpoint::new(backfill.from_block.saturating_sub(1)),
+        )?;
+        info!(target: "pruner", segment = %backfill.segment, stage = %backfill.stage, from_block = %backfill.from_block, to_block = %backfill.to_block, "Prune configuration was loosened, backfilling the segment");
+    }
+
+    Ok(backfills)
+}
+
+/// Returns the pruned range of the segment which is required by the prune mode, if any.
+fn loosened_range<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    tip: BlockNumber,
+    segment: PruneSegment,
+    mode: Option<PruneMode>,
+) -> Result<Option<PruneBackfill>, PrunerError> {
+    let Some(pruned_block) =
+        provider.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+    else {
+        return Ok(None)
+    };
+
+    let required_block = match mode {
+        Some(mode) => mode
+            .prune_target_block(tip, segment)?
+            .map(|(block_number, _)| block_number + 1)
+            .unwrap_or_default(),
+        None => 0,
+    };
+    if required_block > pruned_block {
+        return Ok(None)
+    }
+
+    debug!(target: "pruner", %segment, %pruned_block, %required_block, "Prune configuration was loosened");
+    let stage = match segment {
+        PruneSegment::SenderRecovery => StageId::SenderRecovery,
+        PruneSegment::TransactionLookup => StageId::TransactionLookup,
+        PruneSegment::AccountHistory => StageId::IndexAccountHistory,
+        PruneSegment::StorageHistory => StageId::IndexStorageHistory,
+        _ => StageId::Execution,
+    };
+
+    Ok(Some(PruneBackfill { segment, stage, from_block: required_block, to_block: pruned_block }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{backfill::prepare_backfill, PruneBackfill, PrunerError};
+    use assert_matches::assert_matches;
+    use reth_db::{tables, transaction::DbTxMut};
+    use reth_interfaces::test_utils::{generators, generators::random_block_range};
+    use reth_primitives::{
+        stage::{StageCheckpoint, StageId},
+        Address, PruneCheckpoint, PruneMode, PruneModes, PruneSegment, B256,
+    };
+    use reth_provider::{
+        PruneCheckpointReader, PruneCheckpointWriter, StageCheckpointReader, StageCheckpointWriter,
+    };
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn prepare_backfill_loosened() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=100, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+        let transactions = blocks.iter().flat_map(|block| &block.body).collect::<Vec<_>>();
+        // Senders and lookups below the first required block are pruned already
+        let first_required_tx =
+            blocks[..51].iter().map(|block| block.body.len() as u64).sum::<u64>();
+        db.commit(|tx| {
+            for (tx_number, transaction) in transactions.iter().enumerate() {
+                tx.put::<tables::TxSenders>(tx_number as u64, Address::random())?;
+                tx.put::<tables::TxHashNumber>(transaction.hash(), tx_number as u64)?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let provider = db.factory.provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::SenderRecovery, StageCheckpoint::new(100)).unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::SenderRecovery,
+                PruneCheckpoint {
+                    block_number: Some(90),
+                    tx_number: None,
+                    prune_mode: PruneMode::Distance(10),
+                },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        // Tightened configuration doesn't need a backfill
+        let provider = db.factory.provider_rw().unwrap();
+        let modes = PruneModes { sender_recovery: Some(PruneMode::Full), ..PruneModes::none() };
+        assert_eq!(prepare_backfill(&provider, &modes).unwrap(), vec![]);
+
+        // Loosened configuration requires the senders from the block 51
+        let modes =
+            PruneModes { sender_recovery: Some(PruneMode::Distance(50)), ..PruneModes::none() };
+        assert_eq!(
+            prepare_backfill(&provider, &modes).unwrap(),
+            vec![PruneBackfill {
+                segment: PruneSegment::SenderRecovery,
+                stage: StageId::SenderRecovery,
+                from_block: 51,
+                to_block: 90,
+            }]
+        );
+        provider.commit().unwrap();
+
+        let provider = db.factory.provider().unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::SenderRecovery).unwrap(),
+            Some(StageCheckpoint::new(50))
+        );
+        assert_matches!(
+            provider.get_prune_checkpoint(PruneSegment::SenderRecovery).unwrap(),
+            Some(PruneCheckpoint { block_number: Some(50), .. })
+        );
+        // Only the senders rebuilt by the stage are deleted, the lookups are kept
+        assert_eq!(
+            db.table::<tables::TxSenders>()
+                .unwrap()
+                .into_iter()
+                .map(|(tx_number, _)| tx_number)
+                .collect::<Vec<_>>(),
+            (0..first_required_tx).collect::<Vec<_>>()
+        );
+        assert_eq!(db.table::<tables::TxHashNumber>().unwrap().len(), transactions.len());
+
+        // Loosened transaction lookup only deletes the lookups from the block 51
+        let provider = db.factory.provider_rw().unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::TransactionLookup,
+                PruneCheckpoint {
+                    block_number: Some(90),
+                    tx_number: None,
+                    prune_mode: PruneMode::Distance(10),
+                },
+            )
+            .unwrap();
+        let modes = PruneModes {
+            sender_recovery: Some(PruneMode::Distance(50)),
+            transaction_lookup: Some(PruneMode::Distance(50)),
+            ..PruneModes::none()
+        };
+        assert_eq!(
+            prepare_backfill(&provider, &modes).unwrap(),
+            vec![PruneBackfill {
+                segment: PruneSegment::TransactionLookup,
+                stage: StageId::TransactionLookup,
+                from_block: 51,
+                to_block: 90,
+            }]
+        );
+        provider.commit().unwrap();
+
+        let mut lookups = db
+            .table::<tables::TxHashNumber>()
+            .unwrap()
+            .into_iter()
+            .map(|(_, tx_number)| tx_number)
+            .collect::<Vec<_>>();
+        lookups.sort_unstable();
+        assert_eq!(lookups, (0..first_required_tx).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn prepare_backfill_irreversible() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=100, B256::ZERO, 0..1);
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        let provider = db.factory.provider_rw().unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::TransactionLookup,
+                PruneCheckpoint {
+                    block_number: Some(90),
+                    tx_number: None,
+                    prune_mode: PruneMode::Full,
+                },
+            )
+            .unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::AccountHistory,
+                PruneCheckpoint {
+                    block_number: Some(30),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(31),
+                },
+            )
+            .unwrap();
+
+        // Account history can't be restored, so the transaction lookup isn't backfilled either
+        assert_matches!(
+            prepare_backfill(&provider, &PruneModes::none()),
+            Err(PrunerError::IrreversibleConfigChange {
+                segment: PruneSegment::AccountHistory,
+                pruned_block: 30,
+                required_block: 0,
+            })
+        );
+        assert_matches!(
+            provider.get_prune_checkpoint(PruneSegment::TransactionLookup).unwrap(),
+            Some(PruneCheckpoint { block_number: Some(90), .. })
+        );
+
+        // Transactions to backfill from are pruned. Segments are checked in order, so the
+        // transaction lookup is rejected before the account history.
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::Transactions,
+                PruneCheckpoint {
+                    block_number: Some(60),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(61),
+                },
+            )
+            .unwrap();
+        let modes =
+            PruneModes { transaction_lookup: Some(PruneMode::Before(50)), ..PruneModes::none() };
+        assert_matches!(
+            prepare_backfill(&provider, &modes),
+            Err(PrunerError::BackfillTransactionsPruned {
+                segment: PruneSegment::TransactionLookup,
+                pruned_block: 60,
+                required_block: 50,
+            })
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use reth_db::DatabaseError;
 use reth_interfaces::RethError;
-use reth_primitives::PruneSegmentError;
+use reth_primitives::{BlockNumber, PruneSegment, PruneSegmentError};
 use reth_provider::ProviderError;
 use thiserror::Error;
 
@@ -20,4 +20,26 @@ pub enum PrunerError {
 
     #[error(transparent)]
     Provider(#[from] ProviderError),
+
+    #[error(
+        "{segment} is pruned up to block #{pruned_block}, but the prune configuration requires it \
+         from block #{required_block}. The pruned data can only be restored by re-executing the \
+         chain, so either resync the node or configure {segment} to prune at least up to block \
+         #{pruned_block}"
+    )]
+    IrreversibleConfigChange {
+        segment: PruneSegment,
+        pruned_block: BlockNumber,
+        required_block: BlockNumber,
+    },
+
+    #[error(
+        "{segment} can't be backfilled from block #{required_block}, because the transactions \
+         are pruned from the database up to block #{pruned_block}"
+    )]
+    BackfillTransactionsPruned {
+        segment: PruneSegment,
+        pruned_block: BlockNumber,
+        required_block: BlockNumber,
+    },
 }
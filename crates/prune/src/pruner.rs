@@ -6,7 +6,7 @@ use crate::{
     Metrics, PrunerError, PrunerEvent,
 };
 use reth_db::database::Database;
-use reth_primitives::{BlockNumber, PruneMode, PruneProgress, PruneSegment};
+use reth_primitives::{BlockNumber, PruneMode, PruneProgress};
 use reth_provider::{ProviderFactory, PruneCheckpointReader};
 use reth_snapshot::HighestSnapshotsTracker;
 use reth_tokio_util::EventListeners;
@@ -88,7 +88,6 @@ impl<DB: Database> Pruner<DB> {
         let mut done = true;
         let mut stats = BTreeMap::new();
 
-        // TODO(alexey): prune snapshotted segments of data (headers, transactions)
         let highest_snapshots = *self.highest_snapshots_tracker.borrow();
 
         // Multiply `self.delete_limit` (number of rows to delete per block) by number of blocks
@@ -152,51 +151,38 @@ impl<DB: Database> Pruner<DB> {
         }
 
         if let Some(snapshots) = highest_snapshots {
-            if let (Some(to_block), true) = (snapshots.headers, delete_limit > 0) {
-                let prune_mode = PruneMode::Before(to_block + 1);
-                trace!(
-                    target: "pruner",
-                    prune_segment = ?PruneSegment::Headers,
-                    %to_block,
-                    ?prune_mode,
-                    "Got target block to prune"
-                );
-
-                let segment_start = Instant::now();
-                let segment = segments::Headers::new(prune_mode);
-                let previous_checkpoint = provider.get_prune_checkpoint(PruneSegment::Headers)?;
-                let output = segment
-                    .prune(&provider, PruneInput { previous_checkpoint, to_block, delete_limit })?;
-                if let Some(checkpoint) = output.checkpoint {
-                    segment
-                        .save_checkpoint(&provider, checkpoint.as_prune_checkpoint(prune_mode))?;
+            // Data that was moved to the snapshots is pruned from the database up to the highest
+            // snapshotted block of the corresponding snapshot segment.
+            let snapshotted_segments: [(Option<BlockNumber>, fn(PruneMode) -> Box<dyn Segment<DB>>);
+                4] = [
+                (snapshots.headers, |mode| Box::new(segments::Headers::new(mode))),
+                (snapshots.transactions, |mode| Box::new(segments::Transactions::new(mode))),
+                (snapshots.account_changesets, |mode| {
+                    Box::new(segments::AccountChangeSets::new(mode))
+                }),
+                (snapshots.storage_changesets, |mode| {
+                    Box::new(segments::StorageChangeSets::new(mode))
+                }),
+            ];
+
+            for (to_block, new_segment) in snapshotted_segments {
+                let Some(to_block) = to_block else { continue };
+                if delete_limit == 0 {
+                    break
                 }
-                self.metrics
-                    .get_prune_segment_metrics(PruneSegment::Headers)
-                    .duration_seconds
-                    .record(segment_start.elapsed());
 
-                done = done && output.done;
-                delete_limit = delete_limit.saturating_sub(output.pruned);
-                stats.insert(
-                    PruneSegment::Headers,
-                    (PruneProgress::from_done(output.done), output.pruned),
-                );
-            }
-
-            if let (Some(to_block), true) = (snapshots.transactions, delete_limit > 0) {
                 let prune_mode = PruneMode::Before(to_block + 1);
+                let segment = new_segment(prune_mode);
                 trace!(
                     target: "pruner",
-                    prune_segment = ?PruneSegment::Transactions,
+                    prune_segment = ?segment.segment(),
                     %to_block,
                     ?prune_mode,
                     "Got target block to prune"
                 );
 
                 let segment_start = Instant::now();
-                let segment = segments::Transactions::new(prune_mode);
-                let previous_checkpoint = provider.get_prune_checkpoint(PruneSegment::Headers)?;
+                let previous_checkpoint = provider.get_prune_checkpoint(segment.segment())?;
                 let output = segment
                     .prune(&provider, PruneInput { previous_checkpoint, to_block, delete_limit })?;
                 if let Some(checkpoint) = output.checkpoint {
@@ -204,14 +190,14 @@ impl<DB: Database> Pruner<DB> {
                         .save_checkpoint(&provider, checkpoint.as_prune_checkpoint(prune_mode))?;
                 }
                 self.metrics
-                    .get_prune_segment_metrics(PruneSegment::Transactions)
+                    .get_prune_segment_metrics(segment.segment())
                     .duration_seconds
                     .record(segment_start.elapsed());
 
                 done = done && output.done;
                 delete_limit = delete_limit.saturating_sub(output.pruned);
                 stats.insert(
-                    PruneSegment::Transactions,
+                    segment.segment(),
                     (PruneProgress::from_done(output.done), output.pruned),
                 );
             }
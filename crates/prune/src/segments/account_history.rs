@@ -5,18 +5,25 @@ use crate::{
     PrunerError,
 };
 use reth_db::{database::Database, models::ShardedKey, tables};
-use reth_primitives::{PruneMode, PruneSegment};
+use reth_primitives::{HistoryRetentionConfig, PruneMode, PruneSegment};
 use reth_provider::DatabaseProviderRW;
 use tracing::{instrument, trace};
 
 #[derive(Debug)]
 pub struct AccountHistory {
     mode: PruneMode,
+    retention: HistoryRetentionConfig,
 }
 
 impl AccountHistory {
     pub fn new(mode: PruneMode) -> Self {
-        Self { mode }
+        Self { mode, retention: HistoryRetentionConfig::default() }
+    }
+
+    /// Sets the addresses whose account history is retained.
+    pub fn with_retention(mut self, retention: HistoryRetentionConfig) -> Self {
+        self.retention = retention;
+        self
     }
 }
 
@@ -49,7 +56,7 @@ impl<DB: Database> Segment<DB> for AccountHistory {
             .prune_table_with_range::<tables::AccountChangeSet>(
                 range,
                 input.delete_limit / 2,
-                |_| false,
+                |(_, change)| self.retention.contains(&change.address),
                 |row| last_changeset_pruned_block = Some(row.0),
             )?;
         trace!(target: "pruner", pruned = %pruned_changesets, %done, "Pruned account history (changesets)");
@@ -65,6 +72,7 @@ impl<DB: Database> Segment<DB> for AccountHistory {
             last_changeset_pruned_block,
             |a, b| a.key == b.key,
             |key| ShardedKey::last(key.key),
+            |key| self.retention.contains(&key.key),
         )?;
         trace!(target: "pruner", %processed, pruned = %pruned_indices, %done, "Pruned account history (history)" );
 
@@ -88,10 +96,15 @@ mod tests {
         generators,
         generators::{random_block_range, random_changeset_range, random_eoa_account_range},
     };
-    use reth_primitives::{BlockNumber, PruneCheckpoint, PruneMode, PruneSegment, B256};
+    use reth_primitives::{
+        BlockNumber, HistoryRetentionConfig, PruneCheckpoint, PruneMode, PruneSegment, B256,
+    };
     use reth_provider::PruneCheckpointReader;
     use reth_stages::test_utils::TestStageDB;
-    use std::{collections::BTreeMap, ops::AddAssign};
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        ops::AddAssign,
+    };
 
     #[test]
     fn prune() {
@@ -240,4 +253,63 @@ mod tests {
         test_prune(998, 2, (true, 998));
         test_prune(1400, 3, (true, 804));
     }
+
+    #[test]
+    fn prune_with_retention() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 1..=100, B256::ZERO, 0..1);
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        let accounts =
+            random_eoa_account_range(&mut rng, 0..2).into_iter().collect::<BTreeMap<_, _>>();
+        let retained = *accounts.keys().next().unwrap();
+
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+            0..0,
+            0..0,
+        );
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+        db.insert_history(changesets.clone(), None).expect("insert history");
+
+        let original_changesets = db.table::<tables::AccountChangeSet>().unwrap();
+        let original_shards = db.table::<tables::AccountHistory>().unwrap();
+
+        let to_block = 50;
+        let prune_mode = PruneMode::Before(to_block);
+        let input = PruneInput { previous_checkpoint: None, to_block, delete_limit: 10_000 };
+        let segment = AccountHistory::new(prune_mode)
+            .with_retention(HistoryRetentionConfig(BTreeSet::from([retained])));
+
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert_matches!(result, PruneOutput { done: true, .. });
+        provider.commit().expect("commit");
+
+        // Only the changesets of the other address below the target block are pruned
+        assert_eq!(
+            db.table::<tables::AccountChangeSet>().unwrap(),
+            original_changesets
+                .into_iter()
+                .filter(
+                    |(block_number, change)| *block_number > to_block || change.address == retained
+                )
+                .collect::<Vec<_>>()
+        );
+
+        // History of the retained address is left untouched
+        let actual_shards = db.table::<tables::AccountHistory>().unwrap();
+        assert_eq!(
+            actual_shards.iter().filter(|(key, _)| key.key == retained).collect::<Vec<_>>(),
+            original_shards.iter().filter(|(key, _)| key.key == retained).collect::<Vec<_>>()
+        );
+        assert!(actual_shards
+            .iter()
+            .filter(|(key, _)| key.key != retained)
+            .all(|(_, blocks)| blocks.iter(0).all(|block| block > to_block as usize)));
+    }
 }
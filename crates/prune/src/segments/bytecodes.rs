@@ -0,0 +1,392 @@
+use crate::{
+    segments::{PruneInput, PruneOutput, PruneOutputCheckpoint, Segment},
+    PrunerError,
+};
+use reth_db::{
+    cursor::DbCursorRO,
+    database::Database,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::{HistoryRetentionConfig, PruneMode, PruneSegment, TxNumber, B256};
+use reth_provider::{DatabaseProviderRW, PruneCheckpointReader};
+use std::collections::BTreeSet;
+use tracing::{instrument, trace};
+
+/// Default number of blocks the prune target needs to advance by before a new garbage collection
+/// pass over the bytecodes is started.
+pub const DEFAULT_BYTECODES_PRUNE_INTERVAL: u64 = 10_000;
+
+/// Default number of bytecodes checked for references in a single run.
+pub const DEFAULT_BYTECODES_CHUNK_SIZE: usize = 100_000;
+
+/// Garbage collects the `Bytecodes` table, deleting code which is referenced neither by an account
+/// in `PlainAccountState`, nor by an account changeset above the prune target block, nor by a
+/// changeset of the addresses whose history is retained.
+///
+/// The prune target never goes beyond the account history prune checkpoint, so bytecodes pruning
+/// is at most as aggressive as account history pruning, and skipped if account history is kept.
+///
+/// Bytecodes aren't reference counted, so a pass walks the table in chunks of code hashes, and
+/// every run only checks the accounts and changesets for references to the hashes of its chunk.
+/// A pass is only started once the prune target advanced by the configured interval since the
+/// previous completed one.
+///
+/// The checkpoint block number is the target of the last completed pass. While a pass is in
+/// progress, the checkpoint transaction number holds the first 8 bytes of the code hash the next
+/// run resumes from.
+#[derive(Debug)]
+pub struct Bytecodes {
+    mode: PruneMode,
+    interval: u64,
+    chunk_size: usize,
+    retention: HistoryRetentionConfig,
+}
+
+impl Bytecodes {
+    pub fn new(mode: PruneMode) -> Self {
+        Self {
+            mode,
+            interval: DEFAULT_BYTECODES_PRUNE_INTERVAL,
+            chunk_size: DEFAULT_BYTECODES_CHUNK_SIZE,
+            retention: HistoryRetentionConfig::default(),
+        }
+    }
+
+    /// Sets the number of blocks between the targets of two garbage collection passes.
+    pub fn with_interval(mut self, interval: u64) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the number of bytecodes checked for references in a single run.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Sets the addresses whose account history is retained, so the code referenced by their
+    /// changesets below the prune target block is kept.
+    pub fn with_retention(mut self, retention: HistoryRetentionConfig) -> Self {
+        self.retention = retention;
+        self
+    }
+}
+
+impl<DB: Database> Segment<DB> for Bytecodes {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::Bytecodes
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<PruneOutput, PrunerError> {
+        // Historical account state is served down to the account history prune checkpoint, so the
+        // target is clamped to it to keep the code referenced by the history that's still there.
+        let Some(to_block) = provider
+            .get_prune_checkpoint(PruneSegment::AccountHistory)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map(|block| block.min(input.to_block))
+        else {
+            trace!(target: "pruner", "Account history is not pruned, skipping bytecodes");
+            return Ok(PruneOutput::done())
+        };
+
+        let last_pass = input.previous_checkpoint.and_then(|c| c.block_number);
+        let resume_from = input.previous_checkpoint.and_then(|c| c.tx_number);
+        if let (Some(last_pass), None) = (last_pass, resume_from) {
+            if to_block < last_pass + self.interval {
+                trace!(target: "pruner", %last_pass, "No bytecodes to prune");
+                return Ok(PruneOutput::done())
+            }
+        }
+
+        // Changesets moved to the snapshots are not tracked, so the code they reference can't be
+        // told apart from the unused one.
+        let snapshotted_changesets = provider
+            .get_prune_checkpoint(PruneSegment::AccountChangeSets)?
+            .and_then(|checkpoint| checkpoint.block_number);
+        if snapshotted_changesets.is_some_and(|block| block > to_block) {
+            trace!(target: "pruner", ?snapshotted_changesets, "Account changesets above the prune target were snapshotted, skipping bytecodes");
+            return Ok(PruneOutput::done())
+        }
+
+        let mut codes = provider.tx_ref().cursor_read::<tables::Bytecodes>()?;
+        let mut candidates = BTreeSet::new();
+        let mut next = None;
+        for entry in codes.walk(resume_from.map(code_hash_from_prefix))? {
+            let (code_hash, _) = entry?;
+            if candidates.len() == self.chunk_size {
+                next = Some(code_hash);
+                break
+            }
+            candidates.insert(code_hash);
+        }
+        let checked = candidates.len();
+
+        remove_referenced_code_hashes(provider, to_block, &self.retention, &mut candidates)?;
+        trace!(
+            target: "pruner",
+            %checked,
+            unreferenced = %candidates.len(),
+            "Checked bytecodes chunk"
+        );
+
+        let mut pruned = 0;
+        for code_hash in candidates {
+            if pruned == input.delete_limit {
+                // The rest of the unreferenced code of the chunk is checked again on the next run.
+                next = Some(code_hash);
+                break
+            }
+            provider.tx_ref().delete::<tables::Bytecodes>(code_hash, None)?;
+            pruned += 1;
+        }
+
+        let done = next.is_none();
+        trace!(target: "pruner", %pruned, %done, "Pruned bytecodes");
+
+        Ok(PruneOutput {
+            done,
+            pruned,
+            checkpoint: Some(match next {
+                Some(code_hash) => PruneOutputCheckpoint {
+                    block_number: last_pass,
+                    tx_number: Some(code_hash_prefix(code_hash)),
+                },
+                None => PruneOutputCheckpoint { block_number: Some(to_block), tx_number: None },
+            }),
+        })
+    }
+}
+
+/// Returns the first 8 bytes of the code hash, which are stored as the resume position of a pass.
+fn code_hash_prefix(code_hash: B256) -> TxNumber {
+    TxNumber::from_be_bytes(code_hash[..8].try_into().expect("8 bytes"))
+}
+
+/// Returns the lowest code hash starting with the given prefix.
+fn code_hash_from_prefix(prefix: TxNumber) -> B256 {
+    let mut code_hash = B256::ZERO;
+    code_hash[..8].copy_from_slice(&prefix.to_be_bytes());
+    code_hash
+}
+
+/// Removes the code hashes of the accounts in the plain state, of the accounts in the changesets
+/// above `to_block`, and of the retained accounts in the changesets below it from `candidates`.
+///
+/// Stops as soon as all candidates turned out to be referenced.
+fn remove_referenced_code_hashes<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    to_block: u64,
+    retention: &HistoryRetentionConfig,
+    candidates: &mut BTreeSet<B256>,
+) -> Result<(), PrunerError> {
+    let mut accounts = provider.tx_ref().cursor_read::<tables::PlainAccountState>()?;
+    for entry in accounts.walk(None)? {
+        if candidates.is_empty() {
+            return Ok(())
+        }
+        let (_, account) = entry?;
+        if let Some(code_hash) = account.bytecode_hash {
+            candidates.remove(&code_hash);
+        }
+    }
+
+    let mut changesets = provider.tx_ref().cursor_read::<tables::AccountChangeSet>()?;
+    let start = if retention.is_empty() { to_block + 1 } else { 0 };
+    for entry in changesets.walk_range(start..)? {
+        if candidates.is_empty() {
+            return Ok(())
+        }
+        let (block_number, change) = entry?;
+        if block_number > to_block || retention.contains(&change.address) {
+            if let Some(code_hash) = change.info.and_then(|account| account.bytecode_hash) {
+                candidates.remove(&code_hash);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::code_hash_prefix;
+    use crate::segments::{Bytecodes, PruneInput, PruneOutput, Segment};
+    use assert_matches::assert_matches;
+    use reth_db::{models::AccountBeforeTx, tables, transaction::DbTxMut};
+    use reth_primitives::{
+        Account, Address, BlockNumber, Bytecode, Bytes, PruneCheckpoint, PruneMode, PruneSegment,
+        B256,
+    };
+    use reth_provider::{PruneCheckpointReader, PruneCheckpointWriter};
+    use reth_stages::test_utils::TestStageDB;
+
+    /// Code hashes with distinct prefixes, so a pass can be resumed in between them.
+    fn code_hash(i: u8) -> B256 {
+        let mut code_hash = B256::with_last_byte(i);
+        code_hash[0] = i;
+        code_hash
+    }
+
+    /// Inserts codes 1 to 5: code 1 is in the state, code 2 in a changeset of block 20, code 3 in
+    /// a changeset of block 5, and codes 4 and 5 are not referenced at all. Account history is
+    /// pruned up to `account_history_block`.
+    fn insert_bytecodes(db: &TestStageDB, account_history_block: BlockNumber) {
+        let account = |code: u8| Account {
+            nonce: 1,
+            balance: Default::default(),
+            bytecode_hash: Some(code_hash(code)),
+        };
+
+        db.commit(|tx| {
+            for i in 1..=5 {
+                tx.put::<tables::Bytecodes>(code_hash(i), Bytecode::new_raw(Bytes::from(vec![i])))?;
+            }
+            tx.put::<tables::PlainAccountState>(Address::with_last_byte(1), account(1))?;
+            tx.put::<tables::AccountChangeSet>(
+                20,
+                AccountBeforeTx { address: Address::with_last_byte(2), info: Some(account(2)) },
+            )?;
+            tx.put::<tables::AccountChangeSet>(
+                5,
+                AccountBeforeTx { address: Address::with_last_byte(3), info: Some(account(3)) },
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        let provider = db.factory.provider_rw().unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::AccountHistory,
+                PruneCheckpoint {
+                    block_number: Some(account_history_block),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(account_history_block + 1),
+                },
+            )
+            .unwrap();
+        provider.commit().expect("commit");
+    }
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        insert_bytecodes(&db, 20);
+
+        let test_prune = |to_block: BlockNumber, expected_result: (bool, usize)| {
+            let prune_mode = PruneMode::Before(to_block + 1);
+            let input = PruneInput {
+                previous_checkpoint: db
+                    .factory
+                    .provider()
+                    .unwrap()
+                    .get_prune_checkpoint(PruneSegment::Bytecodes)
+                    .unwrap(),
+                to_block,
+                delete_limit: 1,
+            };
+            let segment = Bytecodes::new(prune_mode).with_interval(10).with_chunk_size(3);
+
+            let provider = db.factory.provider_rw().unwrap();
+            let result = segment.prune(&provider, input).unwrap();
+            assert_matches!(
+                result,
+                PruneOutput { done, pruned, .. } if (done, pruned) == expected_result
+            );
+            if let Some(checkpoint) = result.checkpoint {
+                segment
+                    .save_checkpoint(&provider, checkpoint.as_prune_checkpoint(prune_mode))
+                    .unwrap();
+            }
+            provider.commit().expect("commit");
+
+            db.table::<tables::Bytecodes>()
+                .unwrap()
+                .into_iter()
+                .map(|(code_hash, _)| code_hash)
+                .collect::<Vec<_>>()
+        };
+
+        let checkpoint = || {
+            db.factory
+                .provider()
+                .unwrap()
+                .get_prune_checkpoint(PruneSegment::Bytecodes)
+                .unwrap()
+                .map(|checkpoint| (checkpoint.block_number, checkpoint.tx_number))
+        };
+
+        // Only the first chunk of three codes is checked, and the pass is resumed from the fourth
+        assert_eq!(
+            test_prune(10, (false, 1)),
+            vec![code_hash(1), code_hash(2), code_hash(4), code_hash(5)]
+        );
+        assert_eq!(checkpoint(), Some((None, Some(code_hash_prefix(code_hash(4))))));
+
+        // The delete limit is reached, so the pass is resumed from the code that's left
+        assert_eq!(test_prune(10, (false, 1)), vec![code_hash(1), code_hash(2), code_hash(5)]);
+        assert_eq!(checkpoint(), Some((None, Some(code_hash_prefix(code_hash(5))))));
+
+        assert_eq!(test_prune(10, (true, 1)), vec![code_hash(1), code_hash(2)]);
+        assert_eq!(checkpoint(), Some((Some(10), None)));
+
+        // Next pass only starts once the target advanced by the interval
+        assert_eq!(test_prune(19, (true, 0)), vec![code_hash(1), code_hash(2)]);
+        assert_eq!(test_prune(20, (true, 1)), vec![code_hash(1)]);
+        assert_eq!(checkpoint(), Some((Some(20), None)));
+    }
+
+    #[test]
+    fn clamp_to_account_history() {
+        let db = TestStageDB::default();
+        insert_bytecodes(&db, 4);
+
+        let prune = |to_block: BlockNumber| {
+            let input = PruneInput { previous_checkpoint: None, to_block, delete_limit: 10 };
+            let segment = Bytecodes::new(PruneMode::Before(to_block + 1));
+
+            let provider = db.factory.provider_rw().unwrap();
+            let result = segment.prune(&provider, input).unwrap();
+            provider.commit().expect("commit");
+            result
+        };
+
+        // Account history is only pruned up to block 4, so the code of the changeset at block 5
+        // is kept even though the bytecodes target is above it
+        let result = prune(10);
+        assert_matches!(
+            result,
+            PruneOutput { done: true, pruned: 2, checkpoint: Some(checkpoint) }
+                if checkpoint.block_number == Some(4)
+        );
+        assert_eq!(
+            db.table::<tables::Bytecodes>()
+                .unwrap()
+                .into_iter()
+                .map(|(code_hash, _)| code_hash)
+                .collect::<Vec<_>>(),
+            vec![code_hash(1), code_hash(2), code_hash(3)]
+        );
+
+        // Nothing is pruned while account history isn't
+        let db = TestStageDB::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let result = Bytecodes::new(PruneMode::Full)
+            .prune(
+                &provider,
+                PruneInput { previous_checkpoint: None, to_block: 10, delete_limit: 10 },
+            )
+            .unwrap();
+        assert_matches!(result, PruneOutput { done: true, pruned: 0, checkpoint: None });
+    }
+}
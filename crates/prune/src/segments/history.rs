@@ -10,7 +10,8 @@ use reth_interfaces::db::DatabaseError;
 use reth_primitives::BlockNumber;
 use reth_provider::DatabaseProviderRW;
 
-/// Prune history indices up to the provided block, inclusive.
+/// Prune history indices up to the provided block, inclusive. Shards of the keys matching
+/// `skip_filter` are left untouched.
 ///
 /// Returns total number of processed (walked) and deleted entities.
 pub(crate) fn prune_history_indices<DB, T, SK>(
@@ -18,6 +19,7 @@ pub(crate) fn prune_history_indices<DB, T, SK>(
     to_block: BlockNumber,
     key_matches: impl Fn(&T::Key, &T::Key) -> bool,
     last_key: impl Fn(&T::Key) -> T::Key,
+    skip_filter: impl Fn(&T::Key) -> bool,
 ) -> Result<(usize, usize), DatabaseError>
 where
     DB: Database,
@@ -37,6 +39,15 @@ where
     while let Some(result) = cursor.next()? {
         let (key, blocks): (T::Key, BlockNumberList) = result;
 
+        // If the history of this key is retained, skip to its last shard.
+        if skip_filter(&key) {
+            if key.as_ref().highest_block_number != u64::MAX {
+                cursor.seek_exact(last_key(&key))?;
+            }
+            processed += 1;
+            continue
+        }
+
         // If shard consists only of block numbers less than the target one, delete shard
         // completely.
         if key.as_ref().highest_block_number <= to_block {
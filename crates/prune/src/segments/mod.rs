@@ -1,21 +1,27 @@
+mod account_changesets;
 mod account_history;
+mod bytecodes;
 mod headers;
 mod history;
 mod receipts;
 mod receipts_by_logs;
 mod sender_recovery;
 mod set;
+mod storage_changesets;
 mod storage_history;
 mod transaction_lookup;
 mod transactions;
 
+pub use account_changesets::AccountChangeSets;
 pub use account_history::AccountHistory;
+pub use bytecodes::{Bytecodes, DEFAULT_BYTECODES_CHUNK_SIZE, DEFAULT_BYTECODES_PRUNE_INTERVAL};
 pub use headers::Headers;
 pub use receipts::Receipts;
 pub use receipts_by_logs::ReceiptsByLogs;
 pub use sender_recovery::SenderRecovery;
 pub use set::SegmentSet;
 use std::fmt::Debug;
+pub use storage_changesets::StorageChangeSets;
 pub use storage_history::StorageHistory;
 pub use transaction_lookup::TransactionLookup;
 pub use transactions::Transactions;
@@ -9,18 +9,25 @@ use reth_db::{
     models::{storage_sharded_key::StorageShardedKey, BlockNumberAddress},
     tables,
 };
-use reth_primitives::{PruneMode, PruneSegment};
+use reth_primitives::{HistoryRetentionConfig, PruneMode, PruneSegment};
 use reth_provider::DatabaseProviderRW;
 use tracing::{instrument, trace};
 
 #[derive(Debug)]
 pub struct StorageHistory {
     mode: PruneMode,
+    retention: HistoryRetentionConfig,
 }
 
 impl StorageHistory {
     pub fn new(mode: PruneMode) -> Self {
-        Self { mode }
+        Self { mode, retention: HistoryRetentionConfig::default() }
+    }
+
+    /// Sets the addresses whose storage history is retained.
+    pub fn with_retention(mut self, retention: HistoryRetentionConfig) -> Self {
+        self.retention = retention;
+        self
     }
 }
 
@@ -53,7 +60,7 @@ impl<DB: Database> Segment<DB> for StorageHistory {
             .prune_table_with_range::<tables::StorageChangeSet>(
                 BlockNumberAddress::range(range),
                 input.delete_limit / 2,
-                |_| false,
+                |(key, _)| self.retention.contains(&key.address()),
                 |row| last_changeset_pruned_block = Some(row.0.block_number()),
             )?;
         trace!(target: "pruner", deleted = %pruned_changesets, %done, "Pruned storage history (changesets)");
@@ -69,6 +76,7 @@ impl<DB: Database> Segment<DB> for StorageHistory {
             last_changeset_pruned_block,
             |a, b| a.address == b.address && a.sharded_key.key == b.sharded_key.key,
             |key| StorageShardedKey::last(key.address, key.sharded_key.key),
+            |key| self.retention.contains(&key.address),
         )?;
         trace!(target: "pruner", %processed, deleted = %pruned_indices, %done, "Pruned storage history (history)" );
 
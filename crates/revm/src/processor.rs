@@ -284,16 +284,19 @@ impl<'a> EVMProcessor<'a> {
         self.stats.apply_post_execution_state_changes_duration += time.elapsed();
 
         let time = Instant::now();
-        let retention = if self.tip.map_or(true, |tip| {
-            !self
-                .prune_modes
-                .account_history
-                .map_or(false, |mode| mode.should_prune(block.number, tip)) &&
+        // The reverts are always kept if the history of some addresses is retained, and the
+        // history of the others is pruned by the pruner afterwards.
+        let retention = if !self.prune_modes.history_retention.is_empty() ||
+            self.tip.map_or(true, |tip| {
                 !self
                     .prune_modes
-                    .storage_history
-                    .map_or(false, |mode| mode.should_prune(block.number, tip))
-        }) {
+                    .account_history
+                    .map_or(false, |mode| mode.should_prune(block.number, tip)) &&
+                    !self
+                        .prune_modes
+                        .storage_history
+                        .map_or(false, |mode| mode.should_prune(block.number, tip))
+            }) {
             BundleRetention::Reverts
         } else {
             BundleRetention::PlainState
@@ -542,11 +545,12 @@ mod tests {
         bytes,
         constants::{BEACON_ROOTS_ADDRESS, SYSTEM_ADDRESS},
         keccak256,
-        trie::AccountProof,
+        trie::{AccountProof, MultiProof},
         Account, Bytecode, Bytes, ChainSpecBuilder, ForkCondition, StorageKey, MAINNET,
     };
     use reth_provider::{
-        AccountReader, BlockHashReader, BundleStateWithReceipts, StateRootProvider,
+        AccountReader, BlockHashReader, BundleStateWithReceipts, StateProofProvider,
+        StateRootProvider,
     };
     use reth_trie::updates::TrieUpdates;
     use revm::{Database, TransitionState};
@@ -617,6 +621,16 @@ mod tests {
         }
     }
 
+    impl StateProofProvider for StateProviderTest {
+        fn multiproof(
+            &self,
+            _bundle_state: &BundleStateWithReceipts,
+            _targets: &HashMap<Address, Vec<B256>>,
+        ) -> ProviderResult<MultiProof> {
+            unimplemented!("proof generation is not supported")
+        }
+    }
+
     impl StateProvider for StateProviderTest {
         fn storage(
             &self,
@@ -39,7 +39,7 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
-        bundle::{EthBundleApiServer, EthCallBundleApiServer},
+        bundle::{EthBundleApiServer, EthCallBundleApiServer, EthSendBundleApiServer},
         debug::DebugApiServer,
         engine::{EngineApiServer, EngineEthApiServer},
         eth::EthApiServer,
@@ -66,7 +66,7 @@ pub use clients::*;
 pub mod clients {
     pub use crate::{
         admin::AdminApiClient,
-        bundle::{EthBundleApiClient, EthCallBundleApiClient},
+        bundle::{EthBundleApiClient, EthCallBundleApiClient, EthSendBundleApiClient},
         debug::DebugApiClient,
         engine::{EngineApiClient, EngineEthApiClient},
         eth::EthApiClient,
@@ -74,6 +74,7 @@ pub mod clients {
         mev::MevApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
+        reth::RethApiClient,
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
@@ -1,5 +1,6 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, U256};
+use reth_primitives::{Address, BlobTransactionSidecar, BlockId, B256, U256};
+use reth_rpc_types::{engine::PayloadId, PayloadBuildTrace};
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -12,4 +13,21 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns the build trace of the payload job with the given id, if it is still known
+    #[method(name = "getPayloadBuildTrace")]
+    async fn reth_get_payload_build_trace(
+        &self,
+        payload_id: PayloadId,
+    ) -> RpcResult<Option<PayloadBuildTrace>>;
+
+    /// Returns the blob sidecar of the given blob transaction, if it is still in the blob store.
+    ///
+    /// This includes the sidecars of included transactions that are retained for a number of
+    /// blocks.
+    #[method(name = "getBlobSidecar")]
+    async fn reth_get_blob_sidecar(
+        &self,
+        tx_hash: B256,
+    ) -> RpcResult<Option<BlobTransactionSidecar>>;
 }
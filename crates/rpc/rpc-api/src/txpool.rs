@@ -1,6 +1,8 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::Address;
-use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
+use reth_rpc_types::txpool::{
+    TxpoolContent, TxpoolContentFrom, TxpoolDiff, TxpoolInspect, TxpoolSnapshot, TxpoolStatus,
+};
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +36,20 @@ pub trait TxPoolApi {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent>;
+
+    /// Returns the details of all transactions currently in the pool, together with the sequence
+    /// number of the last change of the pool they reflect.
+    ///
+    /// This is intended to (re)sync the content of the pool before applying the changes of
+    /// `txpool_subscribeDiff`.
+    #[method(name = "snapshot")]
+    async fn txpool_snapshot(&self) -> RpcResult<TxpoolSnapshot>;
+
+    /// Creates a subscription that yields all changes of the content of the pool, in order.
+    #[subscription(
+        name = "subscribeDiff" => "diff",
+        unsubscribe = "unsubscribeDiff",
+        item = TxpoolDiff
+    )]
+    async fn txpool_subscribe_diff(&self) -> jsonrpsee::core::SubscriptionResult;
 }
@@ -48,6 +48,10 @@ pub struct EthConfig {
     pub stale_filter_ttl: std::time::Duration,
     /// Settings for the fee history cache
     pub fee_history_cache: FeeHistoryCacheConfig,
+    /// Whether the node only syncs headers.
+    ///
+    /// If enabled, state queries are rejected and blocks are returned without transactions.
+    pub headers_only: bool,
 }
 
 impl EthConfig {
@@ -74,6 +78,7 @@ impl Default for EthConfig {
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
+            headers_only: false,
         }
     }
 }
@@ -114,4 +119,9 @@ impl EthConfig {
         self.rpc_gas_cap = rpc_gas_cap;
         self
     }
+    /// Configures whether the node only syncs headers
+    pub fn headers_only(mut self, headers_only: bool) -> Self {
+        self.headers_only = headers_only;
+        self
+    }
 }
@@ -161,6 +161,7 @@ use error::{RpcError, ServerKind};
 use reth_ipc::server::IpcServer;
 pub use reth_ipc::server::{Builder as IpcServerBuilder, Endpoint};
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
+use reth_payload_builder::PayloadStore;
 use reth_provider::{
     AccountReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
@@ -174,7 +175,7 @@ use reth_rpc::{
     },
     AdminApi, AuthLayer, BlockingTaskGuard, BlockingTaskPool, Claims, DebugApi, EngineEthApi,
     EthApi, EthFilter, EthPubSub, EthSubscriptionIdProvider, JwtAuthValidator, JwtSecret, NetApi,
-    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, Web3Api,
+    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, ValidationApi, Web3Api,
 };
 use reth_rpc_api::{servers::*, EngineApiServer};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
@@ -253,6 +254,8 @@ pub struct RpcModuleBuilder<Provider, Pool, Network, Tasks, Events> {
     executor: Tasks,
     /// Provides access to chain events, such as new blocks, required by pubsub.
     events: Events,
+    /// The payload builder, used to retrieve payload build traces.
+    payload_store: Option<PayloadStore>,
 }
 
 // === impl RpcBuilder ===
@@ -268,7 +271,7 @@ impl<Provider, Pool, Network, Tasks, Events>
         executor: Tasks,
         events: Events,
     ) -> Self {
-        Self { provider, pool, network, executor, events }
+        Self { provider, pool, network, executor, events, payload_store: None }
     }
 
     /// Configure the provider instance.
@@ -276,8 +279,8 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         P: BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     {
-        let Self { pool, network, executor, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { pool, network, executor, events, payload_store, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, payload_store }
     }
 
     /// Configure the transaction pool instance.
@@ -285,8 +288,8 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         P: TransactionPool + 'static,
     {
-        let Self { provider, network, executor, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { provider, network, executor, events, payload_store, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, payload_store }
     }
 
     /// Configure a [NoopTransactionPool] instance.
@@ -297,13 +300,14 @@ impl<Provider, Pool, Network, Tasks, Events>
     pub fn with_noop_pool(
         self,
     ) -> RpcModuleBuilder<Provider, NoopTransactionPool, Network, Tasks, Events> {
-        let Self { provider, executor, events, network, .. } = self;
+        let Self { provider, executor, events, network, payload_store, .. } = self;
         RpcModuleBuilder {
             provider,
             executor,
             events,
             network,
             pool: NoopTransactionPool::default(),
+            payload_store,
         }
     }
 
@@ -312,8 +316,8 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         N: NetworkInfo + Peers + 'static,
     {
-        let Self { provider, pool, executor, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { provider, pool, executor, events, payload_store, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, payload_store }
     }
 
     /// Configure a [NoopNetwork] instance.
@@ -322,8 +326,15 @@ impl<Provider, Pool, Network, Tasks, Events>
     /// This is only intended for allow easier setup of namespaces that depend on the [EthApi] which
     /// requires a [NetworkInfo] implementation.
     pub fn with_noop_network(self) -> RpcModuleBuilder<Provider, Pool, NoopNetwork, Tasks, Events> {
-        let Self { provider, pool, executor, events, .. } = self;
-        RpcModuleBuilder { provider, pool, executor, events, network: NoopNetwork::default() }
+        let Self { provider, pool, executor, events, payload_store, .. } = self;
+        RpcModuleBuilder {
+            provider,
+            pool,
+            executor,
+            events,
+            network: NoopNetwork::default(),
+            payload_store,
+        }
     }
 
     /// Configure the task executor to use for additional tasks.
@@ -334,8 +345,8 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         T: TaskSpawner + 'static,
     {
-        let Self { pool, network, provider, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { pool, network, provider, events, payload_store, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, payload_store }
     }
 
     /// Configure [TokioTaskExecutor] as the task executor to use for additional tasks.
@@ -345,8 +356,21 @@ impl<Provider, Pool, Network, Tasks, Events>
     pub fn with_tokio_executor(
         self,
     ) -> RpcModuleBuilder<Provider, Pool, Network, TokioTaskExecutor, Events> {
-        let Self { pool, network, provider, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, events, executor: TokioTaskExecutor::default() }
+        let Self { pool, network, provider, events, payload_store, .. } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            events,
+            executor: TokioTaskExecutor::default(),
+            payload_store,
+        }
+    }
+
+    /// Configure the [PayloadStore] that is used to retrieve payload build traces.
+    pub fn with_payload_store(mut self, payload_store: PayloadStore) -> Self {
+        self.payload_store = Some(payload_store);
+        self
     }
 
     /// Configure the event subscriber instance
@@ -354,8 +378,8 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         E: CanonStateSubscriptions + 'static,
     {
-        let Self { provider, pool, executor, network, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { provider, pool, executor, network, payload_store, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, payload_store }
     }
 }
 
@@ -395,7 +419,7 @@ where
     {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, events } = self;
+        let Self { provider, pool, network, executor, events, payload_store } = self;
 
         let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
 
@@ -407,6 +431,7 @@ where
             events,
             config.unwrap_or_default(),
         );
+        registry.payload_store = payload_store;
 
         modules.config = module_config;
         modules.http = registry.maybe_module(http.as_ref());
@@ -445,8 +470,11 @@ where
         self,
         config: RpcModuleConfig,
     ) -> RethModuleRegistry<Provider, Pool, Network, Tasks, Events> {
-        let Self { provider, pool, network, executor, events } = self;
-        RethModuleRegistry::new(provider, pool, network, executor, events, config)
+        let Self { provider, pool, network, executor, events, payload_store } = self;
+        let mut registry =
+            RethModuleRegistry::new(provider, pool, network, executor, events, config);
+        registry.payload_store = payload_store;
+        registry
     }
 
     /// Configures all [RpcModule]s specific to the given [TransportRpcModuleConfig] which can be
@@ -456,7 +484,7 @@ where
     pub fn build(self, module_config: TransportRpcModuleConfig) -> TransportRpcModules<()> {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, events } = self;
+        let Self { provider, pool, network, executor, events, payload_store } = self;
 
         if !module_config.is_empty() {
             let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
@@ -469,6 +497,7 @@ where
                 events,
                 config.unwrap_or_default(),
             );
+            registry.payload_store = payload_store;
 
             modules.config = module_config;
             modules.http = registry.maybe_module(http.as_ref());
@@ -768,6 +797,13 @@ pub enum RethRpcModule {
     /// This is separate from [RethRpcModule::Eth] because it is a non standardized call that
     /// should be opt-in.
     EthCallBundle,
+    /// For the non-standard `eth_` namespace calls `eth_sendBundle` and `eth_cancelBundle`
+    ///
+    /// Bundles are added to the bundle pool of the transaction pool and are included by the
+    /// payload builder.
+    EthBundle,
+    /// `flashbots_` module for validating block submissions of builders
+    Flashbots,
 }
 
 // === impl RethRpcModule ===
@@ -795,6 +831,8 @@ impl FromStr for RethRpcModule {
             "reth" => RethRpcModule::Reth,
             "ots" => RethRpcModule::Ots,
             "eth-call-bundle" | "eth_callBundle" => RethRpcModule::EthCallBundle,
+            "eth-bundle" | "eth_sendBundle" => RethRpcModule::EthBundle,
+            "flashbots" => RethRpcModule::Flashbots,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -838,6 +876,8 @@ pub struct RethModuleRegistry<Provider, Pool, Network, Tasks, Events> {
     blocking_pool_guard: BlockingTaskGuard,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
+    /// The payload builder, used to retrieve payload build traces.
+    payload_store: Option<PayloadStore>,
 }
 
 // === impl RethModuleRegistry ===
@@ -864,6 +904,7 @@ impl<Provider, Pool, Network, Tasks, Events>
             blocking_pool_guard: BlockingTaskGuard::new(config.eth.max_tracing_requests),
             config,
             events,
+            payload_store: None,
         }
     }
 
@@ -1040,6 +1081,13 @@ where
         self
     }
 
+    /// Register Flashbots namespace
+    pub fn register_flashbots(&mut self) -> &mut Self {
+        let validation_api = self.validation_api();
+        self.modules.insert(RethRpcModule::Flashbots, validation_api.into_rpc().into());
+        self
+    }
+
     /// Helper function to create a [RpcModule] if it's not `None`
     fn maybe_module(&mut self, config: Option<&RpcModuleSelection>) -> Option<RpcModule<()>> {
         let config = config?;
@@ -1117,9 +1165,12 @@ where
                         .into_rpc()
                         .into(),
                         RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
-                        RethRpcModule::Txpool => {
-                            TxPoolApi::new(self.pool.clone()).into_rpc().into()
-                        }
+                        RethRpcModule::Txpool => TxPoolApi::with_spawner(
+                            self.pool.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::Rpc => RPCApi::new(
                             namespaces
                                 .iter()
@@ -1130,15 +1181,40 @@ where
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
                         RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
-                                .into_rpc()
-                                .into()
+                            let reth_api = RethApi::new(
+                                self.provider.clone(),
+                                Box::new(self.executor.clone()),
+                            )
+                            .with_pool(self.pool.clone());
+                            match self.payload_store.clone() {
+                                Some(payload_store) => reth_api.with_payload_store(payload_store),
+                                None => reth_api,
+                            }
+                            .into_rpc()
+                            .into()
                         }
                         RethRpcModule::EthCallBundle => {
-                            EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
-                                .into_rpc()
-                                .into()
+                            EthCallBundleApiServer::into_rpc(EthBundle::new(
+                                eth_api.clone(),
+                                self.pool.bundle_pool().cloned(),
+                                self.blocking_pool_guard.clone(),
+                            ))
+                            .into()
                         }
+                        RethRpcModule::EthBundle => {
+                            EthSendBundleApiServer::into_rpc(EthBundle::new(
+                                eth_api.clone(),
+                                self.pool.bundle_pool().cloned(),
+                                self.blocking_pool_guard.clone(),
+                            ))
+                            .into()
+                        }
+                        RethRpcModule::Flashbots => ValidationApi::new(
+                            self.provider.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                     })
                     .clone()
             })
@@ -1209,6 +1285,7 @@ where
                 executor.clone(),
                 blocking_task_pool.clone(),
                 fee_history_cache,
+                self.config.eth.headers_only,
             );
             let filter = EthFilter::new(
                 self.provider.clone(),
@@ -1269,7 +1346,7 @@ where
     /// If called outside of the tokio runtime. See also [Self::eth_api]
     pub fn bundle_api(&mut self) -> EthBundle<EthApi<Provider, Pool, Network>> {
         let eth_api = self.eth_api();
-        EthBundle::new(eth_api, self.blocking_pool_guard.clone())
+        EthBundle::new(eth_api, self.pool.bundle_pool().cloned(), self.blocking_pool_guard.clone())
     }
 
     /// Instantiates OtterscanApi
@@ -1309,7 +1386,17 @@ where
 
     /// Instantiates RethApi
     pub fn reth_api(&mut self) -> RethApi<Provider> {
-        RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+        let reth_api = RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+            .with_pool(self.pool.clone());
+        match self.payload_store.clone() {
+            Some(payload_store) => reth_api.with_payload_store(payload_store),
+            None => reth_api,
+        }
+    }
+
+    /// Instantiates ValidationApi
+    pub fn validation_api(&mut self) -> ValidationApi<Provider> {
+        ValidationApi::new(self.provider.clone(), Box::new(self.executor.clone()))
     }
 }
 
@@ -2195,6 +2282,21 @@ mod tests {
         assert_eq!(selection, RethRpcModule::EthCallBundle);
     }
 
+    #[test]
+    fn parse_eth_bundle() {
+        let selection = "eth-bundle".parse::<RethRpcModule>().unwrap();
+        assert_eq!(selection, RethRpcModule::EthBundle);
+        let selection = "eth_sendBundle".parse::<RethRpcModule>().unwrap();
+        assert_eq!(selection, RethRpcModule::EthBundle);
+    }
+
+    #[test]
+    fn parse_flashbots() {
+        let selection = "flashbots".parse::<RethRpcModule>().unwrap();
+        assert_eq!(selection, RethRpcModule::Flashbots);
+        assert_eq!(selection.to_string(), "flashbots");
+    }
+
     #[test]
     fn parse_eth_call_bundle_selection() {
         let selection = "eth,admin,debug,eth-call-bundle".parse::<RpcModuleSelection>().unwrap();
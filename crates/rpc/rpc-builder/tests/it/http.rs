@@ -1,5 +1,5 @@
 //! Standalone http tests
-use crate::utils::{launch_http, launch_http_ws, launch_ws};
+use crate::utils::{launch_http, launch_http_ws, launch_ws, test_address, test_rpc_builder};
 use jsonrpsee::{
     core::{
         client::{ClientT, SubscriptionClientT},
@@ -7,19 +7,25 @@ use jsonrpsee::{
     },
     types::error::ErrorCode,
 };
+use reth_payload_builder::{
+    error::PayloadBuilderError,
+    test_utils::{TestPayloadJob, TestPayloadJobGenerator},
+    PayloadBuilderAttributes, PayloadBuilderService, PayloadId, PayloadJobGenerator,
+};
 use reth_primitives::{
     hex_literal::hex, Address, BlockId, BlockNumberOrTag, Bytes, NodeRecord, TxHash, B256, B64,
     U256,
 };
 use reth_rpc_api::{
-    clients::{AdminApiClient, EthApiClient},
+    clients::{AdminApiClient, EthApiClient, RethApiClient},
     DebugApiClient, EthFilterApiClient, NetApiClient, OtterscanClient, TraceApiClient,
     Web3ApiClient,
 };
-use reth_rpc_builder::RethRpcModule;
+use reth_rpc_builder::{RethRpcModule, RpcServerConfig, TransportRpcModuleConfig};
 use reth_rpc_types::{
-    trace::filter::TraceFilter, CallRequest, Filter, Index, PendingTransactionFilterKind,
-    TransactionRequest,
+    trace::filter::TraceFilter, CallRequest, Filter, Index, PayloadBuildIteration,
+    PayloadBuildOutcome, PayloadBuildTrace, PayloadSkipReason, PayloadTransactionStatus,
+    PayloadTransactionTrace, PendingTransactionFilterKind, TransactionRequest,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -507,6 +513,80 @@ async fn test_call_otterscan_functions_http_and_ws() {
     let client = handle.http_client().unwrap();
     test_basic_otterscan_calls(&client).await;
 }
+/// A [PayloadJobGenerator] that serves the build trace of a single payload job.
+#[derive(Debug)]
+struct TracedPayloadJobGenerator {
+    id: PayloadId,
+    trace: PayloadBuildTrace,
+}
+
+impl PayloadJobGenerator for TracedPayloadJobGenerator {
+    type Job = TestPayloadJob;
+
+    fn new_payload_job(
+        &self,
+        attr: PayloadBuilderAttributes,
+    ) -> Result<Self::Job, PayloadBuilderError> {
+        TestPayloadJobGenerator::default().new_payload_job(attr)
+    }
+
+    fn build_trace(&self, id: PayloadId) -> Option<PayloadBuildTrace> {
+        (id == self.id).then(|| self.trace.clone())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_reth_payload_build_trace_http() {
+    reth_tracing::init_test_tracing();
+
+    let id = PayloadId::new([1; 8]);
+    let trace = PayloadBuildTrace {
+        iterations: vec![PayloadBuildIteration {
+            outcome: PayloadBuildOutcome::Better,
+            duration_ms: 12,
+            gas_used: 21_000,
+            fees: U256::from(21_000),
+            transactions: vec![
+                PayloadTransactionTrace {
+                    hash: TxHash::with_last_byte(1),
+                    status: PayloadTransactionStatus::Included {
+                        gas_used: 21_000,
+                        fees: U256::from(21_000),
+                    },
+                },
+                PayloadTransactionTrace {
+                    hash: TxHash::with_last_byte(2),
+                    status: PayloadTransactionStatus::Skipped {
+                        reason: PayloadSkipReason::Invalid,
+                        error: Some("nonce too low".to_string()),
+                    },
+                },
+            ],
+        }],
+    };
+    let (service, handle) =
+        PayloadBuilderService::new(TracedPayloadJobGenerator { id, trace: trace.clone() });
+    tokio::spawn(service);
+
+    let server = test_rpc_builder()
+        .with_payload_store(handle.into())
+        .build(TransportRpcModuleConfig::set_http(vec![RethRpcModule::Reth]));
+    let handle = server
+        .start_server(RpcServerConfig::http(Default::default()).with_http_address(test_address()))
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+
+    assert_eq!(
+        RethApiClient::reth_get_payload_build_trace(&client, id).await.unwrap(),
+        Some(trace)
+    );
+    assert_eq!(
+        RethApiClient::reth_get_payload_build_trace(&client, PayloadId::new([2; 8])).await.unwrap(),
+        None
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
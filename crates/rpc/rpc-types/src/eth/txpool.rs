@@ -1,7 +1,7 @@
 //! Types for the `txpool` namespace: <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-txpool>
 
 use crate::Transaction;
-use alloy_primitives::{Address, U256, U64};
+use alloy_primitives::{Address, B256, U256, U64};
 use serde::{
     de::{self, Deserializer, Visitor},
     Deserialize, Serialize,
@@ -176,6 +176,104 @@ pub struct TxpoolStatus {
     pub queued: U64,
 }
 
+/// Transaction Pool Snapshot
+///
+/// Same as [TxpoolContent] with the transactions of every [TxpoolSubpool] listed separately,
+/// together with the sequence number of the last change of the pool that is reflected in it. All
+/// [TxpoolDiff]s with a higher sequence number must be applied to the snapshot to get the current
+/// content of the pool.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolSnapshot {
+    /// sequence number of the last change reflected in the snapshot
+    pub sequence: U64,
+    /// pending tx
+    pub pending: BTreeMap<Address, BTreeMap<String, Transaction>>,
+    /// tx that don't pay the base fee
+    pub base_fee: BTreeMap<Address, BTreeMap<String, Transaction>>,
+    /// blob tx that don't pay the base fee or blob fee
+    pub blob: BTreeMap<Address, BTreeMap<String, Transaction>>,
+    /// queued tx
+    pub queued: BTreeMap<Address, BTreeMap<String, Transaction>>,
+}
+
+/// Transaction Pool Diff
+///
+/// A change of the content of the pool, as emitted by the `txpool_subscribeDiff` subscription.
+///
+/// The sequence number is incremented by one for every change, a gap means changes were dropped
+/// and the content must be resynced from a [TxpoolSnapshot].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxpoolDiff {
+    /// sequence number of the change
+    pub sequence: U64,
+    /// the change
+    #[serde(flatten)]
+    pub kind: TxpoolDiffKind,
+}
+
+/// The kind of a [TxpoolDiff].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TxpoolDiffKind {
+    /// Transaction has been added to the pool.
+    #[serde(rename_all = "camelCase")]
+    Added {
+        /// the added transaction
+        transaction: Box<Transaction>,
+        /// the sub-pool the transaction was added to
+        subpool: TxpoolSubpool,
+    },
+    /// Transaction has been replaced by another transaction with the same sender and nonce.
+    #[serde(rename_all = "camelCase")]
+    Replaced {
+        /// hash of the replaced transaction
+        hash: B256,
+        /// hash of the transaction that replaced it
+        replaced_by: B256,
+    },
+    /// Transaction has been moved to the pending sub-pool.
+    Promoted {
+        /// hash of the transaction
+        hash: B256,
+    },
+    /// Transaction has been moved to a parked sub-pool, from the pending sub-pool or another
+    /// parked sub-pool.
+    Demoted {
+        /// hash of the transaction
+        hash: B256,
+        /// the sub-pool the transaction was moved to
+        subpool: TxpoolSubpool,
+    },
+    /// Transaction was removed from the pool.
+    Discarded {
+        /// hash of the transaction
+        hash: B256,
+    },
+    /// Transaction has been included in a block.
+    #[serde(rename_all = "camelCase")]
+    Mined {
+        /// hash of the transaction
+        hash: B256,
+        /// hash of the block that includes the transaction
+        block_hash: B256,
+    },
+}
+
+/// A sub-pool of the transaction pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolSubpool {
+    /// Transactions that are ready for inclusion in the next block.
+    Pending,
+    /// Transactions that don't pay the base fee of the next block.
+    BaseFee,
+    /// Blob transactions that don't pay the base fee or blob fee of the next block.
+    Blob,
+    /// Transactions that have a nonce gap or can't be paid for by their sender.
+    Queued,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -517,4 +615,26 @@ mod tests {
 
         TxpoolInspect { pending: pending_map, queued: queued_map }
     }
+
+    #[test]
+    fn serde_txpool_diff() {
+        let diff = TxpoolDiff {
+            sequence: U64::from(7),
+            kind: TxpoolDiffKind::Demoted {
+                hash: B256::with_last_byte(1),
+                subpool: TxpoolSubpool::BaseFee,
+            },
+        };
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "sequence": "0x7",
+                "type": "demoted",
+                "hash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "subpool": "baseFee"
+            })
+        );
+        assert_eq!(serde_json::from_value::<TxpoolDiff>(json).unwrap(), diff);
+    }
 }
@@ -0,0 +1,134 @@
+//! Types for the `reth_` namespace.
+
+use alloy_primitives::{TxHash, U256};
+use serde::{Deserialize, Serialize};
+
+/// The build trace of a payload job, returned by `reth_getPayloadBuildTrace`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadBuildTrace {
+    /// All build attempts of the payload job, in order.
+    pub iterations: Vec<PayloadBuildIteration>,
+}
+
+/// A single build attempt of a payload job.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadBuildIteration {
+    /// The outcome of the attempt.
+    pub outcome: PayloadBuildOutcome,
+    /// How long the attempt took, in milliseconds.
+    pub duration_ms: u64,
+    /// The gas used by all included transactions.
+    pub gas_used: u64,
+    /// The total fees of the built payload.
+    pub fees: U256,
+    /// All transactions that were considered, in order.
+    pub transactions: Vec<PayloadTransactionTrace>,
+}
+
+/// The outcome of a payload build attempt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PayloadBuildOutcome {
+    /// The built payload is better than the previous best payload.
+    Better,
+    /// The built payload is not better than the previous best payload and was discarded.
+    Aborted,
+    /// The attempt was cancelled.
+    Cancelled,
+    /// The attempt failed.
+    Failed,
+}
+
+/// What happened to a transaction while building a payload.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadTransactionTrace {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// Whether the transaction was included or skipped.
+    #[serde(flatten)]
+    pub status: PayloadTransactionStatus,
+}
+
+/// Whether a transaction was included in the payload.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum PayloadTransactionStatus {
+    /// The transaction was included.
+    #[serde(rename_all = "camelCase")]
+    Included {
+        /// The gas used by the transaction.
+        gas_used: u64,
+        /// The fees paid to the fee recipient by the transaction.
+        fees: U256,
+    },
+    /// The transaction was skipped.
+    #[serde(rename_all = "camelCase")]
+    Skipped {
+        /// Why the transaction was skipped.
+        reason: PayloadSkipReason,
+        /// Additional details about the error, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+/// Reasons why a transaction was not included in the payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PayloadSkipReason {
+    /// The nonce of the transaction is lower than the sender's nonce.
+    NonceTooLow,
+    /// The nonce of the transaction is higher than the sender's nonce.
+    NonceGap,
+    /// Not enough gas left in the block.
+    InsufficientGas,
+    /// Not enough blob gas left in the block.
+    InsufficientBlobGas,
+    /// The transaction was excluded by the payload building strategy.
+    Excluded,
+    /// The transaction is invalid.
+    Invalid,
+    /// The bundle of the transaction was rejected.
+    BundleRejected,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_payload_build_trace() {
+        let trace = PayloadBuildTrace {
+            iterations: vec![PayloadBuildIteration {
+                outcome: PayloadBuildOutcome::Better,
+                duration_ms: 12,
+                gas_used: 21_000,
+                fees: U256::from(21_000),
+                transactions: vec![
+                    PayloadTransactionTrace {
+                        hash: TxHash::with_last_byte(1),
+                        status: PayloadTransactionStatus::Included {
+                            gas_used: 21_000,
+                            fees: U256::from(21_000),
+                        },
+                    },
+                    PayloadTransactionTrace {
+                        hash: TxHash::with_last_byte(2),
+                        status: PayloadTransactionStatus::Skipped {
+                            reason: PayloadSkipReason::NonceGap,
+                            error: None,
+                        },
+                    },
+                ],
+            }],
+        };
+        let s = serde_json::to_string(&trace).unwrap();
+        assert!(s.contains(r#""status":"skipped","reason":"nonceGap""#));
+        assert!(s.contains(r#""status":"included","gasUsed":21000"#));
+        let decoded: PayloadBuildTrace = serde_json::from_str(&s).unwrap();
+        assert_eq!(decoded, trace);
+    }
+}
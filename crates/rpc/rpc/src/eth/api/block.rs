@@ -10,7 +10,7 @@ use crate::{
     EthApi,
 };
 use reth_network_api::NetworkInfo;
-use reth_primitives::{BlockId, TransactionMeta};
+use reth_primitives::{BlockId, SealedBlock, SealedBlockWithSenders, TransactionMeta};
 
 use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
 use reth_rpc_types::{Index, RichBlock, TransactionReceipt};
@@ -190,8 +190,19 @@ where
         block_id: impl Into<BlockId>,
         full: bool,
     ) -> EthResult<Option<RichBlock>> {
+        let block_id = block_id.into();
         let block = match self.block_with_senders(block_id).await? {
             Some(block) => block,
+            None if self.is_headers_only() => {
+                // there are no block bodies, serve the block without transactions
+                let Some(header) = self.provider().sealed_header_by_id(block_id)? else {
+                    return Ok(None)
+                };
+                SealedBlockWithSenders {
+                    block: SealedBlock { header, ..Default::default() },
+                    senders: Vec::new(),
+                }
+            }
             None => return Ok(None),
         };
         let block_hash = block.hash;
@@ -113,10 +113,14 @@ where
             Box::<TokioTaskExecutor>::default(),
             blocking_task_pool,
             fee_history_cache,
+            false,
         )
     }
 
     /// Creates a new, shareable instance.
+    ///
+    /// If `headers_only` is set, the node is expected to only sync headers: state queries are
+    /// rejected and blocks are returned without transactions.
     #[allow(clippy::too_many_arguments)]
     pub fn with_spawner(
         provider: Provider,
@@ -128,6 +132,7 @@ where
         task_spawner: Box<dyn TaskSpawner>,
         blocking_task_pool: BlockingTaskPool,
         fee_history_cache: FeeHistoryCache,
+        headers_only: bool,
     ) -> Self {
         // get the block number of the latest block
         let latest_block = provider
@@ -150,6 +155,7 @@ where
             pending_block: Default::default(),
             blocking_task_pool,
             fee_history_cache,
+            headers_only,
             #[cfg(feature = "optimism")]
             http_client: reqwest::Client::new(),
         };
@@ -211,6 +217,21 @@ where
     pub fn fee_history_cache(&self) -> &FeeHistoryCache {
         &self.inner.fee_history_cache
     }
+
+    /// Returns true if the node only syncs headers and has no state or block bodies.
+    pub fn is_headers_only(&self) -> bool {
+        self.inner.headers_only
+    }
+
+    /// Returns an error if the node only syncs headers and therefore has no state.
+    fn ensure_state_available(&self) -> EthResult<()> {
+        if self.is_headers_only() {
+            return Err(EthApiError::Unsupported(
+                "state is not available, the node only syncs headers",
+            ))
+        }
+        Ok(())
+    }
 }
 
 // === State access helpers ===
@@ -224,6 +245,7 @@ where
     ///
     /// Note: if not [BlockNumberOrTag::Pending] then this will only return canonical state. See also <https://github.com/paradigmxyz/reth/issues/4515>
     pub fn state_at_block_id(&self, at: BlockId) -> EthResult<StateProviderBox> {
+        self.ensure_state_available()?;
         Ok(self.provider().state_by_block_id(at)?)
     }
 
@@ -237,17 +259,19 @@ where
         if let Some(block_id) = block_id {
             self.state_at_block_id(block_id)
         } else {
-            Ok(self.latest_state()?)
+            self.latest_state()
         }
     }
 
     /// Returns the state at the given block number
-    pub fn state_at_hash(&self, block_hash: B256) -> RethResult<StateProviderBox> {
+    pub fn state_at_hash(&self, block_hash: B256) -> EthResult<StateProviderBox> {
+        self.ensure_state_available()?;
         Ok(self.provider().history_by_block_hash(block_hash)?)
     }
 
     /// Returns the _latest_ state
-    pub fn latest_state(&self) -> RethResult<StateProviderBox> {
+    pub fn latest_state(&self) -> EthResult<StateProviderBox> {
+        self.ensure_state_available()?;
         Ok(self.provider().latest()?)
     }
 }
@@ -300,6 +324,11 @@ where
 
     /// Returns the locally built pending block
     pub(crate) async fn local_pending_block(&self) -> EthResult<Option<SealedBlockWithSenders>> {
+        // without state we're unable to build a pending block
+        if self.is_headers_only() {
+            return Ok(None)
+        }
+
         let pending = self.pending_block_env_and_cfg()?;
         if pending.origin.is_actual_pending() {
             return Ok(pending.origin.into_actual_pending())
@@ -468,6 +497,8 @@ struct EthApiInner<Provider, Pool, Network> {
     blocking_task_pool: BlockingTaskPool,
     /// Cache for block fees history
     fee_history_cache: FeeHistoryCache,
+    /// Whether the node only syncs headers.
+    headers_only: bool,
     /// An http client for communicating with sequencers.
     #[cfg(feature = "optimism")]
     http_client: reqwest::Client,
@@ -95,15 +95,15 @@ where
         // if we are trying to create a proof for the latest block, but have a BlockId as input
         // that is not BlockNumberOrTag::Latest, then we need to figure out whether or not the
         // BlockId corresponds to the latest block
-        let is_latest_block = match block_id {
+        let is_latest_or_pending_block = match block_id {
             BlockId::Number(BlockNumberOrTag::Number(num)) => num == chain_info.best_number,
             BlockId::Hash(hash) => hash == chain_info.best_hash.into(),
-            BlockId::Number(BlockNumberOrTag::Latest) => true,
+            BlockId::Number(BlockNumberOrTag::Latest | BlockNumberOrTag::Pending) => true,
             _ => false,
         };
 
-        // TODO: remove when HistoricalStateProviderRef::proof is implemented
-        if !is_latest_block {
+        // historical proofs need to revert all changesets since the block, which is unbounded
+        if !is_latest_or_pending_block {
             return Err(EthApiError::InvalidBlockRange)
         }
 
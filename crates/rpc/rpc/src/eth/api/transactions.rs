@@ -2,7 +2,7 @@
 use crate::{
     eth::{
         api::pending_block::PendingBlockEnv,
-        error::{EthApiError, EthResult, SignError},
+        error::{EthApiError, EthResult, RpcPoolError, SignError},
         revm_utils::{
             inspect, inspect_and_return_db, prepare_call_env, replay_transactions_until, transact,
             EvmOverrides,
@@ -18,7 +18,8 @@ use reth_primitives::{
     revm::env::{fill_block_env_with_coinbase, tx_env_with_recovered},
     revm_primitives::{db::DatabaseCommit, Env, ExecutionResult, ResultAndState, SpecId, State},
     Address, BlockId, BlockNumberOrTag, Bytes, FromRecoveredPooledTransaction, Header,
-    IntoRecoveredTransaction, Receipt, SealedBlock, SealedBlockWithSenders,
+    IntoRecoveredTransaction, KnownAccountState, Receipt, SealedBlock, SealedBlockWithSenders,
+    TransactionConditional,
     TransactionKind::{Call, Create},
     TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, B256, U128, U256, U64,
 };
@@ -148,6 +149,17 @@ pub trait EthTransactions: Send + Sync {
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> EthResult<B256>;
 
+    /// Decodes and recovers the transaction and submits it to the pool if the given conditions are
+    /// met on the latest state.
+    ///
+    /// The pool only includes the transaction in a block while the conditions hold. Returns the
+    /// hash of the transaction.
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        conditional: TransactionConditional,
+    ) -> EthResult<B256>;
+
     /// Signs transaction with a matching signer, if any and submits the transaction to the pool.
     /// Returns the hash of the signed transaction.
     async fn send_transaction(&self, request: TransactionRequest) -> EthResult<B256>;
@@ -510,6 +522,36 @@ where
         Ok(hash)
     }
 
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        conditional: TransactionConditional,
+    ) -> EthResult<B256> {
+        let recovered = recover_raw_transaction(tx)?;
+
+        // the pool only rechecks the conditions on new blocks, so they must be met on the latest
+        // state already
+        let conditions = conditional.clone();
+        if !self.spawn_tracing_task_with(move |this| this.is_conditional_met(&conditions)).await? {
+            return Err(EthApiError::PoolError(RpcPoolError::ConditionsNotMet))
+        }
+
+        let pool_transaction = <Pool::Transaction>::from_recovered_pooled_transaction(recovered);
+
+        // submit the transaction to the pool with a `Private` origin, since other nodes would
+        // include it regardless of its conditions
+        let hash = self
+            .pool()
+            .add_transaction_with_conditional(
+                TransactionOrigin::Private,
+                pool_transaction,
+                conditional,
+            )
+            .await?;
+
+        Ok(hash)
+    }
+
     async fn send_transaction(&self, mut request: TransactionRequest) -> EthResult<B256> {
         let from = match request.from {
             Some(from) => from,
@@ -892,6 +934,39 @@ where
         BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
     Network: NetworkInfo + 'static,
 {
+    /// Returns `true` if the conditions of a conditional transaction are met on the latest block
+    /// and its state.
+    fn is_conditional_met(&self, conditional: &TransactionConditional) -> EthResult<bool> {
+        let latest = self.provider().latest_header()?.ok_or(EthApiError::UnknownBlockNumber)?;
+        if !conditional.matches_block_attributes(latest.number, latest.timestamp) {
+            return Ok(false)
+        }
+        if conditional.known_accounts.is_empty() {
+            return Ok(true)
+        }
+
+        let state = self.state_at_block_id(BlockId::Hash(latest.hash.into()))?;
+        for (address, expected) in conditional.known_accounts.iter() {
+            match expected {
+                KnownAccountState::StorageRoot(root) => {
+                    if state.proof(*address, &[])?.storage_root != *root {
+                        return Ok(false)
+                    }
+                }
+                KnownAccountState::Slots(slots) => {
+                    for (slot, value) in slots.iter() {
+                        let current = state.storage(*address, *slot)?.unwrap_or_default();
+                        if B256::new(current.to_be_bytes()) != *value {
+                            return Ok(false)
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
     /// Helper function for `eth_getTransactionReceipt`
     ///
     /// Returns the receipt
@@ -13,16 +13,20 @@ use jsonrpsee::core::RpcResult;
 use reth_primitives::{
     keccak256,
     revm_primitives::db::{DatabaseCommit, DatabaseRef},
-    U256,
+    B256, U256,
 };
 use reth_revm::database::StateProviderDatabase;
-use reth_rpc_api::EthCallBundleApiServer;
-use reth_rpc_types::{EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult};
+use reth_rpc_api::{EthCallBundleApiServer, EthSendBundleApiServer};
+use reth_rpc_types::{
+    CancelBundleRequest, EthBundleHash, EthCallBundle, EthCallBundleResponse,
+    EthCallBundleTransactionResult, EthSendBundle,
+};
+use reth_transaction_pool::{BundlePool, PoolBundle};
 use revm::{
     db::CacheDB,
     primitives::{Env, ResultAndState, TxEnv},
 };
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc};
 
 /// `Eth` bundle implementation.
 pub struct EthBundle<Eth> {
@@ -32,8 +36,82 @@ pub struct EthBundle<Eth> {
 
 impl<Eth> EthBundle<Eth> {
     /// Create a new `EthBundle` instance.
-    pub fn new(eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        Self { inner: Arc::new(EthBundleInner { eth_api, blocking_task_guard }) }
+    ///
+    /// Bundles sent via `eth_sendBundle` are added to the given [BundlePool], if any.
+    pub fn new(
+        eth_api: Eth,
+        bundle_pool: Option<BundlePool>,
+        blocking_task_guard: BlockingTaskGuard,
+    ) -> Self {
+        Self { inner: Arc::new(EthBundleInner { eth_api, bundle_pool, blocking_task_guard }) }
+    }
+
+    /// Returns the [BundlePool] or an error if bundles are not supported.
+    fn bundle_pool(&self) -> EthResult<&BundlePool> {
+        self.inner
+            .bundle_pool
+            .as_ref()
+            .ok_or(EthApiError::Unsupported("bundles are not supported by the transaction pool"))
+    }
+
+    /// Adds the bundle to the bundle pool, so that it can be included in the block it targets.
+    ///
+    /// All transactions of the bundle are included atomically, in the given order.
+    pub fn send_bundle(&self, bundle: EthSendBundle) -> EthResult<EthBundleHash> {
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+        } = bundle;
+        let bundle_pool = self.bundle_pool()?;
+        if txs.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::EmptyBundleTransactions.to_string(),
+            ))
+        }
+        if block_number.to::<u64>() == 0 {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BundleMissingBlockNumber.to_string(),
+            ))
+        }
+
+        let mut transactions = Vec::with_capacity(txs.len());
+        for tx in txs {
+            let tx = recover_raw_transaction(tx)?.into_ecrecovered_transaction();
+            if tx.is_eip4844() {
+                return Err(EthApiError::InvalidParams(
+                    EthBundleError::BlobTransactionInBundle.to_string(),
+                ))
+            }
+            transactions.push(tx);
+        }
+
+        let bundle = PoolBundle::new(transactions, block_number.to())
+            .with_timestamps(min_timestamp, max_timestamp)
+            .with_reverting_tx_hashes(reverting_tx_hashes)
+            .with_replacement_uuid(replacement_uuid);
+        let bundle_hash = bundle_pool
+            .add_bundle(bundle)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+        Ok(EthBundleHash { bundle_hash })
+    }
+
+    /// Removes the bundle from the bundle pool.
+    ///
+    /// The bundle can be identified by its hash or by its replacement uuid.
+    pub fn cancel_bundle(&self, request: CancelBundleRequest) -> EthResult<()> {
+        let bundle_pool = self.bundle_pool()?;
+        let removed = match B256::from_str(&request.bundle_hash) {
+            Ok(hash) => bundle_pool.remove_bundle(hash),
+            Err(_) => bundle_pool.cancel_bundle(&request.bundle_hash),
+        };
+        if !removed {
+            return Err(EthApiError::InvalidParams(EthBundleError::UnknownBundle.to_string()))
+        }
+        Ok(())
     }
 }
 
@@ -187,11 +265,27 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<Eth> EthSendBundleApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        Ok(EthBundle::send_bundle(self, bundle)?)
+    }
+
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> RpcResult<()> {
+        Ok(EthBundle::cancel_bundle(self, request)?)
+    }
+}
+
 /// Container type for  `EthBundle` internals
 #[derive(Debug)]
 struct EthBundleInner<Eth> {
     /// Access to commonly used code of the `eth` namespace
     eth_api: Eth,
+    /// The pool bundles are added to
+    bundle_pool: Option<BundlePool>,
     // restrict the number of concurrent tracing calls.
     #[allow(unused)]
     blocking_task_guard: BlockingTaskGuard,
@@ -218,4 +312,10 @@ pub enum EthBundleError {
     /// Thrown if the bundle does not contain a block number, or block number is 0.
     #[error("bundle missing blockNumber")]
     BundleMissingBlockNumber,
+    /// Thrown if the bundle contains a blob transaction.
+    #[error("bundle contains blob transactions")]
+    BlobTransactionInBundle,
+    /// Thrown if the bundle to cancel is not in the pool.
+    #[error("unknown bundle")]
+    UnknownBundle,
 }
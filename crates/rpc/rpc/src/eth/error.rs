@@ -12,7 +12,7 @@ use reth_revm::tracing::js::JsInspectorError;
 use reth_rpc_types::{error::EthRpcErrorCode, BlockError, CallInputError};
 use reth_transaction_pool::error::{
     Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind,
-    PoolTransactionError,
+    PoolTransactionError, ValidationRuleError,
 };
 use revm::primitives::{EVMError, ExecutionResult, Halt, OutOfGasError};
 use std::time::Duration;
@@ -579,12 +579,18 @@ pub enum RpcPoolError {
     /// Eip-4844 related error
     #[error(transparent)]
     Eip4844(#[from] Eip4844PoolTransactionError),
+    /// Rejected by a validation rule of the pool
+    #[error(transparent)]
+    Rule(#[from] ValidationRuleError),
     /// Thrown if a conflicting transaction type is already in the pool
     ///
     /// In other words, thrown if a transaction with the same sender that violates the exclusivity
     /// constraint (blob vs normal tx)
     #[error("address already reserved")]
     AddressAlreadyReserved,
+    /// Thrown if the conditions of a conditional transaction are not met
+    #[error("transaction conditions not met")]
+    ConditionsNotMet,
     #[error(transparent)]
     Other(Box<dyn std::error::Error + Send + Sync>),
 }
@@ -611,6 +617,7 @@ impl From<PoolError> for RpcPoolError {
             PoolErrorKind::ExistingConflictingTransactionType(_, _) => {
                 RpcPoolError::AddressAlreadyReserved
             }
+            PoolErrorKind::ConditionsNotMet => RpcPoolError::ConditionsNotMet,
         }
     }
 }
@@ -630,6 +637,7 @@ impl From<InvalidPoolTransactionError> for RpcPoolError {
             InvalidPoolTransactionError::Underpriced => RpcPoolError::Underpriced,
             InvalidPoolTransactionError::Other(err) => RpcPoolError::PoolTransactionError(err),
             InvalidPoolTransactionError::Eip4844(err) => RpcPoolError::Eip4844(err),
+            InvalidPoolTransactionError::Rule(err) => RpcPoolError::Rule(err),
             InvalidPoolTransactionError::Overdraft => {
                 RpcPoolError::Invalid(RpcInvalidTransactionError::InsufficientFunds)
             }
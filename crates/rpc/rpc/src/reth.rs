@@ -1,11 +1,14 @@
-use crate::eth::error::{EthApiError, EthResult};
+use crate::eth::error::{EthApiError, EthResult, RpcPoolError};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_interfaces::RethResult;
-use reth_primitives::{Address, BlockId, U256};
+use reth_payload_builder::PayloadStore;
+use reth_primitives::{Address, BlobTransactionSidecar, BlockId, B256, U256};
 use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
 use reth_rpc_api::RethApiServer;
+use reth_rpc_types::{engine::PayloadId, PayloadBuildTrace};
 use reth_tasks::TaskSpawner;
+use reth_transaction_pool::{blobstore::BlobStoreError, TransactionPool};
 use std::{collections::HashMap, future::Future, sync::Arc};
 use tokio::sync::oneshot;
 
@@ -14,6 +17,10 @@ use tokio::sync::oneshot;
 /// This type provides the functionality for handling `reth` prototype RPC requests.
 pub struct RethApi<Provider> {
     inner: Arc<RethApiInner<Provider>>,
+    /// The payload builder to retrieve build traces from, if available.
+    payload_store: Option<PayloadStore>,
+    /// The transaction pool to retrieve blob sidecars from, if available.
+    blob_sidecars: Option<Arc<dyn BlobSidecarReader>>,
 }
 
 // === impl RethApi ===
@@ -27,7 +34,47 @@ impl<Provider> RethApi<Provider> {
     /// Create a new instance of the [RethApi]
     pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
         let inner = Arc::new(RethApiInner { provider, task_spawner });
-        Self { inner }
+        Self { inner, payload_store: None, blob_sidecars: None }
+    }
+
+    /// Configures the [PayloadStore] that is used to retrieve payload build traces.
+    pub fn with_payload_store(mut self, payload_store: PayloadStore) -> Self {
+        self.payload_store = Some(payload_store);
+        self
+    }
+
+    /// Configures the transaction pool that is used to retrieve blob sidecars.
+    pub fn with_pool<Pool>(mut self, pool: Pool) -> Self
+    where
+        Pool: TransactionPool + 'static,
+    {
+        self.blob_sidecars = Some(Arc::new(pool));
+        self
+    }
+
+    /// Returns the build trace of the payload job with the given id.
+    ///
+    /// Returns `None` if the payload job is unknown or its trace was already evicted.
+    pub async fn payload_build_trace(
+        &self,
+        payload_id: PayloadId,
+    ) -> EthResult<Option<PayloadBuildTrace>> {
+        let Some(payload_store) = &self.payload_store else {
+            return Err(EthApiError::Unsupported("payload builder is not available"))
+        };
+        Ok(payload_store.build_trace(payload_id).await)
+    }
+
+    /// Returns the blob sidecar of the given transaction from the blob store.
+    ///
+    /// Returns `None` if the sidecar is unknown or was already pruned.
+    pub fn blob_sidecar(&self, tx_hash: B256) -> EthResult<Option<BlobTransactionSidecar>> {
+        let Some(blob_sidecars) = &self.blob_sidecars else {
+            return Err(EthApiError::Unsupported("transaction pool is not available"))
+        };
+        blob_sidecars
+            .blob_sidecar(tx_hash)
+            .map_err(|err| EthApiError::PoolError(RpcPoolError::Other(Box::new(err))))
     }
 }
 
@@ -95,6 +142,22 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(RethApi::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getPayloadBuildTrace`
+    async fn reth_get_payload_build_trace(
+        &self,
+        payload_id: PayloadId,
+    ) -> RpcResult<Option<PayloadBuildTrace>> {
+        Ok(RethApi::payload_build_trace(self, payload_id).await?)
+    }
+
+    /// Handler for `reth_getBlobSidecar`
+    async fn reth_get_blob_sidecar(
+        &self,
+        tx_hash: B256,
+    ) -> RpcResult<Option<BlobTransactionSidecar>> {
+        Ok(RethApi::blob_sidecar(self, tx_hash)?)
+    }
 }
 
 impl<Provider> std::fmt::Debug for RethApi<Provider> {
@@ -105,7 +168,11 @@ impl<Provider> std::fmt::Debug for RethApi<Provider> {
 
 impl<Provider> Clone for RethApi<Provider> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self {
+            inner: Arc::clone(&self.inner),
+            payload_store: self.payload_store.clone(),
+            blob_sidecars: self.blob_sidecars.clone(),
+        }
     }
 }
 
@@ -115,3 +182,21 @@ struct RethApiInner<Provider> {
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
+
+/// Retrieves blob sidecars from the blob store of a transaction pool.
+trait BlobSidecarReader: Send + Sync + 'static {
+    fn blob_sidecar(&self, tx_hash: B256)
+        -> Result<Option<BlobTransactionSidecar>, BlobStoreError>;
+}
+
+impl<Pool> BlobSidecarReader for Pool
+where
+    Pool: TransactionPool + 'static,
+{
+    fn blob_sidecar(
+        &self,
+        tx_hash: B256,
+    ) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+        self.get_blob(tx_hash)
+    }
+}
@@ -1,12 +1,21 @@
+use crate::eth::pubsub::pipe_from_stream;
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult as Result;
+use futures::StreamExt;
+use jsonrpsee::{core::RpcResult as Result, PendingSubscriptionSink};
 use reth_primitives::{Address, U256, U64};
 use reth_rpc_api::TxPoolApiServer;
 use reth_rpc_types::{
-    txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus},
+    txpool::{
+        TxpoolContent, TxpoolContentFrom, TxpoolDiff, TxpoolDiffKind, TxpoolInspect,
+        TxpoolInspectSummary, TxpoolSnapshot, TxpoolStatus, TxpoolSubpool,
+    },
     Transaction,
 };
-use reth_transaction_pool::{AllPoolTransactions, PoolTransaction, TransactionPool};
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
+use reth_transaction_pool::{
+    AllPoolTransactions, PoolDiff, PoolDiffEvent, PoolSnapshot, PoolTransaction, SubPool,
+    TransactionPool,
+};
 use std::collections::BTreeMap;
 use tracing::trace;
 
@@ -17,12 +26,21 @@ use tracing::trace;
 pub struct TxPoolApi<Pool> {
     /// An interface to interact with the pool
     pool: Pool,
+    /// The type that's used to spawn subscription tasks.
+    subscription_task_spawner: Box<dyn TaskSpawner>,
 }
 
 impl<Pool> TxPoolApi<Pool> {
     /// Creates a new instance of `TxpoolApi`.
+    ///
+    /// Subscription tasks are spawned via [tokio::task::spawn]
     pub fn new(pool: Pool) -> Self {
-        TxPoolApi { pool }
+        Self::with_spawner(pool, Box::<TokioTaskExecutor>::default())
+    }
+
+    /// Creates a new instance of `TxpoolApi`.
+    pub fn with_spawner(pool: Pool, subscription_task_spawner: Box<dyn TaskSpawner>) -> Self {
+        TxPoolApi { pool, subscription_task_spawner }
     }
 }
 
@@ -55,6 +73,71 @@ where
 
         content
     }
+
+    fn snapshot(&self) -> TxpoolSnapshot {
+        #[inline]
+        fn insert<T: PoolTransaction>(
+            tx: &T,
+            content: &mut BTreeMap<Address, BTreeMap<String, Transaction>>,
+        ) {
+            let entry = content.entry(tx.sender()).or_default();
+            entry.insert(tx.nonce().to_string(), to_rpc_transaction(tx));
+        }
+
+        let PoolSnapshot { sequence, pending, basefee, blob, queued } = self.pool.pool_snapshot();
+
+        let mut snapshot = TxpoolSnapshot { sequence: U64::from(sequence), ..Default::default() };
+        for (transactions, content) in [
+            (pending, &mut snapshot.pending),
+            (basefee, &mut snapshot.base_fee),
+            (blob, &mut snapshot.blob),
+            (queued, &mut snapshot.queued),
+        ] {
+            for tx in transactions {
+                insert(&tx.transaction, content);
+            }
+        }
+
+        snapshot
+    }
+}
+
+/// Converts a pool transaction into its rpc representation.
+fn to_rpc_transaction<T: PoolTransaction>(tx: &T) -> Transaction {
+    reth_rpc_types_compat::transaction::from_recovered(tx.to_recovered_transaction())
+}
+
+/// Converts a change of the pool into its rpc representation.
+fn to_rpc_diff<T: PoolTransaction>(event: PoolDiffEvent<T>) -> TxpoolDiff {
+    let PoolDiffEvent { sequence, diff } = event;
+    let kind = match diff {
+        PoolDiff::Added { transaction, subpool } => TxpoolDiffKind::Added {
+            transaction: Box::new(to_rpc_transaction(&transaction.transaction)),
+            subpool: to_rpc_subpool(subpool),
+        },
+        PoolDiff::Replaced { tx_hash, replaced_by } => {
+            TxpoolDiffKind::Replaced { hash: tx_hash, replaced_by }
+        }
+        PoolDiff::Promoted(hash) => TxpoolDiffKind::Promoted { hash },
+        PoolDiff::Demoted { tx_hash, subpool } => {
+            TxpoolDiffKind::Demoted { hash: tx_hash, subpool: to_rpc_subpool(subpool) }
+        }
+        PoolDiff::Discarded(hash) => TxpoolDiffKind::Discarded { hash },
+        PoolDiff::Mined { tx_hash, block_hash } => {
+            TxpoolDiffKind::Mined { hash: tx_hash, block_hash }
+        }
+    };
+    TxpoolDiff { sequence: U64::from(sequence), kind }
+}
+
+/// Converts a sub-pool into its rpc representation.
+fn to_rpc_subpool(subpool: SubPool) -> TxpoolSubpool {
+    match subpool {
+        SubPool::Pending => TxpoolSubpool::Pending,
+        SubPool::BaseFee => TxpoolSubpool::BaseFee,
+        SubPool::Blob => TxpoolSubpool::Blob,
+        SubPool::Queued => TxpoolSubpool::Queued,
+    }
 }
 
 #[async_trait]
@@ -138,6 +221,26 @@ where
         trace!(target: "rpc::eth", "Serving txpool_inspect");
         Ok(self.content())
     }
+
+    /// Handler for `txpool_snapshot`
+    async fn txpool_snapshot(&self) -> Result<TxpoolSnapshot> {
+        trace!(target: "rpc::eth", "Serving txpool_snapshot");
+        Ok(self.snapshot())
+    }
+
+    /// Handler for `txpool_subscribeDiff`
+    async fn txpool_subscribe_diff(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let sink = pending.accept().await?;
+        let stream = self.pool.pool_diff_listener().map(to_rpc_diff);
+        self.subscription_task_spawner.spawn(Box::pin(async move {
+            let _ = pipe_from_stream(sink, stream).await;
+        }));
+
+        Ok(())
+    }
 }
 
 impl<Pool> std::fmt::Debug for TxPoolApi<Pool> {
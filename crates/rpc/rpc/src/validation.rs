@@ -0,0 +1,684 @@
+use crate::{
+    eth::error::EthApiError,
+    result::{internal_rpc_err, invalid_params_rpc_err},
+};
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::{Error as RpcError, RpcResult},
+    types::ErrorObject,
+};
+use reth_consensus_common::validation::{
+    validate_block_standalone, validate_header_regarding_parent, validate_header_standalone,
+};
+use reth_interfaces::{consensus::ConsensusError, executor::BlockExecutionError};
+use reth_primitives::{Address, BlockId, SealedBlock, SealedHeader, B256, U256};
+use reth_provider::{
+    BlockExecutor, BlockReaderIdExt, BlockSource, ChainSpecProvider, ProviderError,
+    StateProviderFactory,
+};
+use reth_revm::{database::StateProviderDatabase, processor::EVMProcessor};
+use reth_rpc_api::BlockSubmissionValidationApiServer;
+use reth_rpc_types::{
+    engine::PayloadError,
+    relay::{BidTrace, BuilderBlockValidationRequest, BuilderBlockValidationRequestV2},
+};
+use reth_rpc_types_compat::engine::payload::try_into_sealed_block;
+use reth_tasks::TaskSpawner;
+use std::{future::Future, sync::Arc};
+use tokio::sync::oneshot;
+
+/// The bound divisor of the gas limit, used in update calculations.
+const GAS_LIMIT_BOUND_DIVISOR: u64 = 1024;
+
+/// The minimum gas limit a block can have.
+const MIN_GAS_LIMIT: u64 = 5000;
+
+/// `flashbots` API implementation for relays that validate block submissions of builders.
+///
+/// A submitted payload is executed on top of its parent, which can be a canonical block or a block
+/// of the blockchain tree, without committing anything.
+pub struct ValidationApi<Provider> {
+    inner: Arc<ValidationApiInner<Provider>>,
+}
+
+// === impl ValidationApi ===
+
+impl<Provider> ValidationApi<Provider> {
+    /// The provider that can interact with the chain.
+    pub fn provider(&self) -> &Provider {
+        &self.inner.provider
+    }
+
+    /// Create a new instance of the [ValidationApi]
+    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        let inner = Arc::new(ValidationApiInner { provider, task_spawner });
+        Self { inner }
+    }
+}
+
+impl<Provider> ValidationApi<Provider>
+where
+    Provider: BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + 'static,
+{
+    /// Executes the future on a new blocking task.
+    async fn on_blocking_task<C, F, R>(&self, c: C) -> Result<R, ValidationApiError>
+    where
+        C: FnOnce(Self) -> F,
+        F: Future<Output = Result<R, ValidationApiError>> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        let f = c(this);
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = f.await;
+            let _ = tx.send(res);
+        }));
+        rx.await.map_err(|_| ValidationApiError::Eth(EthApiError::InternalEthError))?
+    }
+
+    /// Validates the block submission of a builder.
+    ///
+    /// The payload must be valid on top of its parent, match the bid trace, pay the proposer the
+    /// claimed value and respect the gas limit the proposer registered.
+    pub async fn validate_builder_submission(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> Result<(), ValidationApiError> {
+        self.on_blocking_task(|this| async move { this.try_validate_builder_submission(request) })
+            .await
+    }
+
+    /// Validates the block submission of a builder, including the withdrawals root of the payload.
+    pub async fn validate_builder_submission_v2(
+        &self,
+        request: BuilderBlockValidationRequestV2,
+    ) -> Result<(), ValidationApiError> {
+        let BuilderBlockValidationRequestV2 { request, registered_gas_limit, withdrawals_root } =
+            request;
+        let block = try_into_sealed_block(request.execution_payload.clone(), None)?;
+        let got = block.withdrawals_root.unwrap_or_default();
+        if got != withdrawals_root {
+            return Err(ValidationApiError::WithdrawalsRootMismatch {
+                got,
+                expected: withdrawals_root,
+            })
+        }
+        self.validate_builder_submission(BuilderBlockValidationRequest {
+            request,
+            registered_gas_limit,
+        })
+        .await
+    }
+
+    /// Returns the header of the parent block, which can be a canonical block or a block of the
+    /// blockchain tree, like the pending block or a block of a sidechain.
+    fn parent_header(&self, parent_hash: B256) -> Result<SealedHeader, ValidationApiError> {
+        if let Some(header) =
+            self.provider().sealed_header_by_id(BlockId::Hash(parent_hash.into()))?
+        {
+            return Ok(header)
+        }
+        self.provider()
+            .find_block_by_hash(parent_hash, BlockSource::Pending)?
+            .map(|block| block.header.seal(parent_hash))
+            .ok_or(ValidationApiError::MissingParentBlock(parent_hash))
+    }
+
+    fn try_validate_builder_submission(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> Result<(), ValidationApiError> {
+        let BuilderBlockValidationRequest { request, registered_gas_limit } = request;
+        let message = request.message;
+        let block = try_into_sealed_block(request.execution_payload, None)?;
+        validate_bid_trace(&message, &block)?;
+
+        let parent = self.parent_header(block.parent_hash)?;
+        validate_gas_limit(&parent, registered_gas_limit, block.gas_limit)?;
+
+        let chain_spec = self.provider().chain_spec();
+        validate_header_standalone(&block.header, &chain_spec)?;
+        validate_header_regarding_parent(&parent, &block.header, &chain_spec)?;
+        validate_block_standalone(&block, &chain_spec)?;
+
+        let state = self.provider().state_by_block_hash(parent.hash)?;
+        let fee_recipient = message.proposer_fee_recipient;
+        let balance_before = state.account_balance(fee_recipient)?.unwrap_or_default();
+
+        let block_with_senders =
+            block.clone().seal_with_senders().ok_or(ValidationApiError::SenderRecovery)?;
+        let mut executor =
+            EVMProcessor::new_with_db(chain_spec, StateProviderDatabase::new(&state));
+        executor.execute_and_verify_receipt(&block_with_senders.unseal(), U256::MAX)?;
+        let bundle_state = executor.take_output_state();
+
+        let got = state.state_root(&bundle_state)?;
+        if got != block.state_root {
+            return Err(ValidationApiError::StateRootMismatch { got, expected: block.state_root })
+        }
+
+        // the proposer is paid if the balance of its fee recipient increased by the claimed value
+        let balance_after = match bundle_state.account(&fee_recipient) {
+            Some(account) => account.map(|account| account.balance).unwrap_or_default(),
+            None => balance_before,
+        };
+        if balance_after >= balance_before.saturating_add(message.value) {
+            return Ok(())
+        }
+
+        // otherwise the proposer must be paid by the last transaction of the block
+        let (Some(payment_tx), Some(Some(receipt))) =
+            (block.body.last(), bundle_state.receipts_by_block(block.number).last())
+        else {
+            return Err(ValidationApiError::MissingPaymentTransaction)
+        };
+        if payment_tx.to() != Some(fee_recipient) {
+            return Err(ValidationApiError::PaymentRecipientMismatch {
+                got: payment_tx.to(),
+                expected: fee_recipient,
+            })
+        }
+        let value = U256::from(payment_tx.value());
+        if value != message.value {
+            return Err(ValidationApiError::PaymentValueMismatch {
+                got: value,
+                expected: message.value,
+            })
+        }
+        if !receipt.success {
+            return Err(ValidationApiError::PaymentTransactionReverted)
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Provider> BlockSubmissionValidationApiServer for ValidationApi<Provider>
+where
+    Provider: BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + 'static,
+{
+    async fn validate_builder_submission_v1(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> RpcResult<()> {
+        Ok(ValidationApi::validate_builder_submission(self, request).await?)
+    }
+
+    async fn validate_builder_submission_v2(
+        &self,
+        request: BuilderBlockValidationRequestV2,
+    ) -> RpcResult<()> {
+        Ok(ValidationApi::validate_builder_submission_v2(self, request).await?)
+    }
+}
+
+impl<Provider> std::fmt::Debug for ValidationApi<Provider> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidationApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider> Clone for ValidationApi<Provider> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct ValidationApiInner<Provider> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+/// Ensures that the bid trace matches the block of the submission.
+fn validate_bid_trace(message: &BidTrace, block: &SealedBlock) -> Result<(), ValidationApiError> {
+    if message.block_hash != block.hash {
+        return Err(ValidationApiError::BlockHashMismatch {
+            got: block.hash,
+            expected: message.block_hash,
+        })
+    }
+    if message.parent_hash != block.parent_hash {
+        return Err(ValidationApiError::ParentHashMismatch {
+            got: block.parent_hash,
+            expected: message.parent_hash,
+        })
+    }
+    if message.gas_limit != block.gas_limit {
+        return Err(ValidationApiError::GasLimitMismatch {
+            got: block.gas_limit,
+            expected: message.gas_limit,
+        })
+    }
+    if message.gas_used != block.gas_used {
+        return Err(ValidationApiError::GasUsedMismatch {
+            got: block.gas_used,
+            expected: message.gas_used,
+        })
+    }
+    Ok(())
+}
+
+/// Ensures that the gas limit of the block moves from the parent's gas limit towards the gas limit
+/// registered by the proposer as far as possible.
+fn validate_gas_limit(
+    parent: &SealedHeader,
+    registered_gas_limit: u64,
+    gas_limit: u64,
+) -> Result<(), ValidationApiError> {
+    let expected = expected_gas_limit(parent.gas_limit, registered_gas_limit);
+    if gas_limit != expected {
+        return Err(ValidationApiError::IncorrectGasLimit { got: gas_limit, expected })
+    }
+    Ok(())
+}
+
+/// Returns the gas limit of the child of a block with the given gas limit that is closest to the
+/// desired gas limit.
+fn expected_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64) -> u64 {
+    let delta = (parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR).saturating_sub(1);
+    let desired_gas_limit = desired_gas_limit.max(MIN_GAS_LIMIT);
+    if parent_gas_limit < desired_gas_limit {
+        (parent_gas_limit + delta).min(desired_gas_limit)
+    } else {
+        parent_gas_limit.saturating_sub(delta).max(desired_gas_limit)
+    }
+}
+
+/// Errors that can occur when validating a block submission.
+#[derive(Debug, thiserror::Error)]
+pub enum ValidationApiError {
+    /// Thrown if the payload can't be converted into a block.
+    #[error(transparent)]
+    Payload(#[from] PayloadError),
+    /// Thrown if the block hash of the bid trace doesn't match the block.
+    #[error("block hash mismatch: got {got}, expected {expected}")]
+    BlockHashMismatch {
+        /// The hash of the block.
+        got: B256,
+        /// The hash of the bid trace.
+        expected: B256,
+    },
+    /// Thrown if the parent hash of the bid trace doesn't match the block.
+    #[error("parent hash mismatch: got {got}, expected {expected}")]
+    ParentHashMismatch {
+        /// The parent hash of the block.
+        got: B256,
+        /// The parent hash of the bid trace.
+        expected: B256,
+    },
+    /// Thrown if the gas limit of the bid trace doesn't match the block.
+    #[error("gas limit mismatch: got {got}, expected {expected}")]
+    GasLimitMismatch {
+        /// The gas limit of the block.
+        got: u64,
+        /// The gas limit of the bid trace.
+        expected: u64,
+    },
+    /// Thrown if the gas used of the bid trace doesn't match the block.
+    #[error("gas used mismatch: got {got}, expected {expected}")]
+    GasUsedMismatch {
+        /// The gas used of the block.
+        got: u64,
+        /// The gas used of the bid trace.
+        expected: u64,
+    },
+    /// Thrown if the withdrawals root of the request doesn't match the block.
+    #[error("withdrawals root mismatch: got {got}, expected {expected}")]
+    WithdrawalsRootMismatch {
+        /// The withdrawals root of the block.
+        got: B256,
+        /// The withdrawals root of the request.
+        expected: B256,
+    },
+    /// Thrown if the parent of the block is unknown.
+    #[error("missing parent block {0}")]
+    MissingParentBlock(B256),
+    /// Thrown if the gas limit of the block doesn't respect the registered gas limit.
+    #[error("incorrect gas limit: got {got}, expected {expected}")]
+    IncorrectGasLimit {
+        /// The gas limit of the block.
+        got: u64,
+        /// The gas limit closest to the registered gas limit.
+        expected: u64,
+    },
+    /// Thrown if the senders of the transactions can't be recovered.
+    #[error("failed to recover transaction senders")]
+    SenderRecovery,
+    /// Thrown if the block is invalid.
+    #[error(transparent)]
+    Consensus(#[from] ConsensusError),
+    /// Thrown if the block can't be executed.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+    /// Thrown if the state root of the block doesn't match the state after execution.
+    #[error("state root mismatch: got {got}, expected {expected}")]
+    StateRootMismatch {
+        /// The state root after execution.
+        got: B256,
+        /// The state root of the block.
+        expected: B256,
+    },
+    /// Thrown if the proposer isn't paid and the block has no transactions.
+    #[error("proposer payment transaction missing")]
+    MissingPaymentTransaction,
+    /// Thrown if the last transaction doesn't pay the fee recipient of the proposer.
+    #[error("payment transaction recipient mismatch: got {got:?}, expected {expected}")]
+    PaymentRecipientMismatch {
+        /// The recipient of the last transaction.
+        got: Option<Address>,
+        /// The fee recipient of the proposer.
+        expected: Address,
+    },
+    /// Thrown if the last transaction doesn't pay the claimed value.
+    #[error("payment transaction value mismatch: got {got}, expected {expected}")]
+    PaymentValueMismatch {
+        /// The value of the last transaction.
+        got: U256,
+        /// The value of the bid trace.
+        expected: U256,
+    },
+    /// Thrown if the payment transaction reverted.
+    #[error("payment transaction reverted")]
+    PaymentTransactionReverted,
+    /// Thrown if the state can't be accessed.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// Other internal errors.
+    #[error(transparent)]
+    Eth(#[from] EthApiError),
+}
+
+impl From<ValidationApiError> for ErrorObject<'static> {
+    fn from(error: ValidationApiError) -> Self {
+        match error {
+            ValidationApiError::Provider(err) => internal_rpc_err(err.to_string()),
+            ValidationApiError::Eth(err) => err.into(),
+            err => invalid_params_rpc_err(err.to_string()),
+        }
+    }
+}
+
+impl From<ValidationApiError> for RpcError {
+    fn from(error: ValidationApiError) -> Self {
+        RpcError::Call(error.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "optimism"))]
+    use reth_primitives::{
+        proofs, sign_message, Block, BlockWithSenders, Bloom, Bytes, ChainSpecBuilder, Header,
+        ReceiptWithBloom, Transaction, TransactionKind, TransactionSigned, TxLegacy,
+        EMPTY_OMMER_ROOT_HASH,
+    };
+    #[cfg(not(feature = "optimism"))]
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    #[cfg(not(feature = "optimism"))]
+    use reth_rpc_types::{engine::ExecutionPayload, relay::SubmitBlockRequest};
+    #[cfg(not(feature = "optimism"))]
+    use reth_rpc_types_compat::engine::payload::try_block_to_payload_v1;
+    #[cfg(not(feature = "optimism"))]
+    use reth_tasks::TokioTaskExecutor;
+
+    #[cfg(not(feature = "optimism"))]
+    const GAS_PRICE: u128 = 10;
+
+    #[cfg(not(feature = "optimism"))]
+    fn coinbase() -> Address {
+        Address::with_last_byte(0xc0)
+    }
+
+    /// A chain with a parent block and funded accounts to build submissions on.
+    #[cfg(not(feature = "optimism"))]
+    struct TestChain {
+        provider: MockEthProvider,
+        parent: SealedHeader,
+    }
+
+    #[cfg(not(feature = "optimism"))]
+    impl TestChain {
+        fn new() -> Self {
+            let chain_spec = Arc::new(ChainSpecBuilder::mainnet().paris_activated().build());
+            let provider = MockEthProvider { chain_spec, ..Default::default() };
+            let parent = Header {
+                gas_limit: 30_000_000,
+                gas_used: 15_000_000,
+                base_fee_per_gas: Some(7),
+                ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                ..Default::default()
+            }
+            .seal_slow();
+            provider.add_block(
+                parent.hash,
+                Block { header: parent.header.clone(), ..Default::default() },
+            );
+            Self { provider, parent }
+        }
+
+        /// Returns a funded account.
+        fn account(&self) -> (B256, Address) {
+            let secret = B256::random();
+            let tx = transfer(secret, 0, Address::ZERO, 0);
+            let address = tx.recover_signer().unwrap();
+            self.provider
+                .add_account(address, ExtendedAccount::new(0, U256::from(10).pow(U256::from(18))));
+            (secret, address)
+        }
+
+        /// Returns a contract that always reverts.
+        fn reverting_contract(&self) -> Address {
+            let address = Address::random();
+            // PUSH1 0 PUSH1 0 REVERT
+            let code = Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xfd]);
+            self.provider
+                .add_account(address, ExtendedAccount::new(0, U256::ZERO).with_bytecode(code));
+            address
+        }
+
+        /// Builds a valid block with the given transactions on top of the parent, except for the
+        /// state root.
+        fn block(&self, body: Vec<TransactionSigned>, state_root: B256) -> SealedBlock {
+            let chain_spec = self.provider.chain_spec();
+            let timestamp = self.parent.timestamp + 12;
+            let mut header = Header {
+                parent_hash: self.parent.hash,
+                number: self.parent.number + 1,
+                gas_limit: self.parent.gas_limit,
+                timestamp,
+                beneficiary: coinbase(),
+                base_fee_per_gas: self
+                    .parent
+                    .next_block_base_fee(chain_spec.base_fee_params(timestamp)),
+                ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                transactions_root: proofs::calculate_transaction_root(&body),
+                state_root,
+                ..Default::default()
+            };
+
+            let block = Block { header: header.clone(), body, ..Default::default() };
+            let senders = block.senders().unwrap();
+            let block = BlockWithSenders { block, senders };
+            let mut executor = EVMProcessor::new_with_db(
+                chain_spec,
+                StateProviderDatabase::new(self.provider.clone()),
+            );
+            let (receipts, gas_used) = executor.execute_transactions(&block, U256::MAX).unwrap();
+            let receipts = receipts.into_iter().map(ReceiptWithBloom::from).collect::<Vec<_>>();
+            header.gas_used = gas_used;
+            header.receipts_root = proofs::calculate_receipt_root(&receipts);
+            header.logs_bloom = receipts.iter().fold(Bloom::ZERO, |bloom, r| bloom | r.bloom);
+
+            SealedBlock { header: header.seal_slow(), body: block.block.body, ..Default::default() }
+        }
+
+        /// Validates the submission of the block that claims to pay the value to the fee recipient.
+        fn validate(
+            &self,
+            block: SealedBlock,
+            fee_recipient: Address,
+            value: U256,
+        ) -> Result<(), ValidationApiError> {
+            let message = BidTrace {
+                slot: 1,
+                parent_hash: block.parent_hash,
+                block_hash: block.hash,
+                builder_public_key: Default::default(),
+                proposer_public_key: Default::default(),
+                proposer_fee_recipient: fee_recipient,
+                gas_limit: block.gas_limit,
+                gas_used: block.gas_used,
+                value,
+            };
+            let request = BuilderBlockValidationRequest {
+                request: SubmitBlockRequest {
+                    message,
+                    execution_payload: ExecutionPayload::V1(try_block_to_payload_v1(block)),
+                    signature: Default::default(),
+                },
+                registered_gas_limit: self.parent.gas_limit,
+            };
+            let api =
+                ValidationApi::new(self.provider.clone(), Box::<TokioTaskExecutor>::default());
+            api.try_validate_builder_submission(request)
+        }
+    }
+
+    #[cfg(not(feature = "optimism"))]
+    fn transfer(secret: B256, nonce: u64, to: Address, value: u128) -> TransactionSigned {
+        let tx = Transaction::Legacy(TxLegacy {
+            chain_id: Some(1),
+            nonce,
+            gas_price: GAS_PRICE,
+            gas_limit: 100_000,
+            to: TransactionKind::Call(to),
+            value: value.into(),
+            ..Default::default()
+        });
+        let signature = sign_message(secret, tx.signature_hash()).unwrap();
+        TransactionSigned::from_transaction_and_signature(tx, signature)
+    }
+
+    #[test]
+    #[cfg(not(feature = "optimism"))]
+    fn proposer_paid_by_balance_delta() {
+        let chain = TestChain::new();
+        let (secret, _) = chain.account();
+        let to = Address::random();
+        let block = chain.block(vec![transfer(secret, 0, to, 1)], B256::ZERO);
+
+        // the fee recipient receives the priority fees of the transfer
+        let fees = U256::from(GAS_PRICE - 7) * U256::from(21_000);
+        chain.validate(block.clone(), coinbase(), fees).unwrap();
+        assert!(matches!(
+            chain.validate(block, coinbase(), fees + U256::from(1)),
+            Err(ValidationApiError::PaymentRecipientMismatch { got, .. }) if got == Some(to)
+        ));
+    }
+
+    #[test]
+    #[cfg(not(feature = "optimism"))]
+    fn proposer_paid_by_last_transaction() {
+        let chain = TestChain::new();
+        let (builder, _) = chain.account();
+        let (proposer, fee_recipient) = chain.account();
+
+        // the fee recipient spends more than it is paid in the same block, so its balance
+        // decreases and the payment is checked against the last transaction
+        let block = chain.block(
+            vec![
+                transfer(proposer, 0, Address::random(), 1),
+                transfer(builder, 0, fee_recipient, 1_000),
+            ],
+            B256::ZERO,
+        );
+        chain.validate(block, fee_recipient, U256::from(1_000)).unwrap();
+    }
+
+    #[test]
+    #[cfg(not(feature = "optimism"))]
+    fn payment_transaction_mismatch() {
+        let chain = TestChain::new();
+        let (builder, _) = chain.account();
+        let fee_recipient = Address::random();
+        let other = Address::random();
+
+        let block = chain.block(vec![transfer(builder, 0, other, 1_000)], B256::ZERO);
+        assert!(matches!(
+            chain.validate(block, fee_recipient, U256::from(1_000)),
+            Err(ValidationApiError::PaymentRecipientMismatch { got, expected })
+                if got == Some(other) && expected == fee_recipient
+        ));
+
+        let block = chain.block(vec![transfer(builder, 0, fee_recipient, 1_000)], B256::ZERO);
+        assert!(matches!(
+            chain.validate(block, fee_recipient, U256::from(1_001)),
+            Err(ValidationApiError::PaymentValueMismatch { got, expected })
+                if got == U256::from(1_000) && expected == U256::from(1_001)
+        ));
+
+        let block = chain.block(vec![], B256::ZERO);
+        assert!(matches!(
+            chain.validate(block, fee_recipient, U256::from(1)),
+            Err(ValidationApiError::MissingPaymentTransaction)
+        ));
+    }
+
+    #[test]
+    #[cfg(not(feature = "optimism"))]
+    fn reverted_payment_transaction() {
+        let chain = TestChain::new();
+        let (builder, _) = chain.account();
+        let fee_recipient = chain.reverting_contract();
+
+        let block = chain.block(vec![transfer(builder, 0, fee_recipient, 1_000)], B256::ZERO);
+        assert!(matches!(
+            chain.validate(block, fee_recipient, U256::from(1_000)),
+            Err(ValidationApiError::PaymentTransactionReverted)
+        ));
+    }
+
+    #[test]
+    #[cfg(not(feature = "optimism"))]
+    fn state_root_mismatch() {
+        let chain = TestChain::new();
+        let (builder, _) = chain.account();
+        let state_root = B256::with_last_byte(1);
+
+        let block = chain.block(vec![transfer(builder, 0, coinbase(), 1_000)], state_root);
+        assert!(matches!(
+            chain.validate(block, coinbase(), U256::ZERO),
+            Err(ValidationApiError::StateRootMismatch { got, expected })
+                if got == B256::ZERO && expected == state_root
+        ));
+    }
+
+    #[test]
+    #[cfg(not(feature = "optimism"))]
+    fn missing_parent_block() {
+        let chain = TestChain::new();
+        let block = chain.block(vec![], B256::ZERO);
+        let other =
+            TestChain { provider: MockEthProvider::default(), parent: chain.parent.clone() };
+        assert!(matches!(
+            other.validate(block, coinbase(), U256::ZERO),
+            Err(ValidationApiError::MissingParentBlock(hash)) if hash == chain.parent.hash
+        ));
+    }
+
+    #[test]
+    fn test_expected_gas_limit() {
+        // moves towards the desired gas limit by at most the bound
+        assert_eq!(expected_gas_limit(30_000_000, 36_000_000), 30_029_295);
+        assert_eq!(expected_gas_limit(30_000_000, 20_000_000), 29_970_705);
+        // stops at the desired gas limit
+        assert_eq!(expected_gas_limit(30_000_000, 30_010_000), 30_010_000);
+        assert_eq!(expected_gas_limit(30_000_000, 30_000_000), 30_000_000);
+        // never below the minimum gas limit
+        assert_eq!(expected_gas_limit(5_000, 0), 5_000);
+    }
+}
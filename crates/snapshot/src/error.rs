@@ -1,5 +1,6 @@
 use reth_db::DatabaseError;
 use reth_interfaces::RethError;
+use reth_nippy_jar::NippyJarError;
 use reth_provider::ProviderError;
 use thiserror::Error;
 
@@ -10,6 +11,9 @@ pub enum SnapshotterError {
     #[error("inconsistent data: {0}")]
     InconsistentData(&'static str),
 
+    #[error("invalid snapshot manifest: {0}")]
+    InvalidManifest(String),
+
     #[error(transparent)]
     Interface(#[from] RethError),
 
@@ -18,4 +22,10 @@ pub enum SnapshotterError {
 
     #[error(transparent)]
     Provider(#[from] ProviderError),
+
+    #[error(transparent)]
+    NippyJar(#[from] NippyJarError),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
 }
@@ -10,10 +10,12 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 mod error;
+pub mod manifest;
 pub mod segments;
 mod snapshotter;
 
 pub use error::SnapshotterError;
+pub use manifest::{ManifestFile, ManifestSnapshot, SnapshotManifest, MANIFEST_FILE_NAME};
 pub use snapshotter::{
     HighestSnapshotsTracker, SnapshotTargets, Snapshotter, SnapshotterResult, SnapshotterWithResult,
 };
@@ -0,0 +1,332 @@
+//! Manifest of a snapshots directory, used to distribute snapshots.
+
+use crate::SnapshotterError;
+use reth_db::snapshot::iter_snapshots;
+use reth_nippy_jar::{compression::Compressors, NippyJar};
+use reth_primitives::{
+    snapshot::{Compression, SegmentHeader},
+    BlockNumber, SnapshotSegment, TxNumber, B256,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::File,
+    io::{self, Read},
+    ops::RangeInclusive,
+    path::{Component, Path, PathBuf},
+};
+
+/// Name of the manifest file inside a snapshots directory.
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Extensions of the files supporting a snapshot data file: offsets, configuration and index.
+const SUPPORTING_FILE_EXTENSIONS: [&str; 3] = ["off", "conf", "idx"];
+
+/// Lists every snapshot of a directory with the files it's made of, so they can be downloaded
+/// and verified by another node.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// Snapshots sorted by segment and block range.
+    pub snapshots: Vec<ManifestSnapshot>,
+}
+
+impl SnapshotManifest {
+    /// Creates the manifest of all snapshots found in `directory`, hashing each of their files.
+    pub fn from_dir(directory: impl AsRef<Path>) -> Result<Self, SnapshotterError> {
+        let directory = directory.as_ref();
+        let mut snapshots = Vec::new();
+
+        for (segment, ranges) in iter_snapshots(directory)? {
+            for (block_range, tx_range) in ranges {
+                snapshots.push(ManifestSnapshot::from_dir(
+                    directory,
+                    segment,
+                    block_range,
+                    tx_range,
+                )?);
+            }
+        }
+        snapshots.sort_by_key(|snapshot| (snapshot.segment, *snapshot.block_range.start()));
+
+        Ok(Self { snapshots })
+    }
+
+    /// Returns the snapshots of `segments`, or all of them if `segments` is empty.
+    pub fn segments<'a>(
+        &'a self,
+        segments: &'a [SnapshotSegment],
+    ) -> impl Iterator<Item = &'a ManifestSnapshot> + 'a {
+        self.snapshots
+            .iter()
+            .filter(move |snapshot| segments.is_empty() || segments.contains(&snapshot.segment))
+    }
+}
+
+/// A snapshot listed in a [`SnapshotManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestSnapshot {
+    /// Snapshot segment.
+    pub segment: SnapshotSegment,
+    /// Block range of the snapshot.
+    pub block_range: RangeInclusive<BlockNumber>,
+    /// Transaction range of the snapshot.
+    pub tx_range: RangeInclusive<TxNumber>,
+    /// Compression used on the snapshot data.
+    pub compression: Compression,
+    /// Files the snapshot is made of: its data file, followed by the supporting files.
+    pub files: Vec<ManifestFile>,
+}
+
+impl ManifestSnapshot {
+    /// Creates the manifest entry of the snapshot with the given ranges in `directory`.
+    fn from_dir(
+        directory: &Path,
+        segment: SnapshotSegment,
+        block_range: RangeInclusive<BlockNumber>,
+        tx_range: RangeInclusive<TxNumber>,
+    ) -> Result<Self, SnapshotterError> {
+        let data_path = directory.join(segment.filename(&block_range, &tx_range));
+        let jar = NippyJar::<SegmentHeader>::load(&data_path)?;
+
+        let compression = match jar.compressor() {
+            Some(Compressors::Zstd(zstd)) if zstd.use_dict => Compression::ZstdWithDictionary,
+            Some(Compressors::Zstd(zstd)) if zstd.long_window().is_some() => {
+                Compression::ZstdLongWindow
+            }
+            Some(Compressors::Zstd(_)) => Compression::Zstd,
+            Some(Compressors::Lz4(_)) => Compression::Lz4,
+            Some(Compressors::Uncompressed(_)) | None => Compression::Uncompressed,
+        };
+
+        let mut files = Vec::new();
+        for path in [data_path, jar.offsets_path(), jar.config_path(), jar.index_path()] {
+            if path.exists() {
+                files.push(ManifestFile::from_path(&path)?);
+            }
+        }
+
+        Ok(Self {
+            segment,
+            block_range: jar.user_header().block_range().clone(),
+            tx_range: jar.user_header().tx_range().clone(),
+            compression,
+            files,
+        })
+    }
+
+    /// Returns the file name of the snapshot data file.
+    pub fn filename(&self) -> String {
+        self.segment.filename(&self.block_range, &self.tx_range)
+    }
+
+    /// Checks that every file of this snapshot is either its data file or one of the supporting
+    /// files of it, so that a manifest can't make us write outside of the snapshots directory.
+    pub fn validate(&self) -> Result<(), SnapshotterError> {
+        let data_file = self.filename();
+        let allowed = std::iter::once(PathBuf::from(&data_file))
+            .chain(
+                SUPPORTING_FILE_EXTENSIONS
+                    .iter()
+                    .map(|ext| Path::new(&data_file).with_extension(ext)),
+            )
+            .collect::<Vec<_>>();
+
+        for file in &self.files {
+            let path = Path::new(&file.name);
+            let mut components = path.components();
+            if !matches!((components.next(), components.next()), (Some(Component::Normal(_)), None)) ||
+                !allowed.iter().any(|allowed| allowed == path)
+            {
+                return Err(SnapshotterError::InvalidManifest(format!(
+                    "file {:?} does not belong to snapshot {data_file}",
+                    file.name
+                )))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the [`SegmentHeader`] of the snapshot data file in `directory` matches the
+    /// segment and ranges of the manifest.
+    pub fn verify_header(&self, directory: impl AsRef<Path>) -> Result<(), SnapshotterError> {
+        let jar = NippyJar::<SegmentHeader>::load(&directory.as_ref().join(self.filename()))?;
+        let header = jar.user_header();
+
+        if header.segment() != self.segment ||
+            header.block_range() != &self.block_range ||
+            header.tx_range() != &self.tx_range
+        {
+            return Err(SnapshotterError::InvalidManifest(format!(
+                "snapshot {} has segment {} with blocks {:?} and transactions {:?}",
+                self.filename(),
+                header.segment(),
+                header.block_range(),
+                header.tx_range()
+            )))
+        }
+
+        Ok(())
+    }
+
+    /// Returns the files of this snapshot which are missing from `directory` or don't match the
+    /// manifest.
+    pub fn missing_files(&self, directory: impl AsRef<Path>) -> io::Result<Vec<&ManifestFile>> {
+        let mut missing = Vec::new();
+        for file in &self.files {
+            if !file.verify(directory.as_ref().join(&file.name))? {
+                missing.push(file);
+            }
+        }
+        Ok(missing)
+    }
+}
+
+/// A file listed in a [`ManifestSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// File name, relative to the snapshots directory.
+    pub name: String,
+    /// File size in bytes.
+    pub size: u64,
+    /// SHA-256 hash of the file content.
+    pub sha256: B256,
+}
+
+impl ManifestFile {
+    /// Creates the manifest entry of the file at `path`.
+    pub fn from_path(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path without file name"))?
+            .to_string_lossy()
+            .into_owned();
+        let (size, sha256) = hash_file(path)?;
+
+        Ok(Self { name, size, sha256 })
+    }
+
+    /// Returns `true` if the file at `path` exists and matches the size and hash of the manifest.
+    pub fn verify(&self, path: impl AsRef<Path>) -> io::Result<bool> {
+        let path = path.as_ref();
+        if !path.exists() || path.metadata()?.len() != self.size {
+            return Ok(false)
+        }
+
+        Ok(hash_file(path)? == (self.size, self.sha256))
+    }
+}
+
+/// Returns the size and SHA-256 hash of the file at `path`.
+fn hash_file(path: &Path) -> io::Result<(u64, B256)> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; 1 << 20];
+    let mut size = 0;
+
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            break
+        }
+        hasher.update(&buf[..read]);
+        size += read as u64;
+    }
+
+    Ok((size, B256::from_slice(&hasher.finalize())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_nippy_jar::ColumnResult;
+
+    #[test]
+    fn manifest_from_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let block_range = 0..=9;
+        let tx_range = 0..=19;
+        let segment = SnapshotSegment::Transactions;
+
+        let mut jar = NippyJar::new(
+            1,
+            &dir.path().join(segment.filename(&block_range, &tx_range)),
+            SegmentHeader::new(block_range.clone(), tx_range.clone(), segment),
+        )
+        .with_lz4();
+        let rows = (0..20u8).map(|i| Ok(vec![i; 32])).collect::<Vec<ColumnResult<Vec<u8>>>>();
+        jar.freeze(vec![rows], 20).unwrap();
+
+        let manifest = SnapshotManifest::from_dir(dir.path()).unwrap();
+        assert_eq!(manifest.snapshots.len(), 1);
+
+        let snapshot = &manifest.snapshots[0];
+        assert_eq!(snapshot.segment, segment);
+        assert_eq!(snapshot.block_range, block_range);
+        assert_eq!(snapshot.tx_range, tx_range);
+        assert_eq!(snapshot.compression, Compression::Lz4);
+        assert_eq!(
+            snapshot.files.iter().map(|file| file.name.as_str()).collect::<Vec<_>>(),
+            [
+                "snapshot_transactions_0_9_0_19",
+                "snapshot_transactions_0_9_0_19.off",
+                "snapshot_transactions_0_9_0_19.conf",
+                "snapshot_transactions_0_9_0_19.idx"
+            ]
+        );
+        assert_eq!(manifest.segments(&[SnapshotSegment::Headers]).count(), 0);
+        assert_eq!(manifest.segments(&[]).count(), 1);
+
+        let decoded: SnapshotManifest =
+            serde_json::from_str(&serde_json::to_string(&manifest).unwrap()).unwrap();
+        assert_eq!(decoded, manifest);
+
+        // Every file matches until one of them gets modified
+        assert!(snapshot.missing_files(dir.path()).unwrap().is_empty());
+
+        let data_path = dir.path().join(snapshot.filename());
+        let mut data = std::fs::read(&data_path).unwrap();
+        data[0] ^= 1;
+        std::fs::write(&data_path, data).unwrap();
+        std::fs::remove_file(jar.index_path()).unwrap();
+
+        assert_eq!(
+            snapshot.missing_files(dir.path()).unwrap(),
+            vec![&snapshot.files[0], &snapshot.files[3]]
+        );
+    }
+
+    #[test]
+    fn validate_file_names() {
+        let file =
+            |name: &str| ManifestFile { name: name.to_string(), size: 0, sha256: B256::ZERO };
+        let mut snapshot = ManifestSnapshot {
+            segment: SnapshotSegment::Headers,
+            block_range: 0..=9,
+            tx_range: 0..=9,
+            compression: Compression::Uncompressed,
+            files: vec![
+                file("snapshot_headers_0_9_0_9"),
+                file("snapshot_headers_0_9_0_9.off"),
+                file("snapshot_headers_0_9_0_9.conf"),
+                file("snapshot_headers_0_9_0_9.idx"),
+            ],
+        };
+        assert!(snapshot.validate().is_ok());
+
+        for name in [
+            "../snapshot_headers_0_9_0_9",
+            "/tmp/snapshot_headers_0_9_0_9",
+            "./snapshot_headers_0_9_0_9.off",
+            "sub/snapshot_headers_0_9_0_9.off",
+            "snapshot_headers_0_9_0_9.part",
+            "snapshot_headers_0_19_0_9",
+            "manifest.json",
+            "",
+        ] {
+            snapshot.files[1] = file(name);
+            assert!(snapshot.validate().is_err(), "{name}");
+        }
+    }
+}
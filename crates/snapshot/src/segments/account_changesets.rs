@@ -0,0 +1,74 @@
+use crate::segments::{snapshot_block_rows, Segment};
+use reth_db::{
+    cursor::DbCursorRO, database::Database, models::BlockAccountChangeSet, table::Compress, tables,
+    transaction::DbTx,
+};
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{
+    snapshot::{Compression, Filters, SegmentConfig, ZstdLongWindowConfig},
+    BlockNumber, SnapshotSegment,
+};
+use reth_provider::DatabaseProviderRO;
+use std::{ops::RangeInclusive, path::Path};
+
+/// Snapshot segment responsible for [SnapshotSegment::AccountChangeSets] part of data.
+///
+/// Each row holds all account changesets of a block as a [BlockAccountChangeSet].
+#[derive(Debug)]
+pub struct AccountChangeSets {
+    config: SegmentConfig,
+}
+
+impl AccountChangeSets {
+    /// Creates new instance of [AccountChangeSets] snapshot segment.
+    pub fn new(compression: Compression, filters: Filters) -> Self {
+        Self {
+            config: SegmentConfig {
+                compression,
+                filters,
+                zstd_long_window: ZstdLongWindowConfig::DEFAULT,
+            },
+        }
+    }
+
+    /// Sets the level and window of [Compression::ZstdLongWindow] compression.
+    pub fn with_zstd_long_window(mut self, zstd_long_window: ZstdLongWindowConfig) -> Self {
+        self.config.zstd_long_window = zstd_long_window;
+        self
+    }
+}
+
+impl Default for AccountChangeSets {
+    fn default() -> Self {
+        Self { config: SnapshotSegment::AccountChangeSets.config() }
+    }
+}
+
+impl Segment for AccountChangeSets {
+    fn segment(&self) -> SnapshotSegment {
+        SnapshotSegment::AccountChangeSets
+    }
+
+    fn snapshot<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+        directory: impl AsRef<Path>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        snapshot_block_rows(
+            provider,
+            directory,
+            self.segment(),
+            self.config,
+            range,
+            || Ok(provider.tx_ref().cursor_read::<tables::AccountChangeSet>()?),
+            |cursor, block| {
+                let accounts = cursor
+                    .walk_range(block..=block)?
+                    .map(|entry| entry.map(|(_, account)| account))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(BlockAccountChangeSet { accounts }.compress())
+            },
+        )
+    }
+}
@@ -5,7 +5,7 @@ use reth_db::{
 };
 use reth_interfaces::provider::ProviderResult;
 use reth_primitives::{
-    snapshot::{Compression, Filters, SegmentConfig},
+    snapshot::{Compression, Filters, SegmentConfig, ZstdLongWindowConfig},
     BlockNumber, SnapshotSegment,
 };
 use reth_provider::DatabaseProviderRO;
@@ -20,7 +20,19 @@ pub struct Headers {
 impl Headers {
     /// Creates new instance of [Headers] snapshot segment.
     pub fn new(compression: Compression, filters: Filters) -> Self {
-        Self { config: SegmentConfig { compression, filters } }
+        Self {
+            config: SegmentConfig {
+                compression,
+                filters,
+                zstd_long_window: ZstdLongWindowConfig::DEFAULT,
+            },
+        }
+    }
+
+    /// Sets the level and window of [Compression::ZstdLongWindow] compression.
+    pub fn with_zstd_long_window(mut self, zstd_long_window: ZstdLongWindowConfig) -> Self {
+        self.config.zstd_long_window = zstd_long_window;
+        self
     }
 }
 
@@ -9,11 +9,17 @@ pub use headers::Headers;
 mod receipts;
 pub use receipts::Receipts;
 
+mod account_changesets;
+pub use account_changesets::AccountChangeSets;
+
+mod storage_changesets;
+pub use storage_changesets::StorageChangeSets;
+
 use reth_db::{
     cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx, RawKey, RawTable,
 };
 use reth_interfaces::provider::ProviderResult;
-use reth_nippy_jar::NippyJar;
+use reth_nippy_jar::{ColumnResult, NippyJar, DEFAULT_CHECKSUM_CHUNK_ROWS};
 use reth_primitives::{
     snapshot::{
         Compression, Filters, InclusionFilter, PerfectHashingFunction, SegmentConfig, SegmentHeader,
@@ -71,7 +77,8 @@ pub(crate) fn prepare_jar<DB: Database, const COLUMNS: usize>(
         COLUMNS,
         &directory.as_ref().join(segment.filename(&block_range, &tx_range).as_str()),
         SegmentHeader::new(block_range, tx_range, segment),
-    );
+    )
+    .with_checksums(DEFAULT_CHECKSUM_CHUNK_ROWS);
 
     nippy_jar = match segment_config.compression {
         Compression::Lz4 => nippy_jar.with_lz4(),
@@ -83,12 +90,17 @@ pub(crate) fn prepare_jar<DB: Database, const COLUMNS: usize>(
             nippy_jar.prepare_compression(dataset.to_vec())?;
             nippy_jar
         }
+        Compression::ZstdLongWindow => nippy_jar
+            .with_zstd(false, 0)
+            .with_zstd_level(segment_config.zstd_long_window.level())
+            .with_zstd_long_window(segment_config.zstd_long_window.window_log()),
         Compression::Uncompressed => nippy_jar,
     };
 
     if let Filters::WithFilters(inclusion_filter, phf) = segment_config.filters {
         nippy_jar = match inclusion_filter {
             InclusionFilter::Cuckoo => nippy_jar.with_cuckoo_filter(total_rows),
+            InclusionFilter::Bloom => nippy_jar.with_bloom_filter(total_rows),
         };
         nippy_jar = match phf {
             PerfectHashingFunction::Fmph => nippy_jar.with_fmph(),
@@ -98,3 +110,44 @@ pub(crate) fn prepare_jar<DB: Database, const COLUMNS: usize>(
 
     Ok(nippy_jar)
 }
+
+/// Snapshots a block based segment with one row per block, built by `block_row` with a cursor
+/// created by `cursor`.
+///
+/// Used by segments whose tables hold a variable amount of entries per block, like the changesets.
+/// Rows are only queried by block number, so filters are never built.
+pub(crate) fn snapshot_block_rows<DB: Database, C>(
+    provider: &DatabaseProviderRO<DB>,
+    directory: impl AsRef<Path>,
+    segment: SnapshotSegment,
+    segment_config: SegmentConfig,
+    block_range: RangeInclusive<BlockNumber>,
+    cursor: impl Fn() -> ProviderResult<C>,
+    block_row: impl Fn(&mut C, BlockNumber) -> ProviderResult<Vec<u8>>,
+) -> ProviderResult<()> {
+    let range_len = block_range.clone().count();
+    let mut jar = prepare_jar::<DB, 1>(
+        provider,
+        directory,
+        segment,
+        SegmentConfig { filters: Filters::WithoutFilters, ..segment_config },
+        block_range.clone(),
+        range_len,
+        || {
+            // The most recent rows (at most 1000) are used to train the dictionary
+            let mut cursor = cursor()?;
+            let start = block_range.end().saturating_sub(1000).max(*block_range.start());
+            Ok([(start..=*block_range.end())
+                .map(|block| block_row(&mut cursor, block))
+                .collect::<ProviderResult<Vec<_>>>()?])
+        },
+    )?;
+
+    let mut cursor = cursor()?;
+    let rows = block_range.map(|block| -> ColumnResult<Vec<u8>> {
+        block_row(&mut cursor, block).map_err(|err| Box::new(err) as _)
+    });
+    jar.freeze(vec![rows], range_len as u64)?;
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::segments::{snapshot_block_rows, Segment};
+use reth_db::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::{AccountStorageChangeSet, BlockNumberAddress, BlockStorageChangeSet},
+    table::Compress,
+    tables,
+    transaction::DbTx,
+};
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{
+    snapshot::{Compression, Filters, SegmentConfig, ZstdLongWindowConfig},
+    BlockNumber, SnapshotSegment,
+};
+use reth_provider::DatabaseProviderRO;
+use std::{ops::RangeInclusive, path::Path};
+
+/// Snapshot segment responsible for [SnapshotSegment::StorageChangeSets] part of data.
+///
+/// Each row holds all storage changesets of a block as a [BlockStorageChangeSet].
+#[derive(Debug)]
+pub struct StorageChangeSets {
+    config: SegmentConfig,
+}
+
+impl StorageChangeSets {
+    /// Creates new instance of [StorageChangeSets] snapshot segment.
+    pub fn new(compression: Compression, filters: Filters) -> Self {
+        Self {
+            config: SegmentConfig {
+                compression,
+                filters,
+                zstd_long_window: ZstdLongWindowConfig::DEFAULT,
+            },
+        }
+    }
+
+    /// Sets the level and window of [Compression::ZstdLongWindow] compression.
+    pub fn with_zstd_long_window(mut self, zstd_long_window: ZstdLongWindowConfig) -> Self {
+        self.config.zstd_long_window = zstd_long_window;
+        self
+    }
+}
+
+impl Default for StorageChangeSets {
+    fn default() -> Self {
+        Self { config: SnapshotSegment::StorageChangeSets.config() }
+    }
+}
+
+impl Segment for StorageChangeSets {
+    fn segment(&self) -> SnapshotSegment {
+        SnapshotSegment::StorageChangeSets
+    }
+
+    fn snapshot<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+        directory: impl AsRef<Path>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        snapshot_block_rows(
+            provider,
+            directory,
+            self.segment(),
+            self.config,
+            range,
+            || Ok(provider.tx_ref().cursor_read::<tables::StorageChangeSet>()?),
+            |cursor, block| {
+                let mut changeset = BlockStorageChangeSet::default();
+                for entry in cursor.walk_range(BlockNumberAddress::range(block..=block))? {
+                    let (block_address, entry) = entry?;
+                    match changeset.accounts.last_mut() {
+                        Some(account) if account.address == block_address.address() => {
+                            account.storage.push(entry)
+                        }
+                        _ => changeset.accounts.push(AccountStorageChangeSet {
+                            address: block_address.address(),
+                            storage: vec![entry],
+                        }),
+                    }
+                }
+                Ok(changeset.compress())
+            },
+        )
+    }
+}
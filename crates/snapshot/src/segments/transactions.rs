@@ -2,7 +2,7 @@ use crate::segments::{prepare_jar, Segment};
 use reth_db::{database::Database, snapshot::create_snapshot_T1, tables};
 use reth_interfaces::provider::ProviderResult;
 use reth_primitives::{
-    snapshot::{Compression, Filters, SegmentConfig, SegmentHeader},
+    snapshot::{Compression, Filters, SegmentConfig, SegmentHeader, ZstdLongWindowConfig},
     BlockNumber, SnapshotSegment, TxNumber,
 };
 use reth_provider::{DatabaseProviderRO, TransactionsProviderExt};
@@ -17,7 +17,19 @@ pub struct Transactions {
 impl Transactions {
     /// Creates new instance of [Transactions] snapshot segment.
     pub fn new(compression: Compression, filters: Filters) -> Self {
-        Self { config: SegmentConfig { compression, filters } }
+        Self {
+            config: SegmentConfig {
+                compression,
+                filters,
+                zstd_long_window: ZstdLongWindowConfig::DEFAULT,
+            },
+        }
+    }
+
+    /// Sets the level and window of [Compression::ZstdLongWindow] compression.
+    pub fn with_zstd_long_window(mut self, zstd_long_window: ZstdLongWindowConfig) -> Self {
+        self.config.zstd_long_window = zstd_long_window;
+        self
     }
 }
 
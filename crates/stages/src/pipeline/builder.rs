@@ -2,6 +2,7 @@ use crate::{pipeline::BoxedStage, MetricEventsSender, Pipeline, Stage, StageSet}
 use reth_db::database::Database;
 use reth_primitives::{stage::StageId, BlockNumber, B256};
 use reth_provider::ProviderFactory;
+use std::collections::HashMap;
 use tokio::sync::watch;
 
 /// Builds a [`Pipeline`].
@@ -12,6 +13,8 @@ where
 {
     /// All configured stages in the order they will be executed.
     stages: Vec<BoxedStage<DB>>,
+    /// Declared dependencies of the stages.
+    dependencies: HashMap<StageId, Vec<StageId>>,
     /// The maximum block number to sync to.
     max_block: Option<BlockNumber>,
     /// A receiver for the current chain tip to sync to.
@@ -40,9 +43,22 @@ where
     /// [`builder`][StageSet::builder] on the set which will convert it to a
     /// [`StageSetBuilder`][crate::StageSetBuilder].
     pub fn add_stages<Set: StageSet<DB>>(mut self, set: Set) -> Self {
-        for stage in set.builder().build() {
-            self.stages.push(stage);
-        }
+        let (stages, dependencies) = set.builder().build_with_dependencies();
+        self.stages.extend(stages);
+        self.dependencies.extend(dependencies);
+        self
+    }
+
+    /// Declares the stages that the stage with the given [`StageId`] depends on, instead of all
+    /// stages before it.
+    ///
+    /// See [`StageSetBuilder::depends_on`][crate::StageSetBuilder::depends_on].
+    pub fn depends_on(
+        mut self,
+        stage_id: StageId,
+        dependencies: impl IntoIterator<Item = StageId>,
+    ) -> Self {
+        self.dependencies.insert(stage_id, dependencies.into_iter().collect());
         self
     }
 
@@ -68,10 +84,11 @@ where
 
     /// Builds the final [`Pipeline`] using the given database.
     pub fn build(self, provider_factory: ProviderFactory<DB>) -> Pipeline<DB> {
-        let Self { stages, max_block, tip_tx, metrics_tx } = self;
+        let Self { stages, dependencies, max_block, tip_tx, metrics_tx } = self;
         Pipeline {
             provider_factory,
             stages,
+            dependencies,
             max_block,
             tip_tx,
             listeners: Default::default(),
@@ -83,7 +100,13 @@ where
 
 impl<DB: Database> Default for PipelineBuilder<DB> {
     fn default() -> Self {
-        Self { stages: Vec::new(), max_block: None, tip_tx: None, metrics_tx: None }
+        Self {
+            stages: Vec::new(),
+            dependencies: HashMap::new(),
+            max_block: None,
+            tip_tx: None,
+            metrics_tx: None,
+        }
     }
 }
 
@@ -91,6 +114,7 @@ impl<DB: Database> std::fmt::Debug for PipelineBuilder<DB> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PipelineBuilder")
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
+            .field("dependencies", &self.dependencies)
             .field("max_block", &self.max_block)
             .finish()
     }
@@ -11,7 +11,7 @@ use reth_primitives::{
 };
 use reth_provider::{ProviderFactory, StageCheckpointReader, StageCheckpointWriter};
 use reth_tokio_util::EventListeners;
-use std::pin::Pin;
+use std::{collections::HashMap, pin::Pin};
 use tokio::sync::watch;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::*;
@@ -49,6 +49,15 @@ pub type PipelineWithResult<DB> = (Pipeline<DB>, Result<ControlFlow, PipelineErr
 /// After the entire pipeline has been run, it will run again unless asked to stop (see
 /// [Pipeline::set_max_block]).
 ///
+/// # Stage dependencies
+///
+/// By default, every stage depends on all stages before it, and runs up to the checkpoint of the
+/// previous one. Stages can declare the stages they depend on instead (see
+/// [PipelineBuilder::depends_on] and [StageSetBuilder::depends_on]), in which case they run up to
+/// the lowest checkpoint of their dependencies. Consecutive stages which don't depend on each
+/// other are still executed one after another, each committing its own progress, but a stage
+/// doesn't wait for the stages before it that it doesn't depend on to advance their checkpoints.
+///
 /// include_mmd!("docs/mermaid/pipeline.mmd")
 ///
 /// # Unwinding
@@ -65,6 +74,8 @@ pub struct Pipeline<DB: Database> {
     provider_factory: ProviderFactory<DB>,
     /// All configured stages in the order they will be executed.
     stages: Vec<BoxedStage<DB>>,
+    /// Declared dependencies of the stages.
+    dependencies: HashMap<StageId, Vec<StageId>>,
     /// The maximum block number to sync to.
     max_block: Option<BlockNumber>,
     /// All listeners for events the pipeline emits.
@@ -178,41 +189,94 @@ where
     /// pipeline (for example the `Finish` stage). Or [ControlFlow::Unwind] of the stage that caused
     /// the unwind.
     pub async fn run_loop(&mut self) -> Result<ControlFlow, PipelineError> {
-        let mut previous_stage = None;
-        for stage_index in 0..self.stages.len() {
-            let stage = &self.stages[stage_index];
-            let stage_id = stage.id();
-
-            trace!(target: "sync::pipeline", stage = %stage_id, "Executing stage");
-            let next = self.execute_stage_to_completion(previous_stage, stage_index).await?;
-
-            trace!(target: "sync::pipeline", stage = %stage_id, ?next, "Completed stage");
-
-            match next {
-                ControlFlow::NoProgress { block_number } => {
-                    if let Some(block_number) = block_number {
-                        self.progress.update(block_number);
+        // Checkpoints of the stages that have already been run in this pass.
+        let mut checkpoints = HashMap::new();
+        let mut previous_batch = Vec::new();
+        for batch in self.stage_batches() {
+            for &stage_index in &batch {
+                let previous_stage = self.stage_target(stage_index, &previous_batch, &checkpoints);
+                let stage_id = self.stages[stage_index].id();
+
+                trace!(target: "sync::pipeline", stage = %stage_id, "Executing stage");
+                let next = self.execute_stage_to_completion(previous_stage, stage_index).await?;
+
+                trace!(target: "sync::pipeline", stage = %stage_id, ?next, "Completed stage");
+
+                match next {
+                    ControlFlow::NoProgress { block_number } => {
+                        if let Some(block_number) = block_number {
+                            self.progress.update(block_number);
+                        }
+                    }
+                    ControlFlow::Continue { block_number } => self.progress.update(block_number),
+                    ControlFlow::Unwind { target, bad_block } => {
+                        self.unwind(target, Some(bad_block.number))?;
+                        return Ok(ControlFlow::Unwind { target, bad_block });
                     }
-                }
-                ControlFlow::Continue { block_number } => self.progress.update(block_number),
-                ControlFlow::Unwind { target, bad_block } => {
-                    self.unwind(target, Some(bad_block.number))?;
-                    return Ok(ControlFlow::Unwind { target, bad_block });
                 }
             }
 
-            previous_stage = Some(
-                self.provider_factory
-                    .provider()?
-                    .get_stage_checkpoint(stage_id)?
-                    .unwrap_or_default()
-                    .block_number,
-            );
+            let provider = self.provider_factory.provider()?;
+            previous_batch.clear();
+            for stage_index in batch {
+                let stage_id = self.stages[stage_index].id();
+                checkpoints.insert(
+                    stage_id,
+                    provider.get_stage_checkpoint(stage_id)?.unwrap_or_default().block_number,
+                );
+                previous_batch.push(stage_id);
+            }
         }
 
         Ok(self.progress.next_ctrl())
     }
 
+    /// Returns the declared dependencies of the stage which are before it in the pipeline, if
+    /// there are any.
+    fn declared_dependencies(&self, stage_index: usize) -> Option<Vec<StageId>> {
+        let dependencies = self.dependencies.get(&self.stages[stage_index].id())?;
+        let dependencies = self.stages[..stage_index]
+            .iter()
+            .map(|stage| stage.id())
+            .filter(|stage_id| dependencies.contains(stage_id))
+            .collect::<Vec<_>>();
+        (!dependencies.is_empty()).then_some(dependencies)
+    }
+
+    /// Splits the stages into batches of consecutive stages which don't depend on each other, in
+    /// the order of execution.
+    fn stage_batches(&self) -> Vec<Vec<usize>> {
+        let mut batches: Vec<Vec<usize>> = Vec::new();
+        for stage_index in 0..self.stages.len() {
+            match (batches.last_mut(), self.declared_dependencies(stage_index)) {
+                (Some(batch), Some(dependencies))
+                    if batch
+                        .iter()
+                        .all(|&index| !dependencies.contains(&self.stages[index].id())) =>
+                {
+                    batch.push(stage_index)
+                }
+                _ => batches.push(vec![stage_index]),
+            }
+        }
+        batches
+    }
+
+    /// Returns the checkpoint the stage should be executed up to, i.e. the lowest checkpoint of
+    /// its declared dependencies, or of the previous batch of stages if it has none.
+    fn stage_target(
+        &self,
+        stage_index: usize,
+        previous_batch: &[StageId],
+        checkpoints: &HashMap<StageId, BlockNumber>,
+    ) -> Option<BlockNumber> {
+        self.declared_dependencies(stage_index)
+            .unwrap_or_else(|| previous_batch.to_vec())
+            .iter()
+            .filter_map(|stage_id| checkpoints.get(stage_id).copied())
+            .min()
+    }
+
     /// Unwind the stages to the target block.
     ///
     /// If the unwind is due to a bad block the number of that block should be specified.
@@ -577,6 +641,299 @@ mod tests {
         );
     }
 
+    /// Runs a pipeline with stages which only depend on the first one.
+    /// Runs stages which only depend on the first one up to its checkpoint, one after another.
+    #[tokio::test]
+    async fn run_pipeline_with_dependencies() {
+        let provider_factory = create_test_provider_factory();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(5), done: false }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("C"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .depends_on(StageId::Other("B"), [StageId::Other("A")])
+            .depends_on(StageId::Other("C"), [StageId::Other("A")])
+            .build(provider_factory.clone());
+        let events = pipeline.events();
+
+        assert_eq!(pipeline.run_loop().await.unwrap(), ControlFlow::Continue { block_number: 10 });
+        drop(pipeline);
+
+        let provider = provider_factory.provider().unwrap();
+        for stage_id in [StageId::Other("A"), StageId::Other("B"), StageId::Other("C")] {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage_id).unwrap(),
+                Some(StageCheckpoint::new(10))
+            );
+        }
+
+        // Check that B and C were both run up to the checkpoint of A
+        assert_eq!(
+            events.collect::<Vec<PipelineEvent>>().await,
+            vec![
+                PipelineEvent::Run {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 1, total: 3 },
+                    stage_id: StageId::Other("A"),
+                    checkpoint: None,
+                    target: None,
+                },
+                PipelineEvent::Ran {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 1, total: 3 },
+                    stage_id: StageId::Other("A"),
+                    result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                },
+                PipelineEvent::Run {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 2, total: 3 },
+                    stage_id: StageId::Other("B"),
+                    checkpoint: None,
+                    target: Some(10),
+                },
+                PipelineEvent::Ran {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 2, total: 3 },
+                    stage_id: StageId::Other("B"),
+                    result: ExecOutput { checkpoint: StageCheckpoint::new(5), done: false },
+                },
+                PipelineEvent::Run {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 2, total: 3 },
+                    stage_id: StageId::Other("B"),
+                    checkpoint: Some(StageCheckpoint::new(5)),
+                    target: Some(10),
+                },
+                PipelineEvent::Ran {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 2, total: 3 },
+                    stage_id: StageId::Other("B"),
+                    result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                },
+                PipelineEvent::Run {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 3, total: 3 },
+                    stage_id: StageId::Other("C"),
+                    checkpoint: None,
+                    target: Some(10),
+                },
+                PipelineEvent::Ran {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 3, total: 3 },
+                    stage_id: StageId::Other("C"),
+                    result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                },
+            ]
+        );
+    }
+
+    /// Runs independent stages where the last one fails. The progress of the stage before it is
+    /// committed regardless.
+    #[tokio::test]
+    async fn run_pipeline_with_dependencies_and_error() {
+        let provider_factory = create_test_provider_factory();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("C"))
+                    .add_exec(Err(StageError::Fatal(Box::new(std::fmt::Error)))),
+            )
+            .depends_on(StageId::Other("B"), [StageId::Other("A")])
+            .depends_on(StageId::Other("C"), [StageId::Other("A")])
+            .build(provider_factory.clone());
+
+        assert_matches!(pipeline.run_loop().await, Err(PipelineError::Stage(StageError::Fatal(_))));
+        drop(pipeline);
+
+        let provider = provider_factory.provider().unwrap();
+        for stage_id in [StageId::Other("A"), StageId::Other("B")] {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage_id).unwrap(),
+                Some(StageCheckpoint::new(10))
+            );
+        }
+        assert_eq!(provider.get_stage_checkpoint(StageId::Other("C")).unwrap(), None);
+    }
+
+    /// Runs independent stages where the last one requires an unwind. All stages are unwound in
+    /// reverse order, and the next run continues from the unwind target.
+    #[tokio::test]
+    async fn run_pipeline_with_dependencies_and_unwind() {
+        let provider_factory = create_test_provider_factory();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }))
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(5) }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }))
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(5) }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("C"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(5), done: false }))
+                    .add_exec(Err(StageError::Block {
+                        block: Box::new(random_header(
+                            &mut generators::rng(),
+                            7,
+                            Default::default(),
+                        )),
+                        error: BlockErrorKind::Validation(
+                            consensus::ConsensusError::BaseFeeMissing,
+                        ),
+                    }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .depends_on(StageId::Other("B"), [StageId::Other("A")])
+            .depends_on(StageId::Other("C"), [StageId::Other("A")])
+            .build(provider_factory.clone());
+        let events = pipeline.events();
+
+        assert_matches!(
+            pipeline.run_loop().await.unwrap(),
+            ControlFlow::Unwind { target: 5, bad_block } if bad_block.number == 7
+        );
+
+        // C is unwound to its last checkpoint, so only A and B are unwound, in reverse order
+        let provider = provider_factory.provider().unwrap();
+        for stage_id in [StageId::Other("A"), StageId::Other("B"), StageId::Other("C")] {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage_id).unwrap(),
+                Some(StageCheckpoint::new(5))
+            );
+        }
+        drop(provider);
+
+        assert_eq!(pipeline.run_loop().await.unwrap(), ControlFlow::Continue { block_number: 10 });
+        drop(pipeline);
+
+        let provider = provider_factory.provider().unwrap();
+        for stage_id in [StageId::Other("A"), StageId::Other("B"), StageId::Other("C")] {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage_id).unwrap(),
+                Some(StageCheckpoint::new(10))
+            );
+        }
+
+        assert_eq!(
+            events.collect::<Vec<PipelineEvent>>().await,
+            vec![
+                PipelineEvent::Run {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 1, total: 3 },
+                    stage_id: StageId::Other("A"),
+                    checkpoint: None,
+                    target: None,
+                },
+                PipelineEvent::Ran {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 1, total: 3 },
+                    stage_id: StageId::Other("A"),
+                    result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                },
+                PipelineEvent::Run {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 2, total: 3 },
+                    stage_id: StageId::Other("B"),
+                    checkpoint: None,
+                    target: Some(10),
+                },
+                PipelineEvent::Ran {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 2, total: 3 },
+                    stage_id: StageId::Other("B"),
+                    result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                },
+                PipelineEvent::Run {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 3, total: 3 },
+                    stage_id: StageId::Other("C"),
+                    checkpoint: None,
+                    target: Some(10),
+                },
+                PipelineEvent::Ran {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 3, total: 3 },
+                    stage_id: StageId::Other("C"),
+                    result: ExecOutput { checkpoint: StageCheckpoint::new(5), done: false },
+                },
+                PipelineEvent::Run {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 3, total: 3 },
+                    stage_id: StageId::Other("C"),
+                    checkpoint: Some(StageCheckpoint::new(5)),
+                    target: Some(10),
+                },
+                PipelineEvent::Error { stage_id: StageId::Other("C") },
+                PipelineEvent::Unwind {
+                    stage_id: StageId::Other("B"),
+                    input: UnwindInput {
+                        checkpoint: StageCheckpoint::new(10),
+                        unwind_to: 5,
+                        bad_block: Some(7)
+                    }
+                },
+                PipelineEvent::Unwound {
+                    stage_id: StageId::Other("B"),
+                    result: UnwindOutput { checkpoint: StageCheckpoint::new(5) },
+                },
+                PipelineEvent::Unwind {
+                    stage_id: StageId::Other("A"),
+                    input: UnwindInput {
+                        checkpoint: StageCheckpoint::new(10),
+                        unwind_to: 5,
+                        bad_block: Some(7)
+                    }
+                },
+                PipelineEvent::Unwound {
+                    stage_id: StageId::Other("A"),
+                    result: UnwindOutput { checkpoint: StageCheckpoint::new(5) },
+                },
+                PipelineEvent::Run {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 1, total: 3 },
+                    stage_id: StageId::Other("A"),
+                    checkpoint: Some(StageCheckpoint::new(5)),
+                    target: None,
+                },
+                PipelineEvent::Ran {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 1, total: 3 },
+                    stage_id: StageId::Other("A"),
+                    result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                },
+                PipelineEvent::Run {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 2, total: 3 },
+                    stage_id: StageId::Other("B"),
+                    checkpoint: Some(StageCheckpoint::new(5)),
+                    target: Some(10),
+                },
+                PipelineEvent::Ran {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 2, total: 3 },
+                    stage_id: StageId::Other("B"),
+                    result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                },
+                PipelineEvent::Run {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 3, total: 3 },
+                    stage_id: StageId::Other("C"),
+                    checkpoint: Some(StageCheckpoint::new(5)),
+                    target: Some(10),
+                },
+                PipelineEvent::Ran {
+                    pipeline_stages_progress: PipelineStagesProgress { current: 3, total: 3 },
+                    stage_id: StageId::Other("C"),
+                    result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+                },
+            ]
+        );
+    }
+
     /// Unwinds a simple pipeline.
     #[tokio::test]
     async fn unwind_pipeline() {
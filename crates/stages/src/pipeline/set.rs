@@ -46,14 +46,19 @@ impl<DB: Database> Debug for StageEntry<DB> {
 /// to the final sync pipeline before/after their dependencies.
 ///
 /// Stages inside the set can be disabled, enabled, overridden and reordered.
+///
+/// By default, every stage depends on the stages before it. Stages with explicitly declared
+/// dependencies (see [`StageSetBuilder::depends_on`]) are only run up to the checkpoints of their
+/// dependencies by the [`Pipeline`](crate::Pipeline).
 pub struct StageSetBuilder<DB> {
     stages: HashMap<StageId, StageEntry<DB>>,
     order: Vec<StageId>,
+    dependencies: HashMap<StageId, Vec<StageId>>,
 }
 
 impl<DB: Database> Default for StageSetBuilder<DB> {
     fn default() -> Self {
-        Self { stages: HashMap::new(), order: Vec::new() }
+        Self { stages: HashMap::new(), order: Vec::new(), dependencies: HashMap::new() }
     }
 }
 
@@ -62,6 +67,7 @@ impl<DB: Database> Debug for StageSetBuilder<DB> {
         f.debug_struct("StageSetBuilder")
             .field("stages", &self.stages)
             .field("order", &self.order)
+            .field("dependencies", &self.dependencies)
             .finish()
     }
 }
@@ -120,7 +126,9 @@ where
     /// If a stage is in both sets, it is removed from its previous place in this set. Because of
     /// this, it is advisable to merge sets first and re-order stages after if needed.
     pub fn add_set<Set: StageSet<DB>>(mut self, set: Set) -> Self {
-        for stage in set.builder().build() {
+        let (stages, dependencies) = set.builder().build_with_dependencies();
+        self.dependencies.extend(dependencies);
+        for stage in stages {
             let target_index = self.order.len();
             self.order.push(stage.id());
             self.upsert_stage_state(stage, target_index);
@@ -200,8 +208,36 @@ where
         self
     }
 
+    /// Declares the stages that the stage with the given [`StageId`] depends on, instead of all
+    /// stages before it.
+    ///
+    /// The pipeline runs the stage up to the lowest checkpoint of its dependencies, rather than up
+    /// to the checkpoint of the previous stage. Stages are still executed one after another in
+    /// the order of the set, and dependencies that are not before the stage are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stage is not in this set.
+    pub fn depends_on(
+        mut self,
+        stage_id: StageId,
+        dependencies: impl IntoIterator<Item = StageId>,
+    ) -> Self {
+        assert!(self.stages.contains_key(&stage_id), "Stage does not exist in set: {stage_id}");
+        self.dependencies.insert(stage_id, dependencies.into_iter().collect());
+        self
+    }
+
     /// Consumes the builder and returns the contained [`Stage`]s in the order specified.
-    pub fn build(mut self) -> Vec<Box<dyn Stage<DB>>> {
+    pub fn build(self) -> Vec<Box<dyn Stage<DB>>> {
+        self.build_with_dependencies().0
+    }
+
+    /// Consumes the builder and returns the contained [`Stage`]s in the order specified, along
+    /// with the declared dependencies of the stages.
+    pub fn build_with_dependencies(
+        mut self,
+    ) -> (Vec<Box<dyn Stage<DB>>>, HashMap<StageId, Vec<StageId>>) {
         let mut stages = Vec::new();
         for id in &self.order {
             if let Some(entry) = self.stages.remove(id) {
@@ -210,7 +246,7 @@ where
                 }
             }
         }
-        stages
+        (stages, self.dependencies)
     }
 }
 
@@ -2,7 +2,7 @@ pub use crate::{
     error::{PipelineError, StageError},
     pipeline::{Pipeline, PipelineBuilder, PipelineEvent, StageSet, StageSetBuilder},
     sets::{
-        DefaultStages, ExecutionStages, HashingStages, HistoryIndexingStages, OfflineStages,
-        OnlineStages,
+        DefaultStages, ExecutionStages, HashingStages, HeaderOnlyStages, HistoryIndexingStages,
+        OfflineStages, OnlineStages,
     },
 };
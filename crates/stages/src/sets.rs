@@ -4,7 +4,8 @@
 //! instance of reth.
 //!
 //! It is also possible to run parts of reth standalone given the required data is present in
-//! the environment, such as [`ExecutionStages`] or [`HashingStages`].
+//! the environment, such as [`ExecutionStages`] or [`HashingStages`]. Nodes that only follow the
+//! header chain can use [`HeaderOnlyStages`].
 //!
 //!
 //! # Examples
@@ -48,6 +49,7 @@ use reth_interfaces::{
     consensus::Consensus,
     p2p::{bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader},
 };
+use reth_primitives::stage::StageId;
 use reth_provider::{ExecutorFactory, HeaderSyncGapProvider, HeaderSyncMode};
 use std::sync::Arc;
 
@@ -212,6 +214,53 @@ where
     }
 }
 
+/// A set containing only the stages required to sync the header chain.
+///
+/// This expands to the following series of stages:
+/// - [`HeaderStage`]
+/// - [`TotalDifficultyStage`]
+/// - [`FinishStage`]
+///
+/// Block bodies are never downloaded and no blocks are executed, so the resulting database only
+/// contains headers, total difficulties and canonical hashes.
+#[derive(Debug)]
+pub struct HeaderOnlyStages<Provider, H> {
+    /// Sync gap provider for the headers stage.
+    provider: Provider,
+    /// The sync mode for the headers stage.
+    header_mode: HeaderSyncMode,
+    /// The consensus engine used to validate incoming data.
+    consensus: Arc<dyn Consensus>,
+    /// The block header downloader
+    header_downloader: H,
+}
+
+impl<Provider, H> HeaderOnlyStages<Provider, H> {
+    /// Create a new set of header only stages with default values.
+    pub fn new(
+        provider: Provider,
+        header_mode: HeaderSyncMode,
+        consensus: Arc<dyn Consensus>,
+        header_downloader: H,
+    ) -> Self {
+        Self { provider, header_mode, consensus, header_downloader }
+    }
+}
+
+impl<DB, Provider, H> StageSet<DB> for HeaderOnlyStages<Provider, H>
+where
+    DB: Database,
+    Provider: HeaderSyncGapProvider + 'static,
+    H: HeaderDownloader + 'static,
+{
+    fn builder(self) -> StageSetBuilder<DB> {
+        StageSetBuilder::default()
+            .add_stage(HeaderStage::new(self.provider, self.header_downloader, self.header_mode))
+            .add_stage(TotalDifficultyStage::new(self.consensus))
+            .add_stage(FinishStage)
+    }
+}
+
 /// A set containing all stages that do not require network access.
 ///
 /// A combination of (in order)
@@ -266,6 +315,8 @@ impl<EF: ExecutorFactory, DB: Database> StageSet<DB> for ExecutionStages<EF> {
 }
 
 /// A set containing all stages that hash account state.
+///
+/// Account and storage hashing both only depend on the unwind of the merkle stage.
 #[derive(Debug, Default)]
 #[non_exhaustive]
 pub struct HashingStages;
@@ -277,10 +328,15 @@ impl<DB: Database> StageSet<DB> for HashingStages {
             .add_stage(AccountHashingStage::default())
             .add_stage(StorageHashingStage::default())
             .add_stage(MerkleStage::default_execution())
+            .depends_on(StageId::AccountHashing, [StageId::MerkleUnwind])
+            .depends_on(StageId::StorageHashing, [StageId::MerkleUnwind])
     }
 }
 
 /// A set containing all stages that do additional indexing for historical state.
+///
+/// The indexing stages only depend on the execution, so they are run up to its checkpoint
+/// regardless of the progress of the stages between them and the execution.
 #[derive(Debug, Default)]
 #[non_exhaustive]
 pub struct HistoryIndexingStages;
@@ -291,5 +347,8 @@ impl<DB: Database> StageSet<DB> for HistoryIndexingStages {
             .add_stage(TransactionLookupStage::default())
             .add_stage(IndexStorageHistoryStage::default())
             .add_stage(IndexAccountHistoryStage::default())
+            .depends_on(StageId::TransactionLookup, [StageId::Execution])
+            .depends_on(StageId::IndexStorageHistory, [StageId::Execution])
+            .depends_on(StageId::IndexAccountHistory, [StageId::Execution])
     }
 }
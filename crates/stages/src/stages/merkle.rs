@@ -15,7 +15,7 @@ use reth_primitives::{
 use reth_provider::{
     DatabaseProviderRW, HeaderProvider, ProviderError, StageCheckpointReader, StageCheckpointWriter,
 };
-use reth_trie::{IntermediateStateRootState, StateRoot, StateRootProgress};
+use reth_trie::{IntermediateStateRootState, StateRoot, StateRootProgress, TrieNodeCache};
 use std::fmt::Debug;
 use tracing::*;
 
@@ -51,6 +51,8 @@ pub enum MerkleStage {
         /// The threshold (in number of blocks) for switching from incremental trie building
         /// of changes to whole rebuild.
         clean_threshold: u64,
+        /// The cache that is populated with the trie nodes computed by incremental trie updates.
+        node_cache: Option<TrieNodeCache>,
     },
     /// The unwind portion of the merkle stage.
     Unwind,
@@ -64,7 +66,7 @@ pub enum MerkleStage {
 impl MerkleStage {
     /// Stage default for the [MerkleStage::Execution].
     pub fn default_execution() -> Self {
-        Self::Execution { clean_threshold: MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD }
+        Self::new_execution(MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD)
     }
 
     /// Stage default for the [MerkleStage::Unwind].
@@ -74,7 +76,17 @@ impl MerkleStage {
 
     /// Create new instance of [MerkleStage::Execution].
     pub fn new_execution(clean_threshold: u64) -> Self {
-        Self::Execution { clean_threshold }
+        Self::Execution { clean_threshold, node_cache: None }
+    }
+
+    /// Sets the cache that is populated with the trie nodes computed by incremental trie updates.
+    ///
+    /// This has no effect on [MerkleStage::Unwind].
+    pub fn with_node_cache(mut self, cache: TrieNodeCache) -> Self {
+        if let MerkleStage::Execution { node_cache, .. } = &mut self {
+            *node_cache = Some(cache);
+        }
+        self
     }
 
     /// Gets the hashing progress
@@ -130,14 +142,16 @@ impl<DB: Database> Stage<DB> for MerkleStage {
         provider: &DatabaseProviderRW<DB>,
         input: ExecInput,
     ) -> Result<ExecOutput, StageError> {
-        let threshold = match self {
+        let (threshold, node_cache) = match self {
             MerkleStage::Unwind => {
                 info!(target: "sync::stages::merkle::unwind", "Stage is always skipped");
                 return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
             }
-            MerkleStage::Execution { clean_threshold } => *clean_threshold,
+            MerkleStage::Execution { clean_threshold, node_cache } => {
+                (*clean_threshold, node_cache.clone())
+            }
             #[cfg(any(test, feature = "test-utils"))]
-            MerkleStage::Both { clean_threshold } => *clean_threshold,
+            MerkleStage::Both { clean_threshold } => (*clean_threshold, None),
         };
 
         let range = input.next_block_range();
@@ -226,9 +240,9 @@ impl<DB: Database> Stage<DB> for MerkleStage {
             }
         } else {
             debug!(target: "sync::stages::merkle::exec", current = ?current_block_number, target = ?to_block, "Updating trie");
-            let (root, updates) =
-                StateRoot::incremental_root_with_updates(provider.tx_ref(), range)
-                    .map_err(|e| StageError::Fatal(Box::new(e)))?;
+            let (root, updates) = StateRoot::incremental_root_calculator(provider.tx_ref(), range)
+                .and_then(|calculator| calculator.with_node_cache(node_cache).root_with_updates())
+                .map_err(|e| StageError::Fatal(Box::new(e)))?;
             updates.flush(provider.tx_ref())?;
 
             let total_hashed_entries = (provider.tx_ref().entries::<tables::HashedAccount>()? +
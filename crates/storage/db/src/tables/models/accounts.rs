@@ -8,7 +8,7 @@ use crate::{
     DatabaseError,
 };
 use reth_codecs::{derive_arbitrary, Compact};
-use reth_primitives::{Account, Address, BlockNumber, Buf};
+use reth_primitives::{Account, Address, BlockNumber, Buf, StorageEntry, B256};
 use serde::{Deserialize, Serialize};
 
 /// Account as it is saved inside [`AccountChangeSet`][crate::tables::AccountChangeSet].
@@ -122,6 +122,62 @@ impl Decode for BlockNumberAddress {
 
 impl_fixed_arbitrary!(BlockNumberAddress, 28);
 
+/// All account changes of a block. Row of the
+/// [`SnapshotSegment::AccountChangeSets`](reth_primitives::SnapshotSegment::AccountChangeSets)
+/// snapshot segment.
+#[derive_arbitrary(compact)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Compact)]
+pub struct BlockAccountChangeSet {
+    /// Accounts before the block, sorted by address.
+    pub accounts: Vec<AccountBeforeTx>,
+}
+
+impl BlockAccountChangeSet {
+    /// Returns the account before the block, if it was changed in the block.
+    pub fn account(&self, address: Address) -> Option<&AccountBeforeTx> {
+        self.accounts
+            .binary_search_by_key(&address, |account| account.address)
+            .ok()
+            .map(|index| &self.accounts[index])
+    }
+}
+
+/// Storage changes of one account in a block.
+#[derive_arbitrary(compact)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Compact)]
+pub struct AccountStorageChangeSet {
+    /// Address of the account.
+    pub address: Address,
+    /// Storage slots before the block, sorted by key.
+    pub storage: Vec<StorageEntry>,
+}
+
+/// All storage changes of a block. Row of the
+/// [`SnapshotSegment::StorageChangeSets`](reth_primitives::SnapshotSegment::StorageChangeSets)
+/// snapshot segment.
+#[derive_arbitrary(compact)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Compact)]
+pub struct BlockStorageChangeSet {
+    /// Storage changes of every account, sorted by address.
+    pub accounts: Vec<AccountStorageChangeSet>,
+}
+
+impl BlockStorageChangeSet {
+    /// Returns the storage slot before the block, if it was changed in the block.
+    pub fn storage(&self, address: Address, key: B256) -> Option<&StorageEntry> {
+        let index = self.accounts.binary_search_by_key(&address, |account| account.address).ok()?;
+        let storage = &self.accounts[index].storage;
+        storage.binary_search_by_key(&key, |entry| entry.key).ok().map(|index| &storage[index])
+    }
+
+    /// Returns an iterator over all changed storage slots with their account address.
+    pub fn iter(&self) -> impl Iterator<Item = (Address, &StorageEntry)> {
+        self.accounts
+            .iter()
+            .flat_map(|account| account.storage.iter().map(|entry| (account.address, entry)))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,148 @@
+use crate::{DataReader, NippyJarError};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Default number of rows covered by one column checksum.
+pub const DEFAULT_CHECKSUM_CHUNK_ROWS: usize = 1_000;
+
+/// CRC32 checksums of the data file, computed per column over chunks of rows.
+///
+/// Checksums are calculated over the bytes as stored on disk (ie. compressed, if a compressor is
+/// used), so verifying them does not require any decompression.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checksums {
+    /// Number of rows covered by a single checksum. The last chunk might be smaller.
+    rows_per_chunk: usize,
+    /// Checksum of every column chunk, laid out as `[chunk * columns + column]`.
+    values: Vec<u32>,
+}
+
+impl Checksums {
+    /// Creates an empty [`Checksums`] with chunks of `rows_per_chunk` rows.
+    pub fn new(rows_per_chunk: usize) -> Self {
+        Self { rows_per_chunk: rows_per_chunk.max(1), values: Vec::new() }
+    }
+
+    /// Returns the number of rows covered by a single checksum.
+    pub fn rows_per_chunk(&self) -> usize {
+        self.rows_per_chunk
+    }
+
+    /// Returns the number of chunks required to cover `rows`.
+    pub fn chunks(&self, rows: usize) -> usize {
+        (rows + self.rows_per_chunk - 1) / self.rows_per_chunk
+    }
+
+    /// Returns the row range of `chunk`, bounded by `rows`.
+    pub fn chunk_rows(&self, chunk: usize, rows: usize) -> Range<usize> {
+        let start = chunk * self.rows_per_chunk;
+        start..(start + self.rows_per_chunk).min(rows)
+    }
+
+    /// Returns the checksum of `column` over `chunk`.
+    pub fn get(&self, chunk: usize, column: usize, columns: usize) -> Option<u32> {
+        self.values.get(chunk * columns + column).copied()
+    }
+
+    /// Returns all checksums.
+    pub fn values(&self) -> &[u32] {
+        &self.values
+    }
+
+    /// Returns a mutable reference to all checksums.
+    pub(crate) fn values_mut(&mut self) -> &mut Vec<u32> {
+        &mut self.values
+    }
+}
+
+/// How much of a [`crate::NippyJar`] gets checked by [`crate::NippyJar::verify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verification {
+    /// Verifies every column chunk.
+    Full,
+    /// Verifies this many column chunks evenly spread over the jar, plus the last one, since
+    /// that's where a truncated copy shows up.
+    Sampled(usize),
+}
+
+impl Verification {
+    /// Returns the chunks to verify out of `chunks`.
+    pub(crate) fn chunks(&self, chunks: usize) -> Vec<usize> {
+        match *self {
+            Verification::Sampled(samples) if samples < chunks => {
+                let mut selected = (0..samples).map(|i| i * chunks / samples).collect::<Vec<_>>();
+                if selected.last() != Some(&(chunks - 1)) {
+                    selected.push(chunks - 1);
+                }
+                selected
+            }
+            _ => (0..chunks).collect(),
+        }
+    }
+}
+
+/// Corrupted rows found by [`crate::NippyJar::verify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Corruption {
+    /// The stored checksum of a column chunk does not match its data.
+    ChecksumMismatch {
+        /// Affected column.
+        column: usize,
+        /// Affected rows.
+        rows: Range<usize>,
+        /// Checksum stored in the configuration.
+        expected: u32,
+        /// Checksum of the data on disk.
+        actual: u32,
+    },
+    /// Rows which are missing from the data or offsets file.
+    Truncated {
+        /// Affected rows.
+        rows: Range<usize>,
+    },
+}
+
+impl Corruption {
+    /// Returns the affected rows.
+    pub fn rows(&self) -> &Range<usize> {
+        match self {
+            Corruption::ChecksumMismatch { rows, .. } | Corruption::Truncated { rows } => rows,
+        }
+    }
+}
+
+impl std::fmt::Display for Corruption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Corruption::ChecksumMismatch { column, rows, expected, actual } => write!(
+                f,
+                "checksum mismatch on column {column} for rows {}..={}: expected {expected:#010x}, got {actual:#010x}",
+                rows.start,
+                rows.end.saturating_sub(1)
+            ),
+            Corruption::Truncated { rows } => {
+                write!(f, "rows {}..={} are missing", rows.start, rows.end.saturating_sub(1))
+            }
+        }
+    }
+}
+
+/// Feeds the on-disk bytes of `rows` into one hasher per column.
+pub(crate) fn hash_rows(
+    reader: &DataReader,
+    columns: usize,
+    rows: Range<usize>,
+    hashers: &mut [crc32fast::Hasher],
+) -> Result<(), NippyJarError> {
+    for row in rows {
+        for (column, hasher) in hashers.iter_mut().enumerate().take(columns) {
+            let index = row * columns + column;
+            let (start, end) = (reader.offset(index) as usize, reader.offset(index + 1) as usize);
+            if start > end || end > reader.size() {
+                return Err(NippyJarError::OffsetOutOfBounds(index))
+            }
+            hasher.update(reader.data(start..end));
+        }
+    }
+    Ok(())
+}
@@ -2,9 +2,12 @@ use crate::NippyJarError;
 use serde::{Deserialize, Serialize};
 
 mod zstd;
-pub use self::zstd::{DecoderDictionary, Decompressor, Zstd, ZstdState};
+pub(crate) use self::zstd::compress_to_vec;
+pub use self::zstd::{DecoderDictionary, Decompressor, Zstd, ZstdState, MAX_LONG_WINDOW_LOG};
 mod lz4;
 pub use self::lz4::Lz4;
+mod uncompressed;
+pub use self::uncompressed::Uncompressed;
 
 /// Trait that will compress column values
 pub trait Compression: Serialize + for<'a> Deserialize<'a> {
@@ -45,6 +48,7 @@ pub trait Compression: Serialize + for<'a> Deserialize<'a> {
 pub enum Compressors {
     Zstd(Zstd),
     Lz4(Lz4),
+    Uncompressed(Uncompressed),
 }
 
 impl Compression for Compressors {
@@ -52,12 +56,14 @@ impl Compression for Compressors {
         match self {
             Compressors::Zstd(zstd) => zstd.decompress_to(value, dest),
             Compressors::Lz4(lz4) => lz4.decompress_to(value, dest),
+            Compressors::Uncompressed(uncompressed) => uncompressed.decompress_to(value, dest),
         }
     }
     fn decompress(&self, value: &[u8]) -> Result<Vec<u8>, NippyJarError> {
         match self {
             Compressors::Zstd(zstd) => zstd.decompress(value),
             Compressors::Lz4(lz4) => lz4.decompress(value),
+            Compressors::Uncompressed(uncompressed) => uncompressed.decompress(value),
         }
     }
 
@@ -67,6 +73,7 @@ impl Compression for Compressors {
             let result = match self {
                 Compressors::Zstd(zstd) => zstd.compress_to(src, dest),
                 Compressors::Lz4(lz4) => lz4.compress_to(src, dest),
+                Compressors::Uncompressed(uncompressed) => uncompressed.compress_to(src, dest),
             };
 
             match result {
@@ -85,6 +92,7 @@ impl Compression for Compressors {
         match self {
             Compressors::Zstd(zstd) => zstd.compress(src),
             Compressors::Lz4(lz4) => lz4.compress(src),
+            Compressors::Uncompressed(uncompressed) => uncompressed.compress(src),
         }
     }
 
@@ -92,6 +100,7 @@ impl Compression for Compressors {
         match self {
             Compressors::Zstd(zstd) => zstd.is_ready(),
             Compressors::Lz4(lz4) => lz4.is_ready(),
+            Compressors::Uncompressed(uncompressed) => uncompressed.is_ready(),
         }
     }
 
@@ -102,6 +111,7 @@ impl Compression for Compressors {
         match self {
             Compressors::Zstd(zstd) => zstd.prepare_compression(columns),
             Compressors::Lz4(lz4) => lz4.prepare_compression(columns),
+            Compressors::Uncompressed(uncompressed) => uncompressed.prepare_compression(columns),
         }
     }
 }
@@ -0,0 +1,30 @@
+use crate::{compression::Compression, NippyJarError};
+use serde::{Deserialize, Serialize};
+
+/// Passthrough [`Compression`] that stores column values as they are.
+///
+/// Useful for columns which don't compress well (eg. hashes), since values can be read straight
+/// from the memory-mapped data file.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default)]
+#[non_exhaustive]
+pub struct Uncompressed;
+
+impl Compression for Uncompressed {
+    fn decompress_to(&self, value: &[u8], dest: &mut Vec<u8>) -> Result<(), NippyJarError> {
+        dest.extend_from_slice(value);
+        Ok(())
+    }
+
+    fn decompress(&self, value: &[u8]) -> Result<Vec<u8>, NippyJarError> {
+        Ok(value.to_vec())
+    }
+
+    fn compress_to(&self, src: &[u8], dest: &mut Vec<u8>) -> Result<usize, NippyJarError> {
+        dest.extend_from_slice(src);
+        Ok(src.len())
+    }
+
+    fn compress(&self, src: &[u8]) -> Result<Vec<u8>, NippyJarError> {
+        Ok(src.to_vec())
+    }
+}
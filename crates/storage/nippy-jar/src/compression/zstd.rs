@@ -12,6 +12,10 @@ pub use zstd::{bulk::Decompressor, dict::DecoderDictionary};
 
 type RawDictionary = Vec<u8>;
 
+/// Largest window log usable with [`Zstd::with_long_window`]. Decoders reject bigger windows unless
+/// they explicitly raise their own limit.
+pub const MAX_LONG_WINDOW_LOG: u32 = 27;
+
 #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum ZstdState {
     #[default]
@@ -27,6 +31,8 @@ pub struct Zstd {
     pub(crate) state: ZstdState,
     /// Compression level. A level of `0` uses zstd's default (currently `3`).
     pub(crate) level: i32,
+    /// Window log used with long distance matching. Disabled if `None`.
+    pub(crate) long_window: Option<u32>,
     /// Uses custom dictionaries to compress data.
     pub use_dict: bool,
     /// Max size of a dictionary
@@ -44,6 +50,7 @@ impl Zstd {
         Self {
             state: if use_dict { ZstdState::PendingDictionary } else { ZstdState::Ready },
             level: 0,
+            long_window: None,
             use_dict,
             max_dict_size,
             dictionaries: None,
@@ -51,11 +58,29 @@ impl Zstd {
         }
     }
 
+    /// Sets the compression level.
     pub fn with_level(mut self, level: i32) -> Self {
         self.level = level;
         self
     }
 
+    /// Enables long distance matching with a window of `2^window_log` bytes, capped at
+    /// [`MAX_LONG_WINDOW_LOG`].
+    pub fn with_long_window(mut self, window_log: u32) -> Self {
+        self.long_window = Some(window_log.min(MAX_LONG_WINDOW_LOG));
+        self
+    }
+
+    /// Returns the compression level.
+    pub fn level(&self) -> i32 {
+        self.level
+    }
+
+    /// Returns the window log used with long distance matching, if enabled.
+    pub fn long_window(&self) -> Option<u32> {
+        self.long_window
+    }
+
     /// Creates a list of [`Decompressor`] if using dictionaries.
     pub fn decompressors(&self) -> Result<Vec<Decompressor<'_>>, NippyJarError> {
         if let Some(dictionaries) = &self.dictionaries {
@@ -66,8 +91,8 @@ impl Zstd {
         Ok(vec![])
     }
 
-    /// If using dictionaries, creates a list of [`Compressor`].
-    pub fn compressors(&self) -> Result<Option<Vec<Compressor<'_>>>, NippyJarError> {
+    /// If using dictionaries, creates a list of [`Compressor`], one per column.
+    pub fn compressors(&self) -> Result<Option<Vec<Compressor<'static>>>, NippyJarError> {
         match self.state {
             ZstdState::PendingDictionary => Err(NippyJarError::CompressorNotReady),
             ZstdState::Ready => {
@@ -77,7 +102,14 @@ impl Zstd {
 
                 if let Some(dictionaries) = &self.dictionaries {
                     debug!(target: "nippy-jar", count=?dictionaries.len(), "Generating ZSTD compressor dictionaries.");
-                    return Ok(Some(dictionaries.compressors()?))
+                    let mut compressors = dictionaries.compressors(self.level)?;
+                    if let Some(window_log) = self.long_window {
+                        for compressor in &mut compressors {
+                            compressor.long_distance_matching(true)?;
+                            compressor.window_log(window_log)?;
+                        }
+                    }
+                    return Ok(Some(compressors))
                 }
                 Ok(None)
             }
@@ -163,14 +195,15 @@ impl Compression for Zstd {
     }
 
     fn compress_to(&self, src: &[u8], dest: &mut Vec<u8>) -> Result<usize, NippyJarError> {
-        let before = dest.len();
-
-        let mut encoder = zstd::Encoder::new(dest, self.level)?;
-        encoder.write_all(src)?;
-
-        let dest = encoder.finish()?;
+        // Single shot compression knows the source size, so zstd shrinks the window to it instead
+        // of allocating the whole long distance window for every value.
+        let mut compressor = Compressor::new(self.level)?;
+        if let Some(window_log) = self.long_window {
+            compressor.long_distance_matching(true)?;
+            compressor.window_log(window_log)?;
+        }
 
-        Ok(dest.len() - before)
+        compress_to_vec(&mut compressor, src, dest)
     }
 
     fn compress(&self, src: &[u8]) -> Result<Vec<u8>, NippyJarError> {
@@ -234,6 +267,24 @@ impl Compression for Zstd {
     }
 }
 
+/// Appends `src` compressed with `compressor` to `dest`, reserving more capacity if necessary.
+///
+/// Returns number of bytes written to `dest`.
+pub(crate) fn compress_to_vec(
+    compressor: &mut Compressor<'_>,
+    src: &[u8],
+    dest: &mut Vec<u8>,
+) -> Result<usize, NippyJarError> {
+    // Reserves the worst case size, since small values might grow once compressed.
+    let before = dest.len();
+    dest.resize(before + zstd::zstd_safe::compress_bound(src.len()), 0);
+
+    let written = compressor.compress_to_buffer(src, &mut dest[before..])?;
+    dest.truncate(before + written);
+
+    Ok(written)
+}
+
 mod dictionaries_serde {
     use super::*;
 
@@ -299,14 +350,15 @@ impl<'a> ZstdDictionaries<'a> {
             .collect::<Result<Vec<_>, _>>()?)
     }
 
-    /// Creates a list of compressors from a list of [`ZstdDictionary::Raw`].
-    pub fn compressors(&self) -> Result<Vec<Compressor<'_>>, NippyJarError> {
+    /// Creates a list of compressors with the given compression `level` from a list of
+    /// [`ZstdDictionary::Raw`].
+    pub fn compressors(&self, level: i32) -> Result<Vec<Compressor<'static>>, NippyJarError> {
         Ok(self
             .iter()
             .flat_map(|dict| {
                 dict.raw()
                     .ok_or(NippyJarError::CompressorNotAllowed)
-                    .map(|dict| Compressor::with_dictionary(0, dict))
+                    .map(|dict| Compressor::with_dictionary(level, dict))
             })
             .collect::<Result<Vec<_>, _>>()?)
     }
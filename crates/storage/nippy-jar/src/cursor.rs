@@ -223,7 +223,9 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
             value_offset..next_value_offset
         };
 
-        if let Some(compression) = self.jar.compressor() {
+        if let Some(compression) =
+            self.jar.compressor().filter(|c| !matches!(c, Compressors::Uncompressed(_)))
+        {
             let from = self.internal_buffer.len();
             match compression {
                 Compressors::Zstd(z) if z.use_dict => {
@@ -45,4 +45,12 @@ pub enum NippyJarError {
     InvalidPruning(u64, u64),
     #[error("jar has been frozen and cannot be modified.")]
     FrozenJar,
+    #[error("offset {0} points outside of the data file.")]
+    OffsetOutOfBounds(usize),
+    #[error("expected {0} checksums, but the jar has {1}.")]
+    InvalidChecksums(usize, usize),
+    #[error("jar is corrupted: {0}.")]
+    Corrupted(crate::Corruption),
+    #[error("unsupported jar version {0}, expected version {1}. The jar must be recreated.")]
+    UnsupportedVersion(usize, usize),
 }
@@ -23,7 +23,7 @@ use sucds::{int_vectors::PrefixSummedEliasFano, Serializable};
 use tracing::*;
 
 pub mod filter;
-use filter::{Cuckoo, InclusionFilter, InclusionFilters};
+use filter::{Bloom, Cuckoo, InclusionFilter, InclusionFilters};
 
 pub mod compression;
 use compression::{Compression, Compressors};
@@ -32,6 +32,9 @@ pub mod phf;
 pub use phf::PHFKey;
 use phf::{Fmph, Functions, GoFmph, PerfectHashingFunction};
 
+mod checksum;
+pub use checksum::{Checksums, Corruption, Verification, DEFAULT_CHECKSUM_CHUNK_ROWS};
+
 mod error;
 pub use error::NippyJarError;
 
@@ -41,7 +44,7 @@ pub use cursor::NippyJarCursor;
 mod writer;
 pub use writer::NippyJarWriter;
 
-const NIPPY_JAR_VERSION: usize = 1;
+const NIPPY_JAR_VERSION: usize = 3;
 
 const INDEX_FILE_EXTENSION: &str = "idx";
 const OFFSETS_FILE_EXTENSION: &str = "off";
@@ -104,6 +107,8 @@ pub struct NippyJar<H = ()> {
     rows: usize,
     /// Optional compression algorithm applied to the data.
     compressor: Option<Compressors>,
+    /// Optional checksums of the data, used to detect corrupted or truncated files.
+    checksums: Option<Checksums>,
     #[serde(skip)]
     /// Optional filter function for data membership checks.
     filter: Option<InclusionFilters>,
@@ -129,6 +134,7 @@ impl<H: NippyJarHeader> std::fmt::Debug for NippyJar<H> {
             .field("rows", &self.rows)
             .field("columns", &self.columns)
             .field("compressor", &self.compressor)
+            .field("checksums (len)", &self.checksums.as_ref().map(|c| c.values().len()))
             .field("filter", &self.filter)
             .field("phf", &self.phf)
             .field("offsets_index (len)", &self.offsets_index.len())
@@ -166,6 +172,7 @@ impl<H: NippyJarHeader> NippyJar<H> {
             rows: 0,
             max_row_size: 0,
             compressor: None,
+            checksums: None,
             filter: None,
             phf: None,
             offsets_index: PrefixSummedEliasFano::default(),
@@ -180,18 +187,54 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Sets the level of [`compression::Zstd`] compression. Does nothing if the jar doesn't use
+    /// it.
+    pub fn with_zstd_level(mut self, level: i32) -> Self {
+        if let Some(Compressors::Zstd(zstd)) = &mut self.compressor {
+            zstd.level = level;
+        }
+        self
+    }
+
+    /// Enables long distance matching of [`compression::Zstd`] compression with a window of
+    /// `2^window_log` bytes. Does nothing if the jar doesn't use it.
+    pub fn with_zstd_long_window(mut self, window_log: u32) -> Self {
+        if let Some(Compressors::Zstd(zstd)) = &mut self.compressor {
+            zstd.long_window = Some(window_log.min(compression::MAX_LONG_WINDOW_LOG));
+        }
+        self
+    }
+
     /// Adds [`compression::Lz4`] compression.
     pub fn with_lz4(mut self) -> Self {
         self.compressor = Some(Compressors::Lz4(compression::Lz4::default()));
         self
     }
 
+    /// Adds [`compression::Uncompressed`] passthrough compression.
+    pub fn with_uncompressed(mut self) -> Self {
+        self.compressor = Some(Compressors::Uncompressed(compression::Uncompressed::default()));
+        self
+    }
+
+    /// Adds [`Checksums`] over chunks of `rows_per_chunk` rows of every column.
+    pub fn with_checksums(mut self, rows_per_chunk: usize) -> Self {
+        self.checksums = Some(Checksums::new(rows_per_chunk));
+        self
+    }
+
     /// Adds [`filter::Cuckoo`] filter.
     pub fn with_cuckoo_filter(mut self, max_capacity: usize) -> Self {
         self.filter = Some(InclusionFilters::Cuckoo(Cuckoo::new(max_capacity)));
         self
     }
 
+    /// Adds [`filter::Bloom`] filter.
+    pub fn with_bloom_filter(mut self, max_capacity: usize) -> Self {
+        self.filter = Some(InclusionFilters::Bloom(Bloom::new(max_capacity)));
+        self
+    }
+
     /// Adds [`phf::Fmph`] perfect hashing function.
     pub fn with_fmph(mut self) -> Self {
         self.phf = Some(Functions::Fmph(Fmph::new()));
@@ -224,6 +267,38 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self.compressor.as_ref()
     }
 
+    /// Gets a reference to the checksums.
+    pub fn checksums(&self) -> Option<&Checksums> {
+        self.checksums.as_ref()
+    }
+
+    /// Returns the number of rows in the jar.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns in the jar.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns the number of bytes taken by each column in the data file.
+    pub fn column_sizes(&self) -> Result<Vec<u64>, NippyJarError> {
+        let mut sizes = vec![0; self.columns];
+        if self.rows == 0 {
+            return Ok(sizes)
+        }
+
+        let reader = DataReader::new(self.data_path())?;
+        for row in 0..self.rows {
+            for (column, size) in sizes.iter_mut().enumerate() {
+                let index = row * self.columns + column;
+                *size += reader.offset(index + 1) - reader.offset(index);
+            }
+        }
+        Ok(sizes)
+    }
+
     /// Gets a mutable reference to the compressor.
     pub fn compressor_mut(&mut self) -> Option<&mut Compressors> {
         self.compressor.as_mut()
@@ -233,15 +308,106 @@ impl<H: NippyJarHeader> NippyJar<H> {
     /// structures or the offset list.
     ///
     /// **The user must ensure the header type matches the one used during the jar's creation.**
+    ///
+    /// Returns [`NippyJarError::UnsupportedVersion`] if the jar was created with a different
+    /// version of the format.
     pub fn load(path: &Path) -> Result<Self, NippyJarError> {
         // Read [`Self`] located at the data file.
-        let config_file = File::open(path.with_extension(CONFIG_FILE_EXTENSION))?;
+        let config = std::fs::read(path.with_extension(CONFIG_FILE_EXTENSION))?;
 
-        let mut obj: Self = bincode::deserialize_from(&config_file)?;
+        // The version is the first field and the layout of the remaining fields depends on it.
+        let version: usize = bincode::deserialize(&config)?;
+        if version != NIPPY_JAR_VERSION {
+            return Err(NippyJarError::UnsupportedVersion(version, NIPPY_JAR_VERSION))
+        }
+
+        let mut obj: Self = bincode::deserialize(&config)?;
         obj.path = path.to_path_buf();
         Ok(obj)
     }
 
+    /// Loads the file configuration like [`Self::load`] and checks the data file against its
+    /// checksums, returning [`NippyJarError::Corrupted`] on the first corruption found.
+    pub fn load_verified(path: &Path, verification: Verification) -> Result<Self, NippyJarError> {
+        let jar = Self::load(path)?;
+        if let Some(corruption) = jar.verify(verification)?.into_iter().next() {
+            return Err(NippyJarError::Corrupted(corruption))
+        }
+        Ok(jar)
+    }
+
+    /// Checks the data and offsets files for missing rows and, if the jar has [`Checksums`],
+    /// compares the checksum of every verified column chunk against the data on disk.
+    ///
+    /// Returns every [`Corruption`] found, sorted by row.
+    pub fn verify(&self, verification: Verification) -> Result<Vec<Corruption>, NippyJarError> {
+        let reader = self.open_data_reader()?;
+        let mut corruptions = Vec::new();
+
+        // Rows whose offsets exist and point inside the data file.
+        let mut valid_rows = reader
+            .offsets_count()?
+            .saturating_sub(1)
+            .checked_div(self.columns)
+            .map_or(self.rows, |rows| rows.min(self.rows));
+        // Offsets are increasing, so look for the first row ending past the data file.
+        let (mut low, mut high) = (0, valid_rows);
+        while low < high {
+            let row = (low + high) / 2;
+            if reader.offset((row + 1) * self.columns) <= reader.size() as u64 {
+                low = row + 1;
+            } else {
+                high = row;
+            }
+        }
+        valid_rows = low;
+
+        let Some(checksums) = &self.checksums else {
+            if valid_rows < self.rows {
+                corruptions.push(Corruption::Truncated { rows: valid_rows..self.rows });
+            }
+            return Ok(corruptions)
+        };
+
+        let chunks = checksums.chunks(self.rows);
+        if checksums.values().len() != chunks * self.columns {
+            return Err(NippyJarError::InvalidChecksums(
+                chunks * self.columns,
+                checksums.values().len(),
+            ))
+        }
+
+        for chunk in verification.chunks(chunks) {
+            let rows = checksums.chunk_rows(chunk, self.rows);
+            // Rows past `valid_rows` are reported as truncated below.
+            if rows.end > valid_rows {
+                continue
+            }
+
+            let mut hashers = vec![crc32fast::Hasher::new(); self.columns];
+            checksum::hash_rows(&reader, self.columns, rows.clone(), &mut hashers)?;
+
+            for (column, hasher) in hashers.into_iter().enumerate() {
+                let expected = checksums.get(chunk, column, self.columns).expect("qed");
+                let actual = hasher.finalize();
+                if expected != actual {
+                    corruptions.push(Corruption::ChecksumMismatch {
+                        column,
+                        rows: rows.clone(),
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        if valid_rows < self.rows {
+            corruptions.push(Corruption::Truncated { rows: valid_rows..self.rows });
+        }
+
+        Ok(corruptions)
+    }
+
     /// Loads filters into memory
     pub fn load_filters(mut self) -> Result<Self, NippyJarError> {
         // Read the offsets lists located at the index file.
@@ -793,6 +959,116 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_zstd_level_and_long_window() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        // Options don't apply to other compressors
+        let nippy = NippyJar::new_without_header(num_columns, file_path.path())
+            .with_lz4()
+            .with_zstd_level(19);
+        assert!(matches!(nippy.compressor(), Some(Compressors::Lz4(_))));
+
+        let mut nippy = NippyJar::new_without_header(num_columns, file_path.path())
+            .with_zstd(false, 5000)
+            .with_zstd_level(19)
+            .with_zstd_long_window(30);
+
+        nippy.freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows).unwrap();
+
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy, loaded_nippy);
+
+        if let Some(Compressors::Zstd(zstd)) = loaded_nippy.compressor() {
+            assert_eq!(zstd.level(), 19);
+            assert_eq!(zstd.long_window(), Some(compression::MAX_LONG_WINDOW_LOG));
+        } else {
+            panic!("Expected Zstd compressor")
+        }
+
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            assert_eq!((row[0], row[1]), (col1[row_index].as_slice(), col2[row_index].as_slice()));
+            row_index += 1;
+        }
+        assert_eq!(row_index, col1.len());
+    }
+
+    #[test]
+    fn test_uncompressed() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let mut nippy =
+            NippyJar::new_without_header(num_columns, file_path.path()).with_uncompressed();
+        nippy.freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows).unwrap();
+
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy, loaded_nippy);
+        assert!(matches!(loaded_nippy.compressor(), Some(Compressors::Uncompressed(_))));
+
+        // Values are stored as they are
+        let column_sizes = loaded_nippy.column_sizes().unwrap();
+        assert_eq!(
+            column_sizes,
+            [col1.iter().map(|v| v.len() as u64).sum(), col2.iter().map(|v| v.len() as u64).sum()]
+        );
+        assert_eq!(column_sizes.iter().sum::<u64>(), file_path.path().metadata().unwrap().len());
+
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            assert_eq!((row[0], row[1]), (col1[row_index].as_slice(), col2[row_index].as_slice()));
+            row_index += 1;
+        }
+        assert_eq!(row_index, col1.len());
+    }
+
+    #[test]
+    fn test_bloom_filter() {
+        let (col1, col2) = test_data(Some(1));
+        let num_columns = 2;
+        let num_rows = col1.len() as u64;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let mut nippy =
+            NippyJar::new_without_header(num_columns, file_path.path()).with_bloom_filter(4);
+
+        assert!(!InclusionFilter::contains(&nippy, &col1[0]).unwrap());
+        for value in &col1[..4] {
+            assert!(InclusionFilter::add(&mut nippy, value).is_ok());
+            assert!(InclusionFilter::contains(&nippy, value).unwrap());
+        }
+        assert!(matches!(
+            InclusionFilter::add(&mut nippy, &col1[4]),
+            Err(NippyJarError::FilterMaxCapacity)
+        ));
+
+        nippy.freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows).unwrap();
+        let loaded_nippy =
+            NippyJar::load_without_header(file_path.path()).unwrap().load_filters().unwrap();
+
+        assert_eq!(nippy, loaded_nippy);
+        for value in &col1[..4] {
+            assert!(InclusionFilter::contains(&loaded_nippy, value).unwrap());
+        }
+
+        // False positive rate stays close to the theoretical 1%
+        let mut filter = Bloom::new(10_000);
+        for i in 0..10_000u64 {
+            filter.add(&i.to_be_bytes()).unwrap();
+        }
+        let false_positives =
+            (10_000..20_000u64).filter(|i| filter.contains(&i.to_be_bytes()).unwrap()).count();
+        assert!(false_positives < 200, "{false_positives} false positives");
+    }
+
     /// Tests NippyJar with everything enabled: compression, filter, offset list and offset index.
     #[test]
     fn test_full_nippy_jar() {
@@ -1046,6 +1322,94 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_unsupported_version() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let mut nippy = NippyJar::new_without_header(2, file_path.path());
+        nippy.freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows).unwrap();
+
+        // rewrite the configuration with the version of a jar without checksums
+        nippy.version = 1;
+        bincode::serialize_into(File::create(nippy.config_path()).unwrap(), &nippy).unwrap();
+
+        assert!(matches!(
+            NippyJar::load_without_header(file_path.path()),
+            Err(NippyJarError::UnsupportedVersion(1, NIPPY_JAR_VERSION))
+        ));
+    }
+
+    #[test]
+    fn test_checksums() {
+        let (col1, col2) = test_data(None);
+        let num_columns = 2;
+        let num_rows = col1.len() as u64;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let mut nippy = NippyJar::new_without_header(num_columns, file_path.path())
+            .with_lz4()
+            .with_checksums(7);
+        nippy.freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows).unwrap();
+
+        // 100 rows in chunks of 7 rows
+        let checksums = nippy.checksums().unwrap().clone();
+        assert_eq!(checksums.values().len(), 15 * num_columns);
+        assert_eq!(nippy.verify(Verification::Full).unwrap(), vec![]);
+
+        let mut loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(loaded_nippy.checksums(), Some(&checksums));
+
+        // Appending continues the checksums of the last partial chunk, and pruning drops them
+        let mut writer = NippyJarWriter::new(&mut loaded_nippy).unwrap();
+        writer.append_rows(vec![clone_with_result(&col2), clone_with_result(&col1)], 3).unwrap();
+        writer.commit().unwrap();
+        assert_eq!(loaded_nippy.rows, 103);
+        assert_eq!(loaded_nippy.checksums().unwrap().values().len(), 15 * num_columns);
+        assert_eq!(loaded_nippy.verify(Verification::Full).unwrap(), vec![]);
+
+        let mut writer = NippyJarWriter::new(&mut loaded_nippy).unwrap();
+        writer.prune_rows(5).unwrap();
+        assert_eq!(loaded_nippy.rows, 98);
+        assert_eq!(loaded_nippy.checksums().unwrap().values().len(), 14 * num_columns);
+        assert_eq!(loaded_nippy.verify(Verification::Full).unwrap(), vec![]);
+        assert_eq!(
+            loaded_nippy.checksums().unwrap().values(),
+            &checksums.values()[..14 * num_columns]
+        );
+
+        // Flip a bit of the second column of row 50
+        let offset = loaded_nippy.open_data_reader().unwrap().offset(50 * num_columns + 1);
+        let mut data = std::fs::read(loaded_nippy.data_path()).unwrap();
+        data[offset as usize] ^= 1;
+        std::fs::write(loaded_nippy.data_path(), &data).unwrap();
+
+        let corruptions = loaded_nippy.verify(Verification::Full).unwrap();
+        assert_eq!(corruptions.len(), 1);
+        assert!(matches!(
+            &corruptions[0],
+            Corruption::ChecksumMismatch { column: 1, rows, .. } if *rows == (49..56)
+        ));
+
+        // Sampling 2 chunks checks chunks 0, 7 and the last one, while sampling 1 skips chunk 7
+        assert_eq!(loaded_nippy.verify(Verification::Sampled(2)).unwrap(), corruptions);
+        assert_eq!(loaded_nippy.verify(Verification::Sampled(1)).unwrap(), vec![]);
+        assert!(matches!(
+            NippyJar::<()>::load_verified(file_path.path(), Verification::Full),
+            Err(NippyJarError::Corrupted(Corruption::ChecksumMismatch { column: 1, .. }))
+        ));
+
+        // Truncate the data file in the middle of row 90
+        let offset = loaded_nippy.open_data_reader().unwrap().offset(90 * num_columns + 1);
+        data.truncate(offset as usize);
+        std::fs::write(loaded_nippy.data_path(), &data).unwrap();
+
+        let corruptions = loaded_nippy.verify(Verification::Full).unwrap();
+        assert_eq!(corruptions.len(), 2);
+        assert_eq!(corruptions[1], Corruption::Truncated { rows: 90..98 });
+    }
+
     fn test_append_consistency_partial_commit(
         file_path: &Path,
         col1: &[Vec<u8>],
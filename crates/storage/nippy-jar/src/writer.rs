@@ -1,4 +1,8 @@
-use crate::{compression::Compression, ColumnResult, NippyJar, NippyJarError, NippyJarHeader};
+use crate::{
+    checksum::hash_rows,
+    compression::{compress_to_vec, Compression, Compressors},
+    ColumnResult, NippyJar, NippyJarError, NippyJarHeader,
+};
 use std::{
     cmp::Ordering,
     fs::{File, OpenOptions},
@@ -22,6 +26,11 @@ const OFFSET_SIZE_BYTES: u64 = 8;
 ///
 /// ## Data file layout
 /// The data file is represented just as a sequence of bytes of data without any delimiters
+///
+/// ## Checksums
+/// If the jar has [`crate::Checksums`], every written column value is fed into a hasher of its
+/// column. Once a chunk of rows is complete its checksums are stored, while the checksums of a
+/// partial last chunk are stored on `commit()` and replaced once more rows are appended.
 pub struct NippyJarWriter<'a, H> {
     /// Reference to the associated [`NippyJar`], containing all necessary configurations for data
     /// handling.
@@ -32,12 +41,18 @@ pub struct NippyJarWriter<'a, H> {
     offsets_file: File,
     /// Temporary buffer to reuse when compressing data.
     tmp_buf: Vec<u8>,
+    /// Zstd compressors with the dictionary of each column. Empty if the jar doesn't use
+    /// dictionaries.
+    dictionary_compressors: Vec<zstd::bulk::Compressor<'static>>,
     /// Used to find the maximum uncompressed size of a row in a jar.
     uncompressed_row_size: usize,
     /// Partial offset list which hasn't been flushed to disk.
     offsets: Vec<u64>,
     /// Column where writer is going to write next.
     column: usize,
+    /// Checksum hashers of the current row chunk, one per column. Empty if the jar has no
+    /// checksums.
+    checksum_hashers: Vec<crc32fast::Hasher>,
 }
 
 impl<'a, H: NippyJarHeader> NippyJarWriter<'a, H> {
@@ -45,14 +60,27 @@ impl<'a, H: NippyJarHeader> NippyJarWriter<'a, H> {
         let (data_file, offsets_file, is_created) =
             Self::create_or_open_files(jar.data_path(), &jar.offsets_path())?;
 
+        let dictionary_compressors = match &jar.compressor {
+            Some(Compressors::Zstd(zstd)) => match zstd.compressors() {
+                Ok(compressors) => compressors.unwrap_or_default(),
+                // Dictionaries of a loaded jar can only be used for decompression. Appended values
+                // are compressed without them, which dictionary decompressors read just as well.
+                Err(NippyJarError::CompressorNotAllowed) => Vec::new(),
+                Err(err) => return Err(err),
+            },
+            _ => Vec::new(),
+        };
+
         let mut writer = Self {
             jar,
             data_file,
             offsets_file,
             tmp_buf: Vec::with_capacity(1_000_000),
+            dictionary_compressors,
             uncompressed_row_size: 0,
             offsets: Vec::with_capacity(1_000_000),
             column: 0,
+            checksum_hashers: Vec::new(),
         };
 
         // If we are opening a previously created jar, we need to check its consistency, and make
@@ -61,6 +89,8 @@ impl<'a, H: NippyJarHeader> NippyJarWriter<'a, H> {
             writer.check_consistency_and_heal()?;
         }
 
+        writer.rehash_partial_chunk()?;
+
         Ok(writer)
     }
 
@@ -178,6 +208,57 @@ impl<'a, H: NippyJarHeader> NippyJarWriter<'a, H> {
         Ok(())
     }
 
+    /// Drops the checksums of the last partial chunk, resets the checksum hashers and feeds them
+    /// the rows of that chunk from disk, so appended rows continue its checksums.
+    ///
+    /// Expects every written offset to be on disk.
+    fn rehash_partial_chunk(&mut self) -> Result<(), NippyJarError> {
+        let (columns, rows) = (self.jar.columns, self.jar.rows);
+        let Some(checksums) = self.jar.checksums.as_mut() else { return Ok(()) };
+
+        let full_chunks = rows / checksums.rows_per_chunk();
+        let partial_rows = full_chunks * checksums.rows_per_chunk()..rows;
+        checksums.values_mut().truncate(full_chunks * columns);
+
+        self.checksum_hashers = vec![crc32fast::Hasher::new(); columns];
+        if !partial_rows.is_empty() {
+            let reader = self.jar.open_data_reader()?;
+            hash_rows(&reader, columns, partial_rows, &mut self.checksum_hashers)?;
+        }
+
+        Ok(())
+    }
+
+    /// Stores the checksums of the chunk holding the last row, replacing the ones of the chunk if
+    /// previously stored as partial. If `reset` is set, the chunk is complete and the hashers start
+    /// over for the next one. Otherwise, only a partial chunk gets stored, since complete ones
+    /// already are.
+    fn store_chunk_checksums(&mut self, reset: bool) {
+        let (columns, rows) = (self.jar.columns, self.jar.rows);
+        let Some(checksums) = self.jar.checksums.as_mut() else { return };
+
+        if rows == 0 || (!reset && rows % checksums.rows_per_chunk() == 0) {
+            return
+        }
+
+        let chunk = (rows - 1) / checksums.rows_per_chunk();
+        let values = checksums.values_mut();
+        values.truncate(chunk * columns);
+
+        if reset {
+            values.extend(
+                std::mem::replace(
+                    &mut self.checksum_hashers,
+                    vec![crc32fast::Hasher::new(); columns],
+                )
+                .into_iter()
+                .map(|hasher| hasher.finalize()),
+            );
+        } else {
+            values.extend(self.checksum_hashers.iter().map(|hasher| hasher.clone().finalize()));
+        }
+    }
+
     /// Appends rows to data file.  `fn commit()` should be called to flush offsets and config to
     /// disk.
     ///
@@ -243,12 +324,21 @@ impl<'a, H: NippyJarHeader> NippyJarWriter<'a, H> {
     /// Writes column to data file. If it's the last column of the row, call `finalize_row()`
     fn write_column(&mut self, value: &[u8]) -> Result<(), NippyJarError> {
         self.uncompressed_row_size += value.len();
-        if let Some(compression) = &self.jar.compressor {
+        let written = if let Some(compressor) = self.dictionary_compressors.get_mut(self.column) {
+            let before = self.tmp_buf.len();
+            let len = compress_to_vec(compressor, value, &mut self.tmp_buf)?;
+            &self.tmp_buf[before..before + len]
+        } else if let Some(compression) = &self.jar.compressor {
             let before = self.tmp_buf.len();
             let len = compression.compress_to(value, &mut self.tmp_buf)?;
-            self.data_file.write_all(&self.tmp_buf[before..before + len])?;
+            &self.tmp_buf[before..before + len]
         } else {
-            self.data_file.write_all(value)?;
+            value
+        };
+        self.data_file.write_all(written)?;
+
+        if let Some(hasher) = self.checksum_hashers.get_mut(self.column) {
+            hasher.update(written);
         }
 
         self.column += 1;
@@ -333,6 +423,14 @@ impl<'a, H: NippyJarHeader> NippyJarWriter<'a, H> {
         if self.jar.rows == 0 {
             self.jar.max_row_size = 0;
         }
+
+        if self.jar.checksums.is_some() {
+            // Remaining in-memory offsets are needed on disk to rehash the last chunk.
+            self.commit_offsets()?;
+            self.rehash_partial_chunk()?;
+            self.store_chunk_checksums(false);
+        }
+
         self.jar.freeze_config()?;
 
         Ok(())
@@ -344,6 +442,11 @@ impl<'a, H: NippyJarHeader> NippyJarWriter<'a, H> {
         self.jar.max_row_size = self.jar.max_row_size.max(self.uncompressed_row_size);
         self.jar.rows += 1;
 
+        // Stores the checksums of a completed chunk.
+        if self.jar.checksums.as_ref().is_some_and(|c| self.jar.rows % c.rows_per_chunk() == 0) {
+            self.store_chunk_checksums(true);
+        }
+
         self.tmp_buf.clear();
         self.uncompressed_row_size = 0;
         self.column = 0;
@@ -355,6 +458,9 @@ impl<'a, H: NippyJarHeader> NippyJarWriter<'a, H> {
 
         self.commit_offsets()?;
 
+        // Stores the checksums of the last partial chunk.
+        self.store_chunk_checksums(false);
+
         // Flushes `max_row_size` and total `rows` to disk.
         self.jar.freeze_config()?;
 
@@ -1,10 +1,14 @@
 use crate::{
     bundle_state::BundleStateWithReceipts, AccountReader, BlockHashReader, BundleStateDataProvider,
-    StateProvider, StateRootProvider,
+    StateProofProvider, StateProvider, StateRootProvider,
+};
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{
+    trie::{AccountProof, MultiProof},
+    Account, Address, BlockNumber, Bytecode, B256,
 };
-use reth_interfaces::provider::{ProviderError, ProviderResult};
-use reth_primitives::{trie::AccountProof, Account, Address, BlockNumber, Bytecode, B256};
 use reth_trie::updates::TrieUpdates;
+use std::collections::HashMap;
 
 /// A state provider that either resolves to data in a wrapped [`crate::BundleStateWithReceipts`],
 /// or an underlying state provider.
@@ -76,6 +80,20 @@ impl<SP: StateProvider, BSDP: BundleStateDataProvider> StateRootProvider
     }
 }
 
+impl<SP: StateProvider, BSDP: BundleStateDataProvider> StateProofProvider
+    for BundleStateProvider<SP, BSDP>
+{
+    fn multiproof(
+        &self,
+        bundle_state: &BundleStateWithReceipts,
+        targets: &HashMap<Address, Vec<B256>>,
+    ) -> ProviderResult<MultiProof> {
+        let mut state = self.bundle_state_data_provider.state().clone();
+        state.extend(bundle_state.clone());
+        self.state_provider.multiproof(&state, targets)
+    }
+}
+
 impl<SP: StateProvider, BSDP: BundleStateDataProvider> StateProvider
     for BundleStateProvider<SP, BSDP>
 {
@@ -102,7 +120,10 @@ impl<SP: StateProvider, BSDP: BundleStateDataProvider> StateProvider
         self.state_provider.bytecode_by_hash(code_hash)
     }
 
-    fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
-        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof> {
+        let targets = HashMap::from([(address, keys.to_vec())]);
+        Ok(self
+            .multiproof(&BundleStateWithReceipts::default(), &targets)?
+            .account_proof(address, keys))
     }
 }
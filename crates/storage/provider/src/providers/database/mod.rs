@@ -5,19 +5,20 @@ use crate::{
     },
     traits::{BlockSource, ReceiptProvider},
     BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, EvmEnvProvider,
-    HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HeaderSyncMode, ProviderError,
-    PruneCheckpointReader, StageCheckpointReader, StateProviderBox, TransactionVariant,
-    TransactionsProvider, WithdrawalsProvider,
+    HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HeaderSyncMode, NodeDataProvider,
+    ProviderError, PruneCheckpointReader, StageCheckpointReader, StateProviderBox,
+    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::{database::Database, init_db, models::StoredBlockBodyIndices, DatabaseEnv};
 use reth_interfaces::{db::LogLevel, provider::ProviderResult, RethError, RethResult};
+use reth_nippy_jar::Verification;
 use reth_primitives::{
     snapshot::HighestSnapshots,
     stage::{StageCheckpoint, StageId},
-    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, ChainInfo,
-    ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
-    Withdrawal, B256, U256,
+    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bytes, ChainInfo,
+    ChainSpec, Header, HistoryRetentionConfig, PruneCheckpoint, PruneSegment, Receipt, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, B256, U256,
 };
 use revm::primitives::{BlockEnv, CfgEnv};
 use std::{
@@ -44,6 +45,8 @@ pub struct ProviderFactory<DB> {
     chain_spec: Arc<ChainSpec>,
     /// Snapshot Provider
     snapshot_provider: Option<Arc<SnapshotProvider>>,
+    /// Addresses whose history is retained by the pruner.
+    history_retention: Option<Arc<HistoryRetentionConfig>>,
 }
 
 impl<DB: Clone> Clone for ProviderFactory<DB> {
@@ -52,6 +55,7 @@ impl<DB: Clone> Clone for ProviderFactory<DB> {
             db: self.db.clone(),
             chain_spec: Arc::clone(&self.chain_spec),
             snapshot_provider: self.snapshot_provider.clone(),
+            history_retention: self.history_retention.clone(),
         }
     }
 }
@@ -61,7 +65,7 @@ impl<DB: Database> ProviderFactory<DB> {}
 impl<DB> ProviderFactory<DB> {
     /// Create new database provider factory.
     pub fn new(db: DB, chain_spec: Arc<ChainSpec>) -> Self {
-        Self { db, chain_spec, snapshot_provider: None }
+        Self { db, chain_spec, snapshot_provider: None, history_retention: None }
     }
 
     /// Create new database provider by passing a path. [`ProviderFactory`] will own the database
@@ -75,22 +79,36 @@ impl<DB> ProviderFactory<DB> {
             db: init_db(path, log_level).map_err(|e| RethError::Custom(e.to_string()))?,
             chain_spec,
             snapshot_provider: None,
+            history_retention: None,
         })
     }
 
     /// Database provider that comes with a shared snapshot provider.
+    ///
+    /// If a [`Verification`] is given, snapshots are checked against their checksums when opened.
     pub fn with_snapshots(
         mut self,
         snapshots_path: PathBuf,
         highest_snapshot_tracker: watch::Receiver<Option<HighestSnapshots>>,
+        verification: Option<Verification>,
     ) -> ProviderResult<Self> {
-        self.snapshot_provider = Some(Arc::new(
-            SnapshotProvider::new(snapshots_path)?
-                .with_highest_tracker(Some(highest_snapshot_tracker)),
-        ));
+        let mut snapshot_provider = SnapshotProvider::new(snapshots_path)?
+            .with_highest_tracker(Some(highest_snapshot_tracker));
+        if let Some(verification) = verification {
+            snapshot_provider = snapshot_provider.with_verification(verification);
+        }
+        self.snapshot_provider = Some(Arc::new(snapshot_provider));
         Ok(self)
     }
 
+    /// Sets the addresses whose account and storage history is retained by the pruner, so the
+    /// historical state of them is available below the history prune checkpoints.
+    pub fn with_history_retention(mut self, history_retention: HistoryRetentionConfig) -> Self {
+        self.history_retention =
+            (!history_retention.is_empty()).then(|| Arc::new(history_retention));
+        self
+    }
+
     /// Returns reference to the underlying database.
     pub fn db_ref(&self) -> &DB {
         &self.db
@@ -153,6 +171,12 @@ impl<DB: Database> ProviderFactory<DB> {
             provider.get_prune_checkpoint(PruneSegment::StorageHistory)?;
 
         let mut state_provider = HistoricalStateProvider::new(provider.into_tx(), block_number);
+        if let Some(snapshot_provider) = &self.snapshot_provider {
+            state_provider = state_provider.with_snapshot_provider(snapshot_provider.clone());
+        }
+        if let Some(history_retention) = &self.history_retention {
+            state_provider = state_provider.with_history_retention(history_retention.clone());
+        }
 
         // If we pruned account or storage history, we can't return state on every historical block.
         // Instead, we should cap it at the latest prune checkpoint for corresponding prune segment.
@@ -496,6 +520,12 @@ impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
     }
 }
 
+impl<DB: Database> NodeDataProvider for ProviderFactory<DB> {
+    fn node_data_by_hash(&self, hash: B256) -> ProviderResult<Option<Bytes>> {
+        self.provider()?.node_data_by_hash(hash)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::ProviderFactory;
@@ -7,9 +7,9 @@ use crate::{
     },
     AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
     Chain, EvmEnvProvider, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
-    HeaderSyncMode, HistoryWriter, OriginalValuesKnown, ProviderError, PruneCheckpointReader,
-    PruneCheckpointWriter, StageCheckpointReader, StorageReader, TransactionVariant,
-    TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    HeaderSyncMode, HistoryWriter, NodeDataProvider, OriginalValuesKnown, ProviderError,
+    PruneCheckpointReader, PruneCheckpointWriter, StageCheckpointReader, StorageReader,
+    TransactionVariant, TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
 };
 use itertools::{izip, Itertools};
 use reth_db::{
@@ -17,8 +17,9 @@ use reth_db::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO},
     database::Database,
     models::{
-        sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals,
+        sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx,
+        BlockAccountChangeSet, BlockNumberAddress, ShardedKey, StoredBlockBodyIndices,
+        StoredBlockOmmers, StoredBlockWithdrawals,
     },
     table::{Table, TableRow},
     tables,
@@ -38,7 +39,7 @@ use reth_primitives::{
     },
     stage::{StageCheckpoint, StageId},
     trie::Nibbles,
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
+    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bytes,
     ChainInfo, ChainSpec, GotExpected, Hardfork, Head, Header, PruneCheckpoint, PruneModes,
     PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, SnapshotSegment,
     StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedEcRecovered,
@@ -274,11 +275,10 @@ impl<TX: DbTx> DatabaseProvider<TX> {
 
         if let Some(snapshot_provider) = &self.snapshot_provider {
             // If there is, check the maximum block or transaction number of the segment.
-            if let Some(snapshot_upper_bound) = match segment {
-                SnapshotSegment::Headers => snapshot_provider.get_highest_snapshot_block(segment),
-                SnapshotSegment::Transactions | SnapshotSegment::Receipts => {
-                    snapshot_provider.get_highest_snapshot_tx(segment)
-                }
+            if let Some(snapshot_upper_bound) = if segment.is_block_based() {
+                snapshot_provider.get_highest_snapshot_block(segment)
+            } else {
+                snapshot_provider.get_highest_snapshot_tx(segment)
             } {
                 if block_or_tx_range.start <= snapshot_upper_bound {
                     let end = block_or_tx_range.end.min(snapshot_upper_bound + 1);
@@ -321,11 +321,10 @@ impl<TX: DbTx> DatabaseProvider<TX> {
     {
         if let Some(provider) = &self.snapshot_provider {
             // If there is, check the maximum block or transaction number of the segment.
-            let snapshot_upper_bound = match segment {
-                SnapshotSegment::Headers => provider.get_highest_snapshot_block(segment),
-                SnapshotSegment::Transactions | SnapshotSegment::Receipts => {
-                    provider.get_highest_snapshot_tx(segment)
-                }
+            let snapshot_upper_bound = if segment.is_block_based() {
+                provider.get_highest_snapshot_block(segment)
+            } else {
+                provider.get_highest_snapshot_tx(segment)
             };
 
             if snapshot_upper_bound
@@ -1016,15 +1015,24 @@ impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        let range = block_number..=block_number;
-        self.tx
-            .cursor_read::<tables::AccountChangeSet>()?
-            .walk_range(range)?
-            .map(|result| -> ProviderResult<_> {
-                let (_, account_before) = result?;
-                Ok(account_before)
-            })
-            .collect()
+        self.get_with_snapshot(
+            SnapshotSegment::AccountChangeSets,
+            block_number,
+            |snapshot| snapshot.account_changeset(block_number),
+            || {
+                let range = block_number..=block_number;
+                self.tx
+                    .cursor_read::<tables::AccountChangeSet>()?
+                    .walk_range(range)?
+                    .map(|result| -> ProviderResult<_> {
+                        let (_, account_before) = result?;
+                        Ok(account_before)
+                    })
+                    .collect::<ProviderResult<_>>()
+                    .map(|accounts| Some(BlockAccountChangeSet { accounts }))
+            },
+        )
+        .map(|changeset| changeset.map(|changeset| changeset.accounts).unwrap_or_default())
     }
 }
 
@@ -2505,6 +2513,15 @@ impl<TX: DbTx> PruneCheckpointReader for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> NodeDataProvider for DatabaseProvider<TX> {
+    /// Returns the contract bytecode with the given hash.
+    ///
+    /// Trie nodes are stored by their path and can not be looked up by hash.
+    fn node_data_by_hash(&self, hash: B256) -> ProviderResult<Option<Bytes>> {
+        Ok(self.tx.get::<tables::Bytecodes>(hash)?.map(|code| code.original_bytes()))
+    }
+}
+
 impl<TX: DbTxMut> PruneCheckpointWriter for DatabaseProvider<TX> {
     fn save_prune_checkpoint(
         &self,
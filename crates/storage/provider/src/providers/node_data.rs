@@ -0,0 +1,65 @@
+use crate::NodeDataProvider;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Bytes, B256};
+use reth_trie::TrieNodeCache;
+
+/// A [NodeDataProvider] that serves trie nodes from a [TrieNodeCache] and falls back to the
+/// wrapped provider, e.g. for contract bytecode.
+///
+/// The cache is expected to be populated by the state root computation of the merkle stage, so
+/// only recently modified trie nodes are available.
+#[derive(Debug, Clone)]
+pub struct CachedNodeDataProvider<Provider> {
+    /// The provider used for everything that is not cached.
+    provider: Provider,
+    /// Recently computed trie nodes.
+    trie_nodes: TrieNodeCache,
+}
+
+impl<Provider> CachedNodeDataProvider<Provider> {
+    /// Creates a new provider that serves trie nodes from the given cache.
+    pub fn new(provider: Provider, trie_nodes: TrieNodeCache) -> Self {
+        Self { provider, trie_nodes }
+    }
+}
+
+impl<Provider: NodeDataProvider> NodeDataProvider for CachedNodeDataProvider<Provider> {
+    fn node_data_by_hash(&self, hash: B256) -> ProviderResult<Option<Bytes>> {
+        if let Some(node) = self.trie_nodes.get(&hash) {
+            return Ok(Some(node))
+        }
+        self.provider.node_data_by_hash(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_provider_factory;
+    use reth_db::{tables, transaction::DbTxMut};
+    use reth_primitives::{keccak256, Bytecode};
+
+    #[test]
+    fn node_data_from_cache_and_bytecodes() {
+        let factory = create_test_provider_factory();
+
+        let code = Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3]);
+        let code_hash = keccak256(&code);
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.clone()))
+            .unwrap();
+        provider.commit().unwrap();
+
+        let node = Bytes::from_static(&[0xc2, 0x80, 0x80]);
+        let node_hash = keccak256(&node);
+        let cache = TrieNodeCache::new(16);
+        cache.insert(node_hash, node.clone());
+
+        let node_data = CachedNodeDataProvider::new(factory, cache);
+        assert_eq!(node_data.node_data_by_hash(node_hash).unwrap(), Some(node));
+        assert_eq!(node_data.node_data_by_hash(code_hash).unwrap(), Some(code));
+        assert_eq!(node_data.node_data_by_hash(B256::ZERO).unwrap(), None);
+    }
+}
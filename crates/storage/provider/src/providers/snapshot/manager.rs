@@ -1,18 +1,23 @@
 use super::{LoadedJar, SnapshotJarProvider};
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider,
-    ReceiptProvider, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
-    WithdrawalsProvider,
+    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, ChangeSetReader,
+    HeaderProvider, ReceiptProvider, TransactionVariant, TransactionsProvider,
+    TransactionsProviderExt, WithdrawalsProvider,
 };
 use dashmap::DashMap;
 use parking_lot::RwLock;
 use reth_db::{
     codecs::CompactU256,
-    models::StoredBlockBodyIndices,
-    snapshot::{iter_snapshots, HeaderMask, ReceiptMask, SnapshotCursor, TransactionMask},
+    models::{
+        AccountBeforeTx, BlockAccountChangeSet, BlockStorageChangeSet, StoredBlockBodyIndices,
+    },
+    snapshot::{
+        iter_snapshots, AccountChangeSetMask, HeaderMask, ReceiptMask, SnapshotCursor,
+        StorageChangeSetMask, TransactionMask,
+    },
 };
 use reth_interfaces::provider::{ProviderError, ProviderResult};
-use reth_nippy_jar::NippyJar;
+use reth_nippy_jar::{NippyJar, Verification};
 use reth_primitives::{
     snapshot::HighestSnapshots, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber,
     BlockWithSenders, ChainInfo, Header, Receipt, SealedBlock, SealedBlockWithSenders,
@@ -52,6 +57,8 @@ pub struct SnapshotProvider {
     /// Whether [`SnapshotJarProvider`] loads filters into memory. If not, `by_hash` queries won't
     /// be able to be queried directly.
     load_filters: bool,
+    /// Whether snapshots are checked against their checksums when loaded, and how thoroughly.
+    verification: Option<Verification>,
 }
 
 impl SnapshotProvider {
@@ -64,6 +71,7 @@ impl SnapshotProvider {
             highest_tracker: None,
             path: path.as_ref().to_path_buf(),
             load_filters: false,
+            verification: None,
         };
 
         provider.update_index()?;
@@ -76,6 +84,13 @@ impl SnapshotProvider {
         self
     }
 
+    /// Verifies the checksums of every snapshot when creating its [`SnapshotJarProvider`].
+    /// Corrupted snapshots fail to load.
+    pub fn with_verification(mut self, verification: Verification) -> Self {
+        self.verification = Some(verification);
+        self
+    }
+
     /// Adds a highest snapshot tracker to the provider
     pub fn with_highest_tracker(
         mut self,
@@ -159,8 +174,12 @@ impl SnapshotProvider {
         if let Some(jar) = self.map.get(&key) {
             Ok(jar.into())
         } else {
-            let jar = NippyJar::load(&self.path.join(segment.filename(block_range, tx_range)))
-                .map(|jar| {
+            let path = self.path.join(segment.filename(block_range, tx_range));
+            let jar = match self.verification {
+                Some(verification) => NippyJar::load_verified(&path, verification),
+                None => NippyJar::load(&path),
+            }
+            .map(|jar| {
                 if self.load_filters {
                     return jar.load_filters()
                 }
@@ -326,9 +345,10 @@ impl SnapshotProvider {
         F: Fn(&mut SnapshotCursor<'_>, u64) -> ProviderResult<Option<T>>,
         P: FnMut(&T) -> bool,
     {
-        let get_provider = |start: u64| match segment {
-            SnapshotSegment::Headers => self.get_segment_provider_from_block(segment, start, None),
-            SnapshotSegment::Transactions | SnapshotSegment::Receipts => {
+        let get_provider = |start: u64| {
+            if segment.is_block_based() {
+                self.get_segment_provider_from_block(segment, start, None)
+            } else {
                 self.get_segment_provider_from_transaction(segment, start, None)
             }
         };
@@ -359,6 +379,56 @@ impl SnapshotProvider {
 
         Ok(result)
     }
+
+    /// Returns the account changesets of the block, or [`None`] if the block has not been
+    /// snapshotted.
+    pub fn account_changeset(
+        &self,
+        block: BlockNumber,
+    ) -> ProviderResult<Option<BlockAccountChangeSet>> {
+        let segment = SnapshotSegment::AccountChangeSets;
+        let Some(jar_provider) = self.get_segment_provider(
+            segment,
+            || self.get_segment_ranges_from_block(segment, block),
+            None,
+        )?
+        else {
+            return Ok(None)
+        };
+        let mut cursor = jar_provider.cursor()?;
+        cursor.get_one::<AccountChangeSetMask<BlockAccountChangeSet>>(block.into())
+    }
+
+    /// Returns the storage changesets of the block, or [`None`] if the block has not been
+    /// snapshotted.
+    pub fn storage_changeset(
+        &self,
+        block: BlockNumber,
+    ) -> ProviderResult<Option<BlockStorageChangeSet>> {
+        let segment = SnapshotSegment::StorageChangeSets;
+        let Some(jar_provider) = self.get_segment_provider(
+            segment,
+            || self.get_segment_ranges_from_block(segment, block),
+            None,
+        )?
+        else {
+            return Ok(None)
+        };
+        let mut cursor = jar_provider.cursor()?;
+        cursor.get_one::<StorageChangeSetMask<BlockStorageChangeSet>>(block.into())
+    }
+}
+
+impl ChangeSetReader for SnapshotProvider {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        Ok(self
+            .account_changeset(block_number)?
+            .map(|changeset| changeset.accounts)
+            .unwrap_or_default())
+    }
 }
 
 impl HeaderProvider for SnapshotProvider {
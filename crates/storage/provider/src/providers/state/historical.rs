@@ -1,10 +1,16 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    BundleStateWithReceipts, ProviderError, StateProvider, StateRootProvider,
+    providers::{
+        state::{hash_multiproof_targets, macros::delegate_provider_impls},
+        SnapshotProvider,
+    },
+    AccountReader, BlockHashReader, BundleStateWithReceipts, ProviderError, StateProofProvider,
+    StateProvider, StateRootProvider,
 };
 use reth_db::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{
+        storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress, ShardedKey,
+    },
     table::Table,
     tables,
     transaction::DbTx,
@@ -12,9 +18,20 @@ use reth_db::{
 };
 use reth_interfaces::provider::ProviderResult;
 use reth_primitives::{
-    trie::AccountProof, Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, B256,
+    keccak256,
+    trie::{AccountProof, MultiProof},
+    Account, Address, BlockNumber, Bytecode, HistoryRetentionConfig, SnapshotSegment, StorageEntry,
+    StorageKey, StorageValue, B256,
+};
+use reth_trie::{
+    hashed_cursor::{HashedPostState, HashedStorage},
+    proof::Proof,
+    updates::TrieUpdates,
+};
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    sync::Arc,
 };
-use reth_trie::updates::TrieUpdates;
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -27,6 +44,14 @@ use reth_trie::updates::TrieUpdates;
 /// - [tables::StorageHistory]
 /// - [tables::AccountChangeSet]
 /// - [tables::StorageChangeSet]
+///
+/// If a [SnapshotProvider] is set, the changesets of blocks below the snapshot boundary are read
+/// from the [SnapshotSegment::AccountChangeSets] and [SnapshotSegment::StorageChangeSets]
+/// snapshots instead, as they may have been pruned from the database.
+///
+/// If a [HistoryRetentionConfig] is set, the history of the retained addresses is available below
+/// the lowest available blocks, while the lookups of all other addresses fail with
+/// [ProviderError::AddressHistoryPruned].
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     /// Transaction
@@ -35,6 +60,10 @@ pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Snapshot provider for changesets that were moved out of the database.
+    snapshot_provider: Option<&'b SnapshotProvider>,
+    /// Addresses whose history is retained in full.
+    history_retention: Option<&'b HistoryRetentionConfig>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -48,7 +77,13 @@ pub enum HistoryInfo {
 impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
     /// Create new StateProvider for historical block number
     pub fn new(tx: &'b TX, block_number: BlockNumber) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            snapshot_provider: None,
+            history_retention: None,
+        }
     }
 
     /// Create new StateProvider for historical block number and lowest block numbers at which
@@ -58,21 +93,131 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         block_number: BlockNumber,
         lowest_available_blocks: LowestAvailableBlocks,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks,
+            snapshot_provider: None,
+            history_retention: None,
+        }
+    }
+
+    /// Set the snapshot provider to read snapshotted changesets from.
+    pub fn with_snapshot_provider(
+        mut self,
+        snapshot_provider: Option<&'b SnapshotProvider>,
+    ) -> Self {
+        self.snapshot_provider = snapshot_provider;
+        self
+    }
+
+    /// Set the addresses whose history is retained in full.
+    pub fn with_history_retention(
+        mut self,
+        history_retention: Option<&'b HistoryRetentionConfig>,
+    ) -> Self {
+        self.history_retention = history_retention;
+        self
+    }
+
+    /// Returns the lowest block number at which the history of the address is available, given
+    /// the lowest available block number of the whole history.
+    ///
+    /// Returns an error if the history at the provider block is pruned.
+    fn lowest_available_history_block_number(
+        &self,
+        address: Address,
+        lowest_available_block_number: Option<BlockNumber>,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        if self.history_retention.is_some_and(|retention| retention.contains(&address)) {
+            // Retained history is never pruned.
+            return Ok(None)
+        }
+
+        if lowest_available_block_number
+            .is_some_and(|block_number| block_number > self.block_number)
+        {
+            return Err(match self.history_retention {
+                Some(_) => ProviderError::AddressHistoryPruned(address, self.block_number),
+                None => ProviderError::StateAtBlockPruned(self.block_number),
+            })
+        }
+
+        Ok(lowest_available_block_number)
+    }
+
+    /// Returns the snapshot provider if the changesets of the segment at the given block have been
+    /// snapshotted.
+    fn snapshotted_changesets(
+        &self,
+        segment: SnapshotSegment,
+        block_number: BlockNumber,
+    ) -> Option<&'b SnapshotProvider> {
+        self.snapshot_provider.filter(|provider| {
+            provider
+                .get_highest_snapshot_block(segment)
+                .map_or(false, |highest| highest >= block_number)
+        })
+    }
+
+    /// Returns the account changeset of the address at the given block from the snapshots or the
+    /// database.
+    fn account_changeset(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        if let Some(provider) =
+            self.snapshotted_changesets(SnapshotSegment::AccountChangeSets, block_number)
+        {
+            return Ok(provider
+                .account_changeset(block_number)?
+                .and_then(|changeset| changeset.account(address).cloned()))
+        }
+
+        Ok(self
+            .tx
+            .cursor_dup_read::<tables::AccountChangeSet>()?
+            .seek_by_key_subkey(block_number, address)?
+            .filter(|acc| acc.address == address))
+    }
+
+    /// Returns the storage changeset of the slot at the given block from the snapshots or the
+    /// database.
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        if let Some(provider) =
+            self.snapshotted_changesets(SnapshotSegment::StorageChangeSets, block_number)
+        {
+            return Ok(provider
+                .storage_changeset(block_number)?
+                .and_then(|changeset| changeset.storage(address, storage_key).copied()))
+        }
+
+        Ok(self
+            .tx
+            .cursor_dup_read::<tables::StorageChangeSet>()?
+            .seek_by_key_subkey((block_number, address).into(), storage_key)?
+            .filter(|entry| entry.key == storage_key))
     }
 
     /// Lookup an account in the AccountHistory table
     pub fn account_history_lookup(&self, address: Address) -> ProviderResult<HistoryInfo> {
-        if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        let lowest_available_block_number = self.lowest_available_history_block_number(
+            address,
+            self.lowest_available_blocks.account_history_block_number,
+        )?;
 
         // history key to search IntegerList of block number changesets.
         let history_key = ShardedKey::new(address, self.block_number);
         self.history_info::<tables::AccountHistory, _>(
             history_key,
             |key| key.key == address,
-            self.lowest_available_blocks.account_history_block_number,
+            lowest_available_block_number,
         )
     }
 
@@ -82,19 +227,106 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         address: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<HistoryInfo> {
-        if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        let lowest_available_block_number = self.lowest_available_history_block_number(
+            address,
+            self.lowest_available_blocks.storage_history_block_number,
+        )?;
 
         // history key to search IntegerList of block number changesets.
         let history_key = StorageShardedKey::new(address, storage_key, self.block_number);
         self.history_info::<tables::StorageHistory, _>(
             history_key,
             |key| key.address == address && key.sharded_key.key == storage_key,
-            self.lowest_available_blocks.storage_history_block_number,
+            lowest_available_block_number,
         )
     }
 
+    /// Returns the hashed state that reverts the latest state to the state at the start of the
+    /// block, built from all account and storage changesets from the block onwards.
+    pub fn revert_state(&self) -> ProviderResult<HashedPostState> {
+        if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
+            !self.lowest_available_blocks.is_storage_history_available(self.block_number)
+        {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+
+        let mut hashed_state = HashedPostState::default();
+
+        // The first changeset of an account holds its value at the start of the block.
+        let mut reverted_accounts = HashSet::new();
+        let mut revert_account = |AccountBeforeTx { address, info }| {
+            if reverted_accounts.insert(address) {
+                let hashed_address = keccak256(address);
+                match info {
+                    Some(account) => hashed_state.insert_account(hashed_address, account),
+                    None => hashed_state.insert_destroyed_account(hashed_address),
+                }
+            }
+        };
+
+        // Snapshotted changesets precede the ones in the database.
+        let mut account_changesets_start = self.block_number;
+        if let Some(provider) = self.snapshot_provider {
+            if let Some(highest) =
+                provider.get_highest_snapshot_block(SnapshotSegment::AccountChangeSets)
+            {
+                for block_number in self.block_number..=highest {
+                    if let Some(changeset) = provider.account_changeset(block_number)? {
+                        changeset.accounts.into_iter().for_each(&mut revert_account);
+                    }
+                }
+                account_changesets_start = account_changesets_start.max(highest + 1);
+            }
+        }
+        let mut account_changesets = self.tx.cursor_read::<tables::AccountChangeSet>()?;
+        for entry in account_changesets.walk_range(account_changesets_start..)? {
+            revert_account(entry?.1);
+        }
+
+        let mut storages = HashMap::<Address, HashMap<B256, StorageValue>>::new();
+        let mut revert_slot = |address: Address, StorageEntry { key, value }| {
+            if let Entry::Vacant(entry) = storages.entry(address).or_default().entry(key) {
+                entry.insert(value);
+            }
+        };
+
+        let mut storage_changesets_start = self.block_number;
+        if let Some(provider) = self.snapshot_provider {
+            if let Some(highest) =
+                provider.get_highest_snapshot_block(SnapshotSegment::StorageChangeSets)
+            {
+                for block_number in self.block_number..=highest {
+                    if let Some(changeset) = provider.storage_changeset(block_number)? {
+                        for (address, entry) in changeset.iter() {
+                            revert_slot(address, *entry);
+                        }
+                    }
+                }
+                storage_changesets_start = storage_changesets_start.max(highest + 1);
+            }
+        }
+        let mut storage_changesets = self.tx.cursor_dup_read::<tables::StorageChangeSet>()?;
+        for entry in storage_changesets
+            .walk_range(BlockNumberAddress((storage_changesets_start, Address::ZERO))..)?
+        {
+            let (block_address, entry) = entry?;
+            revert_slot(block_address.address(), entry);
+        }
+        for (address, storage) in storages {
+            let mut hashed_storage = HashedStorage::new(false);
+            for (key, value) in storage {
+                if value.is_zero() {
+                    hashed_storage.insert_zero_valued_slot(keccak256(key));
+                } else {
+                    hashed_storage.insert_non_zero_valued_storage(keccak256(key), value);
+                }
+            }
+            hashed_state.insert_hashed_storage(keccak256(address), hashed_storage);
+        }
+
+        Ok(hashed_state.sorted())
+    }
+
     fn history_info<T, K>(
         &self,
         key: K,
@@ -158,10 +390,7 @@ impl<'b, TX: DbTx> AccountReader for HistoricalStateProviderRef<'b, TX> {
         match self.account_history_lookup(address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx
-                .cursor_dup_read::<tables::AccountChangeSet>()?
-                .seek_by_key_subkey(changeset_block_number, address)?
-                .filter(|acc| acc.address == address)
+                .account_changeset(changeset_block_number, address)?
                 .ok_or(ProviderError::AccountChangesetNotFound {
                     block_number: changeset_block_number,
                     address,
@@ -211,6 +440,19 @@ impl<'b, TX: DbTx> StateRootProvider for HistoricalStateProviderRef<'b, TX> {
     }
 }
 
+impl<'b, TX: DbTx> StateProofProvider for HistoricalStateProviderRef<'b, TX> {
+    fn multiproof(
+        &self,
+        bundle_state: &BundleStateWithReceipts,
+        targets: &HashMap<Address, Vec<B256>>,
+    ) -> ProviderResult<MultiProof> {
+        let mut hashed_state = self.revert_state()?;
+        hashed_state.extend(bundle_state.hash_state_slow());
+        Proof::overlay_multiproof(self.tx, &hashed_state.sorted(), hash_multiproof_targets(targets))
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+}
+
 impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
     /// Get storage.
     fn storage(
@@ -221,10 +463,7 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx
-                    .cursor_dup_read::<tables::StorageChangeSet>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
+                self.storage_changeset(changeset_block_number, address, storage_key)?
                     .ok_or_else(|| ProviderError::StorageChangesetNotFound {
                         block_number: changeset_block_number,
                         address,
@@ -248,8 +487,11 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
     }
 
     /// Get account and storage proofs.
-    fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
-        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof> {
+        let targets = HashMap::from([(address, keys.to_vec())]);
+        Ok(self
+            .multiproof(&BundleStateWithReceipts::default(), &targets)?
+            .account_proof(address, keys))
     }
 }
 
@@ -263,12 +505,37 @@ pub struct HistoricalStateProvider<TX: DbTx> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Snapshot provider for changesets that were moved out of the database.
+    snapshot_provider: Option<Arc<SnapshotProvider>>,
+    /// Addresses whose history is retained in full.
+    history_retention: Option<Arc<HistoryRetentionConfig>>,
 }
 
 impl<TX: DbTx> HistoricalStateProvider<TX> {
     /// Create new StateProvider for historical block number
     pub fn new(tx: TX, block_number: BlockNumber) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            snapshot_provider: None,
+            history_retention: None,
+        }
+    }
+
+    /// Set the snapshot provider to read snapshotted changesets from.
+    pub fn with_snapshot_provider(mut self, snapshot_provider: Arc<SnapshotProvider>) -> Self {
+        self.snapshot_provider = Some(snapshot_provider);
+        self
+    }
+
+    /// Set the addresses whose history is retained in full.
+    pub fn with_history_retention(
+        mut self,
+        history_retention: Arc<HistoryRetentionConfig>,
+    ) -> Self {
+        self.history_retention = Some(history_retention);
+        self
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -297,6 +564,8 @@ impl<TX: DbTx> HistoricalStateProvider<TX> {
             self.block_number,
             self.lowest_available_blocks,
         )
+        .with_snapshot_provider(self.snapshot_provider.as_deref())
+        .with_history_retention(self.history_retention.as_deref())
     }
 }
 
@@ -334,19 +603,34 @@ impl LowestAvailableBlocks {
 #[cfg(test)]
 mod tests {
     use crate::{
-        providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
+        providers::{
+            state::historical::{HistoryInfo, LowestAvailableBlocks},
+            SnapshotProvider,
+        },
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
     };
     use reth_db::{
         database::Database,
-        models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, AccountStorageChangeSet,
+            BlockAccountChangeSet, BlockStorageChangeSet, ShardedKey,
+        },
+        table::Compress,
         tables,
         test_utils::create_test_rw_db,
         transaction::{DbTx, DbTxMut},
         BlockNumberList,
     };
     use reth_interfaces::provider::ProviderError;
-    use reth_primitives::{address, b256, Account, Address, StorageEntry, B256, U256};
+    use reth_nippy_jar::NippyJar;
+    use reth_primitives::{
+        address, b256, keccak256, snapshot::SegmentHeader, Account, Address,
+        HistoryRetentionConfig, SnapshotSegment, StorageEntry, B256, U256,
+    };
+    use std::{
+        collections::{BTreeSet, HashMap},
+        path::Path,
+    };
 
     const ADDRESS: Address = address!("0000000000000000000000000000000000000001");
     const HIGHER_ADDRESS: Address = address!("0000000000000000000000000000000000000005");
@@ -613,4 +897,208 @@ mod tests {
             Ok(HistoryInfo::MaybeInPlainState)
         );
     }
+
+    #[test]
+    fn history_provider_retained_addresses() {
+        let db = create_test_rw_db();
+        let tx = db.tx().unwrap();
+        let retention = HistoryRetentionConfig(BTreeSet::from([ADDRESS]));
+
+        // state at provider block is pruned, except for the retained address
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &tx,
+            2,
+            LowestAvailableBlocks {
+                account_history_block_number: Some(3),
+                storage_history_block_number: Some(3),
+            },
+        )
+        .with_history_retention(Some(&retention));
+        assert_eq!(provider.account_history_lookup(ADDRESS), Ok(HistoryInfo::NotYetWritten));
+        assert_eq!(
+            provider.storage_history_lookup(ADDRESS, STORAGE),
+            Ok(HistoryInfo::NotYetWritten)
+        );
+        assert_eq!(
+            provider.account_history_lookup(HIGHER_ADDRESS),
+            Err(ProviderError::AddressHistoryPruned(HIGHER_ADDRESS, provider.block_number))
+        );
+        assert_eq!(
+            provider.storage_history_lookup(HIGHER_ADDRESS, STORAGE),
+            Err(ProviderError::AddressHistoryPruned(HIGHER_ADDRESS, provider.block_number))
+        );
+
+        // state at provider block is available for all addresses
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &tx,
+            2,
+            LowestAvailableBlocks {
+                account_history_block_number: Some(2),
+                storage_history_block_number: Some(2),
+            },
+        )
+        .with_history_retention(Some(&retention));
+        assert_eq!(provider.account_history_lookup(ADDRESS), Ok(HistoryInfo::NotYetWritten));
+        assert_eq!(
+            provider.account_history_lookup(HIGHER_ADDRESS),
+            Ok(HistoryInfo::MaybeInPlainState)
+        );
+    }
+
+    #[test]
+    fn history_provider_revert_state() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+
+        let acc_at3 = Account { nonce: 3, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at7 = Account { nonce: 7, balance: U256::ZERO, bytecode_hash: None };
+        tx.put::<tables::AccountChangeSet>(
+            3,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at3) },
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSet>(
+            4,
+            AccountBeforeTx { address: HIGHER_ADDRESS, info: None },
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSet>(
+            7,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at7) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSet>(
+            (3, ADDRESS).into(),
+            StorageEntry { key: STORAGE, value: U256::ZERO },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSet>(
+            (7, ADDRESS).into(),
+            StorageEntry { key: STORAGE, value: U256::from(7) },
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        let hashed_address = keccak256(ADDRESS);
+        let hashed_slot = keccak256(STORAGE);
+
+        // the oldest changeset from the block onwards is reverted
+        let revert_state = HistoricalStateProviderRef::new(&tx, 4).revert_state().unwrap();
+        assert_eq!(
+            revert_state.accounts().collect::<HashMap<_, _>>(),
+            HashMap::from([(hashed_address, Some(acc_at7)), (keccak256(HIGHER_ADDRESS), None)])
+        );
+        let (_, storage) = revert_state.storages().next().unwrap();
+        assert_eq!(storage.storage_slots().collect::<Vec<_>>(), [(hashed_slot, U256::from(7))]);
+
+        let revert_state = HistoricalStateProviderRef::new(&tx, 1).revert_state().unwrap();
+        assert_eq!(
+            revert_state.accounts().collect::<HashMap<_, _>>(),
+            HashMap::from([(hashed_address, Some(acc_at3)), (keccak256(HIGHER_ADDRESS), None)])
+        );
+        let (_, storage) = revert_state.storages().next().unwrap();
+        assert_eq!(storage.storage_slots().collect::<Vec<_>>(), [(hashed_slot, U256::ZERO)]);
+
+        // nothing to revert at the tip
+        let revert_state = HistoricalStateProviderRef::new(&tx, 8).revert_state().unwrap();
+        assert_eq!(revert_state.accounts().count(), 0);
+        assert_eq!(revert_state.storages().count(), 0);
+    }
+
+    #[test]
+    fn history_provider_snapshotted_changesets() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+
+        tx.put::<tables::AccountHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([3, 7]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::StorageHistory>(
+            StorageShardedKey {
+                address: ADDRESS,
+                sharded_key: ShardedKey { key: STORAGE, highest_block_number: u64::MAX },
+            },
+            BlockNumberList::new([3, 7]).unwrap(),
+        )
+        .unwrap();
+
+        let acc_at3 = Account { nonce: 3, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at7 = Account { nonce: 7, balance: U256::ZERO, bytecode_hash: None };
+        let entry_at3 = StorageEntry { key: STORAGE, value: U256::from(3) };
+        let entry_at7 = StorageEntry { key: STORAGE, value: U256::from(7) };
+
+        // the changesets of blocks 0..=3 are snapshotted and pruned from the database
+        tx.put::<tables::AccountChangeSet>(
+            7,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at7) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSet>((7, ADDRESS).into(), entry_at7).unwrap();
+        tx.commit().unwrap();
+
+        let snapshots_dir = tempfile::tempdir().unwrap();
+        let mut account_rows = vec![BlockAccountChangeSet::default(); 4];
+        account_rows[3].accounts.push(AccountBeforeTx { address: ADDRESS, info: Some(acc_at3) });
+        let mut storage_rows = vec![BlockStorageChangeSet::default(); 4];
+        storage_rows[3]
+            .accounts
+            .push(AccountStorageChangeSet { address: ADDRESS, storage: vec![entry_at3] });
+        write_changesets_snapshot(
+            snapshots_dir.path(),
+            SnapshotSegment::AccountChangeSets,
+            account_rows.into_iter().map(Compress::compress).collect(),
+        );
+        write_changesets_snapshot(
+            snapshots_dir.path(),
+            SnapshotSegment::StorageChangeSets,
+            storage_rows.into_iter().map(Compress::compress).collect(),
+        );
+        let snapshot_provider = SnapshotProvider::new(snapshots_dir.path()).unwrap();
+
+        let tx = db.tx().unwrap();
+        let provider = |block_number| {
+            HistoricalStateProviderRef::new(&tx, block_number)
+                .with_snapshot_provider(Some(&snapshot_provider))
+        };
+
+        assert_eq!(provider(2).basic_account(ADDRESS), Ok(Some(acc_at3)));
+        assert_eq!(provider(2).storage(ADDRESS, STORAGE), Ok(Some(entry_at3.value)));
+        assert_eq!(provider(5).basic_account(ADDRESS), Ok(Some(acc_at7)));
+        assert_eq!(provider(5).storage(ADDRESS, STORAGE), Ok(Some(entry_at7.value)));
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 2).basic_account(ADDRESS),
+            Err(ProviderError::AccountChangesetNotFound { block_number: 3, address: ADDRESS })
+        );
+
+        // the reverted state combines the snapshotted and the database changesets
+        let revert_state = provider(2).revert_state().unwrap();
+        assert_eq!(
+            revert_state.accounts().collect::<HashMap<_, _>>(),
+            HashMap::from([(keccak256(ADDRESS), Some(acc_at3))])
+        );
+        let (_, storage) = revert_state.storages().next().unwrap();
+        assert_eq!(
+            storage.storage_slots().collect::<Vec<_>>(),
+            [(keccak256(STORAGE), entry_at3.value)]
+        );
+    }
+
+    fn write_changesets_snapshot(dir: &Path, segment: SnapshotSegment, rows: Vec<Vec<u8>>) {
+        let block_range = 0..=(rows.len() as u64 - 1);
+        let tx_range = 0..=0;
+        let mut jar = NippyJar::new(
+            1,
+            &dir.join(segment.filename(&block_range, &tx_range)),
+            SegmentHeader::new(block_range, tx_range, segment),
+        );
+        let row_count = rows.len() as u64;
+        jar.freeze(
+            vec![rows.into_iter().map(Ok::<_, Box<dyn std::error::Error + Send + Sync>>)],
+            row_count,
+        )
+        .unwrap();
+    }
 }
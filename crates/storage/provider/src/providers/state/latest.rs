@@ -1,6 +1,7 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    BundleStateWithReceipts, StateProvider, StateRootProvider,
+    providers::state::{hash_multiproof_targets, macros::delegate_provider_impls},
+    AccountReader, BlockHashReader, BundleStateWithReceipts, StateProofProvider, StateProvider,
+    StateRootProvider,
 };
 use reth_db::{
     cursor::{DbCursorRO, DbDupCursorRO},
@@ -9,9 +10,11 @@ use reth_db::{
 };
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_primitives::{
-    trie::AccountProof, Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, B256,
+    trie::{AccountProof, MultiProof},
+    Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, B256,
 };
 use reth_trie::{proof::Proof, updates::TrieUpdates};
+use std::collections::HashMap;
 
 /// State provider over latest state that takes tx reference.
 #[derive(Debug)]
@@ -73,6 +76,21 @@ impl<'b, TX: DbTx> StateRootProvider for LatestStateProviderRef<'b, TX> {
     }
 }
 
+impl<'b, TX: DbTx> StateProofProvider for LatestStateProviderRef<'b, TX> {
+    fn multiproof(
+        &self,
+        bundle_state: &BundleStateWithReceipts,
+        targets: &HashMap<Address, Vec<B256>>,
+    ) -> ProviderResult<MultiProof> {
+        Proof::overlay_multiproof(
+            self.db,
+            &bundle_state.hash_state_slow(),
+            hash_multiproof_targets(targets),
+        )
+        .map_err(|err| ProviderError::Database(err.into()))
+    }
+}
+
 impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
     /// Get storage.
     fn storage(
@@ -34,6 +34,9 @@ macro_rules! delegate_provider_impls {
                 fn state_root(&self, state: &crate::BundleStateWithReceipts) -> reth_interfaces::provider::ProviderResult<reth_primitives::B256>;
                 fn state_root_with_updates(&self, state: &crate::BundleStateWithReceipts) -> reth_interfaces::provider::ProviderResult<(reth_primitives::B256, reth_trie::updates::TrieUpdates)>;
             }
+            StateProofProvider $(where [$($generics)*])? {
+                fn multiproof(&self, state: &crate::BundleStateWithReceipts, targets: &std::collections::HashMap<reth_primitives::Address, Vec<reth_primitives::B256>>) -> reth_interfaces::provider::ProviderResult<reth_primitives::trie::MultiProof>;
+            }
             AccountReader $(where [$($generics)*])? {
                 fn basic_account(&self, address: reth_primitives::Address) -> reth_interfaces::provider::ProviderResult<Option<reth_primitives::Account>>;
             }
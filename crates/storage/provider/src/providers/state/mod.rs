@@ -2,3 +2,16 @@
 pub(crate) mod historical;
 pub(crate) mod latest;
 pub(crate) mod macros;
+
+use reth_primitives::{keccak256, Address, B256};
+use std::collections::HashMap;
+
+/// Hashes the addresses and storage slots of multiproof targets.
+pub(crate) fn hash_multiproof_targets(
+    targets: &HashMap<Address, Vec<B256>>,
+) -> HashMap<B256, Vec<B256>> {
+    targets
+        .iter()
+        .map(|(address, slots)| (keccak256(address), slots.iter().map(keccak256).collect()))
+        .collect()
+}
@@ -35,8 +35,8 @@ pub use receipts::{ReceiptProvider, ReceiptProviderIdExt};
 
 mod state;
 pub use state::{
-    BlockchainTreePendingStateProvider, BundleStateDataProvider, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider,
+    BlockchainTreePendingStateProvider, BundleStateDataProvider, StateProofProvider, StateProvider,
+    StateProviderBox, StateProviderFactory, StateRootProvider,
 };
 
 mod transactions;
@@ -68,3 +68,6 @@ pub use history::HistoryWriter;
 
 mod prune_checkpoint;
 pub use prune_checkpoint::{PruneCheckpointReader, PruneCheckpointWriter};
+
+mod node_data;
+pub use node_data::NodeDataProvider;
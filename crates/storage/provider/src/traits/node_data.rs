@@ -0,0 +1,11 @@
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Bytes, B256};
+
+/// Lookup of trie nodes and contract bytecode by their hash.
+///
+/// This is used to serve `GetNodeData` requests of the `eth/66` protocol.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait NodeDataProvider: Send + Sync {
+    /// Returns the RLP encoded trie node or the contract bytecode that hashes to the given hash.
+    fn node_data_by_hash(&self, hash: B256) -> ProviderResult<Option<Bytes>>;
+}
@@ -3,17 +3,21 @@ use crate::{BlockHashReader, BlockIdReader, BundleStateWithReceipts};
 use auto_impl::auto_impl;
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_primitives::{
-    trie::AccountProof, Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag,
-    Bytecode, StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
+    trie::{AccountProof, MultiProof},
+    Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag, Bytecode, StorageKey,
+    StorageValue, B256, KECCAK_EMPTY, U256,
 };
 use reth_trie::updates::TrieUpdates;
+use std::collections::HashMap;
 
 /// Type alias of boxed [StateProvider].
 pub type StateProviderBox = Box<dyn StateProvider>;
 
 /// An abstraction for a type that provides state data.
 #[auto_impl(&, Arc, Box)]
-pub trait StateProvider: BlockHashReader + AccountReader + StateRootProvider + Send + Sync {
+pub trait StateProvider:
+    BlockHashReader + AccountReader + StateRootProvider + StateProofProvider + Send + Sync
+{
     /// Get storage of given account.
     fn storage(
         &self,
@@ -215,7 +219,7 @@ pub trait BlockchainTreePendingStateProvider: Send + Sync {
 /// * [`BundleStateWithReceipts`] contains all changed of accounts and storage of pending chain
 /// * block hashes of pending chain and canonical blocks.
 /// * canonical fork, the block on what pending chain was forked from.
-#[auto_impl[Box,&]]
+#[auto_impl(&, Arc, Box)]
 pub trait BundleStateDataProvider: Send + Sync {
     /// Return post state
     fn state(&self) -> &BundleStateWithReceipts;
@@ -228,7 +232,7 @@ pub trait BundleStateDataProvider: Send + Sync {
 }
 
 /// A type that can compute the state root of a given post state.
-#[auto_impl[Box,&, Arc]]
+#[auto_impl(&, Arc, Box)]
 pub trait StateRootProvider: Send + Sync {
     /// Returns the state root of the `BundleState` on top of the current state.
     ///
@@ -244,3 +248,19 @@ pub trait StateRootProvider: Send + Sync {
         bundle_state: &BundleStateWithReceipts,
     ) -> ProviderResult<(B256, TrieUpdates)>;
 }
+
+/// A type that can generate state proofs on top of a given post state.
+#[auto_impl(&, Arc, Box)]
+pub trait StateProofProvider: Send + Sync {
+    /// Returns the multiproof of the target accounts and their storage slots in the `BundleState`
+    /// on top of the current state.
+    ///
+    /// The proofs of all targets are generated in a single walk of the account trie, so trie
+    /// nodes shared by multiple targets are only included once. Use
+    /// [MultiProof::account_proof] to extract the proof of a single account.
+    fn multiproof(
+        &self,
+        bundle_state: &BundleStateWithReceipts,
+        targets: &HashMap<Address, Vec<B256>>,
+    ) -> ProviderResult<MultiProof>;
+}
@@ -10,7 +10,8 @@ use reth_primitives::{hex_literal::hex, Address};
 use reth_transaction_pool::{
     pool::{BasefeeOrd, ParkedPool, PendingPool},
     test_utils::{MockOrdering, MockTransaction, MockTransactionFactory},
-    SubPoolLimit,
+    EvictionCandidate, EvictionPolicy, LowestTipEviction, OldestEviction, SenderFairnessEviction,
+    SubPool, SubPoolLimit,
 };
 
 // constant seed to use for the rng
@@ -102,7 +103,10 @@ fn txpool_truncate(c: &mut Criterion) {
             truncate_parked(&mut group, "ParkedPool", txs.clone(), senders, max_depth);
 
             // benchmark pending pool
-            truncate_pending(&mut group, "PendingPool", txs, senders, max_depth);
+            truncate_pending(&mut group, "PendingPool", txs.clone(), senders, max_depth);
+
+            // benchmark the eviction order of the configurable policies
+            eviction_order(&mut group, txs, senders, max_depth);
 
             // TODO: benchmark blob truncate
         }
@@ -120,7 +124,49 @@ fn txpool_truncate(c: &mut Criterion) {
     truncate_parked(&mut group, "ParkedPool", txs.clone(), large_senders, max_depth);
 
     // benchmark pending
-    truncate_pending(&mut group, "PendingPool", txs, large_senders, max_depth);
+    truncate_pending(&mut group, "PendingPool", txs.clone(), large_senders, max_depth);
+
+    // benchmark eviction policies
+    eviction_order(&mut group, txs, large_senders, max_depth);
+}
+
+/// Benchmarks the eviction order of all configurable [EvictionPolicy]s, to compare them with the
+/// built-in truncation of the sub-pools.
+fn eviction_order(
+    group: &mut BenchmarkGroup<WallTime>,
+    seed: Vec<MockTransaction>,
+    senders: usize,
+    max_depth: usize,
+) {
+    let mut f = MockTransactionFactory::default();
+    let candidates = seed
+        .iter()
+        .map(|tx| EvictionCandidate::new(&f.validated(tx.clone()), 0, false))
+        .collect::<Vec<_>>();
+
+    let policies: [(&str, &dyn EvictionPolicy); 3] = [
+        ("LowestTipEviction", &LowestTipEviction),
+        ("OldestEviction", &OldestEviction),
+        ("SenderFairnessEviction", &SenderFairnessEviction),
+    ];
+    for (description, policy) in policies {
+        let group_id = format!(
+            "txpool | total txs: {} | total senders: {} | max depth: {} | {}",
+            seed.len(),
+            senders,
+            max_depth,
+            description,
+        );
+
+        group.bench_function(group_id, |b| {
+            b.iter_with_setup(
+                || candidates.clone(),
+                |candidates| {
+                    std::hint::black_box(policy.eviction_order(SubPool::Pending, candidates));
+                },
+            );
+        });
+    }
 }
 
 fn truncate_pending(
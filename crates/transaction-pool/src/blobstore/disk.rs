@@ -5,7 +5,13 @@ use alloy_rlp::{Decodable, Encodable};
 use parking_lot::{Mutex, RwLock};
 use reth_primitives::{BlobTransactionSidecar, TxHash, B256};
 use schnellru::{ByLength, LruMap};
-use std::{fmt, fs, io, path::PathBuf, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs, io,
+    path::PathBuf,
+    str::FromStr,
+    sync::Arc,
+};
 use tracing::{debug, trace};
 
 /// How many [BlobTransactionSidecar] to cache in memory.
@@ -24,16 +30,52 @@ impl DiskFileBlobStore {
         opts: DiskFileBlobStoreConfig,
     ) -> Result<Self, DiskFileBlobStoreError> {
         let blob_dir = blob_dir.into();
-        let DiskFileBlobStoreConfig { max_cached_entries, .. } = opts;
-        let inner = DiskFileBlobStoreInner::new(blob_dir, max_cached_entries);
+        let DiskFileBlobStoreConfig { max_cached_entries, max_disk_size, open } = opts;
+        let inner = DiskFileBlobStoreInner::new(blob_dir, max_cached_entries, max_disk_size);
 
         // initialize the blob store
-        inner.delete_all()?;
-        inner.create_blob_dir()?;
+        match open {
+            OpenDiskFileBlobStore::Clear => {
+                inner.delete_all()?;
+                inner.create_blob_dir()?;
+            }
+            OpenDiskFileBlobStore::ReIndex => {
+                inner.create_blob_dir()?;
+                inner.reindex()?;
+            }
+        }
 
         Ok(Self { inner: Arc::new(inner) })
     }
 
+    /// Deletes all blob sidecars that are not in the given set of transaction hashes and returns
+    /// the number of deleted sidecars.
+    ///
+    /// This is intended to be called on startup after the store was opened with
+    /// [OpenDiskFileBlobStore::ReIndex], to remove orphaned sidecars, for example if the node
+    /// crashed before they could be deleted.
+    pub fn reconcile(&self, keep: &HashSet<B256>) -> usize {
+        let orphans = self
+            .inner
+            .sizes
+            .lock()
+            .keys()
+            .filter(|tx| !keep.contains(*tx))
+            .copied()
+            .collect::<Vec<_>>();
+        let num = orphans.len();
+        if num > 0 {
+            debug!(target:"txpool::blob", num, "Deleting orphaned blob sidecars");
+            let _ = self.inner.delete_many(orphans);
+        }
+        num
+    }
+
+    /// Returns the maximum total size of all blob sidecars on disk, if any.
+    pub fn max_disk_size(&self) -> Option<usize> {
+        self.inner.max_disk_size
+    }
+
     #[cfg(test)]
     fn is_cached(&self, tx: &B256) -> bool {
         self.inner.blob_cache.lock().get(tx).is_some()
@@ -109,20 +151,96 @@ struct DiskFileBlobStoreInner {
     blob_dir: PathBuf,
     blob_cache: Mutex<LruMap<TxHash, BlobTransactionSidecar, ByLength>>,
     size_tracker: BlobStoreSize,
+    /// The size of every blob file on disk.
+    sizes: Mutex<HashMap<TxHash, usize>>,
+    /// The maximum total size of all blob files.
+    max_disk_size: Option<usize>,
     file_lock: RwLock<()>,
 }
 
 impl DiskFileBlobStoreInner {
-    /// Creates a new empty disk file blob store with the given maximum length of the blob cache.
-    fn new(blob_dir: PathBuf, max_length: u32) -> Self {
+    /// Creates a new empty disk file blob store with the given maximum length of the blob cache
+    /// and the maximum total size of all blob files.
+    fn new(blob_dir: PathBuf, max_length: u32, max_disk_size: Option<usize>) -> Self {
         Self {
             blob_dir,
             blob_cache: Mutex::new(LruMap::new(ByLength::new(max_length))),
             size_tracker: Default::default(),
+            sizes: Default::default(),
+            max_disk_size,
             file_lock: Default::default(),
         }
     }
 
+    /// Indexes all blob files that are already in the blob store directory.
+    ///
+    /// Files that are not named after a transaction hash are removed.
+    fn reindex(&self) -> Result<(), DiskFileBlobStoreError> {
+        let entries = fs::read_dir(&self.blob_dir)
+            .map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))?;
+        let _lock = self.file_lock.write();
+        let mut sizes = self.sizes.lock();
+        for entry in entries {
+            let entry =
+                entry.map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))?;
+            let path = entry.path();
+            let tx = entry.file_name().to_str().and_then(|name| B256::from_str(name).ok());
+            match (tx, entry.metadata()) {
+                (Some(tx), Ok(metadata)) if metadata.is_file() => {
+                    sizes.insert(tx, metadata.len() as usize);
+                    self.size_tracker.add_size(metadata.len() as usize);
+                }
+                _ => {
+                    debug!(target:"txpool::blob", ?path, "Removing unknown file from blob store");
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+        self.size_tracker.update_len(sizes.len());
+        debug!(
+            target:"txpool::blob",
+            blobs = sizes.len(),
+            size = self.size_tracker.data_size(),
+            "Reindexed blob store"
+        );
+        Ok(())
+    }
+
+    /// Returns an error if writing a blob file of the given size would exceed the disk quota.
+    ///
+    /// Accounts for the existing file of the transaction, if any, which would be overwritten.
+    fn ensure_quota(
+        &self,
+        sizes: &HashMap<TxHash, usize>,
+        tx: TxHash,
+        size: usize,
+    ) -> Result<(), DiskFileBlobStoreError> {
+        let Some(max_disk_size) = self.max_disk_size else { return Ok(()) };
+        let existing = sizes.get(&tx).copied().unwrap_or_default();
+        let total = self.size_tracker.data_size() - existing + size;
+        if total > max_disk_size {
+            return Err(DiskFileBlobStoreError::QuotaExceeded(tx, size, max_disk_size))
+        }
+        Ok(())
+    }
+
+    /// Records a written blob file of the given size.
+    fn on_written(&self, sizes: &mut HashMap<TxHash, usize>, tx: TxHash, size: usize) {
+        if let Some(existing) = sizes.insert(tx, size) {
+            self.size_tracker.sub_size(existing);
+        }
+        self.size_tracker.add_size(size);
+        self.size_tracker.update_len(sizes.len());
+    }
+
+    /// Records a deleted blob file.
+    fn on_deleted(&self, sizes: &mut HashMap<TxHash, usize>, tx: TxHash) {
+        if let Some(existing) = sizes.remove(&tx) {
+            self.size_tracker.sub_size(existing);
+        }
+        self.size_tracker.update_len(sizes.len());
+    }
+
     fn create_blob_dir(&self) -> Result<(), DiskFileBlobStoreError> {
         debug!(target:"txpool::blob", blob_dir = ?self.blob_dir, "Creating blob store");
         fs::create_dir_all(&self.blob_dir)
@@ -144,11 +262,8 @@ impl DiskFileBlobStoreInner {
     fn insert_one(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
         let mut buf = Vec::with_capacity(data.fields_len());
         data.encode(&mut buf);
+        self.write_one_encoded(tx, &buf)?;
         self.blob_cache.lock().insert(tx, data);
-        let size = self.write_one_encoded(tx, &buf)?;
-
-        self.size_tracker.add_size(size);
-        self.size_tracker.inc_len(1);
         Ok(())
     }
 
@@ -158,31 +273,33 @@ impl DiskFileBlobStoreInner {
             .map(|(tx, data)| {
                 let mut buf = Vec::with_capacity(data.fields_len());
                 data.encode(&mut buf);
-                (self.blob_disk_file(*tx), buf)
+                (*tx, self.blob_disk_file(*tx), buf)
             })
             .collect::<Vec<_>>();
 
-        {
-            let mut cache = self.blob_cache.lock();
-            for (tx, data) in txs {
-                cache.insert(tx, data);
-            }
-        }
-        let mut add = 0;
-        let mut num = 0;
+        let mut written = HashSet::with_capacity(raw.len());
         {
             let _lock = self.file_lock.write();
-            for (path, data) in raw {
-                if let Err(err) = fs::write(&path, &data) {
+            let mut sizes = self.sizes.lock();
+            for (tx, path, data) in raw {
+                if let Err(err) = self.ensure_quota(&sizes, tx, data.len()) {
+                    debug!( target:"txpool::blob", ?err, "Skipping blob file");
+                } else if let Err(err) = fs::write(&path, &data) {
                     debug!( target:"txpool::blob", ?err, ?path, "Failed to write blob file");
                 } else {
-                    add += data.len();
-                    num += 1;
+                    self.on_written(&mut sizes, tx, data.len());
+                    written.insert(tx);
+                }
+            }
+        }
+        {
+            let mut cache = self.blob_cache.lock();
+            for (tx, data) in txs {
+                if written.contains(&tx) {
+                    cache.insert(tx, data);
                 }
             }
         }
-        self.size_tracker.add_size(add);
-        self.size_tracker.inc_len(num);
 
         Ok(())
     }
@@ -268,14 +385,19 @@ impl DiskFileBlobStoreInner {
         res
     }
 
-    /// Retries the blob data for the given transaction hash.
+    /// Writes the encoded blob data for the given transaction hash.
+    ///
+    /// Returns an error if this would exceed the disk quota.
     #[inline]
     fn write_one_encoded(&self, tx: B256, data: &[u8]) -> Result<usize, DiskFileBlobStoreError> {
         trace!( target:"txpool::blob", "[{:?}] writing blob file", tx);
         let path = self.blob_disk_file(tx);
         let _lock = self.file_lock.write();
+        let mut sizes = self.sizes.lock();
+        self.ensure_quota(&sizes, tx, data.len())?;
 
         fs::write(&path, data).map_err(|e| DiskFileBlobStoreError::WriteFile(tx, path, e))?;
+        self.on_written(&mut sizes, tx, data.len());
         Ok(data.len())
     }
 
@@ -284,11 +406,13 @@ impl DiskFileBlobStoreInner {
     fn delete_one(&self, tx: B256) -> Result<(), DiskFileBlobStoreError> {
         trace!( target:"txpool::blob", "[{:?}] deleting blob file", tx);
         let path = self.blob_disk_file(tx);
+        self.blob_cache.lock().remove(&tx);
 
         let _lock = self.file_lock.write();
-        fs::remove_file(&path).map_err(|e| DiskFileBlobStoreError::WriteFile(tx, path, e))?;
-
-        Ok(())
+        let res =
+            fs::remove_file(&path).map_err(|e| DiskFileBlobStoreError::DeleteFile(tx, path, e));
+        self.on_deleted(&mut self.sizes.lock(), tx);
+        res
     }
 
     /// Retries the blob data for the given transaction hash.
@@ -297,15 +421,25 @@ impl DiskFileBlobStoreInner {
         &self,
         txs: impl IntoIterator<Item = TxHash>,
     ) -> Result<(), DiskFileBlobStoreError> {
+        let txs = txs.into_iter().collect::<Vec<_>>();
+        {
+            let mut cache = self.blob_cache.lock();
+            for tx in &txs {
+                cache.remove(tx);
+            }
+        }
+
         let _lock = self.file_lock.write();
-        for tx in txs.into_iter() {
+        let mut sizes = self.sizes.lock();
+        for tx in txs {
             trace!( target:"txpool::blob", "[{:?}] deleting blob file", tx);
             let path = self.blob_disk_file(tx);
 
             let _ = fs::remove_file(&path).map_err(|e| {
-                let err = DiskFileBlobStoreError::WriteFile(tx, path, e);
+                let err = DiskFileBlobStoreError::DeleteFile(tx, path, e);
                 debug!( target:"txpool::blob", ?err);
             });
+            self.on_deleted(&mut sizes, tx);
         }
 
         Ok(())
@@ -361,6 +495,7 @@ impl fmt::Debug for DiskFileBlobStoreInner {
         f.debug_struct("DiskFileBlobStoreInner")
             .field("blob_dir", &self.blob_dir)
             .field("cached_blobs", &self.blob_cache.try_lock().map(|lock| lock.len()))
+            .field("max_disk_size", &self.max_disk_size)
             .finish()
     }
 }
@@ -378,6 +513,9 @@ pub enum DiskFileBlobStoreError {
     WriteFile(TxHash, PathBuf, io::Error),
     #[error("[{0}] failed to delete blob file at {1}: {2}")]
     DeleteFile(TxHash, PathBuf, io::Error),
+    /// Thrown if writing a blob file would exceed the configured disk quota.
+    #[error("[{0}] blob file of {1} bytes exceeds the blobstore disk quota of {2} bytes")]
+    QuotaExceeded(TxHash, usize, usize),
 }
 
 impl From<DiskFileBlobStoreError> for BlobStoreError {
@@ -391,13 +529,41 @@ impl From<DiskFileBlobStoreError> for BlobStoreError {
 pub struct DiskFileBlobStoreConfig {
     /// The maximum number of blobs to keep in the in memory blob cache.
     pub max_cached_entries: u32,
+    /// The maximum total size of all blob files on disk in bytes.
+    ///
+    /// Inserting a blob that would exceed this fails. No limit if `None`.
+    pub max_disk_size: Option<usize>,
     /// How to open the blob store.
     pub open: OpenDiskFileBlobStore,
 }
 
+impl DiskFileBlobStoreConfig {
+    /// Set the maximum number of blobs to keep in the in memory blob cache.
+    pub const fn with_max_cached_entries(mut self, max_cached_entries: u32) -> Self {
+        self.max_cached_entries = max_cached_entries;
+        self
+    }
+
+    /// Set the maximum total size of all blob files on disk in bytes.
+    pub const fn with_max_disk_size(mut self, max_disk_size: Option<usize>) -> Self {
+        self.max_disk_size = max_disk_size;
+        self
+    }
+
+    /// Set how to open the blob store.
+    pub const fn with_open(mut self, open: OpenDiskFileBlobStore) -> Self {
+        self.open = open;
+        self
+    }
+}
+
 impl Default for DiskFileBlobStoreConfig {
     fn default() -> Self {
-        Self { max_cached_entries: DEFAULT_MAX_CACHED_BLOBS, open: Default::default() }
+        Self {
+            max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
+            max_disk_size: None,
+            open: Default::default(),
+        }
     }
 }
 
@@ -407,7 +573,7 @@ pub enum OpenDiskFileBlobStore {
     /// Clear everything in the blob store.
     #[default]
     Clear,
-    /// Keep the existing blob store and index
+    /// Keep the existing blob store and index the blob files in it.
     ReIndex,
 }
 
@@ -463,4 +629,74 @@ mod tests {
         assert!(!store.contains(all_hashes[0]).unwrap());
         assert!(store.get_exact(all_hashes).is_err());
     }
+
+    #[test]
+    fn disk_size_accounting() {
+        let (store, _dir) = tmp_store();
+
+        let blobs = rng_blobs(4);
+        store.insert_all(blobs.clone()).unwrap();
+        assert_eq!(store.blobs_len(), 4);
+        let size = store.data_size_hint().unwrap();
+
+        // overwriting a blob doesn't change the size
+        store.insert(blobs[0].0, blobs[0].1.clone()).unwrap();
+        assert_eq!(store.blobs_len(), 4);
+        assert_eq!(store.data_size_hint().unwrap(), size);
+
+        store.delete(blobs[0].0).unwrap();
+        assert_eq!(store.blobs_len(), 3);
+        assert!(store.data_size_hint().unwrap() < size);
+
+        store.delete_all(blobs.iter().map(|(tx, _)| *tx).collect()).unwrap();
+        assert_eq!(store.blobs_len(), 0);
+        assert_eq!(store.data_size_hint().unwrap(), 0);
+    }
+
+    #[test]
+    fn disk_quota() {
+        let dir = tempfile::tempdir().unwrap();
+        let blobs = rng_blobs(2);
+        let mut buf = Vec::new();
+        blobs[0].1.encode(&mut buf);
+
+        let config = DiskFileBlobStoreConfig::default().with_max_disk_size(Some(buf.len()));
+        let store = DiskFileBlobStore::open(dir.path(), config).unwrap();
+
+        store.insert(blobs[0].0, blobs[0].1.clone()).unwrap();
+        assert!(store.insert(blobs[1].0, blobs[1].1.clone()).is_err());
+        assert!(!store.contains(blobs[1].0).unwrap());
+
+        store.delete(blobs[0].0).unwrap();
+        store.insert(blobs[1].0, blobs[1].1.clone()).unwrap();
+        assert_eq!(store.blobs_len(), 1);
+    }
+
+    #[test]
+    fn disk_reindex_and_reconcile() {
+        let (store, dir) = tmp_store();
+
+        let blobs = rng_blobs(4);
+        store.insert_all(blobs.clone()).unwrap();
+        let size = store.data_size_hint().unwrap();
+        drop(store);
+
+        let config = DiskFileBlobStoreConfig::default().with_open(OpenDiskFileBlobStore::ReIndex);
+        let store = DiskFileBlobStore::open(dir.path(), config).unwrap();
+        assert_eq!(store.blobs_len(), 4);
+        assert_eq!(store.data_size_hint().unwrap(), size);
+        assert_eq!(store.get(blobs[0].0).unwrap().unwrap(), blobs[0].1);
+
+        let keep = HashSet::from([blobs[0].0, blobs[1].0]);
+        assert_eq!(store.reconcile(&keep), 2);
+        assert_eq!(store.blobs_len(), 2);
+        assert!(store.contains(blobs[1].0).unwrap());
+        assert!(!store.contains(blobs[2].0).unwrap());
+
+        // clearing on open removes everything
+        drop(store);
+        let store = DiskFileBlobStore::open(dir.path(), Default::default()).unwrap();
+        assert_eq!(store.blobs_len(), 0);
+        assert!(!store.contains(blobs[0].0).unwrap());
+    }
 }
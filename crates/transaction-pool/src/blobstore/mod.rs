@@ -98,11 +98,6 @@ impl BlobStoreSize {
         self.num_blobs.store(len, std::sync::atomic::Ordering::Relaxed);
     }
 
-    #[inline]
-    pub(crate) fn inc_len(&self, add: usize) {
-        self.num_blobs.fetch_add(add, std::sync::atomic::Ordering::Relaxed);
-    }
-
     #[inline]
     pub(crate) fn data_size(&self) -> usize {
         self.data_size.load(std::sync::atomic::Ordering::Relaxed)
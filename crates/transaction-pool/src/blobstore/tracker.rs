@@ -9,15 +9,26 @@ use std::collections::BTreeMap;
 pub struct BlobStoreCanonTracker {
     /// Keeps track of the blob transactions included in blocks.
     blob_txs_in_blocks: BTreeMap<BlockNumber, Vec<B256>>,
+    /// The highest tracked block number.
+    tip: BlockNumber,
+    /// For how many blocks the blob sidecars of included transactions are retained.
+    retention: u64,
 }
 
 impl BlobStoreCanonTracker {
+    /// Creates a new tracker that retains the blob sidecars of included transactions for at
+    /// least the given number of blocks, even if they are already finalized.
+    pub fn with_retention(retention: u64) -> Self {
+        Self { retention, ..Default::default() }
+    }
+
     /// Adds a block to the blob store maintenance.
     pub fn add_block(
         &mut self,
         block_number: BlockNumber,
         blob_txs: impl IntoIterator<Item = B256>,
     ) {
+        self.tip = self.tip.max(block_number);
         self.blob_txs_in_blocks.insert(block_number, blob_txs.into_iter().collect());
     }
 
@@ -46,13 +57,20 @@ impl BlobStoreCanonTracker {
         self.add_blocks(blob_txs);
     }
 
+    /// Returns all tracked blob transactions.
+    pub fn blob_txs(&self) -> impl Iterator<Item = &B256> + '_ {
+        self.blob_txs_in_blocks.values().flatten()
+    }
+
     /// Invoked when a block is finalized.
     ///
-    /// This returns all blob transactions that were included in blocks that are now finalized.
+    /// This returns all blob transactions that were included in blocks that are now finalized and
+    /// are older than the retention period.
     pub fn on_finalized_block(&mut self, finalized_block: BlockNumber) -> BlobStoreUpdates {
+        let prune_to = finalized_block.min(self.tip.saturating_sub(self.retention));
         let mut finalized = Vec::new();
         while let Some(entry) = self.blob_txs_in_blocks.first_entry() {
-            if *entry.key() <= finalized_block {
+            if *entry.key() <= prune_to {
                 finalized.extend(entry.remove_entry().1);
             } else {
                 break
@@ -98,4 +116,23 @@ mod tests {
             BlobStoreUpdates::Finalized(block2.into_iter().chain(block3).collect::<Vec<_>>())
         );
     }
+
+    #[test]
+    fn test_finalized_tracker_retention() {
+        let mut tracker = BlobStoreCanonTracker::with_retention(2);
+
+        let block1 = vec![B256::random()];
+        let block2 = vec![B256::random()];
+        let block3 = vec![B256::random()];
+        tracker.add_block(1, block1.clone());
+        tracker.add_block(2, block2.clone());
+        tracker.add_block(3, block3.clone());
+
+        // only block 1 is older than the retention period
+        assert_eq!(tracker.on_finalized_block(3), BlobStoreUpdates::Finalized(block1));
+        assert_eq!(tracker.on_finalized_block(3), BlobStoreUpdates::None);
+
+        tracker.add_block(4, vec![]);
+        assert_eq!(tracker.on_finalized_block(3), BlobStoreUpdates::Finalized(block2));
+    }
 }
@@ -0,0 +1,327 @@
+//! A pool of transaction bundles that target a specific block.
+//!
+//! Bundles are submitted via `eth_sendBundle` and are included atomically at the top of the block
+//! they target by the payload builder.
+
+use parking_lot::RwLock;
+use reth_primitives::{keccak256, BlockNumber, TransactionSignedEcRecovered, TxHash, B256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+use tracing::trace;
+
+/// The default maximum number of bundles that can target the same block.
+pub const DEFAULT_MAX_BUNDLES_PER_BLOCK: usize = 1024;
+
+/// A bundle of transactions that must be included in the given order and all or none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolBundle {
+    /// The hash of the bundle, the keccak256 of the concatenated transaction hashes.
+    pub hash: B256,
+    /// The transactions of the bundle in the order they must be included.
+    pub transactions: Vec<TransactionSignedEcRecovered>,
+    /// The block the bundle targets.
+    pub block_number: BlockNumber,
+    /// The minimum timestamp of the block the bundle can be included in.
+    pub min_timestamp: Option<u64>,
+    /// The maximum timestamp of the block the bundle can be included in.
+    pub max_timestamp: Option<u64>,
+    /// Hashes of the transactions of the bundle that are allowed to revert.
+    pub reverting_tx_hashes: Vec<TxHash>,
+    /// Identifier that can be used to replace or cancel the bundle.
+    pub replacement_uuid: Option<String>,
+}
+
+impl PoolBundle {
+    /// Creates a new bundle of the given transactions that targets the given block.
+    pub fn new(transactions: Vec<TransactionSignedEcRecovered>, block_number: BlockNumber) -> Self {
+        let hash_bytes = transactions.iter().flat_map(|tx| tx.hash.0).collect::<Vec<_>>();
+        Self {
+            hash: keccak256(hash_bytes),
+            transactions,
+            block_number,
+            min_timestamp: None,
+            max_timestamp: None,
+            reverting_tx_hashes: Vec::new(),
+            replacement_uuid: None,
+        }
+    }
+
+    /// Sets the range of block timestamps the bundle can be included in.
+    pub fn with_timestamps(mut self, min: Option<u64>, max: Option<u64>) -> Self {
+        self.min_timestamp = min;
+        self.max_timestamp = max;
+        self
+    }
+
+    /// Sets the hashes of the transactions that are allowed to revert.
+    pub fn with_reverting_tx_hashes(mut self, reverting_tx_hashes: Vec<TxHash>) -> Self {
+        self.reverting_tx_hashes = reverting_tx_hashes;
+        self
+    }
+
+    /// Sets the identifier that can be used to replace or cancel the bundle.
+    pub fn with_replacement_uuid(mut self, replacement_uuid: Option<String>) -> Self {
+        self.replacement_uuid = replacement_uuid;
+        self
+    }
+
+    /// Returns `true` if the bundle can be included in a block with the given timestamp.
+    pub fn is_valid_at(&self, timestamp: u64) -> bool {
+        self.min_timestamp.map_or(true, |min| timestamp >= min) &&
+            self.max_timestamp.map_or(true, |max| timestamp <= max)
+    }
+
+    /// Returns `true` if the transaction is allowed to revert.
+    pub fn may_revert(&self, tx: &TxHash) -> bool {
+        self.reverting_tx_hashes.contains(tx)
+    }
+
+    /// Returns the sum of the gas limits of all transactions.
+    pub fn gas_limit(&self) -> u64 {
+        self.transactions.iter().map(|tx| tx.gas_limit()).sum()
+    }
+}
+
+/// Errors that can occur when adding a bundle to the [BundlePool].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundlePoolError {
+    /// Thrown if the bundle doesn't contain any transactions.
+    #[error("bundle has no transactions")]
+    EmptyBundle,
+    /// Thrown if the bundle targets a block that is already canonical.
+    #[error("bundle targets block {0} but the latest block is {1}")]
+    OutdatedBlock(BlockNumber, BlockNumber),
+    /// Thrown if too many bundles target the block.
+    #[error("too many bundles for block {0}")]
+    BlockFull(BlockNumber),
+}
+
+/// A pool of transaction bundles, keyed by the block they target.
+///
+/// Bundles are removed once the block they target is canonical.
+#[derive(Debug, Clone)]
+pub struct BundlePool {
+    inner: Arc<RwLock<BundlePoolInner>>,
+}
+
+impl BundlePool {
+    /// Creates a new pool that accepts at most `max_bundles_per_block` bundles per block.
+    pub fn new(max_bundles_per_block: usize) -> Self {
+        let inner = BundlePoolInner { max_bundles_per_block, ..Default::default() };
+        Self { inner: Arc::new(RwLock::new(inner)) }
+    }
+
+    /// Adds the bundle to the pool and returns its hash.
+    ///
+    /// If there's already a bundle with the same replacement uuid, it is replaced.
+    pub fn add_bundle(&self, bundle: PoolBundle) -> Result<B256, BundlePoolError> {
+        if bundle.transactions.is_empty() {
+            return Err(BundlePoolError::EmptyBundle)
+        }
+        let mut inner = self.inner.write();
+        if bundle.block_number <= inner.last_block {
+            return Err(BundlePoolError::OutdatedBlock(bundle.block_number, inner.last_block))
+        }
+        let replaced =
+            bundle.replacement_uuid.as_ref().and_then(|uuid| inner.by_uuid.get(uuid).copied());
+        let bundles = inner.bundles.get(&bundle.block_number);
+        if bundles.is_some_and(|bundles| bundles.iter().any(|b| b.hash == bundle.hash)) {
+            // already in the pool
+            return Ok(bundle.hash)
+        }
+        // the replaced bundle only frees up capacity if it targets the same block
+        let frees_capacity = replaced.is_some_and(|replaced| {
+            bundles.is_some_and(|bundles| bundles.iter().any(|b| b.hash == replaced))
+        });
+        let len = bundles.map_or(0, Vec::len) - frees_capacity as usize;
+        if len >= inner.max_bundles_per_block {
+            return Err(BundlePoolError::BlockFull(bundle.block_number))
+        }
+        if let Some(replaced) = replaced {
+            inner.remove(replaced);
+        }
+
+        let hash = bundle.hash;
+        trace!(target: "txpool::bundle", ?hash, block_number = bundle.block_number, "adding bundle");
+        if let Some(uuid) = &bundle.replacement_uuid {
+            inner.by_uuid.insert(uuid.clone(), hash);
+        }
+        inner.bundles.entry(bundle.block_number).or_default().push(Arc::new(bundle));
+        Ok(hash)
+    }
+
+    /// Removes the bundle with the given replacement uuid and returns `true` if it existed.
+    pub fn cancel_bundle(&self, replacement_uuid: &str) -> bool {
+        let mut inner = self.inner.write();
+        let Some(hash) = inner.by_uuid.get(replacement_uuid).copied() else { return false };
+        inner.remove(hash)
+    }
+
+    /// Removes the bundle with the given hash and returns `true` if it existed.
+    pub fn remove_bundle(&self, hash: B256) -> bool {
+        self.inner.write().remove(hash)
+    }
+
+    /// Returns all bundles that can be included in the block with the given number and timestamp,
+    /// in the order they were added.
+    pub fn bundles_for_block(
+        &self,
+        block_number: BlockNumber,
+        timestamp: u64,
+    ) -> Vec<Arc<PoolBundle>> {
+        self.inner
+            .read()
+            .bundles
+            .get(&block_number)
+            .map(|bundles| {
+                bundles.iter().filter(|bundle| bundle.is_valid_at(timestamp)).cloned().collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Removes all bundles that target the given block or an earlier one.
+    ///
+    /// This is invoked when the block is canonical.
+    pub fn on_canonical_block(&self, block_number: BlockNumber) {
+        let mut inner = self.inner.write();
+        inner.last_block = inner.last_block.max(block_number);
+        let pending = inner.bundles.split_off(&(block_number + 1));
+        let outdated = std::mem::replace(&mut inner.bundles, pending);
+        for bundle in outdated.into_values().flatten() {
+            if let Some(uuid) = &bundle.replacement_uuid {
+                inner.by_uuid.remove(uuid);
+            }
+        }
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.read().bundles.values().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if there are no bundles in the pool.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().bundles.is_empty()
+    }
+}
+
+impl Default for BundlePool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUNDLES_PER_BLOCK)
+    }
+}
+
+#[derive(Debug, Default)]
+struct BundlePoolInner {
+    /// The maximum number of bundles per block.
+    max_bundles_per_block: usize,
+    /// All bundles by the block they target.
+    bundles: BTreeMap<BlockNumber, Vec<Arc<PoolBundle>>>,
+    /// The hash of the bundle for each replacement uuid.
+    by_uuid: HashMap<String, B256>,
+    /// The latest canonical block.
+    last_block: BlockNumber,
+}
+
+impl BundlePoolInner {
+    /// Removes the bundle with the given hash.
+    fn remove(&mut self, hash: B256) -> bool {
+        let Some((block_number, idx)) = self.bundles.iter().find_map(|(block_number, bundles)| {
+            let idx = bundles.iter().position(|bundle| bundle.hash == hash)?;
+            Some((*block_number, idx))
+        }) else {
+            return false
+        };
+
+        let Some(bundles) = self.bundles.get_mut(&block_number) else { return false };
+        let bundle = bundles.remove(idx);
+        if bundles.is_empty() {
+            self.bundles.remove(&block_number);
+        }
+        if let Some(uuid) = &bundle.replacement_uuid {
+            self.by_uuid.remove(uuid);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        sign_message, Transaction, TransactionKind, TransactionSigned, TxLegacy, U256,
+    };
+
+    fn bundle(nonce: u64, block_number: BlockNumber) -> PoolBundle {
+        let secret = B256::random();
+        let tx = Transaction::Legacy(TxLegacy {
+            nonce,
+            gas_limit: 21_000,
+            to: TransactionKind::Call(Default::default()),
+            value: U256::from(1),
+            ..Default::default()
+        });
+        let signature = sign_message(secret, tx.signature_hash()).unwrap();
+        let tx = TransactionSigned::from_transaction_and_signature(tx, signature)
+            .into_ecrecovered()
+            .unwrap();
+        PoolBundle::new(vec![tx], block_number)
+    }
+
+    #[test]
+    fn add_and_prune_bundles() {
+        let pool = BundlePool::default();
+        let first = bundle(0, 2);
+        let second = bundle(1, 3).with_timestamps(Some(100), Some(200));
+
+        let first_hash = pool.add_bundle(first.clone()).unwrap();
+        assert_eq!(first_hash, first.hash);
+        pool.add_bundle(second.clone()).unwrap();
+        assert_eq!(pool.len(), 2);
+
+        assert_eq!(pool.bundles_for_block(2, 0), vec![Arc::new(first)]);
+        assert!(pool.bundles_for_block(3, 99).is_empty());
+        assert_eq!(pool.bundles_for_block(3, 150), vec![Arc::new(second)]);
+
+        pool.on_canonical_block(2);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.add_bundle(bundle(2, 2)), Err(BundlePoolError::OutdatedBlock(2, 2)));
+    }
+
+    #[test]
+    fn replace_and_cancel_bundles() {
+        let pool = BundlePool::new(1);
+        let uuid = Some("a".to_string());
+
+        pool.add_bundle(bundle(0, 1).with_replacement_uuid(uuid.clone())).unwrap();
+        assert_eq!(pool.add_bundle(bundle(1, 1)), Err(BundlePoolError::BlockFull(1)));
+
+        let replacement = bundle(2, 1).with_replacement_uuid(uuid);
+        pool.add_bundle(replacement.clone()).unwrap();
+        assert_eq!(pool.bundles_for_block(1, 0), vec![Arc::new(replacement)]);
+
+        assert!(pool.cancel_bundle("a"));
+        assert!(!pool.cancel_bundle("a"));
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn rejected_replacement_keeps_bundle() {
+        let pool = BundlePool::new(1);
+        let uuid = Some("a".to_string());
+
+        let replaced = bundle(0, 1).with_replacement_uuid(uuid.clone());
+        pool.add_bundle(replaced.clone()).unwrap();
+        let other = bundle(1, 2);
+        pool.add_bundle(other.clone()).unwrap();
+
+        // the replacement targets a full block, so the replaced bundle must be kept
+        let replacement = bundle(2, 2).with_replacement_uuid(uuid);
+        assert_eq!(pool.add_bundle(replacement), Err(BundlePoolError::BlockFull(2)));
+        assert_eq!(pool.bundles_for_block(1, 0), vec![Arc::new(replaced)]);
+        assert_eq!(pool.bundles_for_block(2, 0), vec![Arc::new(other)]);
+        assert!(pool.cancel_bundle("a"));
+    }
+}
@@ -1,4 +1,9 @@
+use crate::{
+    bundle::DEFAULT_MAX_BUNDLES_PER_BLOCK, DefaultEvictionPolicy, DefaultReplacementPolicy,
+    EvictionPolicy, ReplacementPolicy,
+};
 use reth_primitives::EIP4844_TX_TYPE_ID;
+use std::sync::Arc;
 
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
@@ -35,6 +40,12 @@ pub struct PoolConfig {
     /// How to handle locally received transactions:
     /// [TransactionOrigin::Local](crate::TransactionOrigin).
     pub local_transactions_config: LocalTransactionConfig,
+    /// Decides which transactions are evicted from a sub-pool that exceeds its limit.
+    pub eviction_policy: Arc<dyn EvictionPolicy>,
+    /// Decides whether a transaction can replace an existing transaction.
+    pub replacement_policy: Arc<dyn ReplacementPolicy>,
+    /// Max number of bundles that can target the same block.
+    pub max_bundles_per_block: usize,
 }
 
 impl Default for PoolConfig {
@@ -47,6 +58,9 @@ impl Default for PoolConfig {
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bumps: Default::default(),
             local_transactions_config: Default::default(),
+            eviction_policy: Arc::new(DefaultEvictionPolicy),
+            replacement_policy: Arc::new(DefaultReplacementPolicy),
+            max_bundles_per_block: DEFAULT_MAX_BUNDLES_PER_BLOCK,
         }
     }
 }
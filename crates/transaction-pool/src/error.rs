@@ -59,6 +59,9 @@ pub enum PoolErrorKind {
     /// Thrown if the mutual exclusivity constraint (blob vs normal transaction) is violated.
     #[error("transaction type {1} conflicts with existing transaction for {0}")]
     ExistingConflictingTransactionType(Address, u8),
+    /// Thrown if the conditions of a conditional transaction can no longer be met.
+    #[error("transaction conditions can no longer be met")]
+    ConditionsNotMet,
     /// Any other error that occurred while inserting/validating a transaction. e.g. IO database
     /// error
     #[error(transparent)]
@@ -132,6 +135,10 @@ impl PoolError {
                 // exclusivity (blob vs normal tx) for all senders
                 false
             }
+            PoolErrorKind::ConditionsNotMet => {
+                // the conditions depend on the state of the chain
+                false
+            }
         }
     }
 }
@@ -167,6 +174,38 @@ pub enum Eip4844PoolTransactionError {
     Eip4844NonceGap,
 }
 
+/// Represents rejections by the built-in
+/// [TransactionValidationRule](crate::validate::TransactionValidationRule)s.
+#[derive(Debug, thiserror::Error)]
+pub enum ValidationRuleError {
+    /// Thrown if the sender of the transaction is on a deny-list.
+    #[error("sender {0} is denied")]
+    DeniedSender(Address),
+    /// Thrown if the recipient of the transaction is on a deny-list.
+    #[error("recipient {0} is denied")]
+    DeniedRecipient(Address),
+    /// Thrown if the calldata of the transaction exceeds the configured limit.
+    #[error("calldata size {size} exceeds the limit {limit}")]
+    ExceedsMaxCalldataSize {
+        /// Size of the calldata of the transaction
+        size: usize,
+        /// Maximum allowed size of the calldata
+        limit: usize,
+    },
+    /// Thrown if the transaction creates a contract, but contract creations are not allowed.
+    #[error("contract creation is not allowed")]
+    ContractCreationNotAllowed,
+    /// Thrown if the priority fee of the transaction is below the floor configured for its
+    /// origin.
+    #[error("priority fee {fee} is below the minimum {floor}")]
+    FeeBelowFloor {
+        /// Priority fee, or gas price for legacy transactions, of the transaction
+        fee: u128,
+        /// Minimum fee for transactions of this origin
+        floor: u128,
+    },
+}
+
 /// Represents errors that can happen when validating transactions for the pool
 ///
 /// See [TransactionValidator](crate::TransactionValidator).
@@ -197,6 +236,9 @@ pub enum InvalidPoolTransactionError {
     /// Eip-4844 related errors
     #[error(transparent)]
     Eip4844(#[from] Eip4844PoolTransactionError),
+    /// Thrown if the transaction was rejected by a validation rule
+    #[error(transparent)]
+    Rule(#[from] ValidationRuleError),
     /// Any other error that occurred while inserting/validating that is transaction specific
     #[error(transparent)]
     Other(Box<dyn PoolTransactionError>),
@@ -259,6 +301,10 @@ impl InvalidPoolTransactionError {
             }
             InvalidPoolTransactionError::IntrinsicGasTooLow => true,
             InvalidPoolTransactionError::Overdraft => false,
+            InvalidPoolTransactionError::Rule(_) => {
+                // local policy
+                false
+            }
             InvalidPoolTransactionError::Other(err) => err.is_bad_transaction(),
             InvalidPoolTransactionError::Eip4844(eip4844_err) => {
                 match eip4844_err {
@@ -0,0 +1,334 @@
+//! Journal of local transactions, which persists them across restarts.
+//!
+//! The journal is a file of RLP encoded [PooledTransactionsElement]s. Every new local transaction
+//! is appended to it, and it's periodically rewritten to only contain the local transactions that
+//! are still in the pool. On startup, the journaled transactions are validated and inserted into
+//! the pool again.
+
+use crate::{GetPooledTransactionLimit, TransactionOrigin, TransactionPool};
+use alloy_rlp::{Decodable, Encodable};
+use futures_util::pin_mut;
+use parking_lot::Mutex;
+use reth_primitives::{FromRecoveredPooledTransaction, PooledTransactionsElement};
+use reth_tasks::{shutdown::GracefulShutdown, TaskSpawner};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::oneshot;
+use tracing::{debug, info, trace, warn};
+
+/// Default interval at which the journal is rewritten to only contain the pooled local
+/// transactions.
+pub const DEFAULT_JOURNAL_ROTATION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Settings for journaling local transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionJournalConfig {
+    /// Path of the journal file.
+    pub path: PathBuf,
+    /// Interval at which the journal is rewritten to only contain the local transactions that are
+    /// still in the pool. If zero, the journal is only rewritten on startup and shutdown.
+    ///
+    /// Default: 1 hour
+    pub rotation_interval: Duration,
+}
+
+impl TransactionJournalConfig {
+    /// Creates a new config journaling to the given file.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), rotation_interval: DEFAULT_JOURNAL_ROTATION_INTERVAL }
+    }
+
+    /// Sets the interval at which the journal is rotated.
+    pub fn with_rotation_interval(mut self, rotation_interval: Duration) -> Self {
+        self.rotation_interval = rotation_interval;
+        self
+    }
+}
+
+/// Errors that can occur when accessing the transaction journal.
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionJournalError {
+    /// Thrown if the journal file can't be read.
+    #[error("failed to read transaction journal at {0}: {1}")]
+    Read(PathBuf, io::Error),
+    /// Thrown if the journal file can't be written.
+    #[error("failed to write transaction journal at {0}: {1}")]
+    Write(PathBuf, io::Error),
+    /// Thrown if the blocking task accessing the journal was dropped before it finished.
+    #[error("transaction journal task was dropped")]
+    TaskDropped,
+}
+
+/// File that local transactions are journaled to.
+#[derive(Debug)]
+pub struct TransactionJournal {
+    /// Path of the journal file.
+    path: PathBuf,
+    /// Journal file opened for appending, if it has been written to.
+    writer: Option<File>,
+}
+
+impl TransactionJournal {
+    /// Creates a new journal at the given path. The file is created on the first write.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), writer: None }
+    }
+
+    /// Returns the path of the journal file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads all transactions from the journal.
+    ///
+    /// Returns an empty list if the journal doesn't exist. If the journal ends with a transaction
+    /// that can't be decoded, for example because the node crashed while writing it, all
+    /// transactions before it are returned.
+    pub fn load(&self) -> Result<Vec<PooledTransactionsElement>, TransactionJournalError> {
+        let data = match fs::read(&self.path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(TransactionJournalError::Read(self.path.clone(), err)),
+        };
+
+        let mut buf = data.as_slice();
+        let mut transactions = Vec::new();
+        while !buf.is_empty() {
+            match PooledTransactionsElement::decode(&mut buf) {
+                Ok(transaction) => transactions.push(transaction),
+                Err(err) => {
+                    warn!(target: "txpool::journal", path = ?self.path, %err, remaining = buf.len(), "Failed to decode journaled transaction, discarding the rest of the journal");
+                    break
+                }
+            }
+        }
+
+        Ok(transactions)
+    }
+
+    /// Appends the transaction to the journal.
+    pub fn insert(
+        &mut self,
+        transaction: &PooledTransactionsElement,
+    ) -> Result<(), TransactionJournalError> {
+        if self.writer.is_none() {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .map_err(|err| TransactionJournalError::Write(self.path.clone(), err))?;
+            self.writer = Some(file);
+        }
+        let writer = self.writer.as_mut().expect("journal is open");
+
+        let mut buf = Vec::with_capacity(transaction.length());
+        transaction.encode(&mut buf);
+        writer.write_all(&buf).map_err(|err| TransactionJournalError::Write(self.path.clone(), err))
+    }
+
+    /// Replaces the content of the journal with the given transactions.
+    ///
+    /// The transactions are written to a temporary file first, which then replaces the journal,
+    /// so the previous journal stays intact if writing fails.
+    pub fn rotate(
+        &mut self,
+        transactions: &[PooledTransactionsElement],
+    ) -> Result<(), TransactionJournalError> {
+        let write_err = |err| TransactionJournalError::Write(self.path.clone(), err);
+
+        // Close the current journal, it's reopened on the next insert.
+        self.writer = None;
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(write_err)?;
+        }
+
+        let mut buf = Vec::new();
+        for transaction in transactions {
+            transaction.encode(&mut buf);
+        }
+
+        let tmp_path = self.path.with_extension("new");
+        let mut file = File::create(&tmp_path).map_err(write_err)?;
+        file.write_all(&buf).map_err(write_err)?;
+        file.sync_all().map_err(write_err)?;
+        fs::rename(&tmp_path, &self.path).map_err(write_err)
+    }
+}
+
+/// Journals the local transactions of the pool until shutdown.
+///
+/// The transactions of an existing journal are inserted into the pool as local transactions first.
+/// Afterwards, new local transactions are appended to the journal, and the journal is rotated to
+/// only contain the local transactions still in the pool at the configured interval and on
+/// shutdown. Blob transactions are journaled with their sidecars, which are read from the blob
+/// store of the pool.
+///
+/// The journal file is only accessed on blocking tasks spawned with the given [TaskSpawner].
+pub async fn journal_local_transactions<P, Tasks>(
+    pool: P,
+    task_spawner: Tasks,
+    config: TransactionJournalConfig,
+    shutdown: GracefulShutdown,
+) where
+    P: TransactionPool + 'static,
+    Tasks: TaskSpawner + 'static,
+{
+    let TransactionJournalConfig { path, rotation_interval } = config;
+    let journal = Arc::new(Mutex::new(TransactionJournal::new(path.clone())));
+
+    match with_journal(&task_spawner, &journal, |journal| journal.load()).await {
+        Ok(transactions) if !transactions.is_empty() => {
+            let total = transactions.len();
+            let transactions = transactions
+                .into_iter()
+                .filter_map(|transaction| transaction.try_into_ecrecovered().ok())
+                .map(<P as TransactionPool>::Transaction::from_recovered_pooled_transaction)
+                .collect::<Vec<_>>();
+            let inserted = match pool.add_transactions(TransactionOrigin::Local, transactions).await
+            {
+                Ok(results) => results.into_iter().filter(Result::is_ok).count(),
+                Err(err) => {
+                    warn!(target: "txpool::journal", %err, "Failed to insert journaled transactions");
+                    0
+                }
+            };
+            info!(target: "txpool::journal", ?path, %total, %inserted, "Loaded local transactions from journal");
+        }
+        Ok(_) => {}
+        Err(err) => warn!(target: "txpool::journal", %err, "Failed to load transaction journal"),
+    }
+
+    // Discards the transactions that were not inserted again.
+    rotate_journal(&pool, &task_spawner, &journal).await;
+
+    let mut new_transactions = pool.new_transactions_listener();
+    let mut rotation = (!rotation_interval.is_zero()).then(|| {
+        tokio::time::interval_at(tokio::time::Instant::now() + rotation_interval, rotation_interval)
+    });
+    pin_mut!(shutdown);
+
+    let graceful_guard = loop {
+        tokio::select! {
+            event = new_transactions.recv() => {
+                let Some(event) = event else { break None };
+                if !event.transaction.is_local() {
+                    continue
+                }
+                let hash = *event.transaction.hash();
+                let Some(transaction) = pool
+                    .get_pooled_transaction_elements(vec![hash], GetPooledTransactionLimit::None)
+                    .pop()
+                else {
+                    continue
+                };
+                trace!(target: "txpool::journal", %hash, "Journaling local transaction");
+                let result = with_journal(&task_spawner, &journal, move |journal| {
+                    journal.insert(&transaction)
+                })
+                .await;
+                if let Err(err) = result {
+                    warn!(target: "txpool::journal", %hash, %err, "Failed to journal local transaction");
+                }
+            }
+            _ = rotation.as_mut().expect("rotation is enabled").tick(), if rotation.is_some() => {
+                rotate_journal(&pool, &task_spawner, &journal).await;
+            }
+            guard = &mut shutdown => {
+                break Some(guard)
+            }
+        }
+    };
+
+    rotate_journal(&pool, &task_spawner, &journal).await;
+    drop(graceful_guard)
+}
+
+/// Runs the operation on the journal on a blocking task, since it accesses the file system.
+async fn with_journal<T, F, Tasks>(
+    task_spawner: &Tasks,
+    journal: &Arc<Mutex<TransactionJournal>>,
+    f: F,
+) -> Result<T, TransactionJournalError>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut TransactionJournal) -> Result<T, TransactionJournalError> + Send + 'static,
+    Tasks: TaskSpawner,
+{
+    let (tx, rx) = oneshot::channel();
+    let journal = Arc::clone(journal);
+    task_spawner.spawn_blocking(Box::pin(async move {
+        let _ = tx.send(f(&mut journal.lock()));
+    }));
+    rx.await.unwrap_or(Err(TransactionJournalError::TaskDropped))
+}
+
+/// Rewrites the journal to only contain the local transactions of the pool.
+async fn rotate_journal<P, Tasks>(
+    pool: &P,
+    task_spawner: &Tasks,
+    journal: &Arc<Mutex<TransactionJournal>>,
+) where
+    P: TransactionPool,
+    Tasks: TaskSpawner,
+{
+    let hashes = pool
+        .get_local_transactions()
+        .into_iter()
+        .map(|transaction| *transaction.hash())
+        .collect::<Vec<_>>();
+    let transactions =
+        pool.get_pooled_transaction_elements(hashes, GetPooledTransactionLimit::None);
+
+    let total = transactions.len();
+    match with_journal(task_spawner, journal, move |journal| journal.rotate(&transactions)).await {
+        Ok(()) => {
+            debug!(target: "txpool::journal", transactions = %total, "Rotated transaction journal")
+        }
+        Err(err) => warn!(target: "txpool::journal", %err, "Failed to rotate transaction journal"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TransactionGenerator;
+
+    #[test]
+    fn journal_insert_rotate_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("transactions.rlp");
+        let mut generator = TransactionGenerator::new(rand::thread_rng());
+        let transactions = (0..3)
+            .map(|_| {
+                PooledTransactionsElement::try_from_broadcast(generator.gen_eip1559()).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let mut journal = TransactionJournal::new(&path);
+        assert_eq!(journal.load().unwrap(), vec![]);
+
+        for transaction in &transactions {
+            journal.insert(transaction).unwrap();
+        }
+        assert_eq!(journal.load().unwrap(), transactions);
+
+        journal.rotate(&transactions[1..2]).unwrap();
+        assert_eq!(journal.load().unwrap(), transactions[1..2]);
+
+        // Inserts after the rotation are appended to the new journal
+        journal.insert(&transactions[2]).unwrap();
+        assert_eq!(journal.load().unwrap(), transactions[1..]);
+
+        // A partially written transaction is discarded
+        let mut buf = Vec::new();
+        transactions[0].encode(&mut buf);
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(&buf[..10]).unwrap();
+        assert_eq!(journal.load().unwrap(), transactions[1..]);
+    }
+}
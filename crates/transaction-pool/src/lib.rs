@@ -73,6 +73,11 @@
 //! responsible for inserting blob data for new transactions into the blobstore.
 //! See also [ValidTransaction](validate::ValidTransaction)
 //!
+//! ## Local Transactions Journal
+//!
+//! Local transactions can be persisted across restarts with the [journal], which records them to
+//! disk and inserts them into the pool again on startup.
+//!
 //!
 //! ## Examples
 //!
@@ -146,7 +151,10 @@
 
 use crate::pool::PoolInner;
 use aquamarine as _;
-use reth_primitives::{Address, BlobTransactionSidecar, PooledTransactionsElement, TxHash, U256};
+use reth_primitives::{
+    Address, BlobTransactionSidecar, PooledTransactionsElement, TransactionConditional, TxHash,
+    U256,
+};
 use reth_provider::StateProviderFactory;
 use std::{collections::HashSet, sync::Arc};
 use tokio::sync::mpsc::Receiver;
@@ -154,6 +162,7 @@ use tracing::{instrument, trace};
 
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
+    bundle::{BundlePool, BundlePoolError, PoolBundle},
     config::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
         REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
@@ -161,9 +170,14 @@ pub use crate::{
     },
     error::PoolResult,
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    policy::{
+        BlobFeeBumpReplacement, DefaultEvictionPolicy, DefaultReplacementPolicy, EvictionCandidate,
+        EvictionPolicy, LowestTipEviction, OldestEviction, ReplacementFees, ReplacementPolicy,
+        SenderFairnessEviction,
+    },
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
-        TransactionEvent, TransactionEvents,
+        PoolDiff, PoolDiffEvent, PoolDiffEvents, TransactionEvent, TransactionEvents,
     },
     traits::*,
     validate::{
@@ -173,6 +187,7 @@ pub use crate::{
 };
 
 pub mod error;
+pub mod journal;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
@@ -180,9 +195,11 @@ pub mod pool;
 pub mod validate;
 
 pub mod blobstore;
+pub mod bundle;
 mod config;
 mod identifier;
 mod ordering;
+mod policy;
 mod traits;
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -346,6 +363,16 @@ where
         self.pool.add_transactions(origin, std::iter::once(tx)).pop().expect("exists; qed")
     }
 
+    async fn add_transaction_with_conditional(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        conditional: TransactionConditional,
+    ) -> PoolResult<TxHash> {
+        let (_, tx) = self.validate(origin, transaction).await;
+        self.pool.add_transaction_with_conditional(origin, tx, conditional)
+    }
+
     async fn add_transactions(
         &self,
         origin: TransactionOrigin,
@@ -369,6 +396,10 @@ where
         self.pool.add_all_transactions_event_listener()
     }
 
+    fn pool_diff_listener(&self) -> PoolDiffEvents<Self::Transaction> {
+        self.pool.add_pool_diff_listener()
+    }
+
     fn pending_transactions_listener_for(&self, kind: TransactionListenerKind) -> Receiver<TxHash> {
         self.pool.add_pending_listener(kind)
     }
@@ -443,6 +474,14 @@ where
         self.pool.all_transactions()
     }
 
+    fn pool_snapshot(&self) -> PoolSnapshot<Self::Transaction> {
+        self.pool.snapshot()
+    }
+
+    fn bundle_pool(&self) -> Option<&BundlePool> {
+        Some(self.pool.bundle_pool())
+    }
+
     fn remove_transactions(
         &self,
         hashes: Vec<TxHash>,
@@ -523,6 +562,10 @@ where
         self.pool.update_accounts(accounts);
     }
 
+    fn conditional_accounts(&self) -> HashSet<Address> {
+        self.pool.conditional_accounts()
+    }
+
     fn delete_blob(&self, tx: TxHash) {
         self.pool.delete_blob(tx)
     }
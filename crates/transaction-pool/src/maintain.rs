@@ -3,7 +3,7 @@
 use crate::{
     blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
     metrics::MaintainPoolMetrics,
-    traits::{CanonicalStateUpdate, ChangedAccount, TransactionPoolExt},
+    traits::{CanonicalStateUpdate, ChangedAccount, ChangedStorage, TransactionPoolExt},
     BlockInfo, TransactionPool,
 };
 use futures_util::{
@@ -12,11 +12,11 @@ use futures_util::{
 };
 use reth_primitives::{
     Address, BlockHash, BlockNumber, BlockNumberOrTag, FromRecoveredPooledTransaction,
-    FromRecoveredTransaction, PooledTransactionsElementEcRecovered,
+    FromRecoveredTransaction, PooledTransactionsElementEcRecovered, B256,
 };
 use reth_provider::{
-    BlockReaderIdExt, BundleStateWithReceipts, CanonStateNotification, ChainSpecProvider,
-    ProviderError, StateProviderFactory,
+    BlockReader, BlockReaderIdExt, BundleStateWithReceipts, CanonStateNotification,
+    ChainSpecProvider, ProviderError, StateProviderFactory,
 };
 use reth_tasks::TaskSpawner;
 use std::{
@@ -39,11 +39,16 @@ pub struct MaintainPoolConfig {
     ///
     /// Default: 100
     pub max_reload_accounts: usize,
+    /// Number of blocks for which the blob sidecars of included transactions are kept in the blob
+    /// store, even if the block is already finalized.
+    ///
+    /// Default: 0
+    pub blob_sidecar_retention: u64,
 }
 
 impl Default for MaintainPoolConfig {
     fn default() -> Self {
-        Self { max_update_depth: 64, max_reload_accounts: 100 }
+        Self { max_update_depth: 64, max_reload_accounts: 100, blob_sidecar_retention: 0 }
     }
 }
 
@@ -67,15 +72,93 @@ where
     .boxed()
 }
 
+/// Returns a spawnable future for maintaining the state of the transaction pool that starts with
+/// the given blob store tracker.
+///
+/// See also [load_retained_blob_txs].
+pub fn maintain_transaction_pool_future_with_tracker<Client, P, St, Tasks>(
+    client: Client,
+    pool: P,
+    events: St,
+    task_spawner: Tasks,
+    config: MaintainPoolConfig,
+    blob_store_tracker: BlobStoreCanonTracker,
+) -> BoxFuture<'static, ()>
+where
+    Client: StateProviderFactory + BlockReaderIdExt + ChainSpecProvider + Clone + Send + 'static,
+    P: TransactionPoolExt + 'static,
+    St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+    Tasks: TaskSpawner + 'static,
+{
+    async move {
+        maintain_transaction_pool_inner(
+            client,
+            pool,
+            events,
+            task_spawner,
+            config,
+            Some(blob_store_tracker),
+        )
+        .await;
+    }
+    .boxed()
+}
+
+/// Returns a tracker for the blob transactions of the canonical blocks that are still within the
+/// blob sidecar retention period of the given tip, so their retained sidecars are pruned
+/// eventually.
+pub fn load_retained_blob_txs<Client: BlockReader>(
+    client: &Client,
+    tip: BlockNumber,
+    retention: u64,
+) -> Result<BlobStoreCanonTracker, ProviderError> {
+    let mut tracker = BlobStoreCanonTracker::with_retention(retention);
+    if retention == 0 {
+        return Ok(tracker)
+    }
+    for block_number in (tip.saturating_sub(retention) + 1)..=tip {
+        if let Some(block) = client.block_by_number(block_number)? {
+            let blob_txs = block
+                .body
+                .iter()
+                .filter(|tx| tx.transaction.is_eip4844())
+                .map(|tx| tx.hash)
+                .collect::<Vec<_>>();
+            tracker.add_block(block_number, blob_txs);
+        }
+    }
+    Ok(tracker)
+}
+
 /// Maintains the state of the transaction pool by handling new blocks and reorgs.
 ///
 /// This listens for any new blocks and reorgs and updates the transaction pool's state accordingly
 pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
+    client: Client,
+    pool: P,
+    events: St,
+    task_spawner: Tasks,
+    config: MaintainPoolConfig,
+) where
+    Client: StateProviderFactory + BlockReaderIdExt + ChainSpecProvider + Clone + Send + 'static,
+    P: TransactionPoolExt + 'static,
+    St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+    Tasks: TaskSpawner + 'static,
+{
+    maintain_transaction_pool_inner(client, pool, events, task_spawner, config, None).await
+}
+
+/// Maintains the state of the transaction pool.
+///
+/// If no blob store tracker is given, the blob transactions of the blocks within the retention
+/// period are loaded on startup.
+async fn maintain_transaction_pool_inner<Client, P, St, Tasks>(
     client: Client,
     pool: P,
     mut events: St,
     task_spawner: Tasks,
     config: MaintainPoolConfig,
+    blob_store_tracker: Option<BlobStoreCanonTracker>,
 ) where
     Client: StateProviderFactory + BlockReaderIdExt + ChainSpecProvider + Clone + Send + 'static,
     P: TransactionPoolExt + 'static,
@@ -83,7 +166,14 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     Tasks: TaskSpawner + 'static,
 {
     let metrics = MaintainPoolMetrics::default();
-    let MaintainPoolConfig { max_update_depth, max_reload_accounts } = config;
+    let MaintainPoolConfig { max_update_depth, max_reload_accounts, blob_sidecar_retention } =
+        config;
+    let has_tracker = blob_store_tracker.is_some();
+
+    // keeps track of mined blob transaction so we can clean finalized transactions
+    let mut blob_store_tracker = blob_store_tracker
+        .unwrap_or_else(|| BlobStoreCanonTracker::with_retention(blob_sidecar_retention));
+
     // ensure the pool points to latest state
     if let Ok(Some(latest)) = client.header_by_number_or_tag(BlockNumberOrTag::Latest) {
         let latest = latest.seal_slow();
@@ -91,16 +181,23 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
         let info = BlockInfo {
             last_seen_block_hash: latest.hash,
             last_seen_block_number: latest.number,
+            last_seen_block_timestamp: latest.timestamp,
             pending_basefee: latest
                 .next_block_base_fee(chain_spec.base_fee_params(latest.timestamp + 12))
                 .unwrap_or_default(),
             pending_blob_fee: latest.next_block_blob_fee(),
         };
         pool.set_block_info(info);
-    }
 
-    // keeps track of mined blob transaction so we can clean finalized transactions
-    let mut blob_store_tracker = BlobStoreCanonTracker::default();
+        if !has_tracker {
+            match load_retained_blob_txs(&client, latest.number, blob_sidecar_retention) {
+                Ok(tracker) => blob_store_tracker = tracker,
+                Err(err) => {
+                    debug!(target: "txpool", %err, "failed to load retained blob transactions")
+                }
+            }
+        }
+    }
 
     // keeps track of the latest finalized block
     let mut last_finalized_block =
@@ -166,9 +263,13 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
         }
 
         // check if we have a new finalized block
-        if let Some(finalized) =
-            last_finalized_block.update(client.finalized_block_number().ok().flatten())
-        {
+        let mut finalized =
+            last_finalized_block.update(client.finalized_block_number().ok().flatten());
+        if blob_sidecar_retention > 0 {
+            // retained blobs can also expire with new blocks if the finalized block didn't change
+            finalized = finalized.or(last_finalized_block.last_finalized_block);
+        }
+        if let Some(finalized) = finalized {
             match blob_store_tracker.on_finalized_block(finalized) {
                 BlobStoreUpdates::None => {}
                 BlobStoreUpdates::Finalized(blobs) => {
@@ -282,6 +383,20 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 // we can use extend here because they are unique
                 changed_accounts.extend(new_changed_accounts.into_iter().map(|entry| entry.0));
 
+                // the storage changes of the old chain are reverted, so the storage of accounts
+                // changed in the old chain is unknown
+                let changed_storage = pool
+                    .conditional_accounts()
+                    .into_iter()
+                    .filter_map(|address| match account_storage_changes(old_state, address) {
+                        Some(ChangedStorage { slots: Some(slots), .. }) if slots.is_empty() => {
+                            account_storage_changes(new_state, address)
+                        }
+                        Some(_) => Some(ChangedStorage { address, slots: None }),
+                        None => account_storage_changes(new_state, address),
+                    })
+                    .collect();
+
                 // all transactions mined in the new chain
                 let new_mined_transactions: HashSet<_> = new_blocks.transaction_hashes().collect();
 
@@ -323,6 +438,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     pending_block_base_fee,
                     pending_block_blob_fee,
                     changed_accounts,
+                    changed_storage,
                     // all transactions mined in the new chain need to be removed from the pool
                     mined_transactions: new_mined_transactions.into_iter().collect(),
                 };
@@ -369,6 +485,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     let info = BlockInfo {
                         last_seen_block_hash: tip.hash,
                         last_seen_block_number: tip.number,
+                        last_seen_block_timestamp: tip.timestamp,
                         pending_basefee: pending_block_base_fee,
                         pending_blob_fee: pending_block_blob_fee,
                     };
@@ -387,6 +504,12 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     changed_accounts.push(acc);
                 }
 
+                let changed_storage = pool
+                    .conditional_accounts()
+                    .into_iter()
+                    .filter_map(|address| account_storage_changes(state, address))
+                    .collect();
+
                 let mined_transactions = blocks.transaction_hashes().collect();
 
                 // check if the range of the commit is canonical with the pool's block
@@ -403,6 +526,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     pending_block_base_fee,
                     pending_block_blob_fee,
                     changed_accounts,
+                    changed_storage,
                     mined_transactions,
                 };
                 pool.on_canonical_state_change(update);
@@ -535,6 +659,25 @@ fn changed_accounts_iter(
         .map(|(address, acc)| ChangedAccount { address, nonce: acc.nonce, balance: acc.balance })
 }
 
+/// Returns the storage changes of the account, if the account was changed.
+fn account_storage_changes(
+    state: &BundleStateWithReceipts,
+    address: Address,
+) -> Option<ChangedStorage> {
+    let account = state.state().state().get(&address)?;
+    let slots = (!account.status.was_destroyed()).then(|| {
+        account
+            .storage
+            .iter()
+            .filter(|(_, slot)| slot.is_changed())
+            .map(|(key, slot)| {
+                (B256::new(key.to_be_bytes()), B256::new(slot.present_value.to_be_bytes()))
+            })
+            .collect()
+    });
+    Some(ChangedStorage { address, slots })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
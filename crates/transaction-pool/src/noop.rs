@@ -5,6 +5,7 @@
 
 use crate::{
     blobstore::BlobStoreError,
+    bundle::BundlePool,
     error::PoolError,
     traits::{
         BestTransactionsAttributes, GetPooledTransactionLimit, NewBlobSidecar,
@@ -12,11 +13,11 @@ use crate::{
     },
     validate::ValidTransaction,
     AllPoolTransactions, AllTransactionsEvents, BestTransactions, BlockInfo, EthPooledTransaction,
-    NewTransactionEvent, PoolResult, PoolSize, PoolTransaction, PooledTransactionsElement,
-    PropagatedTransactions, TransactionEvents, TransactionOrigin, TransactionPool,
-    TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
+    NewTransactionEvent, PoolDiffEvents, PoolResult, PoolSize, PoolSnapshot, PoolTransaction,
+    PooledTransactionsElement, PropagatedTransactions, TransactionEvents, TransactionOrigin,
+    TransactionPool, TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
 };
-use reth_primitives::{Address, BlobTransactionSidecar, TxHash};
+use reth_primitives::{Address, BlobTransactionSidecar, TransactionConditional, TxHash};
 use std::{collections::HashSet, marker::PhantomData, sync::Arc};
 use tokio::sync::{mpsc, mpsc::Receiver};
 
@@ -40,6 +41,7 @@ impl TransactionPool for NoopTransactionPool {
         BlockInfo {
             last_seen_block_hash: Default::default(),
             last_seen_block_number: 0,
+            last_seen_block_timestamp: 0,
             pending_basefee: 0,
             pending_blob_fee: None,
         }
@@ -63,6 +65,16 @@ impl TransactionPool for NoopTransactionPool {
         Err(PoolError::other(hash, Box::new(NoopInsertError::new(transaction))))
     }
 
+    async fn add_transaction_with_conditional(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        _conditional: TransactionConditional,
+    ) -> PoolResult<TxHash> {
+        let hash = *transaction.hash();
+        Err(PoolError::other(hash, Box::new(NoopInsertError::new(transaction))))
+    }
+
     async fn add_transactions(
         &self,
         _origin: TransactionOrigin,
@@ -85,6 +97,10 @@ impl TransactionPool for NoopTransactionPool {
         AllTransactionsEvents { events: mpsc::channel(1).1 }
     }
 
+    fn pool_diff_listener(&self) -> PoolDiffEvents<Self::Transaction> {
+        PoolDiffEvents { events: mpsc::channel(1).1 }
+    }
+
     fn pending_transactions_listener_for(
         &self,
         _kind: TransactionListenerKind,
@@ -166,6 +182,14 @@ impl TransactionPool for NoopTransactionPool {
         AllPoolTransactions::default()
     }
 
+    fn pool_snapshot(&self) -> PoolSnapshot<Self::Transaction> {
+        PoolSnapshot { sequence: 0, pending: vec![], basefee: vec![], blob: vec![], queued: vec![] }
+    }
+
+    fn bundle_pool(&self) -> Option<&BundlePool> {
+        None
+    }
+
     fn remove_transactions(
         &self,
         _hashes: Vec<TxHash>,
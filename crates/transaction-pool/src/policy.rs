@@ -0,0 +1,372 @@
+//! Policies that decide which transactions are evicted from a full sub-pool and which
+//! transactions can replace an existing transaction.
+
+use crate::{
+    identifier::TransactionId, PoolTransaction, PriceBumpConfig, SubPool, ValidPoolTransaction,
+};
+use reth_primitives::{Address, TxHash, EIP4844_TX_TYPE_ID};
+use std::{collections::HashMap, fmt, time::Instant};
+
+/// Decides in which order transactions are evicted from a sub-pool that exceeds its
+/// [SubPoolLimit](crate::SubPoolLimit).
+///
+/// Evicting a transaction also evicts all transactions of the same sender with a higher nonce,
+/// since they can't be executed without it.
+pub trait EvictionPolicy: fmt::Debug + Send + Sync {
+    /// Returns `true` if the sub-pool should be truncated in its built-in order, in which case
+    /// [EvictionPolicy::eviction_order] isn't called for it.
+    fn uses_builtin_order(&self, _subpool: SubPool) -> bool {
+        false
+    }
+
+    /// Returns the transactions of the sub-pool in the order they should be evicted in, the first
+    /// transaction is evicted first.
+    ///
+    /// Transactions that aren't returned are only evicted as descendants of evicted transactions.
+    fn eviction_order(
+        &self,
+        subpool: SubPool,
+        candidates: Vec<EvictionCandidate>,
+    ) -> Vec<EvictionCandidate>;
+}
+
+/// Decides whether a transaction can replace an existing transaction with the same sender and
+/// nonce.
+pub trait ReplacementPolicy: fmt::Debug + Send + Sync {
+    /// Returns `true` if the replacement is underpriced and can't replace the existing
+    /// transaction.
+    fn is_underpriced(
+        &self,
+        existing: &ReplacementFees,
+        replacement: &ReplacementFees,
+        price_bumps: &PriceBumpConfig,
+    ) -> bool;
+}
+
+/// A transaction that can be evicted from a sub-pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvictionCandidate {
+    /// Identifier of the transaction in the pool.
+    pub(crate) id: TransactionId,
+    /// Hash of the transaction.
+    pub hash: TxHash,
+    /// Sender of the transaction.
+    pub sender: Address,
+    /// Nonce of the transaction.
+    pub nonce: u64,
+    /// Tip per gas the transaction pays at the base fee of the pending block, zero if it doesn't
+    /// pay the base fee.
+    pub effective_tip: u128,
+    /// When the transaction was added to the pool.
+    pub timestamp: Instant,
+    /// Size of the transaction, see [PoolTransaction::size].
+    pub size: usize,
+    /// Whether the transaction is local and exempt from eviction, see
+    /// [LocalTransactionConfig](crate::LocalTransactionConfig).
+    ///
+    /// The built-in policies only evict exempt transactions if no other transactions are left.
+    pub exempt: bool,
+}
+
+impl EvictionCandidate {
+    /// Creates the candidate for the transaction, given the base fee of the pending block.
+    pub fn new<T: PoolTransaction>(
+        transaction: &ValidPoolTransaction<T>,
+        base_fee: u64,
+        exempt: bool,
+    ) -> Self {
+        Self {
+            id: *transaction.id(),
+            hash: *transaction.hash(),
+            sender: transaction.sender(),
+            nonce: transaction.nonce(),
+            effective_tip: transaction.effective_tip_per_gas(base_fee).unwrap_or_default(),
+            timestamp: transaction.timestamp,
+            size: transaction.size(),
+            exempt,
+        }
+    }
+}
+
+/// The fees of a transaction that are compared when it's replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplacementFees {
+    /// Type of the transaction.
+    pub tx_type: u8,
+    /// Max fee per gas, or gas price for legacy transactions.
+    pub max_fee_per_gas: u128,
+    /// Max priority fee per gas, `None` for legacy transactions.
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// Max fee per blob gas, `None` for non-blob transactions.
+    pub max_fee_per_blob_gas: Option<u128>,
+}
+
+impl ReplacementFees {
+    /// Returns the fees of the transaction.
+    pub(crate) fn new<T: PoolTransaction>(transaction: &ValidPoolTransaction<T>) -> Self {
+        Self {
+            tx_type: transaction.tx_type(),
+            max_fee_per_gas: transaction.max_fee_per_gas(),
+            max_priority_fee_per_gas: transaction.transaction.max_priority_fee_per_gas(),
+            max_fee_per_blob_gas: transaction.transaction.max_fee_per_blob_gas(),
+        }
+    }
+
+    /// Returns `true` if this is a blob transaction.
+    pub fn is_eip4844(&self) -> bool {
+        self.tx_type == EIP4844_TX_TYPE_ID
+    }
+}
+
+/// Truncates every sub-pool in its built-in order.
+///
+/// This is the default [EvictionPolicy].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultEvictionPolicy;
+
+impl EvictionPolicy for DefaultEvictionPolicy {
+    fn uses_builtin_order(&self, _subpool: SubPool) -> bool {
+        true
+    }
+
+    fn eviction_order(
+        &self,
+        _subpool: SubPool,
+        candidates: Vec<EvictionCandidate>,
+    ) -> Vec<EvictionCandidate> {
+        candidates
+    }
+}
+
+/// Evicts the transactions that pay the lowest tip at the base fee of the pending block first.
+///
+/// Transactions with the same tip are evicted newest first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LowestTipEviction;
+
+impl EvictionPolicy for LowestTipEviction {
+    fn eviction_order(
+        &self,
+        _subpool: SubPool,
+        mut candidates: Vec<EvictionCandidate>,
+    ) -> Vec<EvictionCandidate> {
+        candidates.sort_by(|a, b| {
+            a.exempt
+                .cmp(&b.exempt)
+                .then(a.effective_tip.cmp(&b.effective_tip))
+                .then(b.timestamp.cmp(&a.timestamp))
+        });
+        candidates
+    }
+}
+
+/// Evicts the transactions that have been in the pool the longest first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OldestEviction;
+
+impl EvictionPolicy for OldestEviction {
+    fn eviction_order(
+        &self,
+        _subpool: SubPool,
+        mut candidates: Vec<EvictionCandidate>,
+    ) -> Vec<EvictionCandidate> {
+        candidates.sort_by(|a, b| a.exempt.cmp(&b.exempt).then(a.timestamp.cmp(&b.timestamp)));
+        candidates
+    }
+}
+
+/// Evicts the transactions of the senders with the most transactions in the sub-pool first, until
+/// all senders hold the same number of transactions.
+///
+/// The transactions of a sender are evicted highest nonce first, so evicting them never evicts any
+/// other transactions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SenderFairnessEviction;
+
+impl EvictionPolicy for SenderFairnessEviction {
+    fn eviction_order(
+        &self,
+        _subpool: SubPool,
+        mut candidates: Vec<EvictionCandidate>,
+    ) -> Vec<EvictionCandidate> {
+        let mut counts = HashMap::<Address, usize>::new();
+        for candidate in candidates.iter() {
+            *counts.entry(candidate.sender).or_default() += 1;
+        }
+
+        // Sort the transactions of each sender by descending nonce, so the nth transaction of a
+        // sender is evicted when the sender has `count - n` transactions left.
+        candidates.sort_by(|a, b| a.sender.cmp(&b.sender).then(b.nonce.cmp(&a.nonce)));
+        let mut remaining = Vec::with_capacity(candidates.len());
+        let mut evicted_of_sender = 0;
+        for (idx, candidate) in candidates.iter().enumerate() {
+            if idx == 0 || candidates[idx - 1].sender != candidate.sender {
+                evicted_of_sender = 0;
+            }
+            remaining.push(counts[&candidate.sender] - evicted_of_sender);
+            evicted_of_sender += 1;
+        }
+
+        let mut ordered = candidates.into_iter().zip(remaining).collect::<Vec<_>>();
+        ordered.sort_by(|(a, a_remaining), (b, b_remaining)| {
+            a.exempt
+                .cmp(&b.exempt)
+                .then(b_remaining.cmp(a_remaining))
+                .then(a.effective_tip.cmp(&b.effective_tip))
+        });
+        ordered.into_iter().map(|(candidate, _)| candidate).collect()
+    }
+}
+
+/// Requires the fees of a replacement to be higher than the fees of the existing transaction by
+/// the configured [PriceBumpConfig].
+///
+/// This is the default [ReplacementPolicy].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultReplacementPolicy;
+
+impl ReplacementPolicy for DefaultReplacementPolicy {
+    fn is_underpriced(
+        &self,
+        existing: &ReplacementFees,
+        replacement: &ReplacementFees,
+        price_bumps: &PriceBumpConfig,
+    ) -> bool {
+        let price_bump = price_bumps.price_bump(existing.tx_type);
+        is_fee_underpriced(existing, replacement, price_bump) ||
+            is_blob_fee_underpriced(existing, replacement, price_bump)
+    }
+}
+
+/// Applies a separate price bump to the blob fee of blob transactions.
+///
+/// The other fees are bumped according to the [PriceBumpConfig], like with the
+/// [DefaultReplacementPolicy].
+#[derive(Debug, Clone, Copy)]
+pub struct BlobFeeBumpReplacement {
+    /// Price bump (in %) of the max fee per blob gas that's required to replace a blob
+    /// transaction.
+    pub blob_fee_price_bump: u128,
+}
+
+impl ReplacementPolicy for BlobFeeBumpReplacement {
+    fn is_underpriced(
+        &self,
+        existing: &ReplacementFees,
+        replacement: &ReplacementFees,
+        price_bumps: &PriceBumpConfig,
+    ) -> bool {
+        is_fee_underpriced(existing, replacement, price_bumps.price_bump(existing.tx_type)) ||
+            is_blob_fee_underpriced(existing, replacement, self.blob_fee_price_bump)
+    }
+}
+
+/// Returns `true` if the max fee or max priority fee of the replacement isn't bumped enough.
+fn is_fee_underpriced(
+    existing: &ReplacementFees,
+    replacement: &ReplacementFees,
+    price_bump: u128,
+) -> bool {
+    let price_bump_multiplier = (100 + price_bump) / 100;
+
+    if replacement.max_fee_per_gas <= existing.max_fee_per_gas * price_bump_multiplier {
+        return true
+    }
+
+    let existing_max_priority_fee_per_gas = existing.max_priority_fee_per_gas.unwrap_or(0);
+    let replacement_max_priority_fee_per_gas = replacement.max_priority_fee_per_gas.unwrap_or(0);
+
+    replacement_max_priority_fee_per_gas <=
+        existing_max_priority_fee_per_gas * price_bump_multiplier &&
+        existing_max_priority_fee_per_gas != 0 &&
+        replacement_max_priority_fee_per_gas != 0
+}
+
+/// Returns `true` if the existing transaction is a blob transaction and the max fee per blob gas
+/// of the replacement isn't bumped enough.
+///
+/// This enforces that blob transactions can only be replaced by blob transactions.
+fn is_blob_fee_underpriced(
+    existing: &ReplacementFees,
+    replacement: &ReplacementFees,
+    price_bump: u128,
+) -> bool {
+    let Some(existing_max_blob_fee_per_gas) = existing.max_fee_per_blob_gas else { return false };
+    let price_bump_multiplier = (100 + price_bump) / 100;
+    replacement.max_fee_per_blob_gas.unwrap_or(0) <=
+        existing_max_blob_fee_per_gas * price_bump_multiplier
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockTransaction, MockTransactionFactory};
+
+    fn candidates(txs: impl IntoIterator<Item = MockTransaction>) -> Vec<EvictionCandidate> {
+        let mut f = MockTransactionFactory::default();
+        txs.into_iter().map(|tx| EvictionCandidate::new(&f.validated(tx), 0, false)).collect()
+    }
+
+    fn hashes(candidates: Vec<EvictionCandidate>) -> Vec<TxHash> {
+        candidates.into_iter().map(|candidate| candidate.hash).collect()
+    }
+
+    #[test]
+    fn lowest_tip_eviction() {
+        let cheap = MockTransaction::eip1559().with_priority_fee(1).with_max_fee(10);
+        let expensive = MockTransaction::eip1559().with_priority_fee(5).with_max_fee(10);
+        let mut candidates = candidates([expensive.clone(), cheap.clone()]);
+        candidates[1].exempt = true;
+
+        // exempt transactions are evicted last
+        let order = LowestTipEviction.eviction_order(SubPool::Pending, candidates.clone());
+        assert_eq!(hashes(order), vec![*expensive.hash(), *cheap.hash()]);
+
+        candidates[1].exempt = false;
+        let order = LowestTipEviction.eviction_order(SubPool::Pending, candidates);
+        assert_eq!(hashes(order), vec![*cheap.hash(), *expensive.hash()]);
+    }
+
+    #[test]
+    fn sender_fairness_eviction() {
+        let spammer = MockTransaction::eip1559();
+        let spam = [spammer.clone(), spammer.next(), spammer.next().next()];
+        let other = MockTransaction::eip1559();
+        let candidates = candidates(spam.iter().cloned().chain([other.clone()]));
+
+        let order = SenderFairnessEviction.eviction_order(SubPool::Queued, candidates);
+        let order = hashes(order);
+        // the two highest nonces of the spammer are evicted first
+        assert_eq!(order[..2], [*spam[2].hash(), *spam[1].hash()]);
+        assert!(order[2..].contains(spam[0].hash()));
+        assert!(order[2..].contains(other.hash()));
+    }
+
+    #[test]
+    fn blob_fee_bump_replacement() {
+        let existing = ReplacementFees {
+            tx_type: EIP4844_TX_TYPE_ID,
+            max_fee_per_gas: 100,
+            max_priority_fee_per_gas: Some(10),
+            max_fee_per_blob_gas: Some(100),
+        };
+        let replacement = ReplacementFees {
+            max_fee_per_gas: 201,
+            max_priority_fee_per_gas: Some(21),
+            max_fee_per_blob_gas: Some(201),
+            ..existing
+        };
+        let price_bumps = PriceBumpConfig::default();
+
+        assert!(!DefaultReplacementPolicy.is_underpriced(&existing, &replacement, &price_bumps));
+
+        let policy = BlobFeeBumpReplacement { blob_fee_price_bump: 200 };
+        assert!(policy.is_underpriced(&existing, &replacement, &price_bumps));
+        let replacement = ReplacementFees { max_fee_per_blob_gas: Some(301), ..replacement };
+        assert!(!policy.is_underpriced(&existing, &replacement, &price_bumps));
+
+        // blob transactions can only be replaced by blob transactions
+        let replacement = ReplacementFees { tx_type: 2, max_fee_per_blob_gas: None, ..replacement };
+        assert!(DefaultReplacementPolicy.is_underpriced(&existing, &replacement, &price_bumps));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{traits::PropagateKind, PoolTransaction, ValidPoolTransaction};
+use crate::{traits::PropagateKind, PoolTransaction, SubPool, ValidPoolTransaction};
 use reth_primitives::{TxHash, B256};
 use std::sync::Arc;
 
@@ -54,6 +54,85 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
     }
 }
 
+/// A change of the content of the pool.
+///
+/// Applying all changes with a higher sequence number than a [PoolSnapshot](crate::PoolSnapshot)
+/// to the snapshot, in order, yields the current content of the pool.
+#[derive(Debug)]
+pub enum PoolDiff<T: PoolTransaction> {
+    /// Transaction has been added to the given sub-pool.
+    Added {
+        /// The added transaction.
+        transaction: Arc<ValidPoolTransaction<T>>,
+        /// The sub-pool the transaction was added to.
+        subpool: SubPool,
+    },
+    /// Transaction has been replaced by the transaction belonging to the hash.
+    Replaced {
+        /// The hash of the replaced transaction.
+        tx_hash: TxHash,
+        /// The hash of the transaction that replaced it.
+        replaced_by: TxHash,
+    },
+    /// Transaction has been moved to the pending sub-pool.
+    Promoted(TxHash),
+    /// Transaction has been moved to the given parked sub-pool, from the pending sub-pool or
+    /// another parked sub-pool.
+    Demoted {
+        /// The hash of the demoted transaction.
+        tx_hash: TxHash,
+        /// The sub-pool the transaction was moved to.
+        subpool: SubPool,
+    },
+    /// Transaction was removed from the pool.
+    ///
+    /// This is also emitted for transactions that were rejected and never entered the pool.
+    Discarded(TxHash),
+    /// Transaction has been included in the block belonging to this hash.
+    Mined {
+        /// The hash of the mined transaction.
+        tx_hash: TxHash,
+        /// The hash of the mined block that contains the transaction.
+        block_hash: B256,
+    },
+}
+
+impl<T: PoolTransaction> Clone for PoolDiff<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Added { transaction, subpool } => {
+                Self::Added { transaction: Arc::clone(transaction), subpool: *subpool }
+            }
+            Self::Replaced { tx_hash, replaced_by } => {
+                Self::Replaced { tx_hash: *tx_hash, replaced_by: *replaced_by }
+            }
+            Self::Promoted(hash) => Self::Promoted(*hash),
+            Self::Demoted { tx_hash, subpool } => {
+                Self::Demoted { tx_hash: *tx_hash, subpool: *subpool }
+            }
+            Self::Discarded(hash) => Self::Discarded(*hash),
+            Self::Mined { tx_hash, block_hash } => {
+                Self::Mined { tx_hash: *tx_hash, block_hash: *block_hash }
+            }
+        }
+    }
+}
+
+/// A [PoolDiff] and its position in the sequence of all changes of the pool.
+#[derive(Debug)]
+pub struct PoolDiffEvent<T: PoolTransaction> {
+    /// Sequence number of the change, incremented by one for every change of the pool.
+    pub sequence: u64,
+    /// The change of the pool.
+    pub diff: PoolDiff<T>,
+}
+
+impl<T: PoolTransaction> Clone for PoolDiffEvent<T> {
+    fn clone(&self) -> Self {
+        Self { sequence: self.sequence, diff: self.diff.clone() }
+    }
+}
+
 /// Various events that describe status changes of a transaction.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1,9 +1,9 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, TransactionEvent},
+    pool::events::{FullTransactionEvent, PoolDiff, PoolDiffEvent, TransactionEvent},
     traits::PropagateKind,
-    PoolTransaction, ValidPoolTransaction,
+    PoolTransaction, SubPool, ValidPoolTransaction,
 };
 use futures_util::Stream;
 use reth_primitives::{TxHash, B256};
@@ -61,6 +61,25 @@ impl<T: PoolTransaction> Stream for AllTransactionsEvents<T> {
     }
 }
 
+/// A Stream that receives [PoolDiffEvent] for _all_ changes of the pool.
+///
+/// If the receiver falls behind, events are dropped. This is detectable by a gap in the sequence
+/// numbers, after which the content of the pool must be resynced from a
+/// [PoolSnapshot](crate::PoolSnapshot).
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct PoolDiffEvents<T: PoolTransaction> {
+    pub(crate) events: Receiver<PoolDiffEvent<T>>,
+}
+
+impl<T: PoolTransaction> Stream for PoolDiffEvents<T> {
+    type Item = PoolDiffEvent<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().events.poll_recv(cx)
+    }
+}
+
 /// A type that broadcasts [`TransactionEvent`] to installed listeners.
 ///
 /// This is essentially a multi-producer, multi-consumer channel where each event is broadcast to
@@ -71,6 +90,10 @@ pub(crate) struct PoolEventBroadcast<T: PoolTransaction> {
     all_events_broadcaster: AllPoolEventsBroadcaster<T>,
     /// All listeners for events for a certain transaction hash.
     broadcasters_by_hash: HashMap<TxHash, PoolEventBroadcaster>,
+    /// All listeners for changes of the pool.
+    diff_broadcaster: PoolDiffBroadcaster<T>,
+    /// Sequence number of the last change of the pool.
+    sequence: u64,
 }
 
 impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
@@ -78,6 +101,8 @@ impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
         Self {
             all_events_broadcaster: AllPoolEventsBroadcaster::default(),
             broadcasters_by_hash: HashMap::default(),
+            diff_broadcaster: PoolDiffBroadcaster::default(),
+            sequence: 0,
         }
     }
 }
@@ -103,6 +128,17 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         self.all_events_broadcaster.broadcast(pool_event);
     }
 
+    /// Assigns the next sequence number to the change and broadcasts it to all diff listeners.
+    fn broadcast_diff(&mut self, diff: PoolDiff<T>) {
+        self.sequence += 1;
+        self.diff_broadcaster.broadcast(PoolDiffEvent { sequence: self.sequence, diff });
+    }
+
+    /// Returns the sequence number of the last change of the pool.
+    pub(crate) fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
     /// Create a new subscription for the given transaction hash.
     pub(crate) fn subscribe(&mut self, tx_hash: TxHash) -> TransactionEvents {
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
@@ -125,8 +161,45 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         AllTransactionsEvents { events: rx }
     }
 
+    /// Create a new subscription for all changes of the pool.
+    pub(crate) fn subscribe_diff(&mut self) -> PoolDiffEvents<T> {
+        let (tx, rx) = tokio::sync::mpsc::channel(TX_POOL_EVENT_CHANNEL_SIZE);
+        self.diff_broadcaster.senders.push(tx);
+        PoolDiffEvents { events: rx }
+    }
+
+    /// Notify listeners about a transaction that was added to the given sub-pool.
+    pub(crate) fn added(
+        &mut self,
+        tx: &Arc<ValidPoolTransaction<T>>,
+        subpool: SubPool,
+        replaced: Option<Arc<ValidPoolTransaction<T>>>,
+    ) {
+        self.broadcast_diff(PoolDiff::Added { transaction: Arc::clone(tx), subpool });
+        if subpool.is_pending() {
+            self.pending(tx.hash(), replaced);
+        } else {
+            self.queued(tx.hash());
+            if let Some(replaced) = replaced {
+                self.replaced(replaced, *tx.hash());
+            }
+        }
+    }
+
+    /// Notify listeners about a transaction that was moved to the pending pool.
+    pub(crate) fn promoted(&mut self, tx: &TxHash) {
+        self.broadcast_diff(PoolDiff::Promoted(*tx));
+        self.pending(tx, None);
+    }
+
+    /// Notify listeners about a transaction that was moved to the given parked pool.
+    pub(crate) fn demoted(&mut self, tx: &TxHash, subpool: SubPool) {
+        self.broadcast_diff(PoolDiff::Demoted { tx_hash: *tx, subpool });
+        self.queued(tx);
+    }
+
     /// Notify listeners about a transaction that was added to the pending queue.
-    pub(crate) fn pending(&mut self, tx: &TxHash, replaced: Option<Arc<ValidPoolTransaction<T>>>) {
+    fn pending(&mut self, tx: &TxHash, replaced: Option<Arc<ValidPoolTransaction<T>>>) {
         self.broadcast_event(tx, TransactionEvent::Pending, FullTransactionEvent::Pending(*tx));
 
         if let Some(replaced) = replaced {
@@ -137,6 +210,7 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
 
     /// Notify listeners about a transaction that was replaced.
     pub(crate) fn replaced(&mut self, tx: Arc<ValidPoolTransaction<T>>, replaced_by: TxHash) {
+        self.broadcast_diff(PoolDiff::Replaced { tx_hash: *tx.hash(), replaced_by });
         let transaction = Arc::clone(&tx);
         self.broadcast_event(
             tx.hash(),
@@ -146,7 +220,7 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
     }
 
     /// Notify listeners about a transaction that was added to the queued pool.
-    pub(crate) fn queued(&mut self, tx: &TxHash) {
+    fn queued(&mut self, tx: &TxHash) {
         self.broadcast_event(tx, TransactionEvent::Queued, FullTransactionEvent::Queued(*tx));
     }
 
@@ -162,11 +236,13 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
 
     /// Notify listeners about a transaction that was discarded.
     pub(crate) fn discarded(&mut self, tx: &TxHash) {
+        self.broadcast_diff(PoolDiff::Discarded(*tx));
         self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
     }
 
     /// Notify listeners that the transaction was mined
     pub(crate) fn mined(&mut self, tx: &TxHash, block_hash: B256) {
+        self.broadcast_diff(PoolDiff::Mined { tx_hash: *tx, block_hash });
         self.broadcast_event(
             tx,
             TransactionEvent::Mined(block_hash),
@@ -200,6 +276,29 @@ impl<T: PoolTransaction> AllPoolEventsBroadcaster<T> {
     }
 }
 
+/// All Sender half(s) of the channels for changes of the pool.
+#[derive(Debug)]
+struct PoolDiffBroadcaster<T: PoolTransaction> {
+    /// Corresponding sender half(s) for diff listener channel
+    senders: Vec<Sender<PoolDiffEvent<T>>>,
+}
+
+impl<T: PoolTransaction> Default for PoolDiffBroadcaster<T> {
+    fn default() -> Self {
+        Self { senders: Vec::new() }
+    }
+}
+
+impl<T: PoolTransaction> PoolDiffBroadcaster<T> {
+    // Broadcast a change to all listeners. Dropped listeners are silently evicted.
+    fn broadcast(&mut self, event: PoolDiffEvent<T>) {
+        self.senders.retain(|sender| match sender.try_send(event.clone()) {
+            Ok(_) | Err(TrySendError::Full(_)) => true,
+            Err(TrySendError::Closed(_)) => false,
+        })
+    }
+}
+
 /// All Sender half(s) of the event channels for a specific transaction.
 ///
 /// This mimics [tokio::sync::broadcast] but uses separate channels and is unbounded.
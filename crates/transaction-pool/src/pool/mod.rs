@@ -75,7 +75,7 @@ use crate::{
     },
     traits::{
         AllPoolTransactions, BestTransactionsAttributes, BlockInfo, NewTransactionEvent, PoolSize,
-        PoolTransaction, PropagatedTransactions, TransactionOrigin,
+        PoolSnapshot, PoolTransaction, PropagatedTransactions, TransactionOrigin,
     },
     validate::{TransactionValidationOutcome, ValidPoolTransaction},
     CanonicalStateUpdate, ChangedAccount, PoolConfig, TransactionOrdering, TransactionValidator,
@@ -84,7 +84,7 @@ use best::BestTransactions;
 use parking_lot::{Mutex, RwLock};
 use reth_primitives::{
     Address, BlobTransaction, BlobTransactionSidecar, IntoRecoveredTransaction,
-    PooledTransactionsElement, TransactionSigned, TxHash, B256,
+    PooledTransactionsElement, TransactionConditional, TransactionSigned, TxHash, B256,
 };
 use std::{
     collections::{HashMap, HashSet},
@@ -95,18 +95,19 @@ use std::{
 use tokio::sync::mpsc;
 use tracing::{debug, trace, warn};
 mod events;
-pub use events::{FullTransactionEvent, TransactionEvent};
+pub use events::{FullTransactionEvent, PoolDiff, PoolDiffEvent, TransactionEvent};
 
 mod listener;
 use crate::{
-    blobstore::BlobStore,
+    blobstore::{BlobStore, BlobStoreError},
+    bundle::BundlePool,
     metrics::BlobStoreMetrics,
     pool::txpool::UpdateOutcome,
     traits::{GetPooledTransactionLimit, NewBlobSidecar, TransactionListenerKind},
     validate::ValidTransaction,
 };
 use alloy_rlp::Encodable;
-pub use listener::{AllTransactionsEvents, TransactionEvents};
+pub use listener::{AllTransactionsEvents, PoolDiffEvents, TransactionEvents};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool};
 pub use pending::PendingPool;
 
@@ -149,6 +150,8 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Bundles of transactions that target a specific block.
+    bundle_pool: BundlePool,
 }
 
 // === impl PoolInner ===
@@ -169,6 +172,7 @@ where
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
+            bundle_pool: BundlePool::new(config.max_bundles_per_block),
             config,
             blob_store,
             blob_store_metrics: Default::default(),
@@ -180,6 +184,11 @@ where
         &self.blob_store
     }
 
+    /// Returns the pool of transaction bundles.
+    pub(crate) fn bundle_pool(&self) -> &BundlePool {
+        &self.bundle_pool
+    }
+
     /// Returns stats about the size of the pool.
     pub(crate) fn size(&self) -> PoolSize {
         self.pool.read().size()
@@ -191,7 +200,11 @@ where
     }
     /// Returns the currently tracked block
     pub(crate) fn set_block_info(&self, info: BlockInfo) {
-        self.pool.write().set_block_info(info)
+        let UpdateOutcome { promoted, demoted, .. } = self.pool.write().set_block_info(info);
+        let mut listener = self.event_listener.write();
+
+        promoted.iter().for_each(|tx| listener.promoted(tx.hash()));
+        demoted.iter().for_each(|(tx, subpool)| listener.demoted(tx, *subpool));
     }
 
     /// Returns the internal `SenderId` for this address
@@ -279,6 +292,27 @@ where
         self.event_listener.write().subscribe_all()
     }
 
+    /// Adds a listener for all changes of the pool.
+    pub(crate) fn add_pool_diff_listener(&self) -> PoolDiffEvents<T::Transaction> {
+        self.event_listener.write().subscribe_diff()
+    }
+
+    /// Returns all transactions in the pool, and the sequence number of the last change of the
+    /// pool they reflect.
+    pub(crate) fn snapshot(&self) -> PoolSnapshot<T::Transaction> {
+        let pool = self.pool.read();
+        // changes are broadcast after they were applied to the pool, so holding the pool lock
+        // while reading the sequence number ensures all changes up to it are included
+        let sequence = self.event_listener.read().sequence();
+        PoolSnapshot {
+            sequence,
+            pending: pool.subpool_transactions(SubPool::Pending),
+            basefee: pool.subpool_transactions(SubPool::BaseFee),
+            blob: pool.subpool_transactions(SubPool::Blob),
+            queued: pool.subpool_transactions(SubPool::Queued),
+        }
+    }
+
     /// Returns hashes of _all_ transactions in the pool.
     pub(crate) fn pooled_transactions_hashes(&self) -> Vec<TxHash> {
         let pool = self.pool.read();
@@ -340,8 +374,15 @@ where
         trace!(target: "txpool", %update, "updating pool on canonical state change");
 
         let block_info = update.block_info();
-        let CanonicalStateUpdate { new_tip, changed_accounts, mined_transactions, .. } = update;
+        let CanonicalStateUpdate {
+            new_tip,
+            changed_accounts,
+            changed_storage,
+            mined_transactions,
+            ..
+        } = update;
         self.validator.on_new_head_block(new_tip);
+        self.bundle_pool.on_canonical_block(new_tip.number);
 
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
@@ -350,6 +391,7 @@ where
             block_info,
             mined_transactions,
             changed_senders,
+            changed_storage,
         );
 
         // This will discard outdated transactions based on the account's nonce
@@ -364,11 +406,12 @@ where
     /// This will either promote or discard transactions based on the new account state.
     pub(crate) fn update_accounts(&self, accounts: Vec<ChangedAccount>) {
         let changed_senders = self.changed_senders(accounts.into_iter());
-        let UpdateOutcome { promoted, discarded } =
+        let UpdateOutcome { promoted, demoted, discarded } =
             self.pool.write().update_accounts(changed_senders);
         let mut listener = self.event_listener.write();
 
-        promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
+        promoted.iter().for_each(|tx| listener.promoted(tx.hash()));
+        demoted.iter().for_each(|(tx, subpool)| listener.demoted(tx, *subpool));
         discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
 
         // This deletes outdated blob txs from the blob store, based on the account's nonce. This is
@@ -378,12 +421,14 @@ where
 
     /// Add a single validated transaction into the pool.
     ///
-    /// Note: this is only used internally by [`Self::add_transactions()`], all new transaction(s)
-    /// come in through that function, either as a batch or `std::iter::once`.
+    /// Note: this is only used internally by [`Self::add_transactions()`] and
+    /// [`Self::add_transaction_with_conditional()`], all new transaction(s) come in through these
+    /// functions.
     fn add_transaction(
         &self,
         origin: TransactionOrigin,
         tx: TransactionValidationOutcome<T::Transaction>,
+        conditional: Option<TransactionConditional>,
     ) -> PoolResult<TxHash> {
         match tx {
             TransactionValidationOutcome::Valid {
@@ -415,16 +460,43 @@ where
                     timestamp: Instant::now(),
                     origin,
                 };
+                let hash = *tx.hash();
+
+                // store the sidecar before the transaction is added, so a blob transaction whose
+                // sidecar can't be stored is rejected instead of ending up in the pool without it
+                if let Some(sidecar) = &maybe_sidecar {
+                    if let Err(err) = self.insert_blob(hash, sidecar.clone()) {
+                        self.event_listener.write().discarded(&hash);
+                        return Err(PoolError::other(hash, err))
+                    }
+                }
 
-                let added = self.pool.write().add_transaction(tx, balance, state_nonce)?;
-                let hash = *added.hash();
+                let added = match conditional {
+                    Some(conditional) => self.pool.write().add_conditional_transaction(
+                        tx,
+                        balance,
+                        state_nonce,
+                        conditional,
+                    ),
+                    None => self.pool.write().add_transaction(tx, balance, state_nonce),
+                };
+                let added = match added {
+                    Ok(added) => added,
+                    Err(err) => {
+                        // the sidecar of an already imported transaction belongs to it
+                        if maybe_sidecar.is_some() &&
+                            !matches!(err.kind, PoolErrorKind::AlreadyImported)
+                        {
+                            self.delete_blob(hash);
+                        }
+                        return Err(err)
+                    }
+                };
 
                 // transaction was successfully inserted into the pool
                 if let Some(sidecar) = maybe_sidecar {
                     // notify blob sidecar listeners
                     self.on_new_blob_sidecar(&hash, &sidecar);
-                    // store the sidecar in the blob store
-                    self.insert_blob(hash, sidecar);
                 }
 
                 if let Some(replaced) = added.replaced_blob_transaction() {
@@ -481,9 +553,33 @@ where
         origin: TransactionOrigin,
         transactions: impl IntoIterator<Item = TransactionValidationOutcome<T::Transaction>>,
     ) -> Vec<PoolResult<TxHash>> {
-        let added =
-            transactions.into_iter().map(|tx| self.add_transaction(origin, tx)).collect::<Vec<_>>();
+        let added = transactions
+            .into_iter()
+            .map(|tx| self.add_transaction(origin, tx, None))
+            .collect::<Vec<_>>();
+
+        self.enforce_size_limits(added)
+    }
+
+    /// Adds a transaction that may only be included in a block while the given conditions hold.
+    pub(crate) fn add_transaction_with_conditional(
+        &self,
+        origin: TransactionOrigin,
+        tx: TransactionValidationOutcome<T::Transaction>,
+        conditional: TransactionConditional,
+    ) -> PoolResult<TxHash> {
+        let added = self.add_transaction(origin, tx, Some(conditional));
+        self.enforce_size_limits(vec![added]).pop().expect("exists; qed")
+    }
 
+    /// Returns all accounts whose storage is expected by the conditional transactions in the pool.
+    pub(crate) fn conditional_accounts(&self) -> HashSet<Address> {
+        self.pool.read().conditional_accounts()
+    }
+
+    /// Discards the worst transactions if the pool exceeds its size limits after the given
+    /// transactions were added, and adjusts the results accordingly.
+    fn enforce_size_limits(&self, added: Vec<PoolResult<TxHash>>) -> Vec<PoolResult<TxHash>> {
         // If at least one transaction was added successfully, then we enforce the pool size limits.
         let discarded =
             if added.iter().any(Result::is_ok) { self.discard_worst() } else { Default::default() };
@@ -579,13 +675,15 @@ where
             })
         }
 
-        let OnNewCanonicalStateOutcome { mined, promoted, discarded, block_hash } = outcome;
+        let OnNewCanonicalStateOutcome { mined, promoted, demoted, discarded, block_hash } =
+            outcome;
 
         // broadcast specific transaction events
         let mut listener = self.event_listener.write();
 
         mined.iter().for_each(|tx| listener.mined(tx, block_hash));
-        promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
+        promoted.iter().for_each(|tx| listener.promoted(tx.hash()));
+        demoted.iter().for_each(|(tx, subpool)| listener.demoted(tx, *subpool));
         discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
     }
 
@@ -595,17 +693,17 @@ where
 
         match tx {
             AddedTransaction::Pending(tx) => {
-                let AddedPendingTransaction { transaction, promoted, discarded, replaced } = tx;
+                let AddedPendingTransaction { transaction, promoted, demoted, discarded, replaced } =
+                    tx;
 
-                listener.pending(transaction.hash(), replaced.clone());
-                promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
+                listener.added(transaction, SubPool::Pending, replaced.clone());
+                promoted.iter().for_each(|tx| listener.promoted(tx.hash()));
+                demoted.iter().for_each(|(tx, subpool)| listener.demoted(tx, *subpool));
                 discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
             }
-            AddedTransaction::Parked { transaction, replaced, .. } => {
-                listener.queued(transaction.hash());
-                if let Some(replaced) = replaced {
-                    listener.replaced(replaced.clone(), *transaction.hash());
-                }
+            AddedTransaction::Parked { transaction, replaced, subpool, demoted } => {
+                listener.added(transaction, *subpool, replaced.clone());
+                demoted.iter().for_each(|(tx, subpool)| listener.demoted(tx, *subpool));
             }
         }
     }
@@ -744,12 +842,18 @@ where
     }
 
     /// Inserts a blob transaction into the blob store
-    fn insert_blob(&self, hash: TxHash, blob: BlobTransactionSidecar) {
-        if let Err(err) = self.blob_store.insert(hash, blob) {
+    fn insert_blob(
+        &self,
+        hash: TxHash,
+        blob: BlobTransactionSidecar,
+    ) -> Result<(), BlobStoreError> {
+        let res = self.blob_store.insert(hash, blob);
+        if let Err(err) = &res {
             warn!(target: "txpool", ?err, "[{:?}] failed to insert blob", hash);
             self.blob_store_metrics.blobstore_failed_inserts.increment(1);
         }
         self.update_blob_store_metrics();
+        res
     }
 
     /// Delete a blob from the blob store
@@ -888,6 +992,8 @@ pub struct AddedPendingTransaction<T: PoolTransaction> {
     replaced: Option<Arc<ValidPoolTransaction<T>>>,
     /// transactions promoted to the pending queue
     promoted: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transactions moved to a parked pool, and the sub-pool they were moved to
+    demoted: Vec<(TxHash, SubPool)>,
     /// transactions that failed and became discarded
     discarded: Vec<Arc<ValidPoolTransaction<T>>>,
 }
@@ -976,6 +1082,8 @@ pub enum AddedTransaction<T: PoolTransaction> {
         replaced: Option<Arc<ValidPoolTransaction<T>>>,
         /// The subpool it was moved to.
         subpool: SubPool,
+        /// Transactions moved to a parked pool, and the sub-pool they were moved to.
+        demoted: Vec<(TxHash, SubPool)>,
     },
 }
 
@@ -1057,6 +1165,8 @@ pub(crate) struct OnNewCanonicalStateOutcome<T: PoolTransaction> {
     pub(crate) mined: Vec<TxHash>,
     /// Transactions promoted to the pending pool.
     pub(crate) promoted: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// Transactions demoted from the pending pool, and the sub-pool they were moved to.
+    pub(crate) demoted: Vec<(TxHash, SubPool)>,
     /// transaction that were discarded during the update
     pub(crate) discarded: Vec<Arc<ValidPoolTransaction<T>>>,
 }
@@ -13,8 +13,9 @@ use crate::{
         update::{Destination, PoolUpdate},
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
     },
-    traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
-    PoolConfig, PoolResult, PoolTransaction, PriceBumpConfig, TransactionOrdering,
+    traits::{BestTransactionsAttributes, BlockInfo, ChangedStorage, PoolSize},
+    DefaultReplacementPolicy, EvictionCandidate, PoolConfig, PoolResult, PoolTransaction,
+    PriceBumpConfig, ReplacementFees, ReplacementPolicy, SubPoolLimit, TransactionOrdering,
     ValidPoolTransaction, U256,
 };
 use fnv::FnvHashMap;
@@ -22,7 +23,7 @@ use reth_primitives::{
     constants::{
         eip4844::BLOB_TX_MIN_BLOB_GASPRICE, ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE,
     },
-    Address, TxHash, B256,
+    Address, KnownAccountState, TransactionConditional, TxHash, B256,
 };
 use std::{
     cmp::Ordering,
@@ -68,6 +69,14 @@ pub struct TxPool<T: TransactionOrdering> {
     blob_pool: BlobTransactions<T::Transaction>,
     /// All transactions in the pool.
     all_transactions: AllTransactions<T::Transaction>,
+    /// Conditions of the conditional transactions in the pool.
+    conditionals: HashMap<TxHash, TransactionConditional>,
+    /// Conditional transactions whose conditions are not met on the current state.
+    ///
+    /// These are never yielded by the best transactions.
+    unmet_conditionals: HashSet<TxHash>,
+    /// Timestamp of the last seen block, which the conditions are checked against.
+    last_seen_block_timestamp: u64,
     /// Transaction pool metrics
     metrics: TxPoolMetrics,
 }
@@ -84,6 +93,9 @@ impl<T: TransactionOrdering> TxPool<T> {
             basefee_pool: Default::default(),
             blob_pool: Default::default(),
             all_transactions: AllTransactions::new(&config),
+            conditionals: Default::default(),
+            unmet_conditionals: Default::default(),
+            last_seen_block_timestamp: 0,
             config,
             metrics: Default::default(),
         }
@@ -119,13 +131,21 @@ impl<T: TransactionOrdering> TxPool<T> {
         BlockInfo {
             last_seen_block_hash: self.all_transactions.last_seen_block_hash,
             last_seen_block_number: self.all_transactions.last_seen_block_number,
+            last_seen_block_timestamp: self.last_seen_block_timestamp,
             pending_basefee: self.all_transactions.pending_fees.base_fee,
             pending_blob_fee: Some(self.all_transactions.pending_fees.blob_fee),
         }
     }
 
     /// Updates the tracked blob fee
-    fn update_blob_fee(&mut self, mut pending_blob_fee: u128, base_fee_update: Ordering) {
+    ///
+    /// All transactions moved between sub-pools are recorded in the outcome.
+    fn update_blob_fee(
+        &mut self,
+        mut pending_blob_fee: u128,
+        base_fee_update: Ordering,
+        outcome: &mut UpdateOutcome<T::Transaction>,
+    ) {
         std::mem::swap(&mut self.all_transactions.pending_fees.blob_fee, &mut pending_blob_fee);
         match (self.all_transactions.pending_fees.blob_fee.cmp(&pending_blob_fee), base_fee_update)
         {
@@ -148,7 +168,8 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
-                    self.add_transaction_to_subpool(to, tx);
+                    self.add_transaction_to_subpool(to, tx.clone());
+                    outcome.record_move(tx, SubPool::Pending, to);
                 }
             }
             (Ordering::Less, Ordering::Equal) | (_, Ordering::Less) => {
@@ -165,7 +186,8 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
-                    self.add_transaction_to_subpool(to, tx);
+                    self.add_transaction_to_subpool(to, tx.clone());
+                    outcome.record_move(tx, SubPool::Blob, to);
                 }
             }
             (Ordering::Less, Ordering::Greater) => {
@@ -182,7 +204,8 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
-                    self.add_transaction_to_subpool(to, tx);
+                    self.add_transaction_to_subpool(to, tx.clone());
+                    outcome.record_move(tx, SubPool::Pending, to);
                 }
 
                 // decreased blob fee or base fee: recheck blob pool and promote all that are now
@@ -198,7 +221,8 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
-                    self.add_transaction_to_subpool(to, tx);
+                    self.add_transaction_to_subpool(to, tx.clone());
+                    outcome.record_move(tx, SubPool::Blob, to);
                 }
             }
         }
@@ -207,8 +231,12 @@ impl<T: TransactionOrdering> TxPool<T> {
     /// Updates the tracked basefee
     ///
     /// Depending on the change in direction of the basefee, this will promote or demote
-    /// transactions from the basefee pool.
-    fn update_basefee(&mut self, mut pending_basefee: u64) -> Ordering {
+    /// transactions from the basefee pool. All moved transactions are recorded in the outcome.
+    fn update_basefee(
+        &mut self,
+        mut pending_basefee: u64,
+        outcome: &mut UpdateOutcome<T::Transaction>,
+    ) -> Ordering {
         std::mem::swap(&mut self.all_transactions.pending_fees.base_fee, &mut pending_basefee);
         match self.all_transactions.pending_fees.base_fee.cmp(&pending_basefee) {
             Ordering::Equal => {
@@ -227,7 +255,8 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
-                    self.add_transaction_to_subpool(to, tx);
+                    self.add_transaction_to_subpool(to, tx.clone());
+                    outcome.record_move(tx, SubPool::Pending, to);
                 }
 
                 Ordering::Greater
@@ -244,7 +273,8 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
-                    self.add_transaction_to_subpool(to, tx);
+                    self.add_transaction_to_subpool(to, tx.clone());
+                    outcome.record_move(tx, SubPool::BaseFee, to);
                 }
 
                 Ordering::Less
@@ -254,26 +284,40 @@ impl<T: TransactionOrdering> TxPool<T> {
 
     /// Sets the current block info for the pool.
     ///
-    /// This will also apply updates to the pool based on the new base fee
-    pub(crate) fn set_block_info(&mut self, info: BlockInfo) {
+    /// This will also apply updates to the pool based on the new base fee, and returns the
+    /// transactions that were moved between sub-pools.
+    pub(crate) fn set_block_info(&mut self, info: BlockInfo) -> UpdateOutcome<T::Transaction> {
         let BlockInfo {
             last_seen_block_hash,
             last_seen_block_number,
+            last_seen_block_timestamp,
             pending_basefee,
             pending_blob_fee,
         } = info;
         self.all_transactions.last_seen_block_hash = last_seen_block_hash;
         self.all_transactions.last_seen_block_number = last_seen_block_number;
-        let basefee_ordering = self.update_basefee(pending_basefee);
+        self.last_seen_block_timestamp = last_seen_block_timestamp;
+        let mut outcome = UpdateOutcome::default();
+        let basefee_ordering = self.update_basefee(pending_basefee, &mut outcome);
 
         if let Some(blob_fee) = pending_blob_fee {
-            self.update_blob_fee(blob_fee, basefee_ordering)
+            self.update_blob_fee(blob_fee, basefee_ordering, &mut outcome)
         }
+
+        outcome
     }
 
     /// Returns an iterator that yields transactions that are ready to be included in the block.
     pub(crate) fn best_transactions(&self) -> BestTransactions<T> {
-        self.pending_pool.best()
+        let mut best = self.pending_pool.best();
+        self.exclude_unmet_conditionals(&mut best);
+        best
+    }
+
+    /// Marks all conditional transactions with unmet conditions as invalid, so neither they nor
+    /// their descendants are yielded.
+    fn exclude_unmet_conditionals(&self, best: &mut BestTransactions<T>) {
+        best.invalid.extend(self.unmet_conditionals.iter().copied());
     }
 
     /// Returns an iterator that yields transactions that are ready to be included in the block with
@@ -290,16 +334,19 @@ impl<T: TransactionOrdering> TxPool<T> {
             }
             Ordering::Greater => {
                 // base fee increased, we only need to enforces this on the pending pool
-                Box::new(self.pending_pool.best_with_basefee(basefee))
+                let mut best = self.pending_pool.best_with_basefee(basefee);
+                self.exclude_unmet_conditionals(&mut best.best);
+                Box::new(best)
             }
             Ordering::Less => {
                 // base fee decreased, we need to move transactions from the basefee pool to the
                 // pending pool
                 let unlocked = self.basefee_pool.satisfy_base_fee_transactions(basefee);
-                Box::new(
-                    self.pending_pool
-                        .best_with_unlocked(unlocked, self.all_transactions.pending_fees.base_fee),
-                )
+                let mut best = self
+                    .pending_pool
+                    .best_with_unlocked(unlocked, self.all_transactions.pending_fees.base_fee);
+                self.exclude_unmet_conditionals(&mut best);
+                Box::new(best)
             }
         }
     }
@@ -319,7 +366,10 @@ impl<T: TransactionOrdering> TxPool<T> {
             }
             Ordering::Greater => {
                 // base fee increased, we only need to enforce this on the pending pool
-                Box::new(self.pending_pool.best_with_basefee(best_transactions_attributes.basefee))
+                let mut best =
+                    self.pending_pool.best_with_basefee(best_transactions_attributes.basefee);
+                self.exclude_unmet_conditionals(&mut best.best);
+                Box::new(best)
             }
             Ordering::Less => {
                 // base fee decreased, we need to move transactions from the basefee pool to the
@@ -327,10 +377,12 @@ impl<T: TransactionOrdering> TxPool<T> {
                 let unlocked_with_blob =
                     self.blob_pool.satisfy_attributes(best_transactions_attributes);
 
-                Box::new(self.pending_pool.best_with_unlocked(
+                let mut best = self.pending_pool.best_with_unlocked(
                     unlocked_with_blob,
                     self.all_transactions.pending_fees.base_fee,
-                ))
+                );
+                self.exclude_unmet_conditionals(&mut best);
+                Box::new(best)
             }
         }
     }
@@ -347,6 +399,19 @@ impl<T: TransactionOrdering> TxPool<T> {
         queued
     }
 
+    /// Returns all transactions from the given sub-pool
+    pub(crate) fn subpool_transactions(
+        &self,
+        subpool: SubPool,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        match subpool {
+            SubPool::Pending => self.pending_pool.all().collect(),
+            SubPool::BaseFee => self.basefee_pool.all().collect(),
+            SubPool::Blob => self.blob_pool.all().collect(),
+            SubPool::Queued => self.queued_pool.all().collect(),
+        }
+    }
+
     /// Returns `true` if the transaction with the given hash is already included in this pool.
     pub(crate) fn contains(&self, tx_hash: &TxHash) -> bool {
         self.all_transactions.contains(tx_hash)
@@ -405,17 +470,19 @@ impl<T: TransactionOrdering> TxPool<T> {
 
     /// Updates the entire pool after a new block was mined.
     ///
-    /// This removes all mined transactions, updates according to the new base fee and rechecks
-    /// sender allowance.
+    /// This removes all mined transactions, updates according to the new base fee, rechecks
+    /// sender allowance and the conditions of conditional transactions.
     pub(crate) fn on_canonical_state_change(
         &mut self,
         block_info: BlockInfo,
         mined_transactions: Vec<TxHash>,
         changed_senders: HashMap<SenderId, SenderInfo>,
+        changed_storage: Vec<ChangedStorage>,
     ) -> OnNewCanonicalStateOutcome<T::Transaction> {
         // update block info
         let block_hash = block_info.last_seen_block_hash;
         self.all_transactions.set_block_info(block_info);
+        self.last_seen_block_timestamp = block_info.last_seen_block_timestamp;
 
         // Remove all transaction that were included in the block
         for tx_hash in mined_transactions.iter() {
@@ -425,11 +492,67 @@ impl<T: TransactionOrdering> TxPool<T> {
             }
         }
 
-        let UpdateOutcome { promoted, discarded } = self.update_accounts(changed_senders);
+        let UpdateOutcome { promoted, demoted, mut discarded } =
+            self.update_accounts(changed_senders);
+
+        // Remove all conditional transactions whose conditions can no longer be met
+        let unmet = self.update_conditionals(changed_storage);
+        if !unmet.is_empty() {
+            discarded.extend(self.remove_transactions(unmet));
+            self.update_size_metrics();
+        }
 
         self.metrics.performed_state_updates.increment(1);
 
-        OnNewCanonicalStateOutcome { block_hash, mined: mined_transactions, promoted, discarded }
+        OnNewCanonicalStateOutcome {
+            block_hash,
+            mined: mined_transactions,
+            promoted,
+            demoted,
+            discarded,
+        }
+    }
+
+    /// Returns all accounts whose storage is expected by the conditional transactions in the pool.
+    pub(crate) fn conditional_accounts(&self) -> HashSet<Address> {
+        self.conditionals
+            .values()
+            .flat_map(|conditional| conditional.known_accounts.keys().copied())
+            .collect()
+    }
+
+    /// Rechecks the conditions of all conditional transactions against the last seen block and
+    /// the storage changes of the canonical state update.
+    ///
+    /// Returns the transactions whose conditions can no longer be met.
+    fn update_conditionals(&mut self, changed_storage: Vec<ChangedStorage>) -> Vec<TxHash> {
+        // forget the conditions of transactions that are no longer in the pool
+        let all_transactions = &self.all_transactions;
+        self.conditionals.retain(|hash, _| all_transactions.contains(hash));
+        self.unmet_conditionals.clear();
+
+        let number = self.all_transactions.last_seen_block_number;
+        let timestamp = self.last_seen_block_timestamp;
+        let changed_storage = changed_storage
+            .into_iter()
+            .map(|storage| (storage.address, storage.slots))
+            .collect::<HashMap<_, _>>();
+
+        let mut unmet = Vec::new();
+        for (hash, conditional) in self.conditionals.iter() {
+            if conditional.has_exceeded_block_attributes(number, timestamp) ||
+                !storage_matches_conditional(conditional, &changed_storage)
+            {
+                unmet.push(*hash);
+            } else if !conditional.matches_block_attributes(number, timestamp) {
+                self.unmet_conditionals.insert(*hash);
+            }
+        }
+        for hash in unmet.iter() {
+            self.conditionals.remove(hash);
+        }
+
+        unmet
     }
 
     /// Update sub-pools size metrics.
@@ -444,6 +567,34 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.metrics.total_transactions.set(stats.total as f64);
     }
 
+    /// Adds a transaction that may only be included in a block while the given conditions hold.
+    ///
+    /// The expected storage of the conditions must have been checked against the latest state
+    /// before. If the conditions are not met on the last seen block yet, the transaction is held
+    /// back from the best transactions until they are.
+    pub(crate) fn add_conditional_transaction(
+        &mut self,
+        tx: ValidPoolTransaction<T::Transaction>,
+        on_chain_balance: U256,
+        on_chain_nonce: u64,
+        conditional: TransactionConditional,
+    ) -> PoolResult<AddedTransaction<T::Transaction>> {
+        let number = self.all_transactions.last_seen_block_number;
+        let timestamp = self.last_seen_block_timestamp;
+        if conditional.has_exceeded_block_attributes(number, timestamp) {
+            return Err(PoolError::new(*tx.hash(), PoolErrorKind::ConditionsNotMet))
+        }
+
+        let added = self.add_transaction(tx, on_chain_balance, on_chain_nonce)?;
+        let hash = *added.hash();
+        if !conditional.matches_block_attributes(number, timestamp) {
+            self.unmet_conditionals.insert(hash);
+        }
+        self.conditionals.insert(hash, conditional);
+
+        Ok(added)
+    }
+
     /// Adds the transaction into the pool.
     ///
     /// This pool consists of four sub-pools: `Queued`, `Pending`, `BaseFee`, and `Blob`.
@@ -491,7 +642,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                 self.add_new_transaction(transaction.clone(), replaced_tx.clone(), move_to);
                 // Update inserted transactions metric
                 self.metrics.inserted_transactions.increment(1);
-                let UpdateOutcome { promoted, discarded } = self.process_updates(updates);
+                let UpdateOutcome { promoted, demoted, discarded } = self.process_updates(updates);
 
                 let replaced = replaced_tx.map(|(tx, _)| tx);
 
@@ -500,11 +651,12 @@ impl<T: TransactionOrdering> TxPool<T> {
                     AddedTransaction::Pending(AddedPendingTransaction {
                         transaction,
                         promoted,
+                        demoted,
                         discarded,
                         replaced,
                     })
                 } else {
-                    AddedTransaction::Parked { transaction, subpool: move_to, replaced }
+                    AddedTransaction::Parked { transaction, subpool: move_to, replaced, demoted }
                 };
 
                 Ok(res)
@@ -580,11 +732,8 @@ impl<T: TransactionOrdering> TxPool<T> {
                 }
                 Destination::Pool(move_to) => {
                     debug_assert!(!move_to.eq(&current), "destination must be different");
-                    let moved = self.move_transaction(current, move_to, &id);
-                    if matches!(move_to, SubPool::Pending) {
-                        if let Some(tx) = moved {
-                            outcome.promoted.push(tx);
-                        }
+                    if let Some(tx) = self.move_transaction(current, move_to, &id) {
+                        outcome.record_move(tx, current, move_to);
                     }
                 }
             }
@@ -756,18 +905,21 @@ impl<T: TransactionOrdering> TxPool<T> {
 
         // Helper macro that discards the worst transactions for the pools
         macro_rules! discard_worst {
-            ($this:ident, $removed:ident,  [$($limit:ident => $pool:ident),*]  ) => {
+            ($this:ident, $removed:ident,  [$($limit:ident => $pool:ident => $subpool:ident),*]  ) => {
                 $ (
-                while $this
-                        .config
-                        .$limit
-                        .is_exceeded($this.$pool.len(), $this.$pool.size())
-                    {
-                        removed = $this.$pool.truncate_pool($this.config.$limit.clone());
-                        for tx in removed.clone().iter() {
-                            $this.remove_descendants(tx.id(), &mut $removed);
+                let limit = $this.config.$limit.clone();
+                if !$this.evict_by_policy(SubPool::$subpool, &limit, &mut $removed) {
+                    while $this
+                            .config
+                            .$limit
+                            .is_exceeded($this.$pool.len(), $this.$pool.size())
+                        {
+                            removed = $this.$pool.truncate_pool($this.config.$limit.clone());
+                            for tx in removed.clone().iter() {
+                                $this.remove_descendants(tx.id(), &mut $removed);
+                            }
                         }
-                    }
+                }
 
                 )*
             };
@@ -775,16 +927,82 @@ impl<T: TransactionOrdering> TxPool<T> {
 
         discard_worst!(
             self, removed, [
-                pending_limit  => pending_pool,
-                basefee_limit  => basefee_pool,
-                blob_limit => blob_pool,
-                queued_limit  => queued_pool
+                pending_limit  => pending_pool => Pending,
+                basefee_limit  => basefee_pool => BaseFee,
+                blob_limit => blob_pool => Blob,
+                queued_limit  => queued_pool => Queued
             ]
         );
 
         removed
     }
 
+    /// Evicts transactions from the sub-pool in the order of the configured [EvictionPolicy]
+    /// until the sub-pool is within the given limit.
+    ///
+    /// Returns `false` if the policy uses the built-in order, in which case the sub-pool should be
+    /// truncated instead.
+    ///
+    /// [EvictionPolicy]: crate::EvictionPolicy
+    fn evict_by_policy(
+        &mut self,
+        subpool: SubPool,
+        limit: &SubPoolLimit,
+        removed: &mut Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
+    ) -> bool {
+        if self.config.eviction_policy.uses_builtin_order(subpool) {
+            return false
+        }
+        if !limit.is_exceeded(self.subpool_len(subpool), self.subpool_size(subpool)) {
+            return true
+        }
+
+        let base_fee = self.all_transactions.pending_fees.base_fee;
+        let no_local_exemptions = self.config.local_transactions_config.no_local_exemptions();
+        let transactions: Vec<_> = match subpool {
+            SubPool::Queued => self.queued_pool.all().collect(),
+            SubPool::Pending => self.pending_pool.all().collect(),
+            SubPool::BaseFee => self.basefee_pool.all().collect(),
+            SubPool::Blob => self.blob_pool.all().collect(),
+        };
+        let candidates = transactions
+            .iter()
+            .map(|tx| EvictionCandidate::new(tx, base_fee, tx.is_local() && !no_local_exemptions))
+            .collect();
+
+        for candidate in self.config.eviction_policy.eviction_order(subpool, candidates) {
+            if !limit.is_exceeded(self.subpool_len(subpool), self.subpool_size(subpool)) {
+                break
+            }
+            if let Some(tx) = self.remove_transaction(&candidate.id) {
+                self.remove_descendants(tx.id(), removed);
+                removed.push(tx);
+            }
+        }
+
+        true
+    }
+
+    /// Number of transactions in the given sub-pool
+    fn subpool_len(&self, subpool: SubPool) -> usize {
+        match subpool {
+            SubPool::Queued => self.queued_pool.len(),
+            SubPool::Pending => self.pending_pool.len(),
+            SubPool::BaseFee => self.basefee_pool.len(),
+            SubPool::Blob => self.blob_pool.len(),
+        }
+    }
+
+    /// Size of the transactions in the given sub-pool
+    fn subpool_size(&self, subpool: SubPool) -> usize {
+        match subpool {
+            SubPool::Queued => self.queued_pool.size(),
+            SubPool::Pending => self.pending_pool.size(),
+            SubPool::BaseFee => self.basefee_pool.size(),
+            SubPool::Blob => self.blob_pool.size(),
+        }
+    }
+
     /// Number of transactions in the entire pool
     pub(crate) fn len(&self) -> usize {
         self.all_transactions.len()
@@ -880,6 +1098,8 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     pending_fees: PendingFees,
     /// Configured price bump settings for replacements
     price_bumps: PriceBumpConfig,
+    /// Decides whether a transaction can replace an existing transaction.
+    replacement_policy: Arc<dyn ReplacementPolicy>,
     /// How to handle [TransactionOrigin::Local](crate::TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
 }
@@ -890,6 +1110,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         Self {
             max_account_slots: config.max_account_slots,
             price_bumps: config.price_bumps,
+            replacement_policy: config.replacement_policy.clone(),
             local_transactions_config: config.local_transactions_config.clone(),
             ..Default::default()
         }
@@ -942,6 +1163,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let BlockInfo {
             last_seen_block_hash,
             last_seen_block_number,
+            last_seen_block_timestamp: _,
             pending_basefee,
             pending_blob_fee,
         } = block_info;
@@ -1324,49 +1546,19 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
     /// Returns true if the replacement candidate is underpriced and can't replace the existing
     /// transaction.
+    ///
+    /// This is decided by the configured [ReplacementPolicy].
     #[inline]
     fn is_underpriced(
+        &self,
         existing_transaction: &ValidPoolTransaction<T>,
         maybe_replacement: &ValidPoolTransaction<T>,
-        price_bumps: &PriceBumpConfig,
     ) -> bool {
-        let price_bump = price_bumps.price_bump(existing_transaction.tx_type());
-        let price_bump_multiplier = (100 + price_bump) / 100;
-
-        if maybe_replacement.max_fee_per_gas() <=
-            existing_transaction.max_fee_per_gas() * price_bump_multiplier
-        {
-            return true;
-        }
-
-        let existing_max_priority_fee_per_gas =
-            existing_transaction.transaction.max_priority_fee_per_gas().unwrap_or(0);
-        let replacement_max_priority_fee_per_gas =
-            maybe_replacement.transaction.max_priority_fee_per_gas().unwrap_or(0);
-
-        if replacement_max_priority_fee_per_gas <=
-            existing_max_priority_fee_per_gas * price_bump_multiplier &&
-            existing_max_priority_fee_per_gas != 0 &&
-            replacement_max_priority_fee_per_gas != 0
-        {
-            return true;
-        }
-
-        // check max blob fee per gas
-        if let Some(existing_max_blob_fee_per_gas) =
-            existing_transaction.transaction.max_fee_per_blob_gas()
-        {
-            // this enforces that blob txs can only be replaced by blob txs
-            let replacement_max_blob_fee_per_gas =
-                maybe_replacement.transaction.max_fee_per_blob_gas().unwrap_or(0);
-            if replacement_max_blob_fee_per_gas <=
-                existing_max_blob_fee_per_gas * price_bump_multiplier
-            {
-                return true;
-            }
-        }
-
-        false
+        self.replacement_policy.is_underpriced(
+            &ReplacementFees::new(existing_transaction),
+            &ReplacementFees::new(maybe_replacement),
+            &self.price_bumps,
+        )
     }
 
     /// Inserts a new _valid_ transaction into the pool.
@@ -1485,8 +1677,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 let maybe_replacement = transaction.as_ref();
 
                 // Ensure the new transaction is not underpriced
-                if Self::is_underpriced(existing_transaction, maybe_replacement, &self.price_bumps)
-                {
+                if self.is_underpriced(existing_transaction, maybe_replacement) {
                     return Err(InsertErr::Underpriced {
                         transaction: pool_tx.transaction,
                         existing: *entry.get().transaction.hash(),
@@ -1629,6 +1820,7 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             last_seen_block_hash: Default::default(),
             pending_fees: Default::default(),
             price_bumps: Default::default(),
+            replacement_policy: Arc::new(DefaultReplacementPolicy),
             local_transactions_config: Default::default(),
         }
     }
@@ -1731,13 +1923,29 @@ impl<T: PoolTransaction> PoolInternalTransaction<T> {
 pub(crate) struct UpdateOutcome<T: PoolTransaction> {
     /// transactions promoted to the pending pool
     pub(crate) promoted: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transactions moved to a parked pool, and the sub-pool they were moved to
+    pub(crate) demoted: Vec<(TxHash, SubPool)>,
     /// transaction that failed and were discarded
     pub(crate) discarded: Vec<Arc<ValidPoolTransaction<T>>>,
 }
 
+impl<T: PoolTransaction> UpdateOutcome<T> {
+    /// Records that the transaction was moved from one sub-pool to another.
+    fn record_move(&mut self, tx: Arc<ValidPoolTransaction<T>>, from: SubPool, to: SubPool) {
+        if from == to {
+            return
+        }
+        if to.is_pending() {
+            self.promoted.push(tx);
+        } else {
+            self.demoted.push((*tx.hash(), to));
+        }
+    }
+}
+
 impl<T: PoolTransaction> Default for UpdateOutcome<T> {
     fn default() -> Self {
-        Self { promoted: vec![], discarded: vec![] }
+        Self { promoted: vec![], demoted: vec![], discarded: vec![] }
     }
 }
 
@@ -1788,6 +1996,26 @@ impl SenderInfo {
     }
 }
 
+/// Returns `true` if the storage changes don't contradict the expected storage of the conditions.
+///
+/// Accounts whose changes are unknown contradict any expectation, as does any storage change of an
+/// account whose storage root is expected.
+fn storage_matches_conditional(
+    conditional: &TransactionConditional,
+    changed_storage: &HashMap<Address, Option<HashMap<B256, B256>>>,
+) -> bool {
+    conditional.known_accounts.iter().all(|(address, expected)| {
+        let Some(changes) = changed_storage.get(address) else { return true };
+        match (expected, changes) {
+            (_, None) => false,
+            (KnownAccountState::StorageRoot(_), Some(changes)) => changes.is_empty(),
+            (KnownAccountState::Slots(slots), Some(changes)) => slots
+                .iter()
+                .all(|(slot, value)| changes.get(slot).map_or(true, |changed| changed == value)),
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2503,7 +2731,7 @@ mod tests {
 
         assert_eq!(pool.pending_pool.len(), 1);
 
-        pool.update_basefee((tx.max_fee_per_gas() + 1) as u64);
+        pool.update_basefee((tx.max_fee_per_gas() + 1) as u64, &mut Default::default());
 
         assert!(pool.pending_pool.is_empty());
         assert_eq!(pool.basefee_pool.len(), 1);
@@ -2559,4 +2787,149 @@ mod tests {
         assert_eq!(outcome.discarded.len(), 1);
         assert_eq!(pool.pending_pool.len(), 1);
     }
+
+    #[test]
+    fn discard_with_eviction_policy() {
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            pending_limit: SubPoolLimit { max_txs: 2, max_size: usize::MAX },
+            eviction_policy: Arc::new(crate::LowestTipEviction),
+            ..Default::default()
+        };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+
+        let txs = [3, 1, 2].map(|tip| {
+            f.validated(MockTransaction::eip1559().with_priority_fee(tip).with_max_fee(100))
+        });
+        let hashes = txs.iter().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        for tx in txs {
+            pool.add_transaction(tx, U256::from(1_000), 0).unwrap();
+        }
+        assert_eq!(pool.pending_pool.len(), 3);
+
+        let removed = pool.discard_worst();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(*removed[0].hash(), hashes[1]);
+        assert_eq!(pool.pending_pool.len(), 2);
+        assert!(!pool.contains(&hashes[1]));
+        pool.assert_invariants();
+    }
+
+    #[test]
+    fn conditional_transactions() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+        let account = Address::random();
+        let slot = B256::random();
+
+        // only valid from block 2 on
+        let delayed = f.validated(MockTransaction::eip1559());
+        let delayed_conditional =
+            TransactionConditional { block_number_min: Some(2), ..Default::default() };
+        // only valid until block 1
+        let expiring = f.validated(MockTransaction::eip1559());
+        let expiring_conditional =
+            TransactionConditional { block_number_max: Some(1), ..Default::default() };
+        // only valid while the slot is unchanged
+        let known_slot = f.validated(MockTransaction::eip1559());
+        let known_slot_conditional = TransactionConditional {
+            known_accounts: HashMap::from([(
+                account,
+                KnownAccountState::Slots(HashMap::from([(slot, B256::ZERO)])),
+            )]),
+            ..Default::default()
+        };
+
+        for (tx, conditional) in [
+            (&delayed, delayed_conditional),
+            (&expiring, expiring_conditional),
+            (&known_slot, known_slot_conditional),
+        ] {
+            pool.add_conditional_transaction(
+                tx.clone(),
+                on_chain_balance,
+                on_chain_nonce,
+                conditional,
+            )
+            .unwrap();
+        }
+        assert_eq!(pool.pending_pool.len(), 3);
+        assert_eq!(pool.conditional_accounts(), HashSet::from([account]));
+
+        // the delayed transaction is held back until block 2
+        let best = pool.best_transactions().map(|tx| *tx.hash()).collect::<HashSet<_>>();
+        assert_eq!(best, HashSet::from([*expiring.hash(), *known_slot.hash()]));
+
+        let mut block_info = pool.block_info();
+        block_info.last_seen_block_number = 2;
+        let outcome = pool.on_canonical_state_change(
+            block_info,
+            vec![],
+            HashMap::new(),
+            vec![ChangedStorage {
+                address: account,
+                slots: Some(HashMap::from([(slot, B256::with_last_byte(1))])),
+            }],
+        );
+        let discarded = outcome.discarded.iter().map(|tx| *tx.hash()).collect::<HashSet<_>>();
+        assert_eq!(discarded, HashSet::from([*expiring.hash(), *known_slot.hash()]));
+        assert!(pool.conditional_accounts().is_empty());
+
+        let best = pool.best_transactions().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*delayed.hash()]);
+
+        // conditions that can no longer be met are rejected
+        let tx = f.validated(MockTransaction::eip1559());
+        let err = pool
+            .add_conditional_transaction(
+                tx,
+                on_chain_balance,
+                on_chain_nonce,
+                TransactionConditional { block_number_max: Some(1), ..Default::default() },
+            )
+            .unwrap_err();
+        assert!(matches!(err.kind, PoolErrorKind::ConditionsNotMet));
+    }
+
+    #[test]
+    fn conditional_transactions_use_block_info_timestamp() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        // the pool is pointed to the latest block on startup
+        let mut block_info = pool.block_info();
+        block_info.last_seen_block_timestamp = 100;
+        pool.set_block_info(block_info);
+        assert_eq!(pool.block_info().last_seen_block_timestamp, 100);
+
+        // expired before the latest block
+        let err = pool
+            .add_conditional_transaction(
+                f.validated(MockTransaction::eip1559()),
+                U256::MAX,
+                0,
+                TransactionConditional { timestamp_max: Some(50), ..Default::default() },
+            )
+            .unwrap_err();
+        assert!(matches!(err.kind, PoolErrorKind::ConditionsNotMet));
+
+        // only valid after the latest block, so it's held back
+        let delayed = f.validated(MockTransaction::eip1559());
+        pool.add_conditional_transaction(
+            delayed.clone(),
+            U256::MAX,
+            0,
+            TransactionConditional { timestamp_min: Some(112), ..Default::default() },
+        )
+        .unwrap();
+        assert_eq!(pool.best_transactions().count(), 0);
+
+        block_info.last_seen_block_number += 1;
+        block_info.last_seen_block_timestamp = 112;
+        pool.on_canonical_state_change(block_info, vec![], HashMap::new(), vec![]);
+        let best = pool.best_transactions().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*delayed.hash()]);
+    }
 }
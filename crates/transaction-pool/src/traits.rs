@@ -1,6 +1,7 @@
 use crate::{
+    bundle::BundlePool,
     error::PoolResult,
-    pool::{state::SubPool, TransactionEvents},
+    pool::{state::SubPool, PoolDiffEvents, TransactionEvents},
     validate::ValidPoolTransaction,
     AllTransactionsEvents,
 };
@@ -9,8 +10,8 @@ use reth_primitives::{
     AccessList, Address, BlobTransactionSidecar, BlobTransactionValidationError,
     FromRecoveredPooledTransaction, FromRecoveredTransaction, IntoRecoveredTransaction, PeerId,
     PooledTransactionsElement, PooledTransactionsElementEcRecovered, SealedBlock, Transaction,
-    TransactionKind, TransactionSignedEcRecovered, TxEip4844, TxHash, B256, EIP1559_TX_TYPE_ID,
-    EIP4844_TX_TYPE_ID, U256,
+    TransactionConditional, TransactionKind, TransactionSignedEcRecovered, TxEip4844, TxHash, B256,
+    EIP1559_TX_TYPE_ID, EIP4844_TX_TYPE_ID, U256,
 };
 use std::{
     collections::{HashMap, HashSet},
@@ -90,6 +91,21 @@ pub trait TransactionPool: Send + Sync + Clone {
         transaction: Self::Transaction,
     ) -> PoolResult<TxHash>;
 
+    /// Adds an _unvalidated_ transaction into the pool that may only be included in a block while
+    /// the given conditions hold.
+    ///
+    /// The conditions are re-checked on every canonical state change: the transaction is not
+    /// returned by [TransactionPool::best_transactions] while they are unmet, and it's removed
+    /// from the pool once they can no longer be met.
+    ///
+    /// Consumer: RPC
+    async fn add_transaction_with_conditional(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        conditional: TransactionConditional,
+    ) -> PoolResult<TxHash>;
+
     /// Adds the given _unvalidated_ transaction into the pool.
     ///
     /// Returns a list of results.
@@ -109,6 +125,11 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Returns a new transaction change event stream for _all_ transactions in the pool.
     fn all_transactions_event_listener(&self) -> AllTransactionsEvents<Self::Transaction>;
 
+    /// Returns a new stream that yields all changes of the content of the pool, in order.
+    ///
+    /// Together with [TransactionPool::pool_snapshot] this allows tracking the content of the pool.
+    fn pool_diff_listener(&self) -> PoolDiffEvents<Self::Transaction>;
+
     /// Returns a new Stream that yields transactions hashes for new __pending__ transactions
     /// inserted into the pool that are allowed to be propagated.
     ///
@@ -265,6 +286,21 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Consumer: RPC
     fn all_transactions(&self) -> AllPoolTransactions<Self::Transaction>;
 
+    /// Returns all transactions that are currently in the pool, and the sequence number of the
+    /// last change of the pool that is reflected in them.
+    ///
+    /// All changes yielded by [TransactionPool::pool_diff_listener] with a higher sequence number
+    /// must be applied to the snapshot to get the current content of the pool.
+    ///
+    /// Consumer: RPC
+    fn pool_snapshot(&self) -> PoolSnapshot<Self::Transaction>;
+
+    /// Returns the pool of transaction bundles that target a specific block, if the pool supports
+    /// bundles.
+    ///
+    /// Consumer: RPC and payload builder
+    fn bundle_pool(&self) -> Option<&BundlePool>;
+
     /// Removes all transactions corresponding to the given hashes.
     ///
     /// Also removes all _dependent_ transactions.
@@ -370,6 +406,11 @@ pub trait TransactionPoolExt: TransactionPool {
     /// Updates the accounts in the pool
     fn update_accounts(&self, accounts: Vec<ChangedAccount>);
 
+    /// Returns all accounts whose storage is expected by the conditional transactions in the pool.
+    ///
+    /// The storage changes of these accounts must be included in the [CanonicalStateUpdate].
+    fn conditional_accounts(&self) -> HashSet<Address>;
+
     /// Deletes the blob sidecar for the given transaction from the blob store
     fn delete_blob(&self, tx: B256);
 
@@ -429,6 +470,24 @@ impl<T: PoolTransaction> Default for AllPoolTransactions<T> {
     }
 }
 
+/// A point-in-time copy of the content of the pool, see [TransactionPool::pool_snapshot].
+#[derive(Debug, Clone)]
+pub struct PoolSnapshot<T: PoolTransaction> {
+    /// Sequence number of the last change of the pool that is reflected in the snapshot.
+    ///
+    /// Changes with a higher sequence number may already be reflected as well, applying them
+    /// again has no effect.
+    pub sequence: u64,
+    /// Transactions in the pending sub-pool.
+    pub pending: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// Transactions in the basefee sub-pool.
+    pub basefee: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// Transactions in the blob sub-pool.
+    pub blob: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// Transactions in the queued sub-pool.
+    pub queued: Vec<Arc<ValidPoolTransaction<T>>>,
+}
+
 /// Represents a transaction that was propagated over the network.
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
 pub struct PropagatedTransactions(pub HashMap<TxHash, Vec<PropagateKind>>);
@@ -543,6 +602,8 @@ pub struct CanonicalStateUpdate<'a> {
     pub pending_block_blob_fee: Option<u128>,
     /// A set of changed accounts across a range of blocks.
     pub changed_accounts: Vec<ChangedAccount>,
+    /// Changed storage of the accounts that are known to conditional transactions.
+    pub changed_storage: Vec<ChangedStorage>,
     /// All mined transactions in the block range.
     pub mined_transactions: Vec<B256>,
 }
@@ -568,6 +629,7 @@ impl<'a> CanonicalStateUpdate<'a> {
         BlockInfo {
             last_seen_block_hash: self.hash(),
             last_seen_block_number: self.number(),
+            last_seen_block_timestamp: self.timestamp(),
             pending_basefee: self.pending_block_base_fee,
             pending_blob_fee: self.pending_block_blob_fee,
         }
@@ -576,8 +638,8 @@ impl<'a> CanonicalStateUpdate<'a> {
 
 impl<'a> fmt::Display for CanonicalStateUpdate<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{{ hash: {}, number: {}, pending_block_base_fee: {}, pending_block_blob_fee: {:?}, changed_accounts: {}, mined_transactions: {} }}",
-            self.hash(), self.number(), self.pending_block_base_fee, self.pending_block_blob_fee,  self.changed_accounts.len(), self.mined_transactions.len())
+        write!(f, "{{ hash: {}, number: {}, pending_block_base_fee: {}, pending_block_blob_fee: {:?}, changed_accounts: {}, changed_storage: {}, mined_transactions: {} }}",
+            self.hash(), self.number(), self.pending_block_base_fee, self.pending_block_blob_fee,  self.changed_accounts.len(), self.changed_storage.len(), self.mined_transactions.len())
     }
 }
 
@@ -601,6 +663,18 @@ impl ChangedAccount {
     }
 }
 
+/// Represents the changed storage of an account
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ChangedStorage {
+    /// The address of the account.
+    pub address: Address,
+    /// New values of the changed storage slots.
+    ///
+    /// This is `None` if the changes are unknown, for example if the storage was wiped or the
+    /// changes were reverted by a reorg.
+    pub slots: Option<HashMap<B256, B256>>,
+}
+
 /// An `Iterator` that only returns transactions that are ready to be executed.
 ///
 /// This makes no assumptions about the order of the transactions, but expects that _all_
@@ -1103,6 +1177,8 @@ pub struct BlockInfo {
     pub last_seen_block_hash: B256,
     /// Current the currently tracked block.
     pub last_seen_block_number: u64,
+    /// Timestamp of the currently tracked block.
+    pub last_seen_block_timestamp: u64,
     /// Currently enforced base fee: the threshold for the basefee sub-pool.
     ///
     /// Note: this is the derived base fee of the _next_ block that builds on the block the pool is
@@ -4,7 +4,10 @@ use crate::{
     blobstore::BlobStore,
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
     traits::TransactionOrigin,
-    validate::{ValidTransaction, ValidationTask, MAX_INIT_CODE_SIZE, TX_MAX_SIZE},
+    validate::{
+        TransactionValidationRule, ValidTransaction, ValidationTask, MAX_INIT_CODE_SIZE,
+        TX_MAX_SIZE,
+    },
     EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig, PoolTransaction,
     TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
 };
@@ -126,6 +129,8 @@ where
     kzg_settings: Arc<KzgSettings>,
     /// How to handle [TransactionOrigin::Local](TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
+    /// Additional rules transactions are checked against.
+    rules: Vec<Arc<dyn TransactionValidationRule>>,
     /// Marker for the transaction type
     _marker: PhantomData<T>,
 }
@@ -263,6 +268,16 @@ where
             return TransactionValidationOutcome::Invalid(transaction, err)
         }
 
+        // only converted if there are rules to check
+        let recovered = (!self.rules.is_empty()).then(|| transaction.to_recovered_transaction());
+        if let Some(recovered) = &recovered {
+            if let Err(err) =
+                self.rules.iter().try_for_each(|rule| rule.validate_stateless(origin, recovered))
+            {
+                return TransactionValidationOutcome::Invalid(transaction, err)
+            }
+        }
+
         let mut maybe_blob_sidecar = None;
 
         // blob tx checks
@@ -433,6 +448,16 @@ where
             )
         }
 
+        if let Some(recovered) = &recovered {
+            if let Err(err) = self
+                .rules
+                .iter()
+                .try_for_each(|rule| rule.validate_stateful(origin, recovered, &account))
+            {
+                return TransactionValidationOutcome::Invalid(transaction, err)
+            }
+        }
+
         // Return the valid transaction
         TransactionValidationOutcome::Valid {
             balance: account.balance,
@@ -488,6 +513,8 @@ pub struct EthTransactionValidatorBuilder {
     kzg_settings: Arc<KzgSettings>,
     /// How to handle [TransactionOrigin::Local](TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
+    /// Additional rules transactions are checked against.
+    rules: Vec<Arc<dyn TransactionValidationRule>>,
 }
 
 impl EthTransactionValidatorBuilder {
@@ -505,6 +532,7 @@ impl EthTransactionValidatorBuilder {
             propagate_local_transactions: true,
             kzg_settings: Arc::clone(&MAINNET_KZG_TRUSTED_SETUP),
             local_transactions_config: Default::default(),
+            rules: Vec::new(),
 
             // by default all transaction types are allowed
             eip2718: true,
@@ -602,6 +630,14 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Adds a rule that transactions are checked against in addition to the built-in checks.
+    ///
+    /// Rules are checked in the order they were added, see [TransactionValidationRule].
+    pub fn with_rule(mut self, rule: impl TransactionValidationRule + 'static) -> Self {
+        self.rules.push(Arc::new(rule));
+        self
+    }
+
     /// Sets the number of additional tasks to spawn.
     pub fn with_additional_tasks(mut self, additional_tasks: usize) -> Self {
         self.additional_tasks = additional_tasks;
@@ -639,6 +675,7 @@ impl EthTransactionValidatorBuilder {
             propagate_local_transactions,
             kzg_settings,
             local_transactions_config,
+            rules,
             ..
         } = self;
 
@@ -658,6 +695,7 @@ impl EthTransactionValidatorBuilder {
             blob_store: Box::new(blob_store),
             kzg_settings,
             local_transactions_config,
+            rules,
             _marker: Default::default(),
         };
 
@@ -774,7 +812,8 @@ mod tests {
     async fn validate_transaction() {
         use super::*;
         use crate::{
-            blobstore::InMemoryBlobStore, CoinbaseTipOrdering, EthPooledTransaction, Pool,
+            blobstore::InMemoryBlobStore, error::ValidationRuleError,
+            validate::DenyContractCreation, CoinbaseTipOrdering, EthPooledTransaction, Pool,
             TransactionPool,
         };
         use reth_primitives::{
@@ -802,12 +841,25 @@ mod tests {
         );
         let blob_store = InMemoryBlobStore::default();
         let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
-            .build(provider, blob_store.clone());
+            .build(provider.clone(), blob_store.clone());
 
         let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
 
         assert!(outcome.is_valid());
 
+        // the transaction creates a contract
+        let outcome = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .with_rule(DenyContractCreation)
+            .build(provider.clone(), blob_store.clone())
+            .validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::Rule(ValidationRuleError::ContractCreationNotAllowed)
+            )
+        ));
+
         let pool =
             Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
 
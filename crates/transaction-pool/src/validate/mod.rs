@@ -13,11 +13,15 @@ use std::{fmt, time::Instant};
 
 mod constants;
 mod eth;
+mod rules;
 mod task;
 
 /// A `TransactionValidator` implementation that validates ethereum transaction.
 pub use eth::*;
 
+/// Additional rules for the ethereum transaction validator.
+pub use rules::*;
+
 /// A spawnable task that performs transaction validation.
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
 
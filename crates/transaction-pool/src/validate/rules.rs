@@ -0,0 +1,197 @@
+//! Composable rules that extend the validation of the [EthTransactionValidator].
+//!
+//! [EthTransactionValidator]: crate::validate::EthTransactionValidator
+
+use crate::{
+    error::{InvalidPoolTransactionError, ValidationRuleError},
+    traits::TransactionOrigin,
+};
+use reth_primitives::{Account, Address, TransactionSignedEcRecovered};
+use std::{collections::HashSet, fmt};
+
+/// A rule that's checked by the [EthTransactionValidator](crate::validate::EthTransactionValidator)
+/// in addition to its built-in checks.
+///
+/// Rules are configured with
+/// [EthTransactionValidatorBuilder::with_rule](crate::validate::EthTransactionValidatorBuilder::with_rule)
+/// and checked in the order they were added. A transaction is rejected by the first rule that
+/// returns an error.
+pub trait TransactionValidationRule: fmt::Debug + Send + Sync {
+    /// Checks the transaction before the state of its sender is loaded.
+    ///
+    /// This runs after the built-in stateless checks, for example of the transaction type, size
+    /// and gas limit, and before the blob sidecar is validated.
+    fn validate_stateless(
+        &self,
+        _origin: TransactionOrigin,
+        _transaction: &TransactionSignedEcRecovered,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        Ok(())
+    }
+
+    /// Checks the transaction after the built-in stateful checks, with the account of its sender
+    /// at the latest state.
+    fn validate_stateful(
+        &self,
+        _origin: TransactionOrigin,
+        _transaction: &TransactionSignedEcRecovered,
+        _account: &Account,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        Ok(())
+    }
+}
+
+/// Rejects transactions sent by or to any of the denied addresses.
+#[derive(Debug, Clone, Default)]
+pub struct AddressDenyList {
+    /// Addresses that are not allowed to send transactions.
+    pub senders: HashSet<Address>,
+    /// Addresses that are not allowed to receive transactions.
+    pub recipients: HashSet<Address>,
+}
+
+impl TransactionValidationRule for AddressDenyList {
+    fn validate_stateless(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &TransactionSignedEcRecovered,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        let sender = transaction.signer();
+        if self.senders.contains(&sender) {
+            return Err(ValidationRuleError::DeniedSender(sender).into())
+        }
+        if let Some(recipient) = transaction.to().filter(|to| self.recipients.contains(to)) {
+            return Err(ValidationRuleError::DeniedRecipient(recipient).into())
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions with calldata larger than the limit.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxCalldataSize(pub usize);
+
+impl TransactionValidationRule for MaxCalldataSize {
+    fn validate_stateless(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &TransactionSignedEcRecovered,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        let size = transaction.input().len();
+        if size > self.0 {
+            return Err(ValidationRuleError::ExceedsMaxCalldataSize { size, limit: self.0 }.into())
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions that create a contract.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DenyContractCreation;
+
+impl TransactionValidationRule for DenyContractCreation {
+    fn validate_stateless(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &TransactionSignedEcRecovered,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        if transaction.kind().is_create() {
+            return Err(ValidationRuleError::ContractCreationNotAllowed.into())
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions whose priority fee, or gas price for legacy transactions, is below the
+/// floor configured for their origin.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeeFloorPerOrigin {
+    /// Minimum fee of [TransactionOrigin::Local] transactions.
+    pub local: Option<u128>,
+    /// Minimum fee of [TransactionOrigin::External] transactions.
+    pub external: Option<u128>,
+    /// Minimum fee of [TransactionOrigin::Private] transactions.
+    pub private: Option<u128>,
+}
+
+impl TransactionValidationRule for FeeFloorPerOrigin {
+    fn validate_stateless(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &TransactionSignedEcRecovered,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        let floor = match origin {
+            TransactionOrigin::Local => self.local,
+            TransactionOrigin::External => self.external,
+            TransactionOrigin::Private => self.private,
+        };
+        let fee = transaction.priority_fee_or_price();
+        if let Some(floor) = floor.filter(|floor| fee < *floor) {
+            return Err(ValidationRuleError::FeeBelowFloor { fee, floor }.into())
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TransactionBuilder;
+    use assert_matches::assert_matches;
+
+    #[test]
+    fn builtin_rules() {
+        let recipient = Address::random();
+        let call = TransactionBuilder::default()
+            .to(recipient)
+            .input(vec![0; 100])
+            .max_fee_per_gas(10)
+            .max_priority_fee_per_gas(10)
+            .into_eip1559()
+            .into_ecrecovered()
+            .unwrap();
+        let origin = TransactionOrigin::External;
+
+        let deny_list = AddressDenyList {
+            senders: HashSet::from([call.signer()]),
+            recipients: HashSet::from([recipient]),
+        };
+        assert_matches!(
+            deny_list.validate_stateless(origin, &call),
+            Err(InvalidPoolTransactionError::Rule(ValidationRuleError::DeniedSender(sender))) if sender == call.signer()
+        );
+        let deny_list =
+            AddressDenyList { recipients: HashSet::from([recipient]), ..Default::default() };
+        assert_matches!(
+            deny_list.validate_stateless(origin, &call),
+            Err(InvalidPoolTransactionError::Rule(ValidationRuleError::DeniedRecipient(to))) if to == recipient
+        );
+
+        assert_matches!(MaxCalldataSize(100).validate_stateless(origin, &call), Ok(()));
+        assert_matches!(
+            MaxCalldataSize(99).validate_stateless(origin, &call),
+            Err(InvalidPoolTransactionError::Rule(ValidationRuleError::ExceedsMaxCalldataSize {
+                size: 100,
+                limit: 99
+            }))
+        );
+
+        // Transactions without a recipient create a contract
+        let create = TransactionBuilder::default().into_eip1559().into_ecrecovered().unwrap();
+        assert_matches!(DenyContractCreation.validate_stateless(origin, &call), Ok(()));
+        assert_matches!(
+            DenyContractCreation.validate_stateless(origin, &create),
+            Err(InvalidPoolTransactionError::Rule(ValidationRuleError::ContractCreationNotAllowed))
+        );
+
+        let fee_floor = FeeFloorPerOrigin { external: Some(11), ..Default::default() };
+        assert_matches!(fee_floor.validate_stateless(TransactionOrigin::Local, &call), Ok(()));
+        assert_matches!(
+            fee_floor.validate_stateless(origin, &call),
+            Err(InvalidPoolTransactionError::Rule(ValidationRuleError::FeeBelowFloor {
+                fee: 10,
+                floor: 11
+            }))
+        );
+    }
+}
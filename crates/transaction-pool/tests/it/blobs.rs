@@ -1,11 +1,44 @@
 //! Blob transaction tests
 
+use reth_primitives::BlobTransactionSidecar;
 use reth_transaction_pool::{
+    blobstore::{BlobStore, DiskFileBlobStore, DiskFileBlobStoreConfig},
     error::PoolErrorKind,
-    test_utils::{testing_pool, MockTransaction, MockTransactionFactory},
-    TransactionOrigin, TransactionPool,
+    test_utils::{testing_pool, MockOrdering, MockTransaction, MockTransactionFactory},
+    validate::ValidTransaction,
+    Pool, TransactionOrigin, TransactionPool, TransactionValidationOutcome, TransactionValidator,
 };
 
+/// A validator that accepts every transaction and returns a sidecar for blob transactions.
+#[derive(Debug, Default)]
+struct SidecarValidator;
+
+#[async_trait::async_trait]
+impl TransactionValidator for SidecarValidator {
+    type Transaction = MockTransaction;
+
+    async fn validate_transaction(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        let transaction = if transaction.is_eip4844() {
+            ValidTransaction::ValidWithSidecar {
+                transaction,
+                sidecar: BlobTransactionSidecar::default(),
+            }
+        } else {
+            ValidTransaction::Valid(transaction)
+        };
+        TransactionValidationOutcome::Valid {
+            balance: Default::default(),
+            state_nonce: 0,
+            transaction,
+            propagate: true,
+        }
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn blobs_exclusive() {
     let txpool = testing_pool();
@@ -38,3 +71,34 @@ async fn blobs_exclusive() {
         _ => unreachable!(),
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn blob_sidecar_quota_exceeded() {
+    let blob_dir = tempfile::tempdir().unwrap();
+    let blob_store = DiskFileBlobStore::open(
+        blob_dir.path(),
+        DiskFileBlobStoreConfig::default().with_max_disk_size(Some(1)),
+    )
+    .unwrap();
+    let txpool = Pool::new(
+        SidecarValidator,
+        MockOrdering::default(),
+        blob_store.clone(),
+        Default::default(),
+    );
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let blob_tx = mock_tx_factory.create_eip4844();
+    let hash = blob_tx.transaction.get_hash();
+
+    let err = txpool
+        .add_transaction(TransactionOrigin::External, blob_tx.transaction.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.hash, hash);
+    assert!(matches!(err.kind, PoolErrorKind::Other(_)));
+
+    // the transaction must not be in the pool without its sidecar
+    assert!(!txpool.contains(&hash));
+    assert!(txpool.best_transactions().next().is_none());
+    assert!(blob_store.get(hash).unwrap().is_none());
+}
@@ -1,11 +1,14 @@
 use assert_matches::assert_matches;
+use reth_primitives::{constants::MIN_PROTOCOL_BASE_FEE, TxHash};
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
-    test_utils::{testing_pool, testing_pool_with_validator, MockTransactionFactory},
-    FullTransactionEvent, TransactionEvent, TransactionListenerKind, TransactionOrigin,
-    TransactionPool,
+    test_utils::{
+        testing_pool, testing_pool_with_validator, MockTransaction, MockTransactionFactory,
+    },
+    FullTransactionEvent, PoolDiff, PoolSnapshot, SubPool, TransactionEvent,
+    TransactionListenerKind, TransactionOrigin, TransactionPool, TransactionPoolExt,
 };
-use std::{future::poll_fn, task::Poll};
+use std::{collections::HashMap, future::poll_fn, task::Poll};
 use tokio_stream::StreamExt;
 
 #[tokio::test(flavor = "multi_thread")]
@@ -41,6 +44,117 @@ async fn txpool_listener_all() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_diff_listener() {
+    let txpool = testing_pool();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559();
+    let hash = transaction.transaction.get_hash();
+
+    let mut diffs = txpool.pool_diff_listener();
+    let snapshot = txpool.pool_snapshot();
+    assert!(snapshot.pending.is_empty());
+
+    txpool
+        .add_transaction(TransactionOrigin::External, transaction.transaction.clone())
+        .await
+        .unwrap();
+
+    let event = diffs.next().await.unwrap();
+    assert_eq!(event.sequence, snapshot.sequence + 1);
+    assert_matches!(
+        event.diff,
+        PoolDiff::Added { transaction, subpool: SubPool::Pending } if *transaction.hash() == hash
+    );
+
+    let snapshot = txpool.pool_snapshot();
+    assert_eq!(snapshot.sequence, event.sequence);
+    assert_eq!(*snapshot.pending[0].hash(), hash);
+
+    txpool.remove_transactions(vec![hash]);
+    let event = diffs.next().await.unwrap();
+    assert_eq!(event.sequence, snapshot.sequence + 1);
+    assert_matches!(event.diff, PoolDiff::Discarded(discarded) if discarded == hash);
+}
+
+/// Applies all changes up to the sequence number of a new snapshot to the first one, and checks
+/// that it yields the same content as the new snapshot.
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_diff_listener_matches_snapshot() {
+    fn subpools(snapshot: PoolSnapshot<MockTransaction>) -> HashMap<TxHash, SubPool> {
+        let PoolSnapshot { pending, basefee, blob, queued, .. } = snapshot;
+        [
+            (pending, SubPool::Pending),
+            (basefee, SubPool::BaseFee),
+            (blob, SubPool::Blob),
+            (queued, SubPool::Queued),
+        ]
+        .into_iter()
+        .flat_map(|(transactions, subpool)| {
+            transactions.into_iter().map(move |tx| (*tx.hash(), subpool))
+        })
+        .collect()
+    }
+
+    let txpool = testing_pool();
+    let mut diffs = txpool.pool_diff_listener();
+    let snapshot = txpool.pool_snapshot();
+    let mut sequence = snapshot.sequence;
+    let mut content = subpools(snapshot);
+
+    let set_basefee = |pending_basefee| {
+        let mut block_info = txpool.block_info();
+        block_info.pending_basefee = pending_basefee;
+        txpool.set_block_info(block_info);
+    };
+
+    // All transactions are parked in the basefee pool, except for the one with a nonce gap
+    set_basefee(MIN_PROTOCOL_BASE_FEE + 1);
+    let tx0 = MockTransaction::eip1559();
+    let tx1 = tx0.next();
+    let tx2 = tx1.next();
+    for tx in [&tx0, &tx2] {
+        txpool.add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap();
+    }
+    // Filling the gap moves the last transaction from the queued to the basefee pool
+    txpool.add_transaction(TransactionOrigin::External, tx1.clone()).await.unwrap();
+
+    // Promotes all transactions to the pending pool, and demotes them again
+    set_basefee(MIN_PROTOCOL_BASE_FEE);
+    set_basefee(MIN_PROTOCOL_BASE_FEE + 1);
+
+    txpool.remove_transactions(vec![tx2.get_hash()]);
+
+    let snapshot = txpool.pool_snapshot();
+    while sequence < snapshot.sequence {
+        let event = diffs.next().await.unwrap();
+        assert_eq!(event.sequence, sequence + 1);
+        sequence = event.sequence;
+
+        match event.diff {
+            PoolDiff::Added { transaction, subpool } => {
+                content.insert(*transaction.hash(), subpool);
+            }
+            PoolDiff::Promoted(tx_hash) => {
+                assert!(content.insert(tx_hash, SubPool::Pending).is_some());
+            }
+            PoolDiff::Demoted { tx_hash, subpool } => {
+                assert!(content.insert(tx_hash, subpool).is_some());
+            }
+            PoolDiff::Replaced { tx_hash, .. } |
+            PoolDiff::Discarded(tx_hash) |
+            PoolDiff::Mined { tx_hash, .. } => {
+                content.remove(&tx_hash);
+            }
+        }
+    }
+
+    let expected = subpools(snapshot);
+    assert_eq!(expected.len(), 2);
+    assert!(expected.values().all(|subpool| *subpool == SubPool::BaseFee));
+    assert_eq!(content, expected);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn txpool_listener_propagate_only() {
     let txpool = testing_pool_with_validator(MockTransactionValidator::no_propagate_local());
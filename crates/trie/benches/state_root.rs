@@ -0,0 +1,125 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use proptest::{
+    prelude::*,
+    strategy::{Strategy, ValueTree},
+    test_runner::TestRunner,
+};
+use reth_db::{
+    cursor::DbCursorRW,
+    database::Database,
+    tables,
+    test_utils::create_test_rw_db,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::{Account, StorageEntry, B256, U256};
+use reth_trie::{
+    hashed_cursor::{HashedPostState, HashedPostStateCursorFactory, HashedStorage},
+    ParallelStateRoot, StateRoot,
+};
+use std::collections::BTreeMap;
+
+/// Benchmarks the sequential and the parallel state root calculation.
+pub fn calculate_state_root(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Calculate State Root");
+    group.sample_size(20);
+
+    for size in [100, 1_000, 3_000] {
+        let (db_state, updated_state) = generate_test_data(size);
+
+        // setup the database
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        {
+            let mut hashed_storage_cursor = tx.cursor_dup_write::<tables::HashedStorage>().unwrap();
+            for (hashed_address, (account, storage)) in &db_state {
+                tx.put::<tables::HashedAccount>(*hashed_address, *account).unwrap();
+                for (key, value) in storage {
+                    hashed_storage_cursor
+                        .upsert(*hashed_address, StorageEntry { key: *key, value: *value })
+                        .unwrap();
+                }
+            }
+        }
+        let (_, updates) = StateRoot::new(&tx).root_with_updates().unwrap();
+        updates.flush(&tx).unwrap();
+        tx.commit().unwrap();
+
+        let hashed_state = hashed_post_state(&updated_state);
+
+        group.bench_function(format!("sequential | accounts: {size}"), |b| {
+            b.iter(|| {
+                let tx = db.tx().unwrap();
+                let (account_prefix_set, storage_prefix_sets) =
+                    hashed_state.construct_prefix_sets();
+                StateRoot::new(&tx)
+                    .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                        &tx,
+                        &hashed_state,
+                    ))
+                    .with_changed_account_prefixes(account_prefix_set)
+                    .with_changed_storage_prefixes(storage_prefix_sets)
+                    .with_destroyed_accounts(hashed_state.destroyed_accounts())
+                    .root_with_updates()
+                    .unwrap()
+            });
+        });
+
+        group.bench_function(format!("parallel | accounts: {size}"), |b| {
+            b.iter(|| {
+                ParallelStateRoot::new(&db, &hashed_state).incremental_root_with_updates().unwrap()
+            });
+        });
+    }
+}
+
+type State = BTreeMap<B256, (Account, BTreeMap<B256, U256>)>;
+
+/// Generates the initial database state with 100 storage slots per account and the updated
+/// state changing 10 slots of every account.
+fn generate_test_data(size: usize) -> (State, State) {
+    let storage_strategy = prop::collection::btree_map(
+        any::<B256>(),
+        any::<u64>().prop_filter("non-zero value", |v| *v != 0).prop_map(U256::from),
+        100,
+    );
+    let db_state =
+        prop::collection::btree_map(any::<B256>(), (any::<Account>(), storage_strategy), size)
+            .new_tree(&mut TestRunner::new(ProptestConfig::default()))
+            .unwrap()
+            .current();
+
+    let updated_state = db_state
+        .iter()
+        .map(|(hashed_address, (account, storage))| {
+            let account = Account { nonce: account.nonce.wrapping_add(1), ..*account };
+            let storage = storage
+                .keys()
+                .take(10)
+                .map(|slot| (*slot, U256::from(1)))
+                .collect::<BTreeMap<_, _>>();
+            (*hashed_address, (account, storage))
+        })
+        .collect();
+
+    (db_state, updated_state)
+}
+
+fn hashed_post_state(state: &State) -> HashedPostState {
+    let mut hashed_state = HashedPostState::default();
+    for (hashed_address, (account, storage)) in state {
+        hashed_state.insert_account(*hashed_address, *account);
+        let mut hashed_storage = HashedStorage::new(false);
+        for (slot, value) in storage {
+            hashed_storage.insert_non_zero_valued_storage(*slot, *value);
+        }
+        hashed_state.insert_hashed_storage(*hashed_address, hashed_storage);
+    }
+    hashed_state.sorted()
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default();
+    targets = calculate_state_root
+}
+criterion_main!(benches);
@@ -6,7 +6,7 @@ use reth_db::{
     transaction::DbTx,
 };
 use reth_primitives::{trie::Nibbles, Account, StorageEntry, B256, U256};
-use std::collections::{HashMap, HashSet};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
 
 /// The post state account storage with hashed slots.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -64,6 +64,23 @@ impl HashedStorage {
     pub fn insert_zero_valued_slot(&mut self, slot: B256) {
         self.zero_valued_slots.insert(slot);
     }
+
+    /// Extend the storage with the changes of a newer hashed storage.
+    ///
+    /// The slots of `other` take precedence. If `other` was wiped, it replaces this storage.
+    pub fn extend(&mut self, other: HashedStorage) {
+        if other.wiped {
+            *self = other;
+            return
+        }
+
+        let updated = other.storage_slots().map(|(slot, _)| slot).collect::<HashSet<_>>();
+        self.non_zero_valued_storage.retain(|(slot, _)| !updated.contains(slot));
+        self.zero_valued_slots.retain(|slot| !updated.contains(slot));
+        self.non_zero_valued_storage.extend(other.non_zero_valued_storage);
+        self.zero_valued_slots.extend(other.zero_valued_slots);
+        self.sorted = false;
+    }
 }
 
 /// The post state with hashed addresses as keys.
@@ -138,6 +155,32 @@ impl HashedPostState {
         self.storages.insert(hashed_address, hashed_storage);
     }
 
+    /// Extend the post state with the changes of a newer hashed post state.
+    ///
+    /// The accounts and storages of `other` take precedence, see [HashedStorage::extend].
+    pub fn extend(&mut self, other: HashedPostState) {
+        let updated = other
+            .accounts
+            .iter()
+            .map(|(hashed_address, _)| *hashed_address)
+            .chain(other.destroyed_accounts.iter().copied())
+            .collect::<HashSet<_>>();
+        self.accounts.retain(|(hashed_address, _)| !updated.contains(hashed_address));
+        self.destroyed_accounts.retain(|hashed_address| !updated.contains(hashed_address));
+        self.accounts.extend(other.accounts);
+        self.destroyed_accounts.extend(other.destroyed_accounts);
+
+        for (hashed_address, hashed_storage) in other.storages {
+            match self.storages.entry(hashed_address) {
+                Entry::Occupied(mut entry) => entry.get_mut().extend(hashed_storage),
+                Entry::Vacant(entry) => {
+                    entry.insert(hashed_storage);
+                }
+            }
+        }
+        self.sorted = false;
+    }
+
     /// Returns all destroyed accounts.
     pub fn destroyed_accounts(&self) -> HashSet<B256> {
         self.destroyed_accounts.clone()
@@ -1006,6 +1049,58 @@ mod tests {
         assert_storage_cursor_order(&factory, expected);
     }
 
+    #[test]
+    fn extend_post_state() {
+        let address = B256::random();
+        let destroyed = B256::random();
+
+        let mut hashed_storage = HashedStorage::new(false);
+        hashed_storage.insert_non_zero_valued_storage(B256::with_last_byte(1), U256::from(1));
+        hashed_storage.insert_non_zero_valued_storage(B256::with_last_byte(2), U256::from(2));
+        let mut hashed_post_state = HashedPostState::default();
+        hashed_post_state.insert_account(address, Account::default());
+        hashed_post_state.insert_account(destroyed, Account::default());
+        hashed_post_state.insert_hashed_storage(address, hashed_storage);
+
+        let mut newer_storage = HashedStorage::new(false);
+        newer_storage.insert_zero_valued_slot(B256::with_last_byte(1));
+        newer_storage.insert_non_zero_valued_storage(B256::with_last_byte(3), U256::from(3));
+        let mut newer = HashedPostState::default();
+        newer.insert_account(address, Account { nonce: 1, ..Default::default() });
+        newer.insert_destroyed_account(destroyed);
+        newer.insert_hashed_storage(address, newer_storage);
+
+        hashed_post_state.extend(newer);
+        hashed_post_state.sort();
+
+        assert_eq!(
+            hashed_post_state.accounts().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([
+                (address, Some(Account { nonce: 1, ..Default::default() })),
+                (destroyed, None)
+            ])
+        );
+        assert_eq!(
+            hashed_post_state
+                .storages
+                .get(&address)
+                .unwrap()
+                .storage_slots()
+                .collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([
+                (B256::with_last_byte(1), U256::ZERO),
+                (B256::with_last_byte(2), U256::from(2)),
+                (B256::with_last_byte(3), U256::from(3)),
+            ])
+        );
+
+        // a wiped storage replaces the existing one
+        let mut wiped = HashedPostState::default();
+        wiped.insert_hashed_storage(address, HashedStorage::new(true));
+        hashed_post_state.extend(wiped);
+        assert_eq!(hashed_post_state.storages.get(&address), Some(&HashedStorage::new(true)));
+    }
+
     #[test]
     fn fuzz_hashed_storage_cursor() {
         proptest!(ProptestConfig::with_cases(10),
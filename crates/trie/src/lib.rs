@@ -39,11 +39,19 @@ pub mod proof;
 
 /// The implementation of the Merkle Patricia Trie.
 mod trie;
-pub use trie::{StateRoot, StorageRoot};
+pub use trie::{StateRoot, StorageRoot, StorageRootResult};
+
+/// Parallel state root computation.
+mod parallel;
+pub use parallel::ParallelStateRoot;
 
 /// Buffer for trie updates.
 pub mod updates;
 
+/// Cache of recently computed trie nodes.
+mod node_cache;
+pub use node_cache::{TrieNodeCache, DEFAULT_TRIE_NODE_CACHE_SIZE};
+
 /// Utilities for state root checkpoint progress.
 mod progress;
 pub use progress::{IntermediateStateRootState, StateRootProgress};
@@ -0,0 +1,69 @@
+use parking_lot::Mutex;
+use reth_primitives::{Bytes, B256};
+use schnellru::{ByLength, LruMap};
+use std::{fmt, sync::Arc};
+
+/// The default number of trie nodes kept in the [TrieNodeCache].
+pub const DEFAULT_TRIE_NODE_CACHE_SIZE: u32 = 1_000_000;
+
+/// A bounded, shareable cache of RLP encoded trie nodes keyed by their hash.
+///
+/// The cache is populated with the nodes that are computed while calculating the state root, see
+/// [StateRoot::with_node_cache](crate::StateRoot::with_node_cache) and
+/// [ParallelStateRoot::with_node_cache](crate::ParallelStateRoot::with_node_cache). Since the
+/// state root calculation only re-encodes the nodes that changed, the cache holds the most recently
+/// modified nodes of the account and storage tries.
+///
+/// The node feeds the cache from the merkle stage during pipeline sync and from the blockchain tree
+/// when it commits canonical blocks during live sync.
+#[derive(Clone)]
+pub struct TrieNodeCache {
+    inner: Arc<Mutex<LruMap<B256, Bytes, ByLength>>>,
+}
+
+impl TrieNodeCache {
+    /// Creates a new cache that holds up to `max_nodes` trie nodes.
+    pub fn new(max_nodes: u32) -> Self {
+        Self { inner: Arc::new(Mutex::new(LruMap::new(ByLength::new(max_nodes)))) }
+    }
+
+    /// Returns the RLP encoded trie node with the given hash, if cached.
+    pub fn get(&self, hash: &B256) -> Option<Bytes> {
+        self.inner.lock().get(hash).cloned()
+    }
+
+    /// Inserts the RLP encoded trie node with the given hash.
+    pub fn insert(&self, hash: B256, node: Bytes) {
+        self.inner.lock().insert(hash, node);
+    }
+
+    /// Inserts all RLP encoded trie nodes.
+    pub fn extend(&self, nodes: impl IntoIterator<Item = (B256, Bytes)>) {
+        let mut inner = self.inner.lock();
+        for (hash, node) in nodes {
+            inner.insert(hash, node);
+        }
+    }
+
+    /// Returns the number of cached trie nodes.
+    pub fn len(&self) -> usize {
+        self.inner.lock().len()
+    }
+
+    /// Returns true if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for TrieNodeCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRIE_NODE_CACHE_SIZE)
+    }
+}
+
+impl fmt::Debug for TrieNodeCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrieNodeCache").field("len", &self.len()).finish()
+    }
+}
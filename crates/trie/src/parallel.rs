@@ -0,0 +1,276 @@
+use crate::{
+    hashed_cursor::{HashedPostState, HashedPostStateCursorFactory},
+    updates::TrieUpdates,
+    StateRoot, StateRootError, StorageRoot, StorageRootResult, TrieNodeCache,
+};
+use rayon::prelude::*;
+use reth_db::database::Database;
+use reth_primitives::B256;
+use std::collections::HashMap;
+use tracing::debug;
+
+/// ParallelStateRoot is used to compute the state root of the database with the given
+/// [HashedPostState] on top, computing the storage roots of the changed accounts concurrently.
+///
+/// The storage roots of all accounts with changed storage are computed on the rayon thread pool,
+/// each worker using its own read-only transaction. Afterwards, the account trie is walked on the
+/// calling thread with the precomputed storage roots. The resulting root and trie updates are
+/// identical to the ones computed by [StateRoot].
+///
+/// Note: The transactions are opened independently of each other, so the database must not be
+/// modified while the state root is computed.
+#[derive(Debug)]
+pub struct ParallelStateRoot<'a, DB> {
+    /// The database the read-only transactions are opened on.
+    db: &'a DB,
+    /// The hashed state on top of the database.
+    hashed_state: &'a HashedPostState,
+    /// The cache that is populated with all computed trie nodes.
+    node_cache: Option<TrieNodeCache>,
+}
+
+impl<'a, DB> ParallelStateRoot<'a, DB> {
+    /// Create a new [ParallelStateRoot] instance.
+    pub fn new(db: &'a DB, hashed_state: &'a HashedPostState) -> Self {
+        Self { db, hashed_state, node_cache: None }
+    }
+
+    /// Set the cache that is populated with the RLP encoded account and storage trie nodes
+    /// computed during the state root calculation.
+    pub fn with_node_cache(mut self, node_cache: Option<TrieNodeCache>) -> Self {
+        self.node_cache = node_cache;
+        self
+    }
+}
+
+impl<'a, DB: Database> ParallelStateRoot<'a, DB> {
+    /// Computes the state root of the trie with the changed account and storage prefixes of the
+    /// hashed state and existing trie nodes.
+    ///
+    /// # Returns
+    ///
+    /// The updated state root.
+    pub fn incremental_root(self) -> Result<B256, StateRootError> {
+        self.calculate(false).map(|(root, _)| root)
+    }
+
+    /// Computes the state root of the trie with the changed account and storage prefixes of the
+    /// hashed state and existing trie nodes collecting updates in the process.
+    ///
+    /// # Returns
+    ///
+    /// The updated state root and the trie updates.
+    pub fn incremental_root_with_updates(self) -> Result<(B256, TrieUpdates), StateRootError> {
+        self.calculate(true)
+    }
+
+    fn calculate(self, retain_updates: bool) -> Result<(B256, TrieUpdates), StateRootError> {
+        let (account_prefix_set, storage_prefix_sets) = self.hashed_state.construct_prefix_sets();
+        debug!(
+            target: "trie::parallel_state_root",
+            accounts = account_prefix_set.len(),
+            storages = storage_prefix_sets.len(),
+            "calculating state root"
+        );
+
+        let storage_roots = storage_prefix_sets
+            .par_iter()
+            .map_init(
+                || self.db.tx(),
+                |tx, (hashed_address, prefix_set)| {
+                    let tx = tx.as_ref().map_err(Clone::clone)?;
+                    let calculator = StorageRoot::new_hashed(tx, *hashed_address)
+                        .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                            tx,
+                            self.hashed_state,
+                        ))
+                        .with_changed_prefixes(prefix_set.clone())
+                        .with_node_cache(self.node_cache.clone());
+                    let result = if retain_updates {
+                        calculator.root_with_updates()?
+                    } else {
+                        (calculator.root()?, 0, TrieUpdates::default())
+                    };
+                    Ok::<_, StateRootError>((*hashed_address, result))
+                },
+            )
+            .collect::<Result<HashMap<B256, StorageRootResult>, StateRootError>>()?;
+
+        let tx = self.db.tx()?;
+        let calculator = StateRoot::new(&tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(&tx, self.hashed_state))
+            .with_changed_account_prefixes(account_prefix_set)
+            .with_changed_storage_prefixes(storage_prefix_sets)
+            .with_destroyed_accounts(self.hashed_state.destroyed_accounts())
+            .with_node_cache(self.node_cache)
+            .with_storage_roots(storage_roots);
+
+        if retain_updates {
+            calculator.root_with_updates()
+        } else {
+            Ok((calculator.root()?, TrieUpdates::default()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hashed_cursor::HashedStorage, test_utils::state_root_prehashed};
+    use proptest::prelude::*;
+    use reth_db::{
+        cursor::DbCursorRW,
+        tables,
+        test_utils::create_test_rw_db,
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives::{Account, StorageEntry, U256};
+    use std::collections::BTreeMap;
+
+    type State = BTreeMap<B256, (Account, BTreeMap<B256, U256>)>;
+
+    /// The changes of the accounts. An account is destroyed if it is `None`, otherwise its storage
+    /// is wiped before the changed slots are applied if the flag is set.
+    type Changes = BTreeMap<B256, (Option<Account>, bool, BTreeMap<B256, U256>)>;
+
+    fn hashed_post_state(changes: &Changes) -> HashedPostState {
+        let mut hashed_state = HashedPostState::default();
+        for (hashed_address, (account, wiped, storage)) in changes {
+            let Some(account) = account else {
+                hashed_state.insert_destroyed_account(*hashed_address);
+                hashed_state.insert_hashed_storage(*hashed_address, HashedStorage::new(true));
+                continue
+            };
+
+            hashed_state.insert_account(*hashed_address, *account);
+            let mut hashed_storage = HashedStorage::new(*wiped);
+            for (slot, value) in storage {
+                if value.is_zero() {
+                    hashed_storage.insert_zero_valued_slot(*slot);
+                } else {
+                    hashed_storage.insert_non_zero_valued_storage(*slot, *value);
+                }
+            }
+            hashed_state.insert_hashed_storage(*hashed_address, hashed_storage);
+        }
+        hashed_state.sorted()
+    }
+
+    /// Asserts that the parallel root of the changes on top of the initial state matches the
+    /// sequential root and the root of the merged state.
+    fn assert_parallel_root(initial: State, changes: Changes) {
+        let db = create_test_rw_db();
+
+        // write the initial state and its trie
+        let tx = db.tx_mut().unwrap();
+        {
+            let mut hashed_storage_cursor = tx.cursor_dup_write::<tables::HashedStorage>().unwrap();
+            for (hashed_address, (account, storage)) in &initial {
+                tx.put::<tables::HashedAccount>(*hashed_address, *account).unwrap();
+                for (key, value) in storage.iter().filter(|(_, value)| !value.is_zero()) {
+                    hashed_storage_cursor
+                        .upsert(*hashed_address, StorageEntry { key: *key, value: *value })
+                        .unwrap();
+                }
+            }
+        }
+        let (_, updates) = StateRoot::new(&tx).root_with_updates().unwrap();
+        updates.flush(&tx).unwrap();
+        tx.commit().unwrap();
+
+        let hashed_state = hashed_post_state(&changes);
+        let tx = db.tx().unwrap();
+        let (account_prefix_set, storage_prefix_sets) = hashed_state.construct_prefix_sets();
+        let (sequential_root, sequential_updates) = StateRoot::new(&tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(&tx, &hashed_state))
+            .with_changed_account_prefixes(account_prefix_set)
+            .with_changed_storage_prefixes(storage_prefix_sets)
+            .with_destroyed_accounts(hashed_state.destroyed_accounts())
+            .root_with_updates()
+            .unwrap();
+
+        let (parallel_root, parallel_updates) =
+            ParallelStateRoot::new(&db, &hashed_state).incremental_root_with_updates().unwrap();
+        assert_eq!(parallel_root, sequential_root);
+        assert_eq!(*parallel_updates, *sequential_updates);
+        assert_eq!(
+            ParallelStateRoot::new(&db, &hashed_state).incremental_root().unwrap(),
+            sequential_root
+        );
+
+        // the root must match the one of the merged state
+        let mut merged = initial;
+        for (hashed_address, (account, wiped, storage)) in changes {
+            let Some(account) = account else {
+                merged.remove(&hashed_address);
+                continue
+            };
+            let (existing_account, existing_storage) = merged.entry(hashed_address).or_default();
+            *existing_account = account;
+            if wiped {
+                existing_storage.clear();
+            }
+            existing_storage.extend(storage);
+        }
+        let expected =
+            state_root_prehashed(merged.into_iter().map(|(hashed_address, (account, storage))| {
+                (hashed_address, (account, storage.into_iter().filter(|(_, v)| !v.is_zero())))
+            }));
+        assert_eq!(parallel_root, expected);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig {
+            cases: 16, ..ProptestConfig::default()
+        })]
+        #[test]
+        fn parallel_vs_sequential_root(initial: State, changes: Changes) {
+            assert_parallel_root(initial, changes);
+        }
+    }
+
+    #[test]
+    fn parallel_root_wiped_and_empty_storage() {
+        let account = Account { nonce: 1, ..Default::default() };
+        let storage = BTreeMap::from([
+            (B256::with_last_byte(1), U256::from(1)),
+            (B256::with_last_byte(2), U256::from(2)),
+        ]);
+        let initial = State::from([
+            (B256::with_last_byte(1), (account, storage.clone())),
+            (B256::with_last_byte(2), (account, storage.clone())),
+            (B256::with_last_byte(3), (account, storage.clone())),
+            (B256::with_last_byte(4), (account, BTreeMap::new())),
+        ]);
+
+        let changes = Changes::from([
+            // all slots are cleared
+            (
+                B256::with_last_byte(1),
+                (Some(account), false, storage.keys().map(|slot| (*slot, U256::ZERO)).collect()),
+            ),
+            // self-destructed
+            (B256::with_last_byte(2), (None, true, BTreeMap::new())),
+            // self-destructed and re-created with new storage
+            (
+                B256::with_last_byte(3),
+                (
+                    Some(Account { nonce: 2, ..account }),
+                    true,
+                    BTreeMap::from([(B256::with_last_byte(3), U256::from(3))]),
+                ),
+            ),
+            // an account without storage is updated
+            (
+                B256::with_last_byte(4),
+                (Some(Account { nonce: 2, ..account }), false, BTreeMap::new()),
+            ),
+            // a new account without storage
+            (B256::with_last_byte(5), (Some(account), false, BTreeMap::new())),
+            // a new account whose storage is wiped
+            (B256::with_last_byte(6), (Some(account), true, BTreeMap::new())),
+        ]);
+
+        assert_parallel_root(initial, changes);
+    }
+}
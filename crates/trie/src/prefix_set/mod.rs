@@ -1,5 +1,5 @@
 use reth_primitives::trie::Nibbles;
-use std::rc::Rc;
+use std::sync::Arc;
 
 mod loader;
 pub use loader::{LoadedPrefixSets, PrefixSetLoader};
@@ -101,7 +101,7 @@ impl PrefixSetMut {
             self.keys.dedup();
         }
 
-        PrefixSet { keys: Rc::new(self.keys), index: self.index }
+        PrefixSet { keys: Arc::new(self.keys), index: self.index }
     }
 }
 
@@ -110,7 +110,7 @@ impl PrefixSetMut {
 /// See also [PrefixSetMut::freeze].
 #[derive(Debug, Default, Clone)]
 pub struct PrefixSet {
-    keys: Rc<Vec<Nibbles>>,
+    keys: Arc<Vec<Nibbles>>,
     index: usize,
 }
 
@@ -140,6 +140,11 @@ impl PrefixSet {
         false
     }
 
+    /// Returns an iterator over the keys of the set in ascending order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Nibbles> {
+        self.keys.iter()
+    }
+
     /// Returns the number of elements in the set.
     pub fn len(&self) -> usize {
         self.keys.len()
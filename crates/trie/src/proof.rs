@@ -1,7 +1,9 @@
 use crate::{
-    hashed_cursor::{HashedCursorFactory, HashedStorageCursor},
+    hashed_cursor::{
+        HashedCursorFactory, HashedPostState, HashedPostStateCursorFactory, HashedStorageCursor,
+    },
     node_iter::{AccountNode, AccountNodeIter, StorageNode, StorageNodeIter},
-    prefix_set::PrefixSetMut,
+    prefix_set::{PrefixSet, PrefixSetMut},
     trie_cursor::{AccountTrieCursor, StorageTrieCursor},
     walker::TrieWalker,
     StateRootError, StorageRootError,
@@ -9,11 +11,14 @@ use crate::{
 use alloy_rlp::{BufMut, Encodable};
 use reth_db::{tables, transaction::DbTx};
 use reth_primitives::{
-    constants::EMPTY_ROOT_HASH,
     keccak256,
-    trie::{AccountProof, HashBuilder, Nibbles, StorageProof, TrieAccount},
+    trie::{
+        AccountProof, HashBuilder, MultiProof, Nibbles, StorageMultiProof, StorageProof,
+        TrieAccount,
+    },
     Address, B256,
 };
+use std::collections::{HashMap, HashSet};
 
 /// A struct for generating merkle proofs.
 ///
@@ -26,12 +31,68 @@ pub struct Proof<'a, TX, H> {
     tx: &'a TX,
     /// The factory for hashed cursors.
     hashed_cursor_factory: H,
+    /// A set of account prefixes that have changed.
+    changed_account_prefixes: PrefixSet,
+    /// A map containing storage changes with the hashed address as key and a set of storage key
+    /// prefixes as the value.
+    changed_storage_prefixes: HashMap<B256, PrefixSet>,
 }
 
 impl<'a, TX> Proof<'a, TX, &'a TX> {
     /// Create a new [Proof] instance.
     pub fn new(tx: &'a TX) -> Self {
-        Self { tx, hashed_cursor_factory: tx }
+        Self {
+            tx,
+            hashed_cursor_factory: tx,
+            changed_account_prefixes: PrefixSetMut::default().freeze(),
+            changed_storage_prefixes: HashMap::default(),
+        }
+    }
+}
+
+impl<'a, TX: DbTx> Proof<'a, TX, &'a TX> {
+    /// Generate a state multiproof for the target accounts and storage slots of the hashed post
+    /// state on top of the database.
+    ///
+    /// See [Proof::multiproof] for more info.
+    pub fn overlay_multiproof(
+        tx: &'a TX,
+        post_state: &HashedPostState,
+        targets: HashMap<B256, Vec<B256>>,
+    ) -> Result<MultiProof, StateRootError> {
+        let (account_prefix_set, storage_prefix_sets) = post_state.construct_prefix_sets();
+        Proof::new(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, post_state))
+            .with_changed_account_prefixes(account_prefix_set)
+            .with_changed_storage_prefixes(storage_prefix_sets)
+            .multiproof(targets)
+    }
+}
+
+impl<'a, TX, H> Proof<'a, TX, H> {
+    /// Set the changed account prefixes.
+    ///
+    /// The changed prefixes must contain the keys that differ between the hashed state of the
+    /// cursor factory and the trie nodes in the database, e.g. the changes of an in-memory state.
+    pub fn with_changed_account_prefixes(mut self, prefixes: PrefixSet) -> Self {
+        self.changed_account_prefixes = prefixes;
+        self
+    }
+
+    /// Set the changed storage prefixes.
+    pub fn with_changed_storage_prefixes(mut self, prefixes: HashMap<B256, PrefixSet>) -> Self {
+        self.changed_storage_prefixes = prefixes;
+        self
+    }
+
+    /// Set the hashed cursor factory.
+    pub fn with_hashed_cursor_factory<HF>(self, hashed_cursor_factory: HF) -> Proof<'a, TX, HF> {
+        Proof {
+            tx: self.tx,
+            hashed_cursor_factory,
+            changed_account_prefixes: self.changed_account_prefixes,
+            changed_storage_prefixes: self.changed_storage_prefixes,
+        }
     }
 }
 
@@ -46,22 +107,33 @@ where
         address: Address,
         slots: &[B256],
     ) -> Result<AccountProof, StateRootError> {
-        let target_hashed_address = keccak256(address);
-        let target_nibbles = Nibbles::unpack(target_hashed_address);
-        let mut account_proof = AccountProof::new(address);
+        let targets =
+            HashMap::from([(keccak256(address), slots.iter().map(keccak256).collect::<Vec<_>>())]);
+        Ok(self.multiproof(targets)?.account_proof(address, slots))
+    }
 
+    /// Generate a state multiproof for the target accounts and their target storage slots.
+    ///
+    /// The targets are given as hashed addresses with their hashed slots. The proofs of all
+    /// targets are retained in a single walk of the account trie.
+    pub fn multiproof(
+        &self,
+        targets: HashMap<B256, Vec<B256>>,
+    ) -> Result<MultiProof, StateRootError> {
         let hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
         let trie_cursor = AccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
 
         // Create the walker.
-        let mut prefix_set = PrefixSetMut::default();
-        prefix_set.insert(target_nibbles.clone());
+        let target_nibbles = targets.keys().map(Nibbles::unpack).collect::<Vec<_>>();
+        let prefix_set = PrefixSetMut::from(
+            self.changed_account_prefixes.iter().cloned().chain(target_nibbles.iter().cloned()),
+        );
         let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
 
         // Create a hash builder to rebuild the root node since it is not available in the database.
-        let mut hash_builder =
-            HashBuilder::default().with_proof_retainer(Vec::from([target_nibbles.clone()]));
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(target_nibbles);
 
+        let mut multiproof = MultiProof::default();
         let mut account_rlp = Vec::with_capacity(128);
         let mut account_node_iter = AccountNodeIter::new(walker, hashed_account_cursor);
         while let Some(account_node) = account_node_iter.try_next()? {
@@ -70,10 +142,11 @@ where
                     hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
                 }
                 AccountNode::Leaf(hashed_address, account) => {
-                    let storage_root = if hashed_address == target_hashed_address {
-                        let (storage_root, storage_proofs) =
-                            self.storage_root_with_proofs(hashed_address, slots)?;
-                        account_proof.set_account(account, storage_root, storage_proofs);
+                    let storage_root = if let Some(slots) = targets.get(&hashed_address) {
+                        let storage_multiproof = self.storage_multiproof(hashed_address, slots)?;
+                        let storage_root = storage_multiproof.root;
+                        multiproof.accounts.insert(hashed_address, account);
+                        multiproof.storages.insert(hashed_address, storage_multiproof);
                         storage_root
                     } else {
                         self.storage_root(hashed_address)?
@@ -89,17 +162,14 @@ where
         }
 
         let _ = hash_builder.root();
+        multiproof.account_subtree = hash_builder.take_proofs();
 
-        let proofs = hash_builder.take_proofs();
-        account_proof.set_proof(proofs.values().cloned().collect());
-
-        Ok(account_proof)
+        Ok(multiproof)
     }
 
     /// Compute storage root.
     pub fn storage_root(&self, hashed_address: B256) -> Result<B256, StorageRootError> {
-        let (storage_root, _) = self.storage_root_with_proofs(hashed_address, &[])?;
-        Ok(storage_root)
+        Ok(self.storage_multiproof(hashed_address, &[])?.root)
     }
 
     /// Compute the storage root and retain proofs for requested slots.
@@ -108,17 +178,35 @@ where
         hashed_address: B256,
         slots: &[B256],
     ) -> Result<(B256, Vec<StorageProof>), StorageRootError> {
-        let mut hashed_storage_cursor = self.hashed_cursor_factory.hashed_storage_cursor()?;
+        let hashed_slots = slots.iter().map(keccak256).collect::<Vec<_>>();
+        let storage_multiproof = self.storage_multiproof(hashed_address, &hashed_slots)?;
+        let proofs = slots.iter().map(|slot| storage_multiproof.storage_proof(*slot)).collect();
+        Ok((storage_multiproof.root, proofs))
+    }
 
-        let mut proofs = slots.iter().copied().map(StorageProof::new).collect::<Vec<_>>();
+    /// Compute the storage root and retain the proofs of the requested hashed slots.
+    pub fn storage_multiproof(
+        &self,
+        hashed_address: B256,
+        hashed_slots: &[B256],
+    ) -> Result<StorageMultiProof, StorageRootError> {
+        let mut hashed_storage_cursor = self.hashed_cursor_factory.hashed_storage_cursor()?;
 
         // short circuit on empty storage
         if hashed_storage_cursor.is_storage_empty(hashed_address)? {
-            return Ok((EMPTY_ROOT_HASH, proofs))
+            return Ok(StorageMultiProof::default())
         }
 
-        let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
-        let prefix_set = PrefixSetMut::from(target_nibbles.clone()).freeze();
+        let targets = hashed_slots.iter().copied().collect::<HashSet<_>>();
+        let target_nibbles = targets.iter().map(Nibbles::unpack).collect::<Vec<_>>();
+        let changed_prefixes = self
+            .changed_storage_prefixes
+            .get(&hashed_address)
+            .map(|prefix_set| prefix_set.iter().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let prefix_set =
+            PrefixSetMut::from(changed_prefixes.into_iter().chain(target_nibbles.iter().cloned()))
+                .freeze();
         let trie_cursor = StorageTrieCursor::new(
             self.tx.cursor_dup_read::<tables::StoragesTrie>()?,
             hashed_address,
@@ -126,6 +214,7 @@ where
         let walker = TrieWalker::new(trie_cursor, prefix_set);
 
         let mut hash_builder = HashBuilder::default().with_proof_retainer(target_nibbles);
+        let mut values = HashMap::with_capacity(targets.len());
         let mut storage_node_iter =
             StorageNodeIter::new(walker, hashed_storage_cursor, hashed_address);
         while let Some(node) = storage_node_iter.try_next()? {
@@ -134,40 +223,33 @@ where
                     hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
                 }
                 StorageNode::Leaf(hashed_slot, value) => {
-                    let nibbles = Nibbles::unpack(hashed_slot);
-                    if let Some(proof) = proofs.iter_mut().find(|proof| proof.nibbles == nibbles) {
-                        proof.set_value(value);
+                    if targets.contains(&hashed_slot) {
+                        values.insert(hashed_slot, value);
                     }
-                    hash_builder.add_leaf(nibbles, alloy_rlp::encode_fixed_size(&value).as_ref());
+                    hash_builder.add_leaf(
+                        Nibbles::unpack(hashed_slot),
+                        alloy_rlp::encode_fixed_size(&value).as_ref(),
+                    );
                 }
             }
         }
 
         let root = hash_builder.root();
-
-        let all_proof_nodes = hash_builder.take_proofs();
-        for proof in proofs.iter_mut() {
-            // Iterate over all proof nodes and find the matching ones.
-            // The filtered results are guaranteed to be in order.
-            let matching_proof_nodes = all_proof_nodes
-                .iter()
-                .filter(|(path, _)| proof.nibbles.starts_with(path))
-                .map(|(_, node)| node.clone());
-            proof.set_proof(matching_proof_nodes.collect());
-        }
-
-        Ok((root, proofs))
+        Ok(StorageMultiProof { root, subtree: hash_builder.take_proofs(), values })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::StateRoot;
+    use crate::{hashed_cursor::HashedStorage, StateRoot};
     use once_cell::sync::Lazy;
-    use reth_db::database::Database;
+    use reth_db::{database::Database, transaction::DbTxMut};
     use reth_interfaces::RethResult;
-    use reth_primitives::{Account, Bytes, Chain, ChainSpec, StorageEntry, HOLESKY, MAINNET, U256};
+    use reth_primitives::{
+        constants::EMPTY_ROOT_HASH, Account, Bytes, Chain, ChainSpec, StorageEntry, HOLESKY,
+        MAINNET, U256,
+    };
     use reth_provider::{test_utils::create_test_provider_factory, HashingWriter, ProviderFactory};
     use std::{str::FromStr, sync::Arc};
 
@@ -307,6 +389,96 @@ mod tests {
         }
     }
 
+    #[test]
+    fn testspec_multiproof() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let targets = [
+            "0x2031f89b3ea8014eb51a78c316e42af3e0d7695f",
+            "0x33f0fc440b8477fcfbe9d0bf8649e7dea9baedb2",
+            "0x62b0dd4aab2b1a0a04e279e2b828791a10755528",
+            "0x1ed9b1dd266b607ee278726d324b855a093394a6",
+            // nonexistent
+            "0x000d836201318ec6899a67540690382780743281",
+        ]
+        .map(|address| Address::from_str(address).unwrap());
+        let slots = Vec::from([B256::with_last_byte(1), B256::with_last_byte(3)]);
+
+        let provider = factory.provider().unwrap();
+        let proof = Proof::new(provider.tx_ref());
+        let multiproof = proof
+            .multiproof(HashMap::from_iter(targets.iter().map(|address| {
+                (keccak256(address), slots.iter().map(keccak256).collect::<Vec<_>>())
+            })))
+            .unwrap();
+
+        let mut total_proof_nodes = 0;
+        for target in targets {
+            let expected = proof.account_proof(target, &slots).unwrap();
+            total_proof_nodes += expected.proof.len();
+            pretty_assertions::assert_eq!(multiproof.account_proof(target, &slots), expected);
+        }
+
+        // the root node and the shared branch nodes are only included once
+        assert!(multiproof.account_subtree.len() < total_proof_nodes);
+        assert_eq!(multiproof.accounts.len(), 4);
+    }
+
+    #[test]
+    fn testspec_proof_with_hashed_state() {
+        let target = Address::from_str("0x2031f89b3ea8014eb51a78c316e42af3e0d7695f").unwrap();
+        let hashed_address = keccak256(target);
+        let slot = B256::with_last_byte(1);
+        let account = Account { nonce: 1, balance: U256::from(1), bytecode_hash: None };
+
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let mut hashed_state = HashedPostState::default();
+        hashed_state.insert_account(hashed_address, account);
+        let mut hashed_storage = HashedStorage::new(false);
+        hashed_storage.insert_non_zero_valued_storage(keccak256(slot), U256::from(2));
+        hashed_state.insert_hashed_storage(hashed_address, hashed_storage);
+        let hashed_state = hashed_state.sorted();
+        let (account_prefix_set, storage_prefix_sets) = hashed_state.construct_prefix_sets();
+
+        let provider = factory.provider().unwrap();
+        let tx = provider.tx_ref();
+        let proof = Proof::new(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, &hashed_state))
+            .with_changed_account_prefixes(account_prefix_set)
+            .with_changed_storage_prefixes(storage_prefix_sets)
+            .account_proof(target, &[slot])
+            .unwrap();
+
+        // Create the same state in the database.
+        let expected_factory = create_test_provider_factory();
+        insert_genesis(&expected_factory, TEST_SPEC.clone()).unwrap();
+        let provider_rw = expected_factory.provider_rw().unwrap();
+        provider_rw.tx_ref().put::<tables::HashedAccount>(hashed_address, account).unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::HashedStorage>(
+                hashed_address,
+                StorageEntry { key: keccak256(slot), value: U256::from(2) },
+            )
+            .unwrap();
+        provider_rw.tx_ref().clear::<tables::AccountsTrie>().unwrap();
+        provider_rw.tx_ref().clear::<tables::StoragesTrie>().unwrap();
+        let (_, updates) = StateRoot::new(provider_rw.tx_ref()).root_with_updates().unwrap();
+        updates.flush(provider_rw.tx_ref()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = expected_factory.provider().unwrap();
+        let expected = Proof::new(provider.tx_ref()).account_proof(target, &[slot]).unwrap();
+        pretty_assertions::assert_eq!(proof, expected);
+        assert_eq!(proof.info, Some(account));
+        assert_eq!(proof.storage_proofs[0].value, U256::from(2));
+    }
+
     #[test]
     fn mainnet_genesis_account_proof() {
         // Create test database and insert genesis accounts.
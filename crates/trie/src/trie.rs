@@ -6,7 +6,7 @@ use crate::{
     trie_cursor::{AccountTrieCursor, StorageTrieCursor},
     updates::{TrieKey, TrieOp, TrieUpdates},
     walker::TrieWalker,
-    StateRootError, StorageRootError,
+    StateRootError, StorageRootError, TrieNodeCache,
 };
 use alloy_rlp::{BufMut, Encodable};
 use reth_db::{tables, transaction::DbTx};
@@ -40,8 +40,16 @@ pub struct StateRoot<'a, TX, H> {
     previous_state: Option<IntermediateStateRootState>,
     /// The number of updates after which the intermediate progress should be returned.
     threshold: u64,
+    /// The cache that is populated with all computed trie nodes.
+    node_cache: Option<TrieNodeCache>,
+    /// Storage roots that were computed ahead of the account trie walk.
+    storage_roots: HashMap<B256, StorageRootResult>,
 }
 
+/// The storage root, the number of walked storage slots and the storage trie updates of an
+/// account.
+pub type StorageRootResult = (B256, usize, TrieUpdates);
+
 impl<'a, TX, H> StateRoot<'a, TX, H> {
     /// Set the changed account prefixes.
     pub fn with_changed_account_prefixes(mut self, prefixes: PrefixSet) -> Self {
@@ -79,6 +87,25 @@ impl<'a, TX, H> StateRoot<'a, TX, H> {
         self
     }
 
+    /// Set the cache that is populated with the RLP encoded account and storage trie nodes
+    /// computed during the state root calculation.
+    pub fn with_node_cache(mut self, node_cache: Option<TrieNodeCache>) -> Self {
+        self.node_cache = node_cache;
+        self
+    }
+
+    /// Set the storage roots that were computed ahead of time, e.g. by
+    /// [ParallelStateRoot](crate::ParallelStateRoot).
+    ///
+    /// The storage root of an account is only computed during the account trie walk if it is
+    /// missing from this map. The storage trie updates of the results are only retained if the
+    /// account is visited, so they must have been computed with the same changed prefixes and
+    /// hashed state as the ones of this calculator.
+    pub fn with_storage_roots(mut self, storage_roots: HashMap<B256, StorageRootResult>) -> Self {
+        self.storage_roots = storage_roots;
+        self
+    }
+
     /// Set the hashed cursor factory.
     pub fn with_hashed_cursor_factory<HF>(
         self,
@@ -91,6 +118,8 @@ impl<'a, TX, H> StateRoot<'a, TX, H> {
             destroyed_accounts: self.destroyed_accounts,
             threshold: self.threshold,
             previous_state: self.previous_state,
+            node_cache: self.node_cache,
+            storage_roots: self.storage_roots,
             hashed_cursor_factory,
         }
     }
@@ -106,6 +135,8 @@ impl<'a, TX: DbTx> StateRoot<'a, TX, &'a TX> {
             destroyed_accounts: HashSet::default(),
             previous_state: None,
             threshold: 100_000,
+            node_cache: None,
+            storage_roots: HashMap::default(),
             hashed_cursor_factory: tx,
         }
     }
@@ -249,7 +280,9 @@ where
 
         account_node_iter.walker.set_updates(retain_updates);
         hash_builder.set_updates(retain_updates);
+        hash_builder.set_retain_nodes(self.node_cache.is_some());
 
+        let mut storage_roots = self.storage_roots;
         let mut account_rlp = Vec::with_capacity(128);
         let mut hashed_entries_walked = 0;
         while let Some(node) = account_node_iter.try_next()? {
@@ -260,30 +293,40 @@ where
                 AccountNode::Leaf(hashed_address, account) => {
                     hashed_entries_walked += 1;
 
-                    // We assume we can always calculate a storage root without
-                    // OOMing. This opens us up to a potential DOS vector if
-                    // a contract had too many storage entries and they were
-                    // all buffered w/o us returning and committing our intermediate
-                    // progress.
-                    // TODO: We can consider introducing the TrieProgress::Progress/Complete
-                    // abstraction inside StorageRoot, but let's give it a try as-is for now.
-                    let storage_root_calculator = StorageRoot::new_hashed(self.tx, hashed_address)
-                        .with_hashed_cursor_factory(self.hashed_cursor_factory.clone())
-                        .with_changed_prefixes(
-                            self.changed_storage_prefixes
-                                .get(&hashed_address)
-                                .cloned()
-                                .unwrap_or_default(),
-                        );
-
-                    let storage_root = if retain_updates {
-                        let (root, storage_slots_walked, updates) =
-                            storage_root_calculator.root_with_updates()?;
+                    let storage_root = if let Some((root, storage_slots_walked, updates)) =
+                        storage_roots.remove(&hashed_address)
+                    {
                         hashed_entries_walked += storage_slots_walked;
                         trie_updates.extend(updates.into_iter());
                         root
                     } else {
-                        storage_root_calculator.root()?
+                        // We assume we can always calculate a storage root without
+                        // OOMing. This opens us up to a potential DOS vector if
+                        // a contract had too many storage entries and they were
+                        // all buffered w/o us returning and committing our intermediate
+                        // progress.
+                        // TODO: We can consider introducing the TrieProgress::Progress/Complete
+                        // abstraction inside StorageRoot, but let's give it a try as-is for now.
+                        let storage_root_calculator =
+                            StorageRoot::new_hashed(self.tx, hashed_address)
+                                .with_hashed_cursor_factory(self.hashed_cursor_factory.clone())
+                                .with_changed_prefixes(
+                                    self.changed_storage_prefixes
+                                        .get(&hashed_address)
+                                        .cloned()
+                                        .unwrap_or_default(),
+                                )
+                                .with_node_cache(self.node_cache.clone());
+
+                        if retain_updates {
+                            let (root, storage_slots_walked, updates) =
+                                storage_root_calculator.root_with_updates()?;
+                            hashed_entries_walked += storage_slots_walked;
+                            trie_updates.extend(updates.into_iter());
+                            root
+                        } else {
+                            storage_root_calculator.root()?
+                        }
                     };
 
                     let account = TrieAccount::from((account, storage_root));
@@ -298,6 +341,10 @@ where
                         account_node_iter.walker.updates_len() +
                         hash_builder.updates_len();
                     if retain_updates && total_updates_len as u64 >= self.threshold {
+                        if let Some(node_cache) = &self.node_cache {
+                            node_cache.extend(hash_builder.take_retained_nodes());
+                        }
+
                         let (walker_stack, walker_updates) = account_node_iter.walker.split();
                         let (hash_builder, hash_builder_updates) = hash_builder.split();
 
@@ -322,6 +369,10 @@ where
 
         let root = hash_builder.root();
 
+        if let Some(node_cache) = &self.node_cache {
+            node_cache.extend(hash_builder.take_retained_nodes());
+        }
+
         let (_, walker_updates) = account_node_iter.walker.split();
         let (_, hash_builder_updates) = hash_builder.split();
 
@@ -345,6 +396,8 @@ pub struct StorageRoot<'a, TX, H> {
     pub hashed_address: B256,
     /// The set of storage slot prefixes that have changed.
     pub changed_prefixes: PrefixSet,
+    /// The cache that is populated with all computed trie nodes.
+    pub node_cache: Option<TrieNodeCache>,
 }
 
 impl<'a, TX: DbTx> StorageRoot<'a, TX, &'a TX> {
@@ -359,6 +412,7 @@ impl<'a, TX: DbTx> StorageRoot<'a, TX, &'a TX> {
             tx,
             hashed_address,
             changed_prefixes: PrefixSetMut::default().freeze(),
+            node_cache: None,
             hashed_cursor_factory: tx,
         }
     }
@@ -380,6 +434,7 @@ impl<'a, TX, H> StorageRoot<'a, TX, H> {
             tx,
             hashed_address,
             changed_prefixes: PrefixSetMut::default().freeze(),
+            node_cache: None,
             hashed_cursor_factory,
         }
     }
@@ -390,6 +445,13 @@ impl<'a, TX, H> StorageRoot<'a, TX, H> {
         self
     }
 
+    /// Set the cache that is populated with the RLP encoded storage trie nodes computed during the
+    /// storage root calculation.
+    pub fn with_node_cache(mut self, node_cache: Option<TrieNodeCache>) -> Self {
+        self.node_cache = node_cache;
+        self
+    }
+
     /// Set the hashed cursor factory.
     pub fn with_hashed_cursor_factory<HF>(
         self,
@@ -399,6 +461,7 @@ impl<'a, TX, H> StorageRoot<'a, TX, H> {
             tx: self.tx,
             hashed_address: self.hashed_address,
             changed_prefixes: self.changed_prefixes,
+            node_cache: self.node_cache,
             hashed_cursor_factory,
         }
     }
@@ -451,7 +514,9 @@ where
         let walker = TrieWalker::new(trie_cursor, self.changed_prefixes.clone())
             .with_updates(retain_updates);
 
-        let mut hash_builder = HashBuilder::default().with_updates(retain_updates);
+        let mut hash_builder = HashBuilder::default()
+            .with_updates(retain_updates)
+            .with_retained_nodes(self.node_cache.is_some());
 
         let mut storage_slots_walked = 0;
         let mut storage_node_iter =
@@ -473,6 +538,10 @@ where
 
         let root = hash_builder.root();
 
+        if let Some(node_cache) = &self.node_cache {
+            node_cache.extend(hash_builder.take_retained_nodes());
+        }
+
         let (_, hash_builder_updates) = hash_builder.split();
         let (_, walker_updates) = storage_node_iter.walker.split();
 
@@ -697,6 +766,41 @@ mod tests {
         assert_eq!(storage_root(storage.into_iter()), got);
     }
 
+    #[test]
+    fn state_root_populates_node_cache() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap();
+
+        let address = Address::random();
+        let storage = BTreeMap::from([
+            (B256::ZERO, U256::from(3)),
+            (B256::with_last_byte(2), U256::from(1)),
+            (B256::with_last_byte(3), U256::from(7)),
+        ]);
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        insert_account(tx.tx_ref(), address, account, &storage);
+        for i in 0..10 {
+            insert_account(
+                tx.tx_ref(),
+                Address::random(),
+                Account { nonce: i, ..account },
+                &BTreeMap::new(),
+            );
+        }
+        tx.commit().unwrap();
+
+        let tx = factory.provider_rw().unwrap();
+        let node_cache = TrieNodeCache::new(1024);
+        let root =
+            StateRoot::new(tx.tx_ref()).with_node_cache(Some(node_cache.clone())).root().unwrap();
+        let storage_root = StorageRoot::new(tx.tx_ref(), address).root().unwrap();
+
+        let root_node = node_cache.get(&root).expect("state root node is cached");
+        assert_eq!(keccak256(&root_node), root);
+        let storage_root_node = node_cache.get(&storage_root).expect("storage root node is cached");
+        assert_eq!(keccak256(&storage_root_node), storage_root);
+    }
+
     type State = BTreeMap<Address, (Account, BTreeMap<B256, U256>)>;
 
     #[test]